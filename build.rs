@@ -79,6 +79,12 @@ fn main() {
         .flag("-fopenmp")  // Enable OpenMP
         .flag("-fPIC");
     
+    // Optional debug inspection entry points
+    let debug_inspection = env::var("CARGO_FEATURE_DEBUG").is_ok();
+    if debug_inspection {
+        build.define("PARKISSAT_DEBUG", None);
+    }
+    
     // Add painless-src object files to the build first
     let painless_objects = [
         "clauses/ClauseBuffer.o",
@@ -144,7 +150,12 @@ fn main() {
     build.compile("parkissat_wrapper");
     
     // Step 5: Generate bindings
-    let bindings = bindgen::Builder::default()
+    let mut bindgen_builder = bindgen::Builder::default();
+    if debug_inspection {
+        bindgen_builder = bindgen_builder.clang_arg("-DPARKISSAT_DEBUG");
+    }
+    
+    let bindings = bindgen_builder
         .header("wrapper.h")
         .parse_callbacks(Box::new(bindgen::CargoCallbacks::new()))
         .allowlist_function("parkissat_.*")
@@ -15,6 +15,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         random_seed: 42,
         enable_preprocessing: true,
         verbosity: 1,
+        ..SolverConfig::default()
     };
     
     println!("Configuring solver with num_threads = -1 (auto-detect)");
@@ -54,6 +55,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         random_seed: 42,
         enable_preprocessing: true,
         verbosity: 1,
+        ..SolverConfig::default()
     };
     
     solver2.configure(&config2)?;
@@ -0,0 +1,463 @@
+//! AIGER and-inverter graphs for bounded model checking
+//!
+//! [`parse_reader`] reads circuits in the ASCII (`aag`) and binary (`aig`)
+//! AIGER formats, including the bad-state and invariant-constraint sections
+//! of AIGER 1.9. [`Aig::unroll`] turns a circuit into a BMC instance: the
+//! latches start from their reset values, each time frame gets fresh inputs
+//! and a copy of the AND gates built with [`circuit::add_and`], and the
+//! formula asks for a bad state within the bound. A model is a
+//! counterexample, whose inputs [`Unrolling::input_trace`] reads back.
+//!
+//! Circuits without bad-state properties use their outputs as bad states, as
+//! in the HWMCC conventions before AIGER 1.9. Justice and fairness
+//! properties are not supported.
+//!
+//! [`circuit::add_and`]: crate::circuit::add_and
+
+use crate::circuit;
+use crate::cnf::CnfFormula;
+use crate::encodings::ClauseSink;
+use crate::error::{ParkissatError, Result};
+use crate::types::Lit;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+/// A latch: a state bit with its next-state function and reset value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Latch {
+    /// AIGER literal of the latch (even)
+    pub lit: u32,
+    /// AIGER literal of its next value
+    pub next: u32,
+    /// Initial value (`None` = uninitialized, free in the first frame)
+    pub reset: Option<bool>,
+}
+
+/// An AND gate `lhs = rhs0 ∧ rhs1` over AIGER literals
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AndGate {
+    /// Output literal (even)
+    pub lhs: u32,
+    /// First input literal
+    pub rhs0: u32,
+    /// Second input literal
+    pub rhs1: u32,
+}
+
+/// An and-inverter graph
+///
+/// AIGER literals are `2·variable + negated`; literal 0 is false and 1 is true.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Aig {
+    max_var: u32,
+    inputs: Vec<u32>,
+    latches: Vec<Latch>,
+    outputs: Vec<u32>,
+    bad: Vec<u32>,
+    constraints: Vec<u32>,
+    ands: Vec<AndGate>,
+}
+
+/// A circuit unrolled into CNF
+#[derive(Debug, Clone)]
+pub struct Unrolling {
+    /// The BMC instance: satisfiable iff a bad state is reachable within the bound
+    pub formula: CnfFormula,
+    /// Literal of each input in each time frame
+    pub inputs: Vec<Vec<Lit>>,
+    /// Literal of each latch in each time frame
+    pub latches: Vec<Vec<Lit>>,
+    /// Literal that holds iff some bad-state property holds in each time frame
+    pub bad: Vec<Lit>,
+}
+
+impl Unrolling {
+    /// Input values per time frame in a model of [`formula`](Self::formula)
+    pub fn input_trace(&self, model: &[i32]) -> Vec<Vec<bool>> {
+        let value = |lit: Lit| {
+            let var = lit.var().index() as usize;
+            model.get(var - 1).is_some_and(|&value| value > 0) == lit.is_positive()
+        };
+        self.inputs.iter()
+            .map(|frame| frame.iter().map(|&lit| value(lit)).collect())
+            .collect()
+    }
+}
+
+impl Aig {
+    /// Highest variable index
+    pub fn max_var(&self) -> u32 {
+        self.max_var
+    }
+
+    /// Input literals
+    pub fn inputs(&self) -> &[u32] {
+        &self.inputs
+    }
+
+    /// Latches
+    pub fn latches(&self) -> &[Latch] {
+        &self.latches
+    }
+
+    /// Output literals
+    pub fn outputs(&self) -> &[u32] {
+        &self.outputs
+    }
+
+    /// Bad-state property literals
+    pub fn bad(&self) -> &[u32] {
+        &self.bad
+    }
+
+    /// Invariant constraint literals, assumed to hold in every time frame
+    pub fn constraints(&self) -> &[u32] {
+        &self.constraints
+    }
+
+    /// AND gates
+    pub fn ands(&self) -> &[AndGate] {
+        &self.ands
+    }
+
+    /// Unroll the circuit for time frames `0..=bound` into a BMC instance
+    pub fn unroll(&self, bound: usize) -> Result<Unrolling> {
+        let mut formula = CnfFormula::new();
+        let truth = formula.new_var().positive();
+        formula.add_clause_lits(&[truth])?;
+
+        let properties = if self.bad.is_empty() { &self.outputs } else { &self.bad };
+        let gates: Vec<Option<AndGate>> = {
+            let mut gates = vec![None; self.max_var as usize + 1];
+            for &gate in &self.ands {
+                gates[(gate.lhs / 2) as usize] = Some(gate);
+            }
+            gates
+        };
+
+        let mut inputs_per_frame = Vec::with_capacity(bound + 1);
+        let mut latches_per_frame = Vec::with_capacity(bound + 1);
+        let mut bad_per_frame = Vec::with_capacity(bound + 1);
+
+        let mut state: Vec<Lit> = self.latches.iter()
+            .map(|latch| match latch.reset {
+                Some(true) => truth,
+                Some(false) => !truth,
+                None => formula.new_var().positive(),
+            })
+            .collect();
+
+        for _ in 0..=bound {
+            let mut frame = Frame {
+                values: vec![None; self.max_var as usize + 1],
+                expanding: vec![false; self.max_var as usize + 1],
+                gates: &gates,
+                truth,
+            };
+            let inputs: Vec<Lit> = self.inputs.iter()
+                .map(|&input| {
+                    let lit = formula.new_var().positive();
+                    frame.values[(input / 2) as usize] = Some(lit);
+                    lit
+                })
+                .collect();
+            for (latch, &lit) in self.latches.iter().zip(&state) {
+                frame.values[(latch.lit / 2) as usize] = Some(lit);
+            }
+
+            for &constraint in &self.constraints {
+                let lit = frame.lit(&mut formula, constraint)?;
+                formula.add_clause_lits(&[lit])?;
+            }
+            let bad: Vec<Lit> = properties.iter()
+                .map(|&property| frame.lit(&mut formula, property))
+                .collect::<Result<_>>()?;
+            let next: Vec<Lit> = self.latches.iter()
+                .map(|latch| frame.lit(&mut formula, latch.next))
+                .collect::<Result<_>>()?;
+
+            bad_per_frame.push(circuit::add_or(&mut formula, &bad)?);
+            inputs_per_frame.push(inputs);
+            latches_per_frame.push(std::mem::replace(&mut state, next));
+        }
+
+        formula.add_clause_lits(&bad_per_frame)?;
+        Ok(Unrolling {
+            formula,
+            inputs: inputs_per_frame,
+            latches: latches_per_frame,
+            bad: bad_per_frame,
+        })
+    }
+}
+
+/// CNF literals of the circuit's nodes in one time frame
+struct Frame<'a> {
+    /// Literal of each AIGER variable, once encoded
+    values: Vec<Option<Lit>>,
+    /// Gates whose inputs are being encoded, to detect cycles
+    expanding: Vec<bool>,
+    gates: &'a [Option<AndGate>],
+    truth: Lit,
+}
+
+impl Frame<'_> {
+    /// CNF literal of an AIGER literal, encoding the gates it depends on
+    fn lit<S: ClauseSink>(&mut self, sink: &mut S, aiger: u32) -> Result<Lit> {
+        // Depth-first over the gates without recursion, as AIGs can be deep
+        let mut pending = vec![(aiger / 2, false)];
+        while let Some(&(var, expanded)) = pending.last() {
+            if var == 0 || self.values[var as usize].is_some() {
+                pending.pop();
+                continue;
+            }
+            let Some(gate) = self.gates[var as usize] else {
+                return Err(ParkissatError::InvalidConfiguration(format!("AIGER variable {} is undefined", var)));
+            };
+
+            if expanded {
+                let inputs = [self.resolve(gate.rhs0), self.resolve(gate.rhs1)];
+                self.values[var as usize] = Some(circuit::add_and(sink, &inputs)?);
+                pending.pop();
+            } else if self.expanding[var as usize] {
+                return Err(ParkissatError::InvalidConfiguration(format!("AIGER gate {} is cyclic", gate.lhs)));
+            } else {
+                self.expanding[var as usize] = true;
+                *pending.last_mut().expect("stack is not empty") = (var, true);
+                pending.extend([(gate.rhs0 / 2, false), (gate.rhs1 / 2, false)]);
+            }
+        }
+        Ok(self.resolve(aiger))
+    }
+
+    /// Literal of an AIGER literal whose variable is already encoded
+    fn resolve(&self, aiger: u32) -> Lit {
+        let base = match aiger / 2 {
+            0 => !self.truth,
+            var => self.values[var as usize].expect("variable was encoded"),
+        };
+        if aiger % 2 == 1 { !base } else { base }
+    }
+}
+
+fn parse_error(line: usize, message: impl Into<String>) -> ParkissatError {
+    ParkissatError::ParseError {
+        line,
+        message: message.into(),
+    }
+}
+
+/// Line-oriented cursor over the AIGER bytes, with a binary tail
+struct Cursor<'a> {
+    bytes: &'a [u8],
+    position: usize,
+    line: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn next_line(&mut self) -> Result<&'a str> {
+        if self.position >= self.bytes.len() {
+            return Err(parse_error(self.line + 1, "unexpected end of file"));
+        }
+        let rest = &self.bytes[self.position..];
+        let end = rest.iter().position(|&byte| byte == b'\n').unwrap_or(rest.len());
+        self.position += (end + 1).min(rest.len());
+        self.line += 1;
+        std::str::from_utf8(&rest[..end])
+            .map(str::trim)
+            .map_err(|_| parse_error(self.line, "invalid UTF-8"))
+    }
+
+    /// A line of `min` to `max` unsigned numbers
+    fn numbers(&mut self, min: usize, max: usize) -> Result<Vec<u32>> {
+        let line = self.next_line()?;
+        let numbers = line.split_whitespace()
+            .map(|token| token.parse().map_err(|_| parse_error(self.line, format!("invalid number `{}`", token))))
+            .collect::<Result<Vec<u32>>>()?;
+        if numbers.len() < min || numbers.len() > max {
+            return Err(parse_error(self.line, format!("expected {} to {} numbers", min, max)));
+        }
+        Ok(numbers)
+    }
+
+    /// A variable-length encoded delta of the binary AND section
+    fn delta(&mut self) -> Result<u32> {
+        let mut value = 0u32;
+        for shift in (0..35).step_by(7) {
+            let Some(&byte) = self.bytes.get(self.position) else {
+                return Err(parse_error(self.line, "unexpected end of binary AND gates"));
+            };
+            self.position += 1;
+            value |= u32::from(byte & 0x7f).checked_shl(shift).unwrap_or(0);
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(parse_error(self.line, "binary delta is too long"))
+    }
+}
+
+/// Parse an AIGER circuit in ASCII or binary format
+pub fn parse_reader<R: Read>(mut reader: R) -> Result<Aig> {
+    let mut bytes = Vec::new();
+    reader.read_to_end(&mut bytes)?;
+    parse_bytes(&bytes)
+}
+
+/// Parse an AIGER circuit held in memory
+pub fn parse_bytes(bytes: &[u8]) -> Result<Aig> {
+    let mut cursor = Cursor { bytes, position: 0, line: 0 };
+
+    let header = cursor.next_line()?;
+    let fields: Vec<&str> = header.split_whitespace().collect();
+    let binary = match fields.first() {
+        Some(&"aag") => false,
+        Some(&"aig") => true,
+        _ => return Err(parse_error(1, "expected `aag` or `aig` header")),
+    };
+    if fields.len() < 6 || fields.len() > 10 {
+        return Err(parse_error(1, "expected `M I L O A [B C J F]`"));
+    }
+    let counts = fields[1..].iter()
+        .map(|field| field.parse::<u32>().map_err(|_| parse_error(1, format!("invalid count `{}`", field))))
+        .collect::<Result<Vec<u32>>>()?;
+    let count = |index: usize| counts.get(index).copied().unwrap_or(0);
+    let (max_var, num_inputs, num_latches, num_outputs, num_ands) = (count(0), count(1), count(2), count(3), count(4));
+    let (num_bad, num_constraints) = (count(5), count(6));
+    if count(7) > 0 || count(8) > 0 {
+        return Err(parse_error(1, "justice and fairness properties are not supported"));
+    }
+    if u64::from(num_inputs) + u64::from(num_latches) + u64::from(num_ands) > u64::from(max_var) {
+        return Err(parse_error(1, "M is smaller than I + L + A"));
+    }
+
+    let mut aig = Aig { max_var, ..Aig::default() };
+    let check = |lit: u32, line: usize| {
+        if lit / 2 > max_var {
+            Err(parse_error(line, format!("literal {} exceeds the maximum variable", lit)))
+        } else {
+            Ok(lit)
+        }
+    };
+
+    for index in 0..num_inputs {
+        let lit = if binary {
+            2 * (index + 1)
+        } else {
+            cursor.numbers(1, 1)?[0]
+        };
+        aig.inputs.push(check(lit, cursor.line)?);
+    }
+
+    for index in 0..num_latches {
+        let numbers = cursor.numbers(if binary { 1 } else { 2 }, if binary { 2 } else { 3 })?;
+        let (lit, rest) = if binary {
+            (2 * (num_inputs + index + 1), &numbers[..])
+        } else {
+            (numbers[0], &numbers[1..])
+        };
+        let reset = match rest.get(1).copied() {
+            None | Some(0) => Some(false),
+            Some(1) => Some(true),
+            Some(value) if value == lit => None,
+            Some(value) => return Err(parse_error(cursor.line, format!("invalid reset value {}", value))),
+        };
+        aig.latches.push(Latch { lit: check(lit, cursor.line)?, next: check(rest[0], cursor.line)?, reset });
+    }
+
+    for (count, target) in [(num_outputs, &mut aig.outputs), (num_bad, &mut aig.bad), (num_constraints, &mut aig.constraints)] {
+        for _ in 0..count {
+            let lit = cursor.numbers(1, 1)?[0];
+            target.push(check(lit, cursor.line)?);
+        }
+    }
+
+    for index in 0..num_ands {
+        let gate = if binary {
+            let lhs = 2 * (num_inputs + num_latches + index + 1);
+            let (delta0, delta1) = (cursor.delta()?, cursor.delta()?);
+            let rhs0 = lhs.checked_sub(delta0);
+            let rhs1 = rhs0.and_then(|rhs0| rhs0.checked_sub(delta1));
+            match (rhs0, rhs1) {
+                (Some(rhs0), Some(rhs1)) => AndGate { lhs, rhs0, rhs1 },
+                _ => return Err(parse_error(cursor.line, format!("invalid binary AND gate {}", lhs))),
+            }
+        } else {
+            let numbers = cursor.numbers(3, 3)?;
+            AndGate { lhs: numbers[0], rhs0: numbers[1], rhs1: numbers[2] }
+        };
+        for lit in [gate.lhs, gate.rhs0, gate.rhs1] {
+            check(lit, cursor.line)?;
+        }
+        if gate.lhs % 2 == 1 || gate.lhs < 2 {
+            return Err(parse_error(cursor.line, format!("invalid AND gate output {}", gate.lhs)));
+        }
+        aig.ands.push(gate);
+    }
+
+    // The symbol table and comments that may follow are ignored
+    Ok(aig)
+}
+
+/// Parse an AIGER file
+pub fn parse_file<P: AsRef<Path>>(path: P) -> Result<Aig> {
+    parse_reader(File::open(path)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wrapper::{ParkissatSolver, SolverConfig, SolverResult};
+
+    fn solve(unrolling: &Unrolling) -> Option<Vec<i32>> {
+        let mut solver = ParkissatSolver::new().unwrap();
+        solver.configure(&SolverConfig::default()).unwrap();
+        solver.add_formula(&unrolling.formula).unwrap();
+        match solver.solve().unwrap() {
+            SolverResult::Sat => Some(solver.get_model().unwrap()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_toggle_reaches_bad_state() {
+        // One latch that flips every step and starts at 0; the output is the latch
+        for input in [&b"aag 1 0 1 1 0\n2 3\n2\n"[..], b"aig 1 0 1 1 0\n3\n2\n"] {
+            let aig = parse_bytes(input).unwrap();
+            assert_eq!(aig.latches(), &[Latch { lit: 2, next: 3, reset: Some(false) }]);
+
+            assert!(solve(&aig.unroll(0).unwrap()).is_none());
+            assert!(solve(&aig.unroll(1).unwrap()).is_some());
+        }
+    }
+
+    #[test]
+    fn test_and_gate_trace() {
+        // Bad state: both inputs true; the binary gate is 6 = 4 ∧ 2, deltas 2 and 2
+        let ascii = parse_bytes(b"aag 3 2 0 0 1 1\n2\n4\n6\n6 4 2\nc\ncomment\n").unwrap();
+        let binary = parse_bytes(b"aig 3 2 0 0 1 1\n6\n\x02\x02").unwrap();
+        assert_eq!(ascii, binary);
+
+        let unrolling = binary.unroll(2).unwrap();
+        let model = solve(&unrolling).unwrap();
+        let trace = unrolling.input_trace(&model);
+        assert_eq!(trace.len(), 3);
+        assert!(trace.iter().any(|frame| frame == &[true, true]));
+    }
+
+    #[test]
+    fn test_invalid_aiger() {
+        for (input, line) in [
+            (&b"aag 1 1 0 0\n"[..], 1),
+            (b"aag 1 1 0 0 0\n4\n", 2),
+            (b"aag 1 0 0 0 0 0 0 1 0\n", 1),
+            (b"aig 1 0 0 0 1\n", 1),
+        ] {
+            match parse_bytes(input) {
+                Err(ParkissatError::ParseError { line: found, .. }) => assert_eq!(found, line),
+                other => panic!("{:?}", other),
+            }
+        }
+    }
+}
@@ -0,0 +1,311 @@
+//! Automatic strategy selection from instance features
+//!
+//! [`InstanceFeatures`] summarizes a clause set: size, clause-length mix, and
+//! detected structure (XOR constraints encoded in CNF and at-most-one groups
+//! encoded pairwise). [`tune`] maps those features to a portfolio size and
+//! preprocessing setting, and reports why each choice was made so users can
+//! see (and override) what the crate picked.
+
+use crate::error::Result;
+use crate::wrapper::{ParkissatSolver, SolverConfig};
+use std::collections::{HashMap, HashSet};
+
+/// Instances with fewer clauses than this are solved single-threaded
+const SMALL_INSTANCE_CLAUSES: usize = 10_000;
+
+/// Instances with more clauses than this use every available CPU
+const LARGE_INSTANCE_CLAUSES: usize = 200_000;
+
+/// Largest XOR arity recognized (a k-ary XOR needs 2^(k-1) clauses)
+const MAX_XOR_ARITY: usize = 6;
+
+/// Structural features of a CNF instance
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InstanceFeatures {
+    /// Highest variable index used
+    pub variables: usize,
+    /// Number of clauses
+    pub clauses: usize,
+    /// Total number of literal occurrences
+    pub literals: usize,
+    /// Clauses of length 1
+    pub unit_clauses: usize,
+    /// Clauses of length 2
+    pub binary_clauses: usize,
+    /// Clauses of length 3
+    pub ternary_clauses: usize,
+    /// Clauses longer than 3
+    pub long_clauses: usize,
+    /// Length of the longest clause
+    pub max_clause_length: usize,
+    /// Number of XOR constraints detected in CNF form
+    pub xor_constraints: usize,
+    /// Number of pairwise at-most-one groups (of at least 3 literals) detected
+    pub amo_constraints: usize,
+}
+
+impl InstanceFeatures {
+    /// Compute the features of a clause set
+    pub fn from_clauses<C: AsRef<[i32]>>(clauses: &[C]) -> Self {
+        let mut features = InstanceFeatures {
+            clauses: clauses.len(),
+            ..InstanceFeatures::default()
+        };
+
+        for clause in clauses {
+            let clause = clause.as_ref();
+            features.literals += clause.len();
+            features.max_clause_length = features.max_clause_length.max(clause.len());
+
+            match clause.len() {
+                1 => features.unit_clauses += 1,
+                2 => features.binary_clauses += 1,
+                3 => features.ternary_clauses += 1,
+                n if n > 3 => features.long_clauses += 1,
+                _ => {}
+            }
+
+            for &lit in clause {
+                features.variables = features.variables.max(lit.unsigned_abs() as usize);
+            }
+        }
+
+        features.xor_constraints = count_xors(clauses);
+        features.amo_constraints = count_amo_groups(clauses);
+        features
+    }
+
+    /// Ratio of clauses to variables
+    pub fn clause_variable_ratio(&self) -> f64 {
+        if self.variables == 0 {
+            return 0.0;
+        }
+        self.clauses as f64 / self.variables as f64
+    }
+
+    /// Fraction of clauses that are binary
+    pub fn binary_ratio(&self) -> f64 {
+        if self.clauses == 0 {
+            return 0.0;
+        }
+        self.binary_clauses as f64 / self.clauses as f64
+    }
+}
+
+/// A configuration chosen by [`tune`], with the reasoning behind it
+#[derive(Debug, Clone)]
+pub struct Strategy {
+    /// The selected solver configuration
+    pub config: SolverConfig,
+    /// Human-readable explanation of each choice
+    pub reasons: Vec<String>,
+}
+
+/// Pick a solver configuration for an instance with the given features
+pub fn tune(features: &InstanceFeatures) -> Strategy {
+    let mut config = SolverConfig::default();
+    let mut reasons = Vec::new();
+
+    if features.clauses < SMALL_INSTANCE_CLAUSES {
+        config.num_threads = 1;
+        reasons.push(format!(
+            "{} clauses: small instance, a single worker avoids portfolio overhead",
+            features.clauses
+        ));
+    } else if features.clauses <= LARGE_INSTANCE_CLAUSES {
+        let threads = num_cpus::get().clamp(1, 4);
+        config.num_threads = threads as isize;
+        reasons.push(format!(
+            "{} clauses: medium instance, using a portfolio of {} workers",
+            features.clauses, threads
+        ));
+    } else {
+        config.num_threads = -1;
+        reasons.push(format!(
+            "{} clauses: large instance, using one worker per available CPU",
+            features.clauses
+        ));
+    }
+
+    let xor_heavy = features.xor_constraints * 10 >= features.clauses.max(1)
+        || features.xor_constraints >= 1_000;
+
+    if xor_heavy {
+        config.enable_preprocessing = false;
+        reasons.push(format!(
+            "{} XOR constraints detected: preprocessing disabled, elimination tends to blow up XOR chains",
+            features.xor_constraints
+        ));
+    } else if features.clauses >= SMALL_INSTANCE_CLAUSES || features.amo_constraints > 0 {
+        config.enable_preprocessing = true;
+        reasons.push(format!(
+            "clause/variable ratio {:.2}, {} at-most-one groups: preprocessing enabled",
+            features.clause_variable_ratio(), features.amo_constraints
+        ));
+    } else {
+        config.enable_preprocessing = false;
+        reasons.push("small unstructured instance: preprocessing skipped".to_string());
+    }
+
+    Strategy { config, reasons }
+}
+
+impl ParkissatSolver {
+    /// Configure the solver automatically for the given clauses and add them
+    ///
+    /// Returns the strategy that was applied.
+    pub fn configure_auto<C: AsRef<[i32]>>(&mut self, clauses: &[C]) -> Result<Strategy> {
+        let strategy = tune(&InstanceFeatures::from_clauses(clauses));
+        self.configure(&strategy.config)?;
+
+        for clause in clauses {
+            self.add_clause(clause.as_ref())?;
+        }
+
+        Ok(strategy)
+    }
+}
+
+/// Count XOR constraints encoded as the full set of 2^(k-1) clauses over k variables
+fn count_xors<C: AsRef<[i32]>>(clauses: &[C]) -> usize {
+    // Variable set -> distinct sign patterns, split by parity of negations
+    let mut groups: HashMap<Vec<u32>, [HashSet<Vec<i32>>; 2]> = HashMap::new();
+
+    for clause in clauses {
+        let clause = clause.as_ref();
+        if clause.len() < 3 || clause.len() > MAX_XOR_ARITY {
+            continue;
+        }
+
+        let mut sorted = clause.to_vec();
+        sorted.sort_unstable_by_key(|lit| lit.unsigned_abs());
+        let vars: Vec<u32> = sorted.iter().map(|lit| lit.unsigned_abs()).collect();
+        if vars.windows(2).any(|pair| pair[0] == pair[1]) {
+            continue;
+        }
+
+        let parity = sorted.iter().filter(|&&lit| lit < 0).count() % 2;
+        groups.entry(vars).or_default()[parity].insert(sorted);
+    }
+
+    groups
+        .iter()
+        .map(|(vars, patterns)| {
+            let needed = 1usize << (vars.len() - 1);
+            patterns.iter().filter(|clauses| clauses.len() == needed).count()
+        })
+        .sum()
+}
+
+/// Count greedy cliques of size at least 3 in the graph of negative binary clauses
+fn count_amo_groups<C: AsRef<[i32]>>(clauses: &[C]) -> usize {
+    let mut conflicts: HashMap<i32, HashSet<i32>> = HashMap::new();
+
+    for clause in clauses {
+        if let [a, b] = *clause.as_ref() {
+            if a != b && a != -b {
+                // (¬a ∨ ¬b) forbids literals -a and -b from both being true
+                conflicts.entry(-a).or_default().insert(-b);
+                conflicts.entry(-b).or_default().insert(-a);
+            }
+        }
+    }
+
+    let mut literals: Vec<i32> = conflicts.keys().copied().collect();
+    literals.sort_unstable();
+
+    let mut used = HashSet::new();
+    let mut groups = 0;
+
+    for &lit in &literals {
+        if used.contains(&lit) {
+            continue;
+        }
+
+        let mut neighbors: Vec<i32> = conflicts[&lit].iter().copied().collect();
+        neighbors.sort_unstable();
+
+        let mut clique = vec![lit];
+        for candidate in neighbors {
+            if used.contains(&candidate) {
+                continue;
+            }
+            if clique.iter().all(|member| conflicts[&candidate].contains(member)) {
+                clique.push(candidate);
+            }
+        }
+
+        if clique.len() >= 3 {
+            groups += 1;
+            used.extend(clique);
+        }
+    }
+
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clause_length_features() {
+        let clauses = vec![vec![1], vec![1, -2], vec![2, 3, -4], vec![1, 2, 3, 4, 5]];
+        let features = InstanceFeatures::from_clauses(&clauses);
+
+        assert_eq!(features.variables, 5);
+        assert_eq!(features.clauses, 4);
+        assert_eq!(features.literals, 11);
+        assert_eq!(features.unit_clauses, 1);
+        assert_eq!(features.binary_clauses, 1);
+        assert_eq!(features.ternary_clauses, 1);
+        assert_eq!(features.long_clauses, 1);
+        assert_eq!(features.max_clause_length, 5);
+    }
+
+    #[test]
+    fn test_structure_detection() {
+        // x1 ⊕ x2 ⊕ x3 = 1
+        let mut clauses = vec![vec![1, 2, 3], vec![1, -2, -3], vec![-1, 2, -3], vec![-1, -2, 3]];
+        // At most one of x4, x5, x6
+        clauses.extend([vec![-4, -5], vec![-4, -6], vec![-5, -6]]);
+
+        let features = InstanceFeatures::from_clauses(&clauses);
+        assert_eq!(features.xor_constraints, 1);
+        assert_eq!(features.amo_constraints, 1);
+    }
+
+    #[test]
+    fn test_tune_small_instance() {
+        let clauses = vec![vec![1, 2], vec![-1, 2]];
+        let strategy = tune(&InstanceFeatures::from_clauses(&clauses));
+
+        assert_eq!(strategy.config.num_threads, 1);
+        assert!(!strategy.config.enable_preprocessing);
+        assert_eq!(strategy.reasons.len(), 2);
+    }
+
+    #[test]
+    fn test_tune_xor_heavy_instance() {
+        let features = InstanceFeatures {
+            variables: 30_000,
+            clauses: 400_000,
+            xor_constraints: 50_000,
+            ..InstanceFeatures::default()
+        };
+        let strategy = tune(&features);
+
+        assert_eq!(strategy.config.num_threads, -1);
+        assert!(!strategy.config.enable_preprocessing);
+    }
+
+    #[test]
+    fn test_configure_auto() {
+        let mut solver = ParkissatSolver::new().unwrap();
+        let strategy = solver.configure_auto(&[vec![1, 2], vec![-1, 2]]).unwrap();
+
+        assert!(solver.is_configured());
+        assert_eq!(solver.variable_count(), 2);
+        assert!(!strategy.reasons.is_empty());
+    }
+}
@@ -0,0 +1,178 @@
+//! Backbone computation
+//!
+//! The backbone of a satisfiable formula is the set of literals that are true
+//! in every model. It is computed by iterative solving: the literals of a
+//! first model are the candidates, and each candidate `l` is tested by
+//! solving under the assumption `¬l`. An UNSAT answer proves `l` part of the
+//! backbone; a SAT answer yields a model that rules out every candidate it
+//! disagrees with. Literals already fixed at decision level 0 are accepted
+//! without a solve, and proven backbone literals are assumed in later tests
+//! to speed them up.
+
+use crate::error::{ParkissatError, Result};
+use crate::wrapper::{ParkissatSolver, SolverResult};
+
+/// Options for backbone computation
+#[derive(Debug, Clone, Default)]
+pub struct BackboneOptions {
+    /// Restrict the backbone to these variables (default: all variables)
+    pub variables: Option<Vec<i32>>,
+
+    /// Stop after this many solves (default: no limit)
+    ///
+    /// Candidates that were not tested in time are reported as undecided.
+    pub max_solves: Option<usize>,
+}
+
+/// Outcome of [`ParkissatSolver::compute_backbone_with`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Backbone {
+    /// Literals proven true in every model, ordered by variable
+    pub literals: Vec<i32>,
+
+    /// Candidate literals left untested because the solve limit was reached
+    pub undecided: Vec<i32>,
+}
+
+impl Backbone {
+    /// Whether every candidate was decided
+    pub fn is_complete(&self) -> bool {
+        self.undecided.is_empty()
+    }
+}
+
+impl ParkissatSolver {
+    /// Compute the literals true in every model of the formula
+    ///
+    /// Fails with `NoSolution` if the formula is unsatisfiable, since it has
+    /// no models to agree on.
+    pub fn compute_backbone(&mut self) -> Result<Vec<i32>> {
+        Ok(self.compute_backbone_with(&BackboneOptions::default())?.literals)
+    }
+
+    /// Compute the backbone with a restricted variable set or a solve limit
+    ///
+    /// Every solve runs against the solver's current clauses, clause groups,
+    /// and scopes; afterwards the last result is that of the final test.
+    pub fn compute_backbone_with(&mut self, options: &BackboneOptions) -> Result<Backbone> {
+        if !self.is_configured() {
+            return Err(ParkissatError::NotConfigured);
+        }
+
+        let mut variables = match &options.variables {
+            Some(variables) => variables.clone(),
+            None => (1..=self.variable_count() as i32).collect(),
+        };
+        for &var in &variables {
+            if var <= 0 {
+                return Err(ParkissatError::InvalidVariable(var));
+            }
+        }
+        variables.sort_unstable();
+        variables.dedup();
+
+        // The first solve finds the candidates, so at least one is needed
+        if options.max_solves == Some(0) {
+            return Err(ParkissatError::InvalidConfiguration(
+                "Backbone solve limit must be positive".to_string()
+            ));
+        }
+        let mut solves_left = options.max_solves.unwrap_or(usize::MAX) - 1;
+        if !self.is_satisfiable_under(&[])? {
+            return Err(ParkissatError::NoSolution);
+        }
+
+        let model = self.get_model()?;
+        let model_value = |model: &[i32], var: i32| model.get(var as usize - 1).map(|&lit| lit > 0);
+        let fixed = self.fixed_literals();
+
+        let mut literals = Vec::new();
+        let mut candidates = Vec::new();
+        for &var in &variables {
+            match fixed.binary_search_by_key(&var, |lit| lit.abs()) {
+                Ok(index) => literals.push(fixed[index]),
+                Err(_) => candidates.push(if model_value(&model, var).unwrap_or(false) { var } else { -var }),
+            }
+        }
+
+        let mut undecided = Vec::new();
+        while let Some(candidate) = candidates.pop() {
+            if solves_left == 0 {
+                undecided.push(candidate);
+                continue;
+            }
+            solves_left -= 1;
+
+            let mut assumptions = literals.clone();
+            assumptions.push(-candidate);
+            if !self.is_satisfiable_under(&assumptions)? {
+                literals.push(candidate);
+                continue;
+            }
+
+            // Candidates the new model disagrees with are not in the backbone
+            let model = self.get_model()?;
+            candidates.retain(|&lit| model_value(&model, lit.abs()) == Some(lit > 0));
+        }
+
+        literals.sort_unstable_by_key(|lit| lit.abs());
+        undecided.sort_unstable_by_key(|lit| lit.abs());
+        Ok(Backbone { literals, undecided })
+    }
+
+    fn is_satisfiable_under(&mut self, assumptions: &[i32]) -> Result<bool> {
+        match self.solve_with_assumptions(assumptions)? {
+            SolverResult::Sat => Ok(true),
+            SolverResult::Unsat => Ok(false),
+            SolverResult::Unknown => Err(ParkissatError::Interrupted),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cnf::solver_with;
+    use crate::wrapper::SolverConfig;
+
+    #[test]
+    fn test_backbone() {
+        // 3 holds whatever 1 is and forces ¬2; 1, 4 and 5 are free
+        let mut solver = solver_with(&SolverConfig::default(), &[&[1, 3], &[-1, 3], &[-3, -2], &[4, 5]]);
+
+        assert_eq!(solver.compute_backbone().unwrap(), vec![-2, 3]);
+
+        let options = BackboneOptions {
+            variables: Some(vec![3, 4]),
+            ..BackboneOptions::default()
+        };
+        assert_eq!(solver.compute_backbone_with(&options).unwrap().literals, vec![3]);
+    }
+
+    #[test]
+    fn test_backbone_solve_limit() {
+        let mut solver = solver_with(&SolverConfig::default(), &[&[1], &[-1, 2], &[2, 3], &[-3, 4]]);
+
+        let options = BackboneOptions {
+            max_solves: Some(1),
+            ..BackboneOptions::default()
+        };
+        let backbone = solver.compute_backbone_with(&options).unwrap();
+        // Only literals fixed by the first solve are proven without testing
+        assert!(backbone.literals.iter().all(|lit| [1, 2].contains(lit)));
+        assert_eq!(backbone.literals.len() + backbone.undecided.len(), 4);
+        assert!(!backbone.is_complete());
+
+        let options = BackboneOptions {
+            max_solves: Some(0),
+            ..BackboneOptions::default()
+        };
+        assert!(solver.compute_backbone_with(&options).is_err());
+    }
+
+    #[test]
+    fn test_backbone_unsat() {
+        let mut solver = solver_with(&SolverConfig::default(), &[&[1], &[-1]]);
+        assert_eq!(solver.compute_backbone().unwrap_err(), ParkissatError::NoSolution);
+    }
+}
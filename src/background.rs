@@ -0,0 +1,139 @@
+//! Solving on a background thread
+//!
+//! [`ParkissatSolver::solve_async`] moves the solver onto a new thread and
+//! returns a [`SolveHandle`] right away. The caller can poll the handle, hand
+//! out [`InterruptHandle`]s to cancel the run from other threads, and
+//! [`join`](SolveHandle::join) to get the solver back with its result.
+
+use crate::error::Result;
+use crate::interrupt::InterruptHandle;
+use crate::wrapper::{ParkissatSolver, SolverResult};
+use std::thread::{self, JoinHandle};
+
+/// A solve running on a background thread
+pub struct SolveHandle {
+    thread: JoinHandle<(ParkissatSolver, Result<SolverResult>)>,
+    interrupt: InterruptHandle,
+}
+
+impl SolveHandle {
+    /// A handle that cancels this solve from any thread
+    pub fn interrupt_handle(&self) -> InterruptHandle {
+        self.interrupt.clone()
+    }
+
+    /// Cancel the solve; [`join`](Self::join) then returns `Unknown` unless it already finished
+    pub fn interrupt(&self) {
+        self.interrupt.interrupt();
+    }
+
+    /// Check whether the solve has finished, without blocking
+    pub fn is_finished(&self) -> bool {
+        self.thread.is_finished()
+    }
+
+    /// Wait for the solve and take back the solver with its result
+    pub fn join(self) -> (ParkissatSolver, Result<SolverResult>) {
+        match self.thread.join() {
+            Ok(finished) => finished,
+            Err(panic) => std::panic::resume_unwind(panic),
+        }
+    }
+}
+
+impl ParkissatSolver {
+    /// Solve on a background thread
+    ///
+    /// Interrupts requested before this call, or sent through the returned
+    /// handle at any later point, cancel the run.
+    pub fn solve_async(self) -> SolveHandle {
+        self.solve_async_with_assumptions(&[])
+    }
+
+    /// Solve under assumptions on a background thread
+    pub fn solve_async_with_assumptions(mut self, assumptions: &[i32]) -> SolveHandle {
+        let interrupt = self.interrupt_handle.clone();
+        let assumptions = assumptions.to_vec();
+
+        let thread = thread::spawn(move || {
+            let result = self.solve_with_assumptions(&assumptions);
+            (self, result)
+        });
+
+        SolveHandle { thread, interrupt }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cnf::{configured_solver, pigeonhole};
+    use crate::error::ParkissatError;
+
+    #[test]
+    fn test_solve_async() {
+        let mut solver = configured_solver();
+        solver.add_clause(&[1, 2]).unwrap();
+        solver.add_clause(&[-1]).unwrap();
+
+        let (solver, result) = solver.solve_async().join();
+        assert_eq!(result.unwrap(), SolverResult::Sat);
+        assert!(solver.get_model_value(2).unwrap());
+
+        let (_, result) = solver.solve_async_with_assumptions(&[-2]).join();
+        assert_eq!(result.unwrap(), SolverResult::Unsat);
+    }
+
+    #[test]
+    fn test_interrupt_before_start_is_honored() {
+        let mut solver = configured_solver();
+        solver.add_clause(&[1, 2]).unwrap();
+
+        let handle = solver.solve_async();
+        handle.interrupt_handle().interrupt();
+        let (mut solver, result) = handle.join();
+        assert_eq!(result.unwrap(), SolverResult::Unknown);
+
+        // The interrupt is spent once the cancelled solve returns
+        assert_eq!(solver.solve().unwrap(), SolverResult::Sat);
+    }
+
+    #[test]
+    fn test_interrupt_before_solve() {
+        let mut solver = configured_solver();
+        solver.add_clause(&[1, 2]).unwrap();
+
+        solver.interrupt();
+        assert_eq!(solver.solve().unwrap(), SolverResult::Unknown);
+        assert_eq!(solver.solve().unwrap(), SolverResult::Sat);
+    }
+
+    #[test]
+    fn test_interrupt_from_another_thread() {
+        let mut solver = configured_solver();
+        solver.add_formula(&pigeonhole(7, 6)).unwrap();
+
+        let handle = solver.solve_async();
+        let interrupt = handle.interrupt_handle();
+        thread::spawn(move || interrupt.interrupt()).join().unwrap();
+
+        let (_, result) = handle.join();
+        assert_ne!(result.unwrap(), SolverResult::Sat);
+    }
+
+    #[test]
+    fn test_unconfigured_solver_is_returned() {
+        let (solver, result) = ParkissatSolver::new().unwrap().solve_async().join();
+        assert_eq!(result.unwrap_err(), ParkissatError::NotConfigured);
+        assert!(!solver.is_configured());
+    }
+
+    #[test]
+    fn test_handle_outlives_solver() {
+        let solver = configured_solver();
+        let handle = solver.solve_async();
+        let interrupt = handle.interrupt_handle();
+        drop(handle.join());
+        interrupt.interrupt();
+    }
+}
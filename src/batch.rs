@@ -0,0 +1,128 @@
+//! Batches of assumption sets against one formula
+//!
+//! [`ParkissatSolver::solve_all_assumptions`] answers many assumption cubes
+//! against the same clauses, the query pattern of bounded model checkers and
+//! test generators. Rather than running the portfolio on one cube at a time,
+//! the cubes are distributed over as many single-threaded solvers as the
+//! solver is configured with threads. Each of them loads a copy of the
+//! clauses once and takes the next unanswered cube whenever it finishes one,
+//! keeping what it learned between cubes.
+
+use crate::error::{ParkissatError, Result};
+use crate::wrapper::{ParkissatSolver, SolverConfig, SolverResult};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+impl ParkissatSolver {
+    /// Solve the formula under each assumption set, in parallel
+    ///
+    /// Returns one result per assumption set, in order. Clause groups,
+    /// scopes, and editable clauses apply as in a solve. The copies run with
+    /// the configured timeout and seed but without a proof, and do not see
+    /// options set with `set_option`, phase hints, or callbacks, so the
+    /// solver's own last result and model are left untouched. With a single
+    /// configured thread, the sets are solved one after the other on this
+    /// solver instead, which keeps the result of the last set.
+    pub fn solve_all_assumptions(&mut self, assumption_sets: &[Vec<i32>]) -> Result<Vec<SolverResult>> {
+        if !self.is_configured() {
+            return Err(ParkissatError::NotConfigured);
+        }
+        if assumption_sets.iter().any(|assumptions| assumptions.contains(&0)) {
+            return Err(ParkissatError::InvalidClause("Assumption cannot be zero".to_string()));
+        }
+
+        let threads = match self.config.num_threads {
+            -1 => num_cpus::get(),
+            threads => threads.max(1) as usize,
+        };
+        let workers = threads.min(assumption_sets.len());
+        if workers <= 1 {
+            return assumption_sets.iter()
+                .map(|assumptions| self.solve_with_assumptions(assumptions))
+                .collect();
+        }
+
+        let clauses = self.all_clauses()?;
+        let mut selectors = self.editable.selectors();
+        selectors.extend(self.groups.enabled_selectors());
+        let config = SolverConfig {
+            num_threads: 1,
+            proof_output: None,
+            ..self.config.clone()
+        };
+        let variable_count = self.variable_count();
+
+        let next = AtomicUsize::new(0);
+        let results = Mutex::new(vec![SolverResult::Unknown; assumption_sets.len()]);
+        let answer = || -> Result<()> {
+            let mut solver = ParkissatSolver::new()?;
+            solver.configure(&config)?;
+            solver.set_variable_count(variable_count)?;
+            solver.add_clauses_iter(&clauses)?;
+
+            loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(assumptions) = assumption_sets.get(index) else {
+                    return Ok(());
+                };
+                let mut all_assumptions = selectors.clone();
+                all_assumptions.extend_from_slice(assumptions);
+                let result = solver.solve_with_assumptions(&all_assumptions)?;
+                results.lock().unwrap()[index] = result;
+            }
+        };
+
+        let outcomes: Vec<Result<()>> = thread::scope(|scope| {
+            let handles: Vec<_> = (0..workers).map(|_| scope.spawn(answer)).collect();
+            handles.into_iter()
+                .map(|handle| handle.join().unwrap_or_else(|panic| std::panic::resume_unwind(panic)))
+                .collect()
+        });
+        outcomes.into_iter().collect::<Result<()>>()?;
+
+        Ok(results.into_inner().unwrap())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cnf::solver_with;
+
+    #[test]
+    fn test_solve_all_assumptions() {
+        let clauses: &[&[i32]] = &[&[1, 2], &[-1, 3], &[-2, -3]];
+        let sets = vec![vec![1], vec![2], vec![1, 2], vec![-1, -2], vec![3], vec![-3, 1]];
+        let expected = [
+            SolverResult::Sat,
+            SolverResult::Sat,
+            SolverResult::Unsat,
+            SolverResult::Unsat,
+            SolverResult::Sat,
+            SolverResult::Unsat,
+        ];
+
+        for threads in [1, 3] {
+            let config = SolverConfig { num_threads: threads, ..SolverConfig::default() };
+            let mut solver = solver_with(&config, clauses);
+            assert_eq!(solver.solve_all_assumptions(&sets).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn test_solve_all_assumptions_with_groups() {
+        let config = SolverConfig { num_threads: 2, ..SolverConfig::default() };
+        let mut solver = solver_with(&config, &[&[1, 2]]);
+        let group = solver.new_clause_group().unwrap();
+        solver.add_clause_to_group(group, &[-1]).unwrap();
+
+        let sets = vec![vec![1], vec![2]];
+        assert_eq!(solver.solve_all_assumptions(&sets).unwrap(), [SolverResult::Unsat, SolverResult::Sat]);
+        solver.disable_group(group).unwrap();
+        assert_eq!(solver.solve_all_assumptions(&sets).unwrap(), [SolverResult::Sat, SolverResult::Sat]);
+
+        assert!(solver.solve_all_assumptions(&[vec![0]]).is_err());
+        assert!(solver.solve_all_assumptions(&[]).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,35 @@
+//! Shared solving service exposing ParKissat-RS over HTTP
+
+use parkissat_sys::daemon::{Daemon, DaemonConfig};
+use std::path::PathBuf;
+use std::time::Duration;
+
+fn usage() -> ! {
+    eprintln!("Usage: parkissat-daemon [--bind ADDR] [--spool DIR] [--jobs N] [--max-threads N] [--max-timeout SECS] [--max-body-bytes N] [--max-connections N]");
+    std::process::exit(2);
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut config = DaemonConfig::default();
+
+    let mut args = std::env::args().skip(1);
+    while let Some(flag) = args.next() {
+        let value = args.next().unwrap_or_else(|| usage());
+        match flag.as_str() {
+            "--bind" => config.bind_addr = value,
+            "--spool" => config.spool_dir = PathBuf::from(value),
+            "--jobs" => config.max_concurrent_jobs = value.parse()?,
+            "--max-threads" => config.max_threads_per_job = value.parse()?,
+            "--max-timeout" => config.max_timeout = Duration::from_secs(value.parse()?),
+            "--max-body-bytes" => config.max_body_bytes = value.parse()?,
+            "--max-connections" => config.max_connections = value.parse()?,
+            _ => usage(),
+        }
+    }
+
+    let daemon = Daemon::new(config)?;
+    println!("Listening on {}", daemon.config().bind_addr);
+    daemon.serve()?;
+
+    Ok(())
+}
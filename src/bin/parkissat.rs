@@ -0,0 +1,78 @@
+//! SAT-competition style solver executable for ParKissat-RS
+//!
+//! Prints `s` and `v` lines on stdout and exits with 10 (SAT), 20 (UNSAT),
+//! or 0 (UNKNOWN).
+
+use parkissat_sys::format;
+use parkissat_sys::{ParkissatSolver, ProofFormat, SolverConfig, SolverResult};
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::Duration;
+
+fn usage() -> ! {
+    eprintln!("Usage: parkissat [--threads N] [--timeout SECS] [--seed N] [--proof FILE] [--binary-proof] [--quiet] <input.cnf>");
+    std::process::exit(1);
+}
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(code) => ExitCode::from(code),
+        Err(err) => {
+            println!("c error: {}", err);
+            println!("s UNKNOWN");
+            ExitCode::from(1)
+        }
+    }
+}
+
+fn run() -> Result<u8, Box<dyn std::error::Error>> {
+    let mut config = SolverConfig::default();
+    let mut input = None;
+    let mut quiet = false;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().unwrap_or_else(|| usage());
+        match arg.as_str() {
+            "--threads" | "-t" => config.num_threads = value().parse()?,
+            "--timeout" => config.timeout = Duration::try_from_secs_f64(value().parse()?)?,
+            "--seed" => config.random_seed = value().parse()?,
+            "--proof" => config.proof_output = Some(PathBuf::from(value())),
+            "--binary-proof" => config.proof_format = ProofFormat::BinaryDrat,
+            "--quiet" | "-q" => quiet = true,
+            "--help" | "-h" => usage(),
+            _ if arg.starts_with('-') || input.is_some() => usage(),
+            _ => input = Some(PathBuf::from(arg)),
+        }
+    }
+    let input = input.unwrap_or_else(|| usage());
+
+    let mut solver = ParkissatSolver::new()?;
+    solver.configure(&config)?;
+    solver.load_dimacs(&input)?;
+
+    #[cfg(all(feature = "signal", unix))]
+    let _guard = solver.interrupt_on_signal()?;
+
+    let result = solver.solve()?;
+
+    if !quiet {
+        let stats = solver.get_statistics()?;
+        println!("c conflicts: {}", stats.conflicts);
+        println!("c decisions: {}", stats.decisions);
+        println!("c propagations: {}", stats.propagations);
+        println!("c wall time: {:.3}s", stats.solve_wall_time.as_secs_f64());
+    }
+
+    let model = match result {
+        SolverResult::Sat => solver.get_model()?,
+        _ => Vec::new(),
+    };
+    format::write_competition_output(std::io::stdout().lock(), result, &model)?;
+
+    Ok(match result {
+        SolverResult::Sat => 10,
+        SolverResult::Unsat => 20,
+        SolverResult::Unknown => 0,
+    })
+}
@@ -0,0 +1,308 @@
+//! Clause sharing between independent solver processes
+//!
+//! A [`ClauseHub`] listens on a TCP socket and relays every clause it receives
+//! from one peer to all other connected peers. Each solver process connects a
+//! [`ClauseBridge`], publishes short learned clauses to it, and periodically
+//! imports the clauses published by the others.
+//!
+//! Clauses travel as frames of a little-endian `u32` length followed by that
+//! many little-endian `i32` literals. Only clauses implied by the shared
+//! formula may be exchanged: importing a clause adds it to the solver as a
+//! regular clause.
+
+use crate::error::{ParkissatError, Result};
+use crate::wrapper::ParkissatSolver;
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+/// Longest clause accepted on the wire
+const MAX_FRAME_LITERALS: usize = 1 << 16;
+
+type Peers = Arc<Mutex<Vec<(u64, TcpStream)>>>;
+
+/// Relays clauses between connected [`ClauseBridge`]s
+pub struct ClauseHub {
+    addr: SocketAddr,
+    shutdown: Arc<AtomicBool>,
+    peers: Peers,
+}
+
+impl ClauseHub {
+    /// Start a hub listening on the given address (use port 0 for any free port)
+    pub fn bind<A: ToSocketAddrs>(addr: A) -> Result<Self> {
+        let listener = TcpListener::bind(addr)?;
+        let addr = listener.local_addr()?;
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let peers: Peers = Arc::new(Mutex::new(Vec::new()));
+
+        {
+            let shutdown = Arc::clone(&shutdown);
+            let peers = Arc::clone(&peers);
+            thread::spawn(move || accept_loop(listener, shutdown, peers));
+        }
+
+        Ok(Self { addr, shutdown, peers })
+    }
+
+    /// Address peers should connect to
+    pub fn local_addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Number of currently connected peers
+    pub fn peer_count(&self) -> usize {
+        self.peers.lock().unwrap().len()
+    }
+}
+
+impl Drop for ClauseHub {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::SeqCst);
+        // Wake the accept loop so it notices the shutdown flag
+        let _ = TcpStream::connect(self.addr);
+
+        for (_, stream) in self.peers.lock().unwrap().drain(..) {
+            let _ = stream.shutdown(std::net::Shutdown::Both);
+        }
+    }
+}
+
+fn accept_loop(listener: TcpListener, shutdown: Arc<AtomicBool>, peers: Peers) {
+    let mut next_id = 0u64;
+
+    for stream in listener.incoming() {
+        if shutdown.load(Ordering::SeqCst) {
+            break;
+        }
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(_) => continue,
+        };
+        let writer = match stream.try_clone() {
+            Ok(writer) => writer,
+            Err(_) => continue,
+        };
+
+        let id = next_id;
+        next_id += 1;
+        peers.lock().unwrap().push((id, writer));
+
+        let peers = Arc::clone(&peers);
+        thread::spawn(move || relay(id, stream, peers));
+    }
+}
+
+/// Forward every clause read from peer `id` to all other peers
+fn relay(id: u64, mut stream: TcpStream, peers: Peers) {
+    while let Ok(clause) = read_frame(&mut stream) {
+        let frame = encode_frame(&clause);
+        // Drop peers whose connection has failed
+        peers.lock().unwrap().retain_mut(|(peer, writer)| {
+            *peer == id || writer.write_all(&frame).is_ok()
+        });
+    }
+
+    peers.lock().unwrap().retain(|(peer, _)| *peer != id);
+}
+
+/// One process's connection to a [`ClauseHub`]
+pub struct ClauseBridge {
+    stream: TcpStream,
+    incoming: Receiver<Vec<i32>>,
+    max_length: usize,
+    published: u64,
+    received: u64,
+}
+
+impl ClauseBridge {
+    /// Connect to a hub, sharing only clauses of at most `max_length` literals
+    pub fn connect<A: ToSocketAddrs>(addr: A, max_length: usize) -> Result<Self> {
+        if max_length == 0 || max_length > MAX_FRAME_LITERALS {
+            return Err(ParkissatError::InvalidConfiguration(
+                format!("Maximum shared clause length must be between 1 and {}", MAX_FRAME_LITERALS)
+            ));
+        }
+
+        let stream = TcpStream::connect(addr)?;
+        stream.set_nodelay(true)?;
+        let mut reader = stream.try_clone()?;
+
+        let (tx, incoming) = mpsc::channel();
+        thread::spawn(move || {
+            while let Ok(clause) = read_frame(&mut reader) {
+                if tx.send(clause).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Ok(Self {
+            stream,
+            incoming,
+            max_length,
+            published: 0,
+            received: 0,
+        })
+    }
+
+    /// Publish a clause to the other peers
+    ///
+    /// Returns `false` without sending if the clause is longer than the
+    /// bridge's maximum length.
+    pub fn publish(&mut self, clause: &[i32]) -> Result<bool> {
+        if clause.contains(&0) {
+            return Err(ParkissatError::InvalidClause("Literal cannot be zero".to_string()));
+        }
+        if clause.is_empty() || clause.len() > self.max_length {
+            return Ok(false);
+        }
+
+        self.stream.write_all(&encode_frame(clause))?;
+        self.published += 1;
+        Ok(true)
+    }
+
+    /// Take every clause received from other peers so far, without blocking
+    pub fn receive(&mut self) -> Vec<Vec<i32>> {
+        let clauses: Vec<Vec<i32>> = self.incoming.try_iter().collect();
+        self.received += clauses.len() as u64;
+        clauses
+    }
+
+    /// Number of clauses published by this bridge
+    pub fn published(&self) -> u64 {
+        self.published
+    }
+
+    /// Number of clauses received by this bridge
+    pub fn received(&self) -> u64 {
+        self.received
+    }
+}
+
+impl ParkissatSolver {
+    /// Add every clause received on the bridge to the formula
+    ///
+    /// Returns the number of clauses imported.
+    pub fn import_shared_clauses(&mut self, bridge: &mut ClauseBridge) -> Result<usize> {
+        if !self.is_configured() {
+            return Err(ParkissatError::NotConfigured);
+        }
+
+        let clauses = bridge.receive();
+        for clause in &clauses {
+            self.add_clause(clause)?;
+        }
+
+        Ok(clauses.len())
+    }
+}
+
+fn encode_frame(clause: &[i32]) -> Vec<u8> {
+    let mut frame = Vec::with_capacity(4 + 4 * clause.len());
+    frame.extend_from_slice(&(clause.len() as u32).to_le_bytes());
+    for &lit in clause {
+        frame.extend_from_slice(&lit.to_le_bytes());
+    }
+    frame
+}
+
+fn read_frame<R: Read>(reader: &mut R) -> Result<Vec<i32>> {
+    let mut word = [0u8; 4];
+    reader.read_exact(&mut word)?;
+
+    let len = u32::from_le_bytes(word) as usize;
+    if len > MAX_FRAME_LITERALS {
+        return Err(ParkissatError::InvalidClause(format!("Shared clause of {} literals is too long", len)));
+    }
+
+    let mut clause = Vec::with_capacity(len);
+    for _ in 0..len {
+        reader.read_exact(&mut word)?;
+        clause.push(i32::from_le_bytes(word));
+    }
+
+    Ok(clause)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wrapper::{SolverConfig, SolverResult};
+    use std::time::{Duration, Instant};
+
+    fn wait_for(bridge: &mut ClauseBridge, count: usize) -> Vec<Vec<i32>> {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let mut clauses = Vec::new();
+        while clauses.len() < count && Instant::now() < deadline {
+            clauses.extend(bridge.receive());
+            thread::sleep(Duration::from_millis(10));
+        }
+        clauses
+    }
+
+    fn wait_for_peers(hub: &ClauseHub, count: usize) {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while hub.peer_count() < count && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    #[test]
+    fn test_frame_roundtrip() {
+        let frame = encode_frame(&[1, -2, 3]);
+        assert_eq!(read_frame(&mut frame.as_slice()).unwrap(), vec![1, -2, 3]);
+        assert!(read_frame(&mut &frame[..6]).is_err());
+    }
+
+    #[test]
+    fn test_hub_relays_to_other_peers() {
+        let hub = ClauseHub::bind("127.0.0.1:0").unwrap();
+        let mut a = ClauseBridge::connect(hub.local_addr(), 2).unwrap();
+        let mut b = ClauseBridge::connect(hub.local_addr(), 2).unwrap();
+        wait_for_peers(&hub, 2);
+
+        assert!(a.publish(&[1, -2]).unwrap());
+        assert!(!a.publish(&[1, 2, 3]).unwrap());
+        assert_eq!(a.published(), 1);
+
+        assert_eq!(wait_for(&mut b, 1), vec![vec![1, -2]]);
+        assert_eq!(b.received(), 1);
+        assert!(a.receive().is_empty());
+    }
+
+    #[test]
+    fn test_import_shared_clauses() {
+        let hub = ClauseHub::bind("127.0.0.1:0").unwrap();
+        let mut a = ClauseBridge::connect(hub.local_addr(), 4).unwrap();
+        let mut b = ClauseBridge::connect(hub.local_addr(), 4).unwrap();
+        wait_for_peers(&hub, 2);
+
+        let mut solver = ParkissatSolver::new().unwrap();
+        solver.configure(&SolverConfig::default()).unwrap();
+        solver.add_clause(&[1, 2]).unwrap();
+
+        a.publish(&[-1]).unwrap();
+        a.publish(&[-2]).unwrap();
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let mut imported = 0;
+        while imported < 2 && Instant::now() < deadline {
+            imported += solver.import_shared_clauses(&mut b).unwrap();
+            thread::sleep(Duration::from_millis(10));
+        }
+
+        assert_eq!(imported, 2);
+        assert_eq!(solver.solve().unwrap(), SolverResult::Unsat);
+    }
+
+    #[test]
+    fn test_invalid_bridge_length() {
+        let hub = ClauseHub::bind("127.0.0.1:0").unwrap();
+        assert!(ClauseBridge::connect(hub.local_addr(), 0).is_err());
+    }
+}
@@ -0,0 +1,262 @@
+//! Type-state solver API
+//!
+//! [`ParkissatSolver`] checks at runtime that it has been configured before
+//! clauses are added and that the last answer was SAT before a model is read.
+//! The types here move those checks to compile time: a [`SolverBuilder`] only
+//! collects configuration, [`SolverBuilder::build`] yields a
+//! [`ConfiguredSolver`] that accepts clauses, and only a SAT answer produces
+//! the [`SolvedSolver`] that holds the model. A `SolvedSolver` borrows its
+//! solver, so no clause can be added while the model is in use.
+//!
+//! Adding a clause before configuring does not compile:
+//!
+//! ```compile_fail
+//! let mut builder = parkissat_sys::SolverBuilder::new();
+//! builder.add_clause(&[1, 2]);
+//! ```
+//!
+//! Neither does reading a model without a SAT answer:
+//!
+//! ```compile_fail
+//! let mut solver = parkissat_sys::SolverBuilder::new().build().unwrap();
+//! solver.add_clause(&[1, 2]).unwrap();
+//! solver.get_model();
+//! ```
+
+use crate::cnf::CnfFormula;
+use crate::error::Result;
+use crate::interrupt::InterruptHandle;
+use crate::model::Model;
+use crate::wrapper::{ParkissatSolver, SolverConfig, SolverResult, SolverStatistics, UnknownReason};
+use std::path::Path;
+use std::time::Duration;
+
+/// Collects the configuration of a solver that does not exist yet
+#[derive(Debug, Clone, Default)]
+pub struct SolverBuilder {
+    config: SolverConfig,
+}
+
+impl SolverBuilder {
+    /// Start from the default configuration
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replace the whole configuration
+    pub fn with_config(mut self, config: SolverConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Number of parallel threads (-1 = use all available CPUs)
+    pub fn num_threads(mut self, num_threads: isize) -> Self {
+        self.config.num_threads = num_threads;
+        self
+    }
+
+    /// Timeout of each solve (zero = no timeout)
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.config.timeout = timeout;
+        self
+    }
+
+    /// Random seed for diversification
+    pub fn random_seed(mut self, seed: u32) -> Self {
+        self.config.random_seed = seed;
+        self
+    }
+
+    /// Create and configure the solver
+    pub fn build(self) -> Result<ConfiguredSolver> {
+        let mut solver = ParkissatSolver::new()?;
+        solver.configure(&self.config)?;
+        Ok(ConfiguredSolver { solver })
+    }
+}
+
+/// A configured solver that accepts clauses and solves
+pub struct ConfiguredSolver {
+    solver: ParkissatSolver,
+}
+
+impl ConfiguredSolver {
+    /// Add a clause to the problem
+    pub fn add_clause(&mut self, literals: &[i32]) -> Result<()> {
+        self.solver.add_clause(literals)
+    }
+
+    /// Add zero-terminated clauses from a flat buffer
+    pub fn add_clauses(&mut self, literals: &[i32]) -> Result<()> {
+        self.solver.add_clauses(literals)
+    }
+
+    /// Add every clause of a formula
+    pub fn add_formula(&mut self, formula: &CnfFormula) -> Result<()> {
+        self.solver.add_formula(formula)
+    }
+
+    /// Load a problem from a DIMACS file
+    pub fn load_dimacs<P: AsRef<Path>>(&mut self, path: P) -> Result<()> {
+        self.solver.load_dimacs(path)
+    }
+
+    /// Allocate a fresh variable
+    pub fn new_variable(&mut self) -> Result<i32> {
+        self.solver.new_variable()
+    }
+
+    /// Solve the current problem
+    pub fn solve(&mut self) -> Result<SolveOutcome<'_>> {
+        self.solve_with_assumptions(&[])
+    }
+
+    /// Solve under assumptions that hold for this call only
+    pub fn solve_with_assumptions(&mut self, assumptions: &[i32]) -> Result<SolveOutcome<'_>> {
+        match self.solver.solve_with_assumptions(assumptions)? {
+            SolverResult::Sat => {
+                let model = self.solver.model()?;
+                Ok(SolveOutcome::Sat(SolvedSolver {
+                    solver: &self.solver,
+                    model,
+                }))
+            }
+            SolverResult::Unsat => Ok(SolveOutcome::Unsat {
+                failed_assumptions: self.solver.failed_assumptions()?,
+            }),
+            SolverResult::Unknown => Ok(SolveOutcome::Unknown(self.solver.unknown_reason())),
+        }
+    }
+
+    /// Get a thread-safe handle that interrupts this solver
+    pub fn interrupt_handle(&self) -> InterruptHandle {
+        self.solver.interrupt_handle()
+    }
+
+    /// Get solver statistics
+    pub fn statistics(&self) -> Result<SolverStatistics> {
+        self.solver.get_statistics()
+    }
+
+    /// The underlying solver, for the parts of the API not mirrored here
+    pub fn as_solver(&self) -> &ParkissatSolver {
+        &self.solver
+    }
+
+    /// Give up the compile-time checks and return the underlying solver
+    pub fn into_solver(self) -> ParkissatSolver {
+        self.solver
+    }
+}
+
+impl std::fmt::Debug for ConfiguredSolver {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ConfiguredSolver").finish_non_exhaustive()
+    }
+}
+
+/// Answer of [`ConfiguredSolver::solve`]
+#[derive(Debug)]
+pub enum SolveOutcome<'a> {
+    /// The formula is satisfiable; the model is available
+    Sat(SolvedSolver<'a>),
+    /// The formula is unsatisfiable under the assumptions
+    Unsat {
+        /// Assumptions used to refute the call (empty if the formula itself is UNSAT)
+        failed_assumptions: Vec<i32>,
+    },
+    /// The search stopped without an answer
+    Unknown(Option<UnknownReason>),
+}
+
+impl SolveOutcome<'_> {
+    /// The plain result without the data attached to it
+    pub fn result(&self) -> SolverResult {
+        match self {
+            SolveOutcome::Sat(_) => SolverResult::Sat,
+            SolveOutcome::Unsat { .. } => SolverResult::Unsat,
+            SolveOutcome::Unknown(_) => SolverResult::Unknown,
+        }
+    }
+
+    /// Whether the search stopped because the timeout or deadline elapsed
+    ///
+    /// Statistics of the stopped search remain available from the solver.
+    pub fn is_timeout(&self) -> bool {
+        matches!(self, SolveOutcome::Unknown(Some(UnknownReason::Timeout)))
+    }
+}
+
+/// A solver whose last answer was SAT, borrowed for as long as the model is read
+pub struct SolvedSolver<'a> {
+    solver: &'a ParkissatSolver,
+    model: Model,
+}
+
+impl SolvedSolver<'_> {
+    /// The satisfying assignment
+    pub fn model(&self) -> &Model {
+        &self.model
+    }
+
+    /// The model as signed DIMACS literals
+    pub fn get_model(&self) -> Vec<i32> {
+        self.model.to_dimacs()
+    }
+
+    /// Value of a variable (`None` if it is outside the model)
+    pub fn get_model_value(&self, variable: i32) -> Option<bool> {
+        self.solver.get_model_value(variable).ok()
+    }
+
+    /// Check the model against every added clause
+    pub fn verify_model(&self) -> Result<bool> {
+        self.solver.verify_model()
+    }
+
+    /// Index of the portfolio worker that found the model
+    pub fn winning_worker(&self) -> Option<usize> {
+        self.solver.winning_worker()
+    }
+}
+
+impl std::fmt::Debug for SolvedSolver<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SolvedSolver").field("model", &self.model).finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_type_state_flow() {
+        let mut solver = SolverBuilder::new().num_threads(2).random_seed(7).build().unwrap();
+        solver.add_clause(&[1, 2]).unwrap();
+        solver.add_clauses(&[-1, 3, 0]).unwrap();
+
+        match solver.solve().unwrap() {
+            SolveOutcome::Sat(solved) => {
+                assert!(solved.verify_model().unwrap());
+                assert_eq!(solved.get_model(), solved.model().to_dimacs());
+                assert_eq!(solved.get_model_value(9), None);
+            }
+            outcome => panic!("expected SAT, got {:?}", outcome.result()),
+        }
+
+        // The model is released, so clauses can be added again
+        solver.add_clause(&[-3]).unwrap();
+        let outcome = solver.solve_with_assumptions(&[-2]).unwrap();
+        assert_eq!(outcome.result(), SolverResult::Unsat);
+        assert!(matches!(outcome, SolveOutcome::Unsat { failed_assumptions } if failed_assumptions == vec![-2]));
+
+        let solver = solver.into_solver();
+        assert!(solver.is_configured());
+    }
+
+    #[test]
+    fn test_invalid_configuration_fails_to_build() {
+        assert!(SolverBuilder::new().num_threads(0).build().is_err());
+    }
+}
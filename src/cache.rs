@@ -0,0 +1,339 @@
+//! Formula fingerprints and result caching
+//!
+//! A [`Fingerprint`] is a 128-bit hash of a clause set that does not depend
+//! on the order of clauses, the order of literals within a clause, or
+//! repeated clauses, so the same subproblem built twice (e.g. in a CEGAR
+//! loop) hashes the same. [`ParkissatSolver::solve_cached`] looks the
+//! fingerprint and the assumptions up in a [`ResultCache`] before solving and
+//! stores definite answers afterwards. [`MemoryCache`] keeps entries for the
+//! life of the process, [`DiskCache`] keeps them in a directory across runs;
+//! other stores plug in by implementing the trait.
+
+use crate::cnf::CnfFormula;
+use crate::count::SplitMix64;
+use crate::ensemble::parse_competition_output;
+use crate::error::Result;
+use crate::format::write_competition_output;
+use crate::wrapper::{ParkissatSolver, SolverResult};
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Order-insensitive hash of a clause set and its variable count
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Fingerprint(pub u128);
+
+/// 32 lowercase hex digits
+impl fmt::Display for Fingerprint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:032x}", self.0)
+    }
+}
+
+/// Two independent 64-bit lanes, stable across platforms and processes
+#[derive(Clone, Copy)]
+struct Hasher128(u64, u64);
+
+impl Hasher128 {
+    fn new() -> Self {
+        Self(0x243f_6a88_85a3_08d3, 0x1319_8a2e_0370_7344)
+    }
+
+    fn write(&mut self, value: u64) {
+        self.0 = SplitMix64(self.0 ^ value).next_u64();
+        self.1 = SplitMix64(self.1.rotate_left(29) ^ value).next_u64();
+    }
+
+    fn finish(self) -> u128 {
+        (u128::from(self.0) << 64) | u128::from(self.1)
+    }
+}
+
+impl Fingerprint {
+    /// Fingerprint of a clause set over `num_vars` variables
+    pub fn of<I, C>(clauses: I, num_vars: usize) -> Self
+    where
+        I: IntoIterator<Item = C>,
+        C: AsRef<[i32]>,
+    {
+        let mut literals = Vec::new();
+        let mut hashes: Vec<u128> = clauses
+            .into_iter()
+            .map(|clause| {
+                literals.clear();
+                literals.extend_from_slice(clause.as_ref());
+                literals.sort_unstable();
+                literals.dedup();
+
+                let mut hasher = Hasher128::new();
+                for &lit in &literals {
+                    hasher.write(lit as i64 as u64);
+                }
+                hasher.write(literals.len() as u64);
+                hasher.finish()
+            })
+            .collect();
+        hashes.sort_unstable();
+        hashes.dedup();
+
+        let mut hasher = Hasher128::new();
+        hasher.write(num_vars as u64);
+        hasher.write(hashes.len() as u64);
+        for hash in hashes {
+            hasher.write((hash >> 64) as u64);
+            hasher.write(hash as u64);
+        }
+        Fingerprint(hasher.finish())
+    }
+}
+
+impl CnfFormula {
+    /// Order-insensitive fingerprint of the formula
+    pub fn fingerprint(&self) -> Fingerprint {
+        Fingerprint::of(self.clauses(), self.num_vars())
+    }
+}
+
+/// Cache lookup key: a formula and a set of assumptions
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CacheKey {
+    /// Fingerprint of the clauses
+    pub formula: Fingerprint,
+    /// Sorted and deduplicated, since their order does not change the answer
+    pub assumptions: Vec<i32>,
+}
+
+impl CacheKey {
+    /// Key for solving `formula` under `assumptions`
+    pub fn new(formula: Fingerprint, assumptions: &[i32]) -> Self {
+        let mut assumptions = assumptions.to_vec();
+        assumptions.sort_unstable();
+        assumptions.dedup();
+        Self { formula, assumptions }
+    }
+}
+
+/// A definite answer remembered for a [`CacheKey`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CachedResult {
+    /// The answer
+    pub result: SolverResult,
+    /// The model, if the answer is SAT
+    pub model: Option<Vec<i32>>,
+}
+
+/// A store of solve results
+pub trait ResultCache {
+    /// The entry for `key`, if any
+    fn get(&mut self, key: &CacheKey) -> Result<Option<CachedResult>>;
+
+    /// Remember an entry, replacing any previous one for `key`
+    fn insert(&mut self, key: CacheKey, entry: CachedResult) -> Result<()>;
+}
+
+/// Result cache held in memory
+#[derive(Debug, Default)]
+pub struct MemoryCache {
+    entries: HashMap<CacheKey, CachedResult>,
+}
+
+impl MemoryCache {
+    /// Create an empty cache
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of entries
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the cache holds no entries
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Forget every entry
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+impl ResultCache for MemoryCache {
+    fn get(&mut self, key: &CacheKey) -> Result<Option<CachedResult>> {
+        Ok(self.entries.get(key).cloned())
+    }
+
+    fn insert(&mut self, key: CacheKey, entry: CachedResult) -> Result<()> {
+        self.entries.insert(key, entry);
+        Ok(())
+    }
+}
+
+/// Result cache kept in a directory, one file per entry
+///
+/// Each file holds the assumptions on a `c assumptions` line followed by
+/// the answer in SAT-competition format, and is replaced atomically, so
+/// several processes can share a directory.
+#[derive(Debug, Clone)]
+pub struct DiskCache {
+    dir: PathBuf,
+}
+
+impl DiskCache {
+    /// Use `dir` as the cache, creating it if needed
+    pub fn open<P: AsRef<Path>>(dir: P) -> Result<Self> {
+        fs::create_dir_all(dir.as_ref())?;
+        Ok(Self { dir: dir.as_ref().to_path_buf() })
+    }
+
+    fn entry_path(&self, key: &CacheKey) -> PathBuf {
+        let assumptions = Fingerprint::of([&key.assumptions], 0);
+        self.dir.join(format!("{}-{}.sol", key.formula, assumptions))
+    }
+
+    fn assumptions_line(key: &CacheKey) -> String {
+        let literals: Vec<String> = key.assumptions.iter().map(i32::to_string).collect();
+        format!("c assumptions {}", literals.join(" "))
+    }
+}
+
+impl ResultCache for DiskCache {
+    fn get(&mut self, key: &CacheKey) -> Result<Option<CachedResult>> {
+        let contents = match fs::read_to_string(self.entry_path(key)) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err.into()),
+        };
+
+        // A different key whose assumptions hash the same
+        if contents.lines().next().map(str::trim_end) != Some(Self::assumptions_line(key).trim_end()) {
+            return Ok(None);
+        }
+        let (result, model) = parse_competition_output(&contents);
+        Ok(match result {
+            SolverResult::Unknown => None,
+            result => Some(CachedResult { result, model }),
+        })
+    }
+
+    fn insert(&mut self, key: CacheKey, entry: CachedResult) -> Result<()> {
+        let mut contents = Self::assumptions_line(&key).into_bytes();
+        contents.push(b'\n');
+        write_competition_output(&mut contents, entry.result, entry.model.as_deref().unwrap_or_default())?;
+
+        let path = self.entry_path(&key);
+        let partial = path.with_extension(format!("tmp{}", std::process::id()));
+        fs::write(&partial, contents)?;
+        fs::rename(&partial, &path)?;
+        Ok(())
+    }
+}
+
+impl ParkissatSolver {
+    /// Order-insensitive fingerprint of every clause given to the solver
+    ///
+    /// DIMACS files passed to [`load_dimacs`](Self::load_dimacs) are parsed
+    /// again to collect their clauses.
+    pub fn fingerprint(&self) -> Result<Fingerprint> {
+        Ok(Fingerprint::of(self.all_clauses()?, self.variable_count()))
+    }
+
+    /// Solve under assumptions, answering from `cache` when it can
+    ///
+    /// SAT and UNSAT answers are stored in the cache; `Unknown` is returned
+    /// without being stored. A cached answer does not run the solver, so
+    /// the model is only available in the returned entry, not from
+    /// `get_model`. The key covers the clauses and `assumptions` only, so
+    /// avoid toggling clause groups or editable clauses between cached solves.
+    pub fn solve_cached(&mut self, cache: &mut dyn ResultCache, assumptions: &[i32]) -> Result<CachedResult> {
+        let key = CacheKey::new(self.fingerprint()?, assumptions);
+        if let Some(entry) = cache.get(&key)? {
+            return Ok(entry);
+        }
+
+        let result = self.solve_with_assumptions(assumptions)?;
+        let entry = CachedResult {
+            result,
+            model: match result {
+                SolverResult::Sat => Some(self.get_model()?),
+                _ => None,
+            },
+        };
+        if result != SolverResult::Unknown {
+            cache.insert(key, entry.clone())?;
+        }
+        Ok(entry)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cnf::configured_solver;
+
+    #[test]
+    fn test_fingerprint_is_order_insensitive() {
+        let base = Fingerprint::of([vec![1, -2], vec![2, 3], vec![-1]], 3);
+        assert_eq!(Fingerprint::of([vec![-1], vec![3, 2], vec![-2, 1], vec![2, 3]], 3), base);
+        assert_eq!(Fingerprint::of([vec![1, -2, 1], vec![2, 3], vec![-1]], 3), base);
+
+        assert_ne!(Fingerprint::of([vec![1, -2], vec![2, 3], vec![1]], 3), base);
+        assert_ne!(Fingerprint::of([vec![1, -2], vec![2, 3], vec![-1]], 4), base);
+        assert_ne!(Fingerprint::of([vec![1, -2, 2, 3], vec![-1]], 3), base);
+        assert_eq!(base.to_string().len(), 32);
+
+        let mut formula = CnfFormula::with_variables(3);
+        formula.add_clause(&[2, 3]).unwrap();
+        formula.add_clause(&[-1]).unwrap();
+        formula.add_clause(&[-2, 1]).unwrap();
+        assert_eq!(formula.fingerprint(), base);
+
+        let mut solver = configured_solver();
+        solver.add_formula(&formula).unwrap();
+        assert_eq!(solver.fingerprint().unwrap(), base);
+    }
+
+    #[test]
+    fn test_memory_cache() {
+        let mut cache = MemoryCache::new();
+        let mut solver = configured_solver();
+        solver.add_clauses(&[1, 2, 0, -1, 2, 0]).unwrap();
+
+        let entry = solver.solve_cached(&mut cache, &[]).unwrap();
+        assert_eq!(entry.result, SolverResult::Sat);
+        assert!(entry.model.unwrap().contains(&2));
+        assert_eq!(solver.solve_cached(&mut cache, &[-2, 1]).unwrap().result, SolverResult::Unsat);
+        assert_eq!(cache.len(), 2);
+
+        // The same subproblem built in another order is answered from the cache
+        let mut other = configured_solver();
+        other.add_clauses(&[2, -1, 0, 2, 1, 0]).unwrap();
+        let key = CacheKey::new(other.fingerprint().unwrap(), &[1, -2]);
+        let marker = CachedResult { result: SolverResult::Unsat, model: Some(Vec::new()) };
+        cache.insert(key, marker.clone()).unwrap();
+        assert_eq!(other.solve_cached(&mut cache, &[-2, 1, -2]).unwrap(), marker);
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn test_disk_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut solver = configured_solver();
+        solver.add_clauses(&[1, 2, 0, -1, 2, 0]).unwrap();
+
+        let mut cache = DiskCache::open(dir.path().join("results")).unwrap();
+        let entry = solver.solve_cached(&mut cache, &[1]).unwrap();
+        assert_eq!(entry.result, SolverResult::Sat);
+
+        // A new cache over the same directory sees the entry
+        let mut reopened = DiskCache::open(dir.path().join("results")).unwrap();
+        let key = CacheKey::new(solver.fingerprint().unwrap(), &[1]);
+        assert_eq!(reopened.get(&key).unwrap(), Some(entry));
+        assert_eq!(reopened.get(&CacheKey::new(key.formula, &[-1])).unwrap(), None);
+
+        reopened.insert(CacheKey::new(key.formula, &[-2]), CachedResult { result: SolverResult::Unsat, model: None }).unwrap();
+        assert_eq!(solver.solve_cached(&mut cache, &[-2]).unwrap().result, SolverResult::Unsat);
+    }
+}
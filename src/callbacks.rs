@@ -0,0 +1,165 @@
+//! Callbacks into user code during a solve
+//!
+//! [`ParkissatSolver::set_terminate_callback`] installs an IPASIR-style
+//! terminate function. A monitor thread polls it about once per millisecond
+//! while the solver searches; as soon as it returns `true` the workers are
+//! stopped and the solve returns
+//! [`SolverResult::Unknown`](crate::SolverResult::Unknown) with
+//! [`UnknownReason::Interrupted`](crate::UnknownReason::Interrupted).
+//!
+//! [`ParkissatSolver::set_learn_callback`] streams clauses learned by the
+//! portfolio to user code, for example to feed them to a solver in another
+//! process or to log them for analysis. It sees the clauses the workers
+//! export for sharing, collected every `SharingConfig::interval` whether or
+//! not sharing between workers is enabled.
+
+use crate::ffi;
+use crate::wrapper::ParkissatSolver;
+use std::os::raw::{c_int, c_void};
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+
+/// Asked whether the search should stop
+pub(crate) type TerminateCallback = Box<dyn FnMut() -> bool + Send>;
+
+/// Receives learned clauses as DIMACS literals
+pub(crate) type LearnCallback = Box<dyn FnMut(&[i32]) + Send>;
+
+/// Called by the C++ monitor thread with the solver's `terminate` box
+unsafe extern "C" fn terminate_trampoline(state: *mut c_void) -> c_int {
+    let callback = &mut *(state as *mut TerminateCallback);
+    // A panic must not unwind into C++; treat it as a request to stop
+    let stop = panic::catch_unwind(AssertUnwindSafe(callback)).unwrap_or(true);
+    c_int::from(stop)
+}
+
+/// Called by the C++ sharer thread with the solver's `learn` box
+unsafe extern "C" fn learn_trampoline(state: *mut c_void, literals: *const c_int, size: c_int) {
+    let callback = &mut *(state as *mut LearnCallback);
+    let clause = std::slice::from_raw_parts(literals, size as usize);
+    // A panic must not unwind into C++; the clause is simply dropped
+    let _ = panic::catch_unwind(AssertUnwindSafe(|| callback(clause)));
+}
+
+impl ParkissatSolver {
+    /// Stop the search when `callback` returns `true`
+    ///
+    /// The callback runs on a background thread, about once per millisecond,
+    /// during each later solve. It replaces any previous terminate callback.
+    pub fn set_terminate_callback<F>(&mut self, callback: F)
+    where
+        F: FnMut() -> bool + Send + 'static,
+    {
+        let mut callback: Box<TerminateCallback> = Box::new(Box::new(callback));
+        let state = &mut *callback as *mut TerminateCallback as *mut c_void;
+        unsafe {
+            ffi::parkissat_set_terminate(self.solver, state, Some(terminate_trampoline));
+        }
+        self.terminate = Some(callback);
+    }
+
+    /// Remove the terminate callback
+    pub fn clear_terminate_callback(&mut self) {
+        unsafe {
+            ffi::parkissat_set_terminate(self.solver, ptr::null_mut(), None);
+        }
+        self.terminate = None;
+    }
+
+    /// Stream learned clauses of at most `max_len` literals to `callback`
+    ///
+    /// The callback runs on a background thread during each later solve,
+    /// once per clause, and again right after the search for the clauses
+    /// exported last. Which clauses are exported at all is up to the workers,
+    /// which favour short, low-LBD clauses; every clause is implied by the
+    /// formula. It replaces any previous learn callback.
+    pub fn set_learn_callback<F>(&mut self, max_len: usize, callback: F)
+    where
+        F: FnMut(&[i32]) + Send + 'static,
+    {
+        let max_len = c_int::try_from(max_len).unwrap_or(c_int::MAX);
+        let mut callback: Box<LearnCallback> = Box::new(Box::new(callback));
+        let state = &mut *callback as *mut LearnCallback as *mut c_void;
+        unsafe {
+            ffi::parkissat_set_learn(self.solver, state, max_len, Some(learn_trampoline));
+        }
+        self.learn = Some(callback);
+    }
+
+    /// Remove the learn callback
+    pub fn clear_learn_callback(&mut self) {
+        unsafe {
+            ffi::parkissat_set_learn(self.solver, ptr::null_mut(), 0, None);
+        }
+        self.learn = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cnf::pigeonhole;
+    use crate::wrapper::{ParkissatSolver, SolverConfig, SolverResult, UnknownReason};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn test_terminate_callback() {
+        let mut solver = ParkissatSolver::new().unwrap();
+        solver.configure(&SolverConfig::default()).unwrap();
+        // Far too hard to finish in time
+        solver.add_formula(&pigeonhole(12, 11)).unwrap();
+
+        let polls = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&polls);
+        solver.set_terminate_callback(move || counter.fetch_add(1, Ordering::SeqCst) >= 3);
+
+        assert_eq!(solver.solve().unwrap(), SolverResult::Unknown);
+        assert_eq!(solver.unknown_reason(), Some(UnknownReason::Interrupted));
+        assert!(polls.load(Ordering::SeqCst) >= 4);
+    }
+
+    #[test]
+    fn test_clear_terminate_callback() {
+        let mut solver = ParkissatSolver::new().unwrap();
+        solver.configure(&SolverConfig::default()).unwrap();
+        solver.add_clause(&[1, 2]).unwrap();
+        solver.add_clause(&[-1]).unwrap();
+
+        solver.set_terminate_callback(|| true);
+        assert_eq!(solver.solve().unwrap(), SolverResult::Unknown);
+
+        solver.clear_terminate_callback();
+        assert_eq!(solver.solve().unwrap(), SolverResult::Sat);
+    }
+
+    #[test]
+    fn test_learn_callback() {
+        let mut solver = ParkissatSolver::new().unwrap();
+        solver.configure(&SolverConfig::default()).unwrap();
+
+        solver.add_formula(&pigeonhole(6, 5)).unwrap();
+
+        let learned = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&learned);
+        solver.set_learn_callback(8, move |clause| sink.lock().unwrap().push(clause.to_vec()));
+        assert_eq!(solver.solve().unwrap(), SolverResult::Unsat);
+
+        let received = learned.lock().unwrap().len();
+        assert!(received > 0);
+        assert!(learned.lock().unwrap().iter().all(|clause| !clause.is_empty() && clause.len() <= 8));
+
+        solver.clear_learn_callback();
+        assert_eq!(solver.solve().unwrap(), SolverResult::Unsat);
+        assert_eq!(learned.lock().unwrap().len(), received);
+    }
+
+    #[test]
+    fn test_panicking_callback_stops_search() {
+        let mut solver = ParkissatSolver::new().unwrap();
+        solver.configure(&SolverConfig::default()).unwrap();
+        solver.add_formula(&pigeonhole(12, 11)).unwrap();
+
+        solver.set_terminate_callback(|| panic!("stop"));
+        assert_eq!(solver.solve().unwrap(), SolverResult::Unknown);
+    }
+}
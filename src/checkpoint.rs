@@ -0,0 +1,257 @@
+//! Checkpoints of long solves
+//!
+//! [`ParkissatSolver::set_checkpoint`] makes every later solve write a
+//! checkpoint file at a fixed interval and once more when it ends, including
+//! when it is interrupted or times out. A checkpoint holds the clauses the
+//! workers exported so far and each worker's saved phases and branching
+//! scores; it does not hold the formula. After a preemption,
+//! [`ParkissatSolver::resume_from`] restores that progress into a solver
+//! that has loaded the same formula again, so the search continues from the
+//! learned clauses and heuristic state instead of from scratch.
+//!
+//! The file is text, one item per line:
+//!
+//! ```text
+//! c parkissat checkpoint
+//! p checkpoint <variables> <workers>
+//! l <literals> 0              (learned clause)
+//! w <worker>                  (start of a worker's state)
+//! v <variable> <phase> <score>  (phase 1, -1, or 0 = none)
+//! ```
+
+use crate::error::{ParkissatError, Result};
+use crate::ffi;
+use crate::wrapper::ParkissatSolver;
+use std::ffi::CString;
+use std::os::raw::c_int;
+use std::path::Path;
+use std::time::Duration;
+
+/// Phases and scores of one worker, indexed by variable (entry 0 unused)
+#[derive(Debug, Default)]
+struct WorkerState {
+    phases: Vec<i8>,
+    scores: Vec<f64>,
+}
+
+#[derive(Debug, Default)]
+struct Checkpoint {
+    num_vars: usize,
+    learned: Vec<Vec<i32>>,
+    workers: Vec<WorkerState>,
+}
+
+fn parse_error(line: usize, message: &str) -> ParkissatError {
+    ParkissatError::ParseError { line, message: message.to_string() }
+}
+
+fn parse_checkpoint(text: &str) -> Result<Checkpoint> {
+    let mut checkpoint = None;
+
+    for (index, line) in text.lines().enumerate() {
+        let number = index + 1;
+        let mut fields = line.split_whitespace();
+        let Some(kind) = fields.next() else { continue };
+        if kind == "c" {
+            continue;
+        }
+
+        if kind == "p" {
+            if checkpoint.is_some() || fields.next() != Some("checkpoint") {
+                return Err(parse_error(number, "expected a single `p checkpoint` header"));
+            }
+            let mut count = || fields.next().and_then(|field| field.parse::<usize>().ok());
+            let (Some(num_vars), Some(_workers)) = (count(), count()) else {
+                return Err(parse_error(number, "invalid header"));
+            };
+            checkpoint = Some(Checkpoint { num_vars, ..Checkpoint::default() });
+            continue;
+        }
+
+        let Some(checkpoint) = checkpoint.as_mut() else {
+            return Err(parse_error(number, "missing `p checkpoint` header"));
+        };
+        let in_range = |lit: i32| lit != 0 && lit.unsigned_abs() as usize <= checkpoint.num_vars;
+        match kind {
+            "l" => {
+                let literals: Vec<i32> = fields
+                    .map(|field| field.parse::<i32>().map_err(|_| parse_error(number, "invalid literal")))
+                    .collect::<Result<_>>()?;
+                let Some((&0, clause)) = literals.split_last() else {
+                    return Err(parse_error(number, "clause must end with 0"));
+                };
+                if clause.is_empty() || !clause.iter().all(|&lit| in_range(lit)) {
+                    return Err(parse_error(number, "invalid learned clause"));
+                }
+                checkpoint.learned.push(clause.to_vec());
+            }
+            "w" => {
+                checkpoint.workers.push(WorkerState {
+                    phases: vec![0; checkpoint.num_vars + 1],
+                    scores: vec![0.0; checkpoint.num_vars + 1],
+                });
+            }
+            "v" => {
+                let var = fields.next().and_then(|field| field.parse::<i32>().ok());
+                let phase = fields.next().and_then(|field| field.parse::<i8>().ok());
+                let score = fields.next().and_then(|field| field.parse::<f64>().ok());
+                let (Some(var), Some(phase @ -1..=1), Some(score)) = (var, phase, score) else {
+                    return Err(parse_error(number, "invalid variable state"));
+                };
+                if !in_range(var) || var < 0 {
+                    return Err(parse_error(number, "variable out of range"));
+                }
+                let Some(worker) = checkpoint.workers.last_mut() else {
+                    return Err(parse_error(number, "variable state outside a worker section"));
+                };
+                worker.phases[var as usize] = phase;
+                worker.scores[var as usize] = score;
+            }
+            _ => return Err(parse_error(number, "unknown line")),
+        }
+    }
+
+    checkpoint.ok_or_else(|| parse_error(1, "missing `p checkpoint` header"))
+}
+
+impl ParkissatSolver {
+    /// Write a checkpoint to `path` every `interval` during later solves,
+    /// and when each solve ends
+    ///
+    /// The file is replaced atomically. A checkpoint that cannot be written
+    /// makes the solve fail with `IoError`. Learned clauses accumulate in
+    /// the checkpoints until `reset`.
+    pub fn set_checkpoint<P: AsRef<Path>>(&mut self, path: P, interval: Duration) -> Result<()> {
+        if !self.is_configured() {
+            return Err(ParkissatError::NotConfigured);
+        }
+        if interval.is_zero() {
+            return Err(ParkissatError::InvalidConfiguration(
+                "Checkpoint interval must be positive".to_string()
+            ));
+        }
+
+        let path = path.as_ref();
+        let path_str = path.to_str()
+            .ok_or_else(|| ParkissatError::IoError("Invalid checkpoint path".to_string()))?;
+        // Fail early if the checkpoint cannot be created
+        std::fs::OpenOptions::new().create(true).append(true).open(path)
+            .map_err(|e| ParkissatError::IoError(format!("Cannot write checkpoint {}: {}", path_str, e)))?;
+        let c_path = CString::new(path_str)?;
+
+        let interval_ms = interval.as_millis().clamp(1, c_int::MAX as u128) as c_int;
+        unsafe {
+            ffi::parkissat_set_checkpoint(self.solver, c_path.as_ptr(), interval_ms);
+        }
+        self.check_error()
+    }
+
+    /// Stop writing checkpoints
+    pub fn clear_checkpoint(&mut self) {
+        unsafe {
+            ffi::parkissat_set_checkpoint(self.solver, std::ptr::null(), 0);
+        }
+    }
+
+    /// Restore the progress saved in a checkpoint into the next solve
+    ///
+    /// The solver must already hold the formula the checkpoint was taken
+    /// from. Its learned clauses are injected into the next solve, and
+    /// each worker starts from the phases and scores of a checkpointed one
+    /// (worker `i` from worker `i` modulo their count, if the thread counts
+    /// differ). Phase hints take precedence.
+    pub fn resume_from<P: AsRef<Path>>(&mut self, path: P) -> Result<()> {
+        if !self.is_configured() {
+            return Err(ParkissatError::NotConfigured);
+        }
+
+        let text = std::fs::read_to_string(path)?;
+        let checkpoint = parse_checkpoint(&text)?;
+        if checkpoint.num_vars > self.variable_count() {
+            return Err(ParkissatError::InvalidConfiguration(format!(
+                "Checkpoint covers {} variables but the formula has {}",
+                checkpoint.num_vars,
+                self.variable_count()
+            )));
+        }
+
+        let injector = self.clause_injector();
+        for clause in &checkpoint.learned {
+            injector.inject(clause)?;
+        }
+
+        for (worker, state) in checkpoint.workers.iter().enumerate() {
+            unsafe {
+                ffi::parkissat_resume_worker(
+                    self.solver,
+                    worker as c_int,
+                    state.phases.as_ptr(),
+                    state.scores.as_ptr(),
+                    state.phases.len().min(c_int::MAX as usize) as c_int,
+                );
+            }
+        }
+
+        self.check_error()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cnf::pigeonhole;
+    use crate::wrapper::{SolverConfig, SolverResult};
+
+    #[test]
+    fn test_checkpoint_and_resume() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("solve.checkpoint");
+
+        // A solve cut short by its timeout still leaves a final checkpoint
+        let mut solver = ParkissatSolver::new().unwrap();
+        let config = SolverConfig { num_threads: 2, timeout: Duration::from_millis(300), ..SolverConfig::default() };
+        solver.configure(&config).unwrap();
+        solver.add_formula(&pigeonhole(9, 8)).unwrap();
+        solver.set_checkpoint(&path, Duration::from_millis(50)).unwrap();
+        solver.solve().unwrap();
+
+        let text = std::fs::read_to_string(&path).unwrap();
+        let checkpoint = parse_checkpoint(&text).unwrap();
+        assert_eq!(checkpoint.num_vars, 72);
+        assert_eq!(checkpoint.workers.len(), 2);
+        assert!(!dir.path().join("solve.checkpoint.tmp").exists());
+
+        // A fresh solver with a different thread count picks the search up
+        let mut resumed = ParkissatSolver::new().unwrap();
+        resumed.configure(&SolverConfig { num_threads: 3, ..SolverConfig::default() }).unwrap();
+        resumed.add_formula(&pigeonhole(9, 8)).unwrap();
+        resumed.resume_from(&path).unwrap();
+        assert_eq!(resumed.solve().unwrap(), SolverResult::Unsat);
+    }
+
+    #[test]
+    fn test_resume_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("solve.checkpoint");
+        let mut solver = ParkissatSolver::new().unwrap();
+        assert_eq!(solver.resume_from(&path).unwrap_err(), ParkissatError::NotConfigured);
+
+        solver.configure(&SolverConfig::default()).unwrap();
+        solver.add_clause(&[1, 2]).unwrap();
+        assert!(matches!(solver.set_checkpoint(&path, Duration::ZERO), Err(ParkissatError::InvalidConfiguration(_))));
+        assert!(matches!(solver.resume_from(dir.path().join("missing")), Err(ParkissatError::IoError(_))));
+
+        std::fs::write(&path, "p checkpoint 3 1\nl 1 3 0\n").unwrap();
+        assert!(matches!(solver.resume_from(&path), Err(ParkissatError::InvalidConfiguration(_))));
+
+        std::fs::write(&path, "p checkpoint 2 1\nw 0\nv 1 2 0.5\n").unwrap();
+        assert_eq!(
+            solver.resume_from(&path).unwrap_err(),
+            ParkissatError::ParseError { line: 3, message: "invalid variable state".to_string() }
+        );
+
+        std::fs::write(&path, "c parkissat checkpoint\np checkpoint 2 1\nl -1 2 0\nw 0\nv 1 -1 3.5\nv 2 1 0\n").unwrap();
+        solver.resume_from(&path).unwrap();
+        assert_eq!(solver.solve().unwrap(), SolverResult::Sat);
+    }
+}
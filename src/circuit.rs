@@ -0,0 +1,125 @@
+//! Tseitin-encoded logic gates
+//!
+//! Each constructor allocates a fresh output variable, adds the clauses that
+//! make it equivalent to the gate applied to its inputs, and returns the
+//! output literal. Gates compose, so formulas can be built structurally
+//! (`add_xor(sink, add_and(sink, &[a, b])?, c)?`) rather than clause by
+//! clause.
+//!
+//! Gates work with any [`ClauseSink`], including a configured
+//! [`ParkissatSolver`](crate::ParkissatSolver).
+
+use crate::encodings::ClauseSink;
+use crate::error::Result;
+use crate::types::Lit;
+
+/// Output ↔ all inputs are true (an empty conjunction is true)
+pub fn add_and<S: ClauseSink>(sink: &mut S, inputs: &[Lit]) -> Result<Lit> {
+    let output = sink.fresh_var()?.positive();
+
+    // output → input
+    for &input in inputs {
+        sink.emit_clause(&[!output, input])?;
+    }
+
+    // all inputs → output
+    let mut clause: Vec<Lit> = inputs.iter().map(|&input| !input).collect();
+    clause.push(output);
+    sink.emit_clause(&clause)?;
+
+    Ok(output)
+}
+
+/// Output ↔ at least one input is true (an empty disjunction is false)
+pub fn add_or<S: ClauseSink>(sink: &mut S, inputs: &[Lit]) -> Result<Lit> {
+    // a ∨ b = ¬(¬a ∧ ¬b)
+    let negated: Vec<Lit> = inputs.iter().map(|&input| !input).collect();
+    Ok(!add_and(sink, &negated)?)
+}
+
+/// Output ↔ exactly one of `a` and `b` is true
+pub fn add_xor<S: ClauseSink>(sink: &mut S, a: Lit, b: Lit) -> Result<Lit> {
+    let output = sink.fresh_var()?.positive();
+    sink.emit_clause(&[!output, a, b])?;
+    sink.emit_clause(&[!output, !a, !b])?;
+    sink.emit_clause(&[output, !a, b])?;
+    sink.emit_clause(&[output, a, !b])?;
+    Ok(output)
+}
+
+/// Output ↔ `a` and `b` have the same value
+pub fn add_iff<S: ClauseSink>(sink: &mut S, a: Lit, b: Lit) -> Result<Lit> {
+    Ok(!add_xor(sink, a, b)?)
+}
+
+/// Output ↔ if `condition` then `then` else `otherwise`
+pub fn add_ite<S: ClauseSink>(sink: &mut S, condition: Lit, then: Lit, otherwise: Lit) -> Result<Lit> {
+    let output = sink.fresh_var()?.positive();
+    sink.add_ite(condition, then, otherwise, output)?;
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cnf::CnfFormula;
+    use crate::types::Var;
+    use crate::wrapper::{ParkissatSolver, SolverConfig, SolverResult};
+
+    /// Check that a gate's output matches `expected` on every input assignment
+    fn check_gate(arity: usize, build: impl Fn(&mut CnfFormula, &[Lit]) -> Lit, expected: impl Fn(&[bool]) -> bool) {
+        let mut formula = CnfFormula::new();
+        let inputs: Vec<Lit> = (0..arity).map(|_| formula.new_var().positive()).collect();
+        let output = build(&mut formula, &inputs);
+
+        let mut solver = ParkissatSolver::new().unwrap();
+        solver.configure(&SolverConfig::default()).unwrap();
+        solver.add_formula(&formula).unwrap();
+
+        for mask in 0u32..1 << arity {
+            let values: Vec<bool> = (0..arity).map(|i| mask & (1 << i) != 0).collect();
+            let mut assumptions: Vec<Lit> = (0..arity)
+                .map(|i| Var::new(i as u32 + 1).unwrap().lit(values[i]))
+                .collect();
+
+            // The gate is functional: the output is forced
+            assumptions.push(if expected(&values) { !output } else { output });
+            assert_eq!(solver.solve_with_lit_assumptions(&assumptions).unwrap(), SolverResult::Unsat);
+            assumptions.pop();
+            assumptions.push(if expected(&values) { output } else { !output });
+            assert_eq!(solver.solve_with_lit_assumptions(&assumptions).unwrap(), SolverResult::Sat);
+        }
+    }
+
+    #[test]
+    fn test_and_or() {
+        for arity in 0..=3 {
+            check_gate(arity, |f, x| add_and(f, x).unwrap(), |v| v.iter().all(|&b| b));
+            check_gate(arity, |f, x| add_or(f, x).unwrap(), |v| v.iter().any(|&b| b));
+        }
+    }
+
+    #[test]
+    fn test_binary_gates() {
+        check_gate(2, |f, x| add_xor(f, x[0], x[1]).unwrap(), |v| v[0] != v[1]);
+        check_gate(2, |f, x| add_iff(f, x[0], x[1]).unwrap(), |v| v[0] == v[1]);
+        check_gate(3, |f, x| add_ite(f, x[0], x[1], x[2]).unwrap(), |v| if v[0] { v[1] } else { v[2] });
+    }
+
+    #[test]
+    fn test_nested_circuit_in_solver() {
+        let mut solver = ParkissatSolver::new().unwrap();
+        solver.configure(&SolverConfig::default()).unwrap();
+        let a = solver.new_var().unwrap().positive();
+        let b = solver.new_var().unwrap().positive();
+
+        // (a ∧ ¬b) ⊕ b must hold, and b is false
+        let left = add_and(&mut solver, &[a, !b]).unwrap();
+        let out = add_xor(&mut solver, left, b).unwrap();
+        solver.add_clause_lits(&[out]).unwrap();
+        solver.add_clause_lits(&[!b]).unwrap();
+
+        assert_eq!(solver.solve().unwrap(), SolverResult::Sat);
+        assert!(solver.lit_value(a).unwrap());
+    }
+}
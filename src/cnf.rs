@@ -0,0 +1,278 @@
+//! Standalone CNF formulas
+//!
+//! [`CnfFormula`] collects clauses without a configured solver, so encodings
+//! can be built, combined, and unit-tested on their own and then loaded into
+//! a solver with [`ParkissatSolver::add_formula`].
+
+use crate::error::{ParkissatError, Result};
+use crate::model::Model;
+use crate::types::{Lit, Var};
+#[cfg(test)]
+use crate::wrapper::SolverConfig;
+use crate::wrapper::ParkissatSolver;
+use std::io::Write;
+
+/// A formula in conjunctive normal form
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CnfFormula {
+    clauses: Vec<Vec<i32>>,
+    num_vars: usize,
+}
+
+impl CnfFormula {
+    /// Create an empty formula
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create an empty formula over `num_vars` variables
+    pub fn with_variables(num_vars: usize) -> Self {
+        Self {
+            clauses: Vec::new(),
+            num_vars,
+        }
+    }
+
+    /// Add a clause
+    pub fn add_clause(&mut self, literals: &[i32]) -> Result<()> {
+        if literals.is_empty() {
+            return Err(ParkissatError::InvalidClause("Empty clause".to_string()));
+        }
+
+        for &lit in literals {
+            if lit == 0 {
+                return Err(ParkissatError::InvalidClause("Literal cannot be zero".to_string()));
+            }
+        }
+
+        for &lit in literals {
+            self.num_vars = self.num_vars.max(lit.unsigned_abs() as usize);
+        }
+        self.clauses.push(literals.to_vec());
+        Ok(())
+    }
+
+    /// Add a clause of typed literals
+    pub fn add_clause_lits(&mut self, lits: &[Lit]) -> Result<()> {
+        let literals: Vec<i32> = lits.iter().map(|lit| lit.to_dimacs()).collect();
+        self.add_clause(&literals)
+    }
+
+    /// Allocate a fresh variable
+    pub fn new_var(&mut self) -> Var {
+        self.num_vars += 1;
+        Var::new(self.num_vars as u32).expect("variable count exceeds i32::MAX")
+    }
+
+    /// Number of variables (highest variable index used or allocated)
+    pub fn num_vars(&self) -> usize {
+        self.num_vars
+    }
+
+    /// Number of clauses
+    pub fn num_clauses(&self) -> usize {
+        self.clauses.len()
+    }
+
+    /// Check whether the formula has no clauses
+    pub fn is_empty(&self) -> bool {
+        self.clauses.is_empty()
+    }
+
+    /// The clauses of the formula
+    pub fn clauses(&self) -> &[Vec<i32>] {
+        &self.clauses
+    }
+
+    /// Iterate over the clauses of the formula
+    pub fn iter(&self) -> impl Iterator<Item = &[i32]> {
+        self.clauses.iter().map(|clause| clause.as_slice())
+    }
+
+    /// Add every clause of `other` to this formula, sharing variables
+    pub fn merge(&mut self, other: &CnfFormula) {
+        self.clauses.extend(other.clauses.iter().cloned());
+        self.num_vars = self.num_vars.max(other.num_vars);
+    }
+
+    /// Add every clause of `other` with its variables renamed apart
+    ///
+    /// Variable `v` of `other` becomes `v + self.num_vars()`. Returns the
+    /// offset that was applied.
+    pub fn merge_disjoint(&mut self, other: &CnfFormula) -> usize {
+        let offset = self.num_vars;
+        for clause in &other.clauses {
+            self.clauses.push(
+                clause
+                    .iter()
+                    .map(|&lit| if lit > 0 { lit + offset as i32 } else { lit - offset as i32 })
+                    .collect(),
+            );
+        }
+        self.num_vars += other.num_vars;
+        offset
+    }
+
+    /// Write the formula in DIMACS format
+    pub fn write_dimacs<W: Write>(&self, mut writer: W) -> Result<()> {
+        writeln!(writer, "p cnf {} {}", self.num_vars, self.clauses.len())?;
+        for clause in &self.clauses {
+            for lit in clause {
+                write!(writer, "{} ", lit)?;
+            }
+            writeln!(writer, "0")?;
+        }
+        Ok(())
+    }
+
+    /// Check whether an assignment satisfies every clause
+    ///
+    /// `model` lists signed literals as returned by
+    /// [`ParkissatSolver::get_model`]; unlisted variables count as false.
+    pub fn is_satisfied_by(&self, model: &[i32]) -> bool {
+        let mut values = vec![false; self.num_vars + 1];
+        for &lit in model {
+            let var = lit.unsigned_abs() as usize;
+            if lit > 0 && var < values.len() {
+                values[var] = true;
+            }
+        }
+
+        self.clauses.iter().all(|clause| {
+            clause.iter().any(|&lit| values[lit.unsigned_abs() as usize] == (lit > 0))
+        })
+    }
+
+    /// Check whether a [`Model`] satisfies every clause
+    ///
+    /// Unlike [`is_satisfied_by`](Self::is_satisfied_by), a literal of a
+    /// variable the model leaves unassigned is never true.
+    pub fn evaluate(&self, model: &Model) -> bool {
+        self.clauses.iter().all(|clause| satisfies(model, clause))
+    }
+}
+
+/// Whether the model makes at least one literal of the clause true
+pub(crate) fn satisfies(model: &Model, clause: &[i32]) -> bool {
+    clause.iter().filter_map(|&lit| Lit::new(lit)).any(|lit| model.lit_value(lit) == Some(true))
+}
+
+impl ParkissatSolver {
+    /// Add every clause of a formula
+    pub fn add_formula(&mut self, formula: &CnfFormula) -> Result<()> {
+        if !self.is_configured() {
+            return Err(ParkissatError::NotConfigured);
+        }
+
+        if formula.num_vars() > self.variable_count() {
+            self.set_variable_count(formula.num_vars())?;
+        }
+
+        self.add_clauses_iter(formula.iter())
+    }
+}
+
+/// Pigeons in holes, one variable per pair; unsatisfiable and exponentially
+/// hard for CDCL when there are more pigeons than holes
+#[cfg(test)]
+pub(crate) fn pigeonhole(pigeons: i32, holes: i32) -> CnfFormula {
+    let var = |pigeon: i32, hole: i32| pigeon * holes + hole + 1;
+    let mut formula = CnfFormula::new();
+    for pigeon in 0..pigeons {
+        formula.add_clause(&(0..holes).map(|hole| var(pigeon, hole)).collect::<Vec<_>>()).unwrap();
+    }
+    for hole in 0..holes {
+        for a in 0..pigeons {
+            for b in a + 1..pigeons {
+                formula.add_clause(&[-var(a, hole), -var(b, hole)]).unwrap();
+            }
+        }
+    }
+    formula
+}
+
+/// A solver with the default configuration
+#[cfg(test)]
+pub(crate) fn configured_solver() -> ParkissatSolver {
+    solver_with(&SolverConfig::default(), &[])
+}
+
+/// A solver configured with `config` that holds `clauses`
+#[cfg(test)]
+pub(crate) fn solver_with(config: &SolverConfig, clauses: &[&[i32]]) -> ParkissatSolver {
+    let mut solver = ParkissatSolver::new().unwrap();
+    solver.configure(config).unwrap();
+    for clause in clauses {
+        solver.add_clause(clause).unwrap();
+    }
+    solver
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wrapper::{SolverConfig, SolverResult};
+
+    #[test]
+    fn test_formula_building() {
+        let mut formula = CnfFormula::new();
+        formula.add_clause(&[1, -3]).unwrap();
+        let x = formula.new_var();
+        formula.add_clause_lits(&[x.positive()]).unwrap();
+
+        assert_eq!(x.index(), 4);
+        assert_eq!(formula.num_vars(), 4);
+        assert_eq!(formula.num_clauses(), 2);
+        assert!(formula.add_clause(&[]).is_err());
+        assert!(formula.add_clause(&[1, 0]).is_err());
+        assert_eq!(formula.num_clauses(), 2);
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut a = CnfFormula::new();
+        a.add_clause(&[1, 2]).unwrap();
+        let mut b = CnfFormula::new();
+        b.add_clause(&[-1, 3]).unwrap();
+
+        let mut shared = a.clone();
+        shared.merge(&b);
+        assert_eq!(shared.clauses(), &[vec![1, 2], vec![-1, 3]]);
+        assert_eq!(shared.num_vars(), 3);
+
+        let offset = a.merge_disjoint(&b);
+        assert_eq!(offset, 2);
+        assert_eq!(a.clauses(), &[vec![1, 2], vec![-3, 5]]);
+        assert_eq!(a.num_vars(), 5);
+    }
+
+    #[test]
+    fn test_write_dimacs_and_check_model() {
+        let mut formula = CnfFormula::new();
+        formula.add_clause(&[1, -2]).unwrap();
+        formula.add_clause(&[2]).unwrap();
+
+        let mut output = Vec::new();
+        formula.write_dimacs(&mut output).unwrap();
+        assert_eq!(String::from_utf8(output).unwrap(), "p cnf 2 2\n1 -2 0\n2 0\n");
+
+        assert!(formula.is_satisfied_by(&[1, 2]));
+        assert!(!formula.is_satisfied_by(&[-1, 2]));
+    }
+
+    #[test]
+    fn test_add_formula() {
+        let mut formula = CnfFormula::with_variables(5);
+        formula.add_clause(&[1, 2]).unwrap();
+        formula.add_clause(&[-1]).unwrap();
+
+        let mut solver = ParkissatSolver::new().unwrap();
+        assert_eq!(solver.add_formula(&formula).unwrap_err(), ParkissatError::NotConfigured);
+
+        solver.configure(&SolverConfig::default()).unwrap();
+        solver.add_formula(&formula).unwrap();
+        assert_eq!(solver.variable_count(), 5);
+        assert_eq!(solver.solve().unwrap(), SolverResult::Sat);
+        assert!(formula.is_satisfied_by(&solver.get_model().unwrap()));
+    }
+}
@@ -0,0 +1,477 @@
+//! Model counting on top of ParKissat-RS
+//!
+//! Counting is done by enumeration: every model found is blocked over the
+//! projection variables and the solver is called again, until the formula
+//! becomes unsatisfiable or the limit is exceeded. Blocking clauses are
+//! guarded by a fresh activation literal and retired once counting finishes,
+//! so the solver can keep being used for the original formula afterwards.
+//!
+//! Weighted counts assign a weight to literals; the weight of a projected
+//! model is the product of the weights of its literals, and the weighted
+//! count is the sum over all projected models.
+//!
+//! Approximate counts follow ApproxMC: random XOR constraints over the
+//! projection variables cut the models into cells of roughly equal size. The
+//! number of XORs is raised until a cell holds fewer models than a threshold
+//! that depends on the tolerance; that cell's count, scaled by the number of
+//! cells, estimates the total. The median over repeated rounds meets the
+//! requested confidence. XOR constraints are encoded as clauses in chunks of
+//! four variables, guarded by activation literals like blocking clauses.
+
+use crate::error::{ParkissatError, Result};
+use crate::wrapper::{ParkissatSolver, SolverResult};
+use std::collections::HashMap;
+
+/// Outcome of an exact model count
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModelCount {
+    /// The exact number of models over the projection set
+    Exact(u64),
+    /// There are more models than the given limit
+    Exceeds(u64),
+}
+
+impl ModelCount {
+    /// Get the exact count, if it was computed
+    pub fn exact(&self) -> Option<u64> {
+        match self {
+            ModelCount::Exact(count) => Some(*count),
+            ModelCount::Exceeds(_) => None,
+        }
+    }
+}
+
+/// Outcome of a weighted model count
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WeightedCount {
+    /// The exact weighted count over the projection set
+    Exact(f64),
+    /// Enumeration hit the model limit; the true count is at least this value
+    LowerBound(f64),
+}
+
+impl WeightedCount {
+    /// Get the counted weight, exact or not
+    pub fn value(&self) -> f64 {
+        match self {
+            WeightedCount::Exact(weight) | WeightedCount::LowerBound(weight) => *weight,
+        }
+    }
+
+    /// Check whether the count is exact
+    pub fn is_exact(&self) -> bool {
+        matches!(self, WeightedCount::Exact(_))
+    }
+}
+
+/// Options for approximate model counting
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ApproxOptions {
+    /// Tolerance ε: the estimate lies within a factor of 1 + ε of the true count (default: 0.8)
+    pub epsilon: f64,
+
+    /// Confidence δ: the tolerance holds with probability at least 1 - δ (default: 0.2)
+    pub delta: f64,
+
+    /// Seed for the random XOR constraints (default: 1)
+    pub seed: u64,
+}
+
+impl Default for ApproxOptions {
+    fn default() -> Self {
+        Self {
+            epsilon: 0.8,
+            delta: 0.2,
+            seed: 1,
+        }
+    }
+}
+
+/// Outcome of an approximate model count
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApproxCount {
+    /// There are few enough models to count them exactly
+    Exact(u64),
+    /// `cell_count * 2^hashes` models, within the requested tolerance and confidence
+    Estimate {
+        /// Median number of models in a cell
+        cell_count: u64,
+        /// Number of XOR constraints cutting out that cell
+        hashes: u32,
+    },
+}
+
+impl ApproxCount {
+    /// The (estimated) number of models
+    pub fn value(&self) -> f64 {
+        match *self {
+            ApproxCount::Exact(count) => count as f64,
+            ApproxCount::Estimate { cell_count, hashes } => cell_count as f64 * 2f64.powi(hashes as i32),
+        }
+    }
+
+    /// Check whether the count is exact
+    pub fn is_exact(&self) -> bool {
+        matches!(self, ApproxCount::Exact(_))
+    }
+}
+
+/// SplitMix64, enough for reproducible XOR constraints and cube splits
+pub(crate) struct SplitMix64(pub(crate) u64);
+
+impl SplitMix64 {
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    pub(crate) fn next_bool(&mut self) -> bool {
+        self.next_u64() >> 63 == 1
+    }
+}
+
+impl ParkissatSolver {
+    /// Count the assignments to `projection` that extend to a model
+    ///
+    /// Enumeration stops as soon as more than `limit` models have been found,
+    /// so this is only practical for small projection sets or small counts.
+    pub fn count_models_exact(&mut self, projection: &[i32], limit: u64) -> Result<ModelCount> {
+        if !self.is_configured() {
+            return Err(ParkissatError::NotConfigured);
+        }
+
+        for &var in projection {
+            if var <= 0 {
+                return Err(ParkissatError::InvalidVariable(var));
+            }
+        }
+
+        let mut count = 0u64;
+        let complete = self.enumerate_projected(projection, limit, &[], |_| count += 1)?;
+
+        if complete {
+            Ok(ModelCount::Exact(count))
+        } else {
+            Ok(ModelCount::Exceeds(limit))
+        }
+    }
+
+    /// Compute the weighted model count projected onto the weighted variables
+    ///
+    /// `weights` maps literals to non-negative weights; a literal without an
+    /// entry has weight 1. The projection set is every variable occurring in
+    /// `weights`. At most `limit` projected models are enumerated; if there
+    /// are more, the sum so far is returned as a lower bound.
+    pub fn count_weighted(&mut self, weights: &HashMap<i32, f64>, limit: u64) -> Result<WeightedCount> {
+        if !self.is_configured() {
+            return Err(ParkissatError::NotConfigured);
+        }
+
+        for (&lit, &weight) in weights {
+            if lit == 0 {
+                return Err(ParkissatError::InvalidVariable(lit));
+            }
+            if !weight.is_finite() || weight < 0.0 {
+                return Err(ParkissatError::InvalidConfiguration(
+                    format!("Weight of literal {} must be finite and non-negative", lit)
+                ));
+            }
+        }
+
+        let mut projection: Vec<i32> = weights.keys().map(|lit| lit.abs()).collect();
+        projection.sort_unstable();
+        projection.dedup();
+
+        let literal_weight = |lit: i32| weights.get(&lit).copied().unwrap_or(1.0);
+
+        let mut total = 0.0;
+        let complete = self.enumerate_projected(&projection, limit, &[], |assignment| {
+            total += projection.iter()
+                .zip(assignment)
+                .map(|(&var, &value)| literal_weight(if value { var } else { -var }))
+                .product::<f64>();
+        })?;
+
+        if complete {
+            Ok(WeightedCount::Exact(total))
+        } else {
+            Ok(WeightedCount::LowerBound(total))
+        }
+    }
+
+    /// Estimate the number of assignments to `projection` that extend to a model
+    ///
+    /// Formulas with few projected models are counted exactly. Otherwise the
+    /// estimate is within a factor of `1 + epsilon` of the true count with
+    /// probability at least `1 - delta`. Every round solves the formula many
+    /// times, so tighter tolerances and confidences cost considerably more.
+    pub fn count_models_approx(&mut self, projection: &[i32], options: &ApproxOptions) -> Result<ApproxCount> {
+        if !self.is_configured() {
+            return Err(ParkissatError::NotConfigured);
+        }
+
+        for &var in projection {
+            if var <= 0 {
+                return Err(ParkissatError::InvalidVariable(var));
+            }
+        }
+        let valid = options.epsilon > 0.0 && options.delta > 0.0 && options.delta < 1.0;
+        if !valid {
+            return Err(ParkissatError::InvalidConfiguration(
+                "Tolerance must be positive and confidence between 0 and 1".to_string()
+            ));
+        }
+
+        let mut projection = projection.to_vec();
+        projection.sort_unstable();
+        projection.dedup();
+
+        let epsilon = options.epsilon;
+        let threshold = (1.0 + 9.84 * (1.0 + epsilon / (1.0 + epsilon)) * (1.0 + 1.0 / epsilon).powi(2)).ceil() as u64;
+        let rounds = (17.0 * (3.0 / options.delta).log2()).ceil() as usize;
+
+        let mut found = 0u64;
+        if self.enumerate_projected(&projection, threshold - 1, &[], |_| found += 1)? {
+            return Ok(ApproxCount::Exact(found));
+        }
+
+        let mut rng = SplitMix64(options.seed);
+        let mut estimates = Vec::with_capacity(rounds);
+        for _ in 0..rounds {
+            estimates.push(self.approx_round(&projection, threshold, &mut rng)?);
+        }
+
+        // The median of the estimates cell_count * 2^hashes
+        estimates.sort_unstable_by(|a, b| {
+            let value = |&(count, hashes): &(u64, u32)| count as f64 * 2f64.powi(hashes as i32);
+            value(a).total_cmp(&value(b))
+        });
+        let (cell_count, hashes) = estimates[estimates.len() / 2];
+        Ok(ApproxCount::Estimate { cell_count, hashes })
+    }
+
+    /// Add XORs until a cell holds fewer than `threshold` models; returns its count and the XOR count
+    fn approx_round(&mut self, projection: &[i32], threshold: u64, rng: &mut SplitMix64) -> Result<(u64, u32)> {
+        let mut activations = Vec::new();
+        let outcome = self.approx_cell(projection, threshold, rng, &mut activations);
+
+        for activation in activations {
+            self.add_clause(&[-activation])?;
+        }
+        outcome
+    }
+
+    fn approx_cell(&mut self, projection: &[i32], threshold: u64, rng: &mut SplitMix64, activations: &mut Vec<i32>) -> Result<(u64, u32)> {
+        loop {
+            let activation = self.new_variable()?;
+            activations.push(activation);
+
+            let variables: Vec<i32> = projection.iter().copied().filter(|_| rng.next_bool()).collect();
+            self.add_xor(&variables, rng.next_bool(), activation)?;
+
+            let mut count = 0u64;
+            let complete = self.enumerate_projected(projection, threshold - 1, activations, |_| count += 1)?;
+            if complete || activations.len() >= projection.len() {
+                return Ok((count, activations.len() as u32));
+            }
+        }
+    }
+
+    /// Add `XOR(variables) = parity`, active while `activation` is assumed
+    fn add_xor(&mut self, variables: &[i32], parity: bool, activation: i32) -> Result<()> {
+        let mut variables = variables.to_vec();
+        let mut parity = parity;
+
+        // Replace three variables at a time by an auxiliary variable equal to their XOR
+        while variables.len() > 4 {
+            let aux = self.new_variable()?;
+            let mut chunk: Vec<i32> = variables.drain(..3).collect();
+            chunk.push(aux);
+            self.add_xor_clauses(&chunk, false, activation)?;
+            variables.push(aux);
+        }
+
+        if variables.is_empty() {
+            if parity {
+                self.add_clause(&[-activation])?;
+            }
+            return Ok(());
+        }
+        if variables.len() == 1 && !parity {
+            // x = 0 is the unit ¬x
+            variables[0] = -variables[0];
+            parity = true;
+        }
+        self.add_xor_clauses(&variables, parity, activation)
+    }
+
+    /// One clause per assignment of at most four variables with the wrong parity
+    fn add_xor_clauses(&mut self, variables: &[i32], parity: bool, activation: i32) -> Result<()> {
+        let mut clause = Vec::with_capacity(variables.len() + 1);
+        for assignment in 0u32..(1 << variables.len()) {
+            if (assignment.count_ones() % 2 == 1) == parity {
+                continue;
+            }
+            clause.clear();
+            clause.push(-activation);
+            for (bit, &var) in variables.iter().enumerate() {
+                clause.push(if assignment & (1 << bit) != 0 { -var } else { var });
+            }
+            self.add_clause(&clause)?;
+        }
+        Ok(())
+    }
+
+    /// Enumerate up to `limit` distinct assignments to `projection` under `assumptions`
+    ///
+    /// Calls `on_model` with the value of each projection variable for every
+    /// assignment found. Returns `true` if all assignments were enumerated.
+    fn enumerate_projected<F>(&mut self, projection: &[i32], limit: u64, assumptions: &[i32], mut on_model: F) -> Result<bool>
+    where
+        F: FnMut(&[bool]),
+    {
+        let activation = self.new_variable()?;
+        let outcome = self.enumerate_blocked(projection, limit, activation, assumptions, &mut on_model);
+
+        // Retire the blocking clauses regardless of how enumeration ended
+        self.add_clause(&[-activation])?;
+
+        outcome
+    }
+
+    fn enumerate_blocked<F>(&mut self, projection: &[i32], limit: u64, activation: i32, assumptions: &[i32], on_model: &mut F) -> Result<bool>
+    where
+        F: FnMut(&[bool]),
+    {
+        let mut all_assumptions = assumptions.to_vec();
+        all_assumptions.push(activation);
+        let mut found = 0u64;
+        let mut assignment = Vec::with_capacity(projection.len());
+        let mut blocking = Vec::with_capacity(projection.len() + 1);
+
+        loop {
+            match self.solve_with_assumptions(&all_assumptions)? {
+                SolverResult::Sat => {
+                    if found == limit {
+                        return Ok(false);
+                    }
+                    found += 1;
+
+                    assignment.clear();
+                    blocking.clear();
+                    blocking.push(-activation);
+                    for &var in projection {
+                        let value = self.get_model_value(var)?;
+                        assignment.push(value);
+                        blocking.push(if value { -var } else { var });
+                    }
+
+                    on_model(&assignment);
+                    self.add_clause(&blocking)?;
+                }
+                SolverResult::Unsat => return Ok(true),
+                SolverResult::Unknown => return Err(ParkissatError::Interrupted),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cnf::configured_solver;
+
+    #[test]
+    fn test_exact_count() {
+        let mut solver = configured_solver();
+        solver.add_clause(&[1, 2]).unwrap();
+
+        assert_eq!(solver.count_models_exact(&[1, 2], 10).unwrap(), ModelCount::Exact(3));
+        assert_eq!(solver.count_models_exact(&[1], 10).unwrap(), ModelCount::Exact(2));
+
+        // The formula itself is unaffected by counting
+        assert_eq!(solver.solve().unwrap(), SolverResult::Sat);
+    }
+
+    #[test]
+    fn test_count_limit() {
+        let mut solver = configured_solver();
+        solver.add_clause(&[1, 2, 3]).unwrap();
+
+        let count = solver.count_models_exact(&[1, 2, 3], 4).unwrap();
+        assert_eq!(count, ModelCount::Exceeds(4));
+        assert_eq!(count.exact(), None);
+    }
+
+    #[test]
+    fn test_count_unsat() {
+        let mut solver = configured_solver();
+        solver.add_clause(&[1]).unwrap();
+        solver.add_clause(&[-1]).unwrap();
+
+        assert_eq!(solver.count_models_exact(&[1], 10).unwrap(), ModelCount::Exact(0));
+    }
+
+    #[test]
+    fn test_weighted_count() {
+        let mut solver = configured_solver();
+        solver.add_clause(&[1, 2]).unwrap();
+
+        let weights = HashMap::from([(1, 0.3), (-1, 0.7), (2, 0.6), (-2, 0.4)]);
+        let count = solver.count_weighted(&weights, 10).unwrap();
+
+        // P(x1 ∨ x2) = 1 - 0.7 * 0.4
+        assert!(count.is_exact());
+        assert!((count.value() - 0.72).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_weighted_count_limit() {
+        let mut solver = configured_solver();
+        solver.add_clause(&[1, 2]).unwrap();
+
+        let weights = HashMap::from([(1, 1.0), (2, 1.0)]);
+        let count = solver.count_weighted(&weights, 1).unwrap();
+        assert!(!count.is_exact());
+        assert!(count.value() >= 1.0);
+
+        let invalid = HashMap::from([(1, -0.5)]);
+        assert!(matches!(
+            solver.count_weighted(&invalid, 10),
+            Err(ParkissatError::InvalidConfiguration(_))
+        ));
+    }
+
+    #[test]
+    fn test_approx_count() {
+        let mut solver = configured_solver();
+        solver.add_clause(&[1, 2]).unwrap();
+
+        // Few models are counted exactly
+        let options = ApproxOptions::default();
+        assert_eq!(solver.count_models_approx(&[1, 2], &options).unwrap(), ApproxCount::Exact(3));
+
+        // 2^7 - 1 models over the projection, with a loose tolerance to keep rounds short
+        solver.add_clause(&[3, 4, 5, 6, 7]).unwrap();
+        let options = ApproxOptions { epsilon: 4.0, delta: 0.9, seed: 7 };
+        let count = solver.count_models_approx(&[3, 4, 5, 6, 7, 8, 9], &options).unwrap();
+        assert!(!count.is_exact());
+        assert!(count.value() >= 124.0 / 5.0 && count.value() <= 124.0 * 5.0);
+
+        // The XOR constraints are retired afterwards
+        assert_eq!(solver.count_models_exact(&[1, 2], 10).unwrap(), ModelCount::Exact(3));
+        assert!(solver.count_models_approx(&[1], &ApproxOptions { epsilon: 0.0, ..options }).is_err());
+    }
+
+    #[test]
+    fn test_invalid_projection() {
+        let mut solver = configured_solver();
+        assert_eq!(
+            solver.count_models_exact(&[0], 10).unwrap_err(),
+            ParkissatError::InvalidVariable(0)
+        );
+    }
+}
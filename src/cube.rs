@@ -0,0 +1,355 @@
+//! Cube-and-conquer solving
+//!
+//! [`solve`] splits a formula into cubes, conjunctions of literals that
+//! together cover every assignment, and solves the formula under each cube as
+//! assumptions on a pool of [`ParkissatSolver`] instances. The first SAT cube
+//! answers for the whole formula and interrupts the other workers; the
+//! formula is UNSAT once every cube has been refuted.
+//!
+//! Cubes come from a binary splitting tree over the most frequent variables.
+//! Lookahead splitting unit-propagates both polarities of each candidate and
+//! branches on the one that implies the most on both sides; random splitting
+//! picks a candidate at random. Branches whose propagation already conflicts
+//! are refuted while splitting and never reach a worker. Each worker loads
+//! the formula once and solves its cubes incrementally.
+
+use crate::cnf::CnfFormula;
+use crate::count::SplitMix64;
+use crate::error::{ParkissatError, Result};
+use crate::interrupt::InterruptHandle;
+use crate::propagate::{PropagationResult, Propagator};
+use crate::wrapper::{ParkissatSolver, SolverConfig, SolverResult};
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+/// How the splitting variable of each cube is chosen
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SplitStrategy {
+    /// Branch on the candidate whose two polarities propagate the most literals
+    Lookahead,
+    /// Branch on a random candidate
+    Random {
+        /// Seed of the random choices
+        seed: u64,
+    },
+}
+
+/// Options for cube-and-conquer solving
+#[derive(Debug, Clone)]
+pub struct CubeOptions {
+    /// Depth of the splitting tree, giving at most `2^depth` cubes (default: 6)
+    pub depth: usize,
+
+    /// Choice of splitting variables (default: lookahead)
+    pub strategy: SplitStrategy,
+
+    /// Number of most frequent unassigned variables considered at each split (default: 32)
+    pub candidates: usize,
+
+    /// Number of solver instances solving cubes in parallel (default: number of CPUs)
+    pub workers: usize,
+
+    /// Configuration of every worker solver (default: single-threaded)
+    pub config: SolverConfig,
+}
+
+impl Default for CubeOptions {
+    fn default() -> Self {
+        Self {
+            depth: 6,
+            strategy: SplitStrategy::Lookahead,
+            candidates: 32,
+            workers: num_cpus::get(),
+            config: SolverConfig::default(),
+        }
+    }
+}
+
+/// Result of [`solve`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CubeOutcome {
+    /// The answer for the whole formula
+    pub result: SolverResult,
+    /// The model, if the answer is SAT
+    pub model: Option<Vec<i32>>,
+    /// The cube that was found SAT
+    pub cube: Option<Vec<i32>>,
+    /// Number of cubes handed to the workers
+    pub cubes: usize,
+    /// Number of those cubes the workers refuted
+    pub refuted: usize,
+}
+
+/// Split a formula into cubes
+///
+/// Every model of the formula satisfies one of the cubes. Cubes refuted by
+/// unit propagation are left out, so an unsatisfiable formula may yield none.
+pub fn generate_cubes(formula: &CnfFormula, options: &CubeOptions) -> Vec<Vec<i32>> {
+    let mut occurrences = vec![0usize; formula.num_vars() + 1];
+    for clause in formula.iter() {
+        for &lit in clause {
+            occurrences[lit.unsigned_abs() as usize] += 1;
+        }
+    }
+    let mut variables: Vec<i32> = (1..=formula.num_vars() as i32)
+        .filter(|&var| occurrences[var as usize] > 0)
+        .collect();
+    variables.sort_by_key(|&var| std::cmp::Reverse(occurrences[var as usize]));
+
+    let mut splitter = Splitter {
+        propagator: Propagator::new(formula.clauses().iter().cloned(), formula.num_clauses()),
+        variables,
+        candidates: options.candidates.max(1),
+        strategy: options.strategy,
+        rng: SplitMix64(match options.strategy {
+            SplitStrategy::Random { seed } => seed,
+            SplitStrategy::Lookahead => 0,
+        }),
+    };
+
+    let mut cubes = Vec::new();
+    splitter.split(Vec::new(), options.depth, &mut cubes);
+    cubes
+}
+
+/// Solve a formula by cube-and-conquer
+pub fn solve(formula: &CnfFormula, options: &CubeOptions) -> Result<CubeOutcome> {
+    if options.workers == 0 {
+        return Err(ParkissatError::InvalidConfiguration(
+            "Cube-and-conquer needs at least one worker".to_string()
+        ));
+    }
+
+    let cubes = generate_cubes(formula, options);
+    let mut outcome = CubeOutcome {
+        result: SolverResult::Unsat,
+        model: None,
+        cube: None,
+        cubes: cubes.len(),
+        refuted: 0,
+    };
+    if cubes.is_empty() {
+        return Ok(outcome);
+    }
+
+    let queue = Arc::new(Mutex::new(Queue {
+        cubes,
+        handles: Vec::new(),
+        stopped: false,
+    }));
+    let (tx, rx) = mpsc::channel::<(Vec<i32>, Answer)>();
+
+    let workers: Vec<_> = (0..options.workers.min(outcome.cubes))
+        .map(|_| {
+            let queue = Arc::clone(&queue);
+            let tx = tx.clone();
+            let formula = formula.clone();
+            let config = options.config.clone();
+            thread::spawn(move || {
+                if let Err(err) = conquer(&formula, &config, &queue, &tx) {
+                    let _ = tx.send((Vec::new(), Err(err)));
+                }
+            })
+        })
+        .collect();
+    drop(tx);
+
+    let mut first_error = None;
+    let mut unknown = false;
+    for (cube, answer) in rx {
+        match answer {
+            Ok((SolverResult::Sat, model)) => {
+                outcome.result = SolverResult::Sat;
+                outcome.model = model;
+                outcome.cube = Some(cube);
+                queue.lock().unwrap().stop();
+                break;
+            }
+            Ok((SolverResult::Unsat, _)) => outcome.refuted += 1,
+            Ok((SolverResult::Unknown, _)) => unknown = true,
+            Err(err) => {
+                first_error.get_or_insert(err);
+                queue.lock().unwrap().stop();
+            }
+        }
+    }
+
+    for worker in workers {
+        let _ = worker.join();
+    }
+
+    if outcome.result == SolverResult::Sat {
+        return Ok(outcome);
+    }
+    if let Some(err) = first_error {
+        return Err(err);
+    }
+    if unknown || outcome.refuted < outcome.cubes {
+        outcome.result = SolverResult::Unknown;
+    }
+    Ok(outcome)
+}
+
+type Answer = Result<(SolverResult, Option<Vec<i32>>)>;
+
+/// Cubes left to solve, shared by the workers
+struct Queue {
+    cubes: Vec<Vec<i32>>,
+    /// Interrupt handles of the running workers
+    handles: Vec<InterruptHandle>,
+    stopped: bool,
+}
+
+impl Queue {
+    fn stop(&mut self) {
+        self.stopped = true;
+        self.cubes.clear();
+        for handle in &self.handles {
+            handle.interrupt();
+        }
+    }
+}
+
+/// Worker loop: solve cubes from the queue until it is empty or stopped
+fn conquer(formula: &CnfFormula, config: &SolverConfig, queue: &Mutex<Queue>, tx: &mpsc::Sender<(Vec<i32>, Answer)>) -> Result<()> {
+    let mut solver = ParkissatSolver::new()?;
+    solver.configure(config)?;
+    solver.add_formula(formula)?;
+
+    {
+        let mut queue = queue.lock().unwrap();
+        if queue.stopped {
+            return Ok(());
+        }
+        queue.handles.push(solver.interrupt_handle());
+    }
+
+    loop {
+        let Some(cube) = queue.lock().unwrap().cubes.pop() else {
+            return Ok(());
+        };
+
+        let result = solver.solve_with_assumptions(&cube)?;
+        let model = match result {
+            SolverResult::Sat => Some(solver.get_model()?),
+            _ => None,
+        };
+        if tx.send((cube, Ok((result, model)))).is_err() {
+            return Ok(());
+        }
+    }
+}
+
+struct Splitter {
+    propagator: Propagator,
+    /// Variables of the formula, most frequent first
+    variables: Vec<i32>,
+    candidates: usize,
+    strategy: SplitStrategy,
+    rng: SplitMix64,
+}
+
+impl Splitter {
+    fn split(&mut self, cube: Vec<i32>, depth: usize, cubes: &mut Vec<Vec<i32>>) {
+        let PropagationResult::Consistent(implied) = self.propagator.run(&[], &cube) else {
+            return;
+        };
+        if depth == 0 {
+            cubes.push(cube);
+            return;
+        }
+
+        let assigned = |var: i32| cube.iter().chain(&implied).any(|lit| lit.abs() == var);
+        let candidates: Vec<i32> = self.variables.iter()
+            .copied()
+            .filter(|&var| !assigned(var))
+            .take(self.candidates)
+            .collect();
+        if candidates.is_empty() {
+            cubes.push(cube);
+            return;
+        }
+
+        let var = match self.strategy {
+            SplitStrategy::Random { .. } => candidates[(self.rng.next_u64() % candidates.len() as u64) as usize],
+            SplitStrategy::Lookahead => self.lookahead(&cube, implied.len(), &candidates),
+        };
+        for lit in [var, -var] {
+            let mut branch = cube.clone();
+            branch.push(lit);
+            self.split(branch, depth - 1, cubes);
+        }
+    }
+
+    /// The candidate maximizing the product of the literals each polarity implies
+    fn lookahead(&mut self, cube: &[i32], implied: usize, candidates: &[i32]) -> i32 {
+        let mut best = (0, candidates[0]);
+        for &var in candidates {
+            let mut score = 1;
+            for lit in [var, -var] {
+                let mut branch = cube.to_vec();
+                branch.push(lit);
+                // A conflicting polarity makes the branch free to refute
+                score *= match self.propagator.run(&[], &branch) {
+                    PropagationResult::Consistent(lits) => lits.len().saturating_sub(implied) + 1,
+                    _ => self.variables.len() + 1,
+                };
+            }
+            if score > best.0 {
+                best = (score, var);
+            }
+        }
+        best.1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cnf::pigeonhole;
+
+    fn formula(clauses: &[&[i32]]) -> CnfFormula {
+        let mut formula = CnfFormula::new();
+        for clause in clauses {
+            formula.add_clause(clause).unwrap();
+        }
+        formula
+    }
+
+    #[test]
+    fn test_generate_cubes() {
+        let formula = formula(&[&[1, 2, 3], &[-1, 2], &[-2, 3], &[3, 4]]);
+        let options = CubeOptions { depth: 2, ..CubeOptions::default() };
+        let cubes = generate_cubes(&formula, &options);
+        assert!(!cubes.is_empty() && cubes.len() <= 4);
+        assert!(cubes.iter().all(|cube| cube.len() <= 2));
+
+        let random = CubeOptions { strategy: SplitStrategy::Random { seed: 3 }, ..options };
+        assert!(generate_cubes(&formula, &random).len() <= 4);
+
+        // Propagation refutes every branch of a contradiction
+        assert!(generate_cubes(&self::formula(&[&[1], &[-1]]), &CubeOptions::default()).is_empty());
+    }
+
+    #[test]
+    fn test_solve_sat() {
+        let formula = formula(&[&[1, 2], &[-1, 3], &[-2, -3], &[3, 4], &[-4, -1]]);
+        let options = CubeOptions { depth: 3, workers: 2, ..CubeOptions::default() };
+        let outcome = solve(&formula, &options).unwrap();
+        assert_eq!(outcome.result, SolverResult::Sat);
+        assert!(formula.is_satisfied_by(&outcome.model.unwrap()));
+        assert!(outcome.cube.is_some());
+    }
+
+    #[test]
+    fn test_solve_unsat() {
+        let options = CubeOptions { depth: 3, workers: 2, ..CubeOptions::default() };
+        let outcome = solve(&pigeonhole(5, 4), &options).unwrap();
+        assert_eq!(outcome.result, SolverResult::Unsat);
+        assert_eq!(outcome.refuted, outcome.cubes);
+
+        let options = CubeOptions { workers: 0, ..options };
+        assert!(solve(&pigeonhole(3, 2), &options).is_err());
+    }
+}
@@ -0,0 +1,915 @@
+//! Long-running solver service with a small REST API
+//!
+//! Available with the `daemon` feature. Formulas are submitted as DIMACS
+//! request bodies, queued, and solved by a fixed number of worker threads,
+//! each of which owns its own `ParkissatSolver`. Per-job thread counts and
+//! timeouts are checked against the quotas in [`DaemonConfig`].
+//!
+//! The queue is persistent: every job is spooled to disk as its formula plus a
+//! small metadata file (priority, resource limits, status), and a daemon
+//! created on an existing spool directory resumes the jobs that were still
+//! queued or running when the previous instance stopped. Higher priority jobs
+//! are solved first; jobs with equal priority run in submission order.
+//!
+//! Each spooled job is a reproducibility bundle: the formula plus every
+//! setting the solve depends on (threads, timeout, random seed) and the crate
+//! version that accepted it, so a resumed job runs exactly as submitted.
+//! Resumed jobs are checked against the quotas of the new instance and fail
+//! if they exceed them.
+//!
+//! Jobs run on the daemon's own worker threads rather than a
+//! [`SolverPool`](crate::SolverPool). A pool hands out queries in submission
+//! order to workers that share one configuration, while the daemon picks jobs
+//! by priority, configures each solver from its job's threads, timeout, and
+//! seed, records a job as running before solving it, and skips jobs removed
+//! while queued. A [`SolveHandle`](crate::SolveHandle) per job would only add
+//! a thread without taking over any of that scheduling.
+//!
+//! Endpoints:
+//! - `POST /jobs?threads=N&timeout=S&priority=P&seed=R` with a DIMACS body: submit a formula
+//! - `GET /jobs/{id}`: poll the status of a job
+//! - `GET /jobs/{id}/model`: fetch the model of a satisfiable job
+//! - `DELETE /jobs/{id}`: forget a job that is not running and remove its spool files
+
+use crate::error::{ParkissatError, Result};
+use crate::wrapper::{ParkissatSolver, SolverConfig, SolverResult};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+use std::time::Duration;
+
+/// Configuration for the solver daemon
+#[derive(Debug, Clone)]
+pub struct DaemonConfig {
+    /// Address to listen on
+    pub bind_addr: String,
+
+    /// Directory where submitted formulas are stored
+    pub spool_dir: PathBuf,
+
+    /// Number of jobs solved concurrently
+    pub max_concurrent_jobs: usize,
+
+    /// Maximum number of solver threads a single job may request
+    pub max_threads_per_job: usize,
+
+    /// Maximum timeout a single job may request (0 = unlimited)
+    pub max_timeout: Duration,
+
+    /// Largest request body accepted, in bytes; larger requests get 413
+    pub max_body_bytes: usize,
+
+    /// Read and write timeout of client connections (0 = none)
+    pub io_timeout: Duration,
+
+    /// Number of client connections handled at once; further ones get 503
+    pub max_connections: usize,
+}
+
+impl Default for DaemonConfig {
+    fn default() -> Self {
+        Self {
+            bind_addr: "127.0.0.1:8080".to_string(),
+            spool_dir: std::env::temp_dir().join("parkissat-daemon"),
+            max_concurrent_jobs: 1,
+            max_threads_per_job: num_cpus::get(),
+            max_timeout: Duration::from_secs(0),
+            max_body_bytes: 64 << 20,
+            io_timeout: Duration::from_secs(30),
+            max_connections: 64,
+        }
+    }
+}
+
+/// Resources requested by a submitted job
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JobRequest {
+    /// Number of solver threads
+    pub threads: usize,
+
+    /// Timeout (0 = use the daemon maximum)
+    pub timeout: Duration,
+
+    /// Scheduling priority (higher runs first)
+    pub priority: i32,
+
+    /// Random seed of the solver (0 = default)
+    pub seed: u32,
+}
+
+impl Default for JobRequest {
+    fn default() -> Self {
+        Self {
+            threads: 1,
+            timeout: Duration::from_secs(0),
+            priority: 0,
+            seed: 0,
+        }
+    }
+}
+
+/// Status of a submitted job
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JobStatus {
+    /// Waiting for a free worker
+    Queued,
+    /// Currently being solved
+    Running,
+    /// Solving finished with the given result
+    Finished(SolverResult),
+    /// The job could not be solved
+    Failed(String),
+}
+
+impl JobStatus {
+    fn name(&self) -> &'static str {
+        match self {
+            JobStatus::Queued => "queued",
+            JobStatus::Running => "running",
+            JobStatus::Finished(SolverResult::Sat) => "sat",
+            JobStatus::Finished(SolverResult::Unsat) => "unsat",
+            JobStatus::Finished(SolverResult::Unknown) => "unknown",
+            JobStatus::Failed(_) => "failed",
+        }
+    }
+
+    fn from_name(name: &str, error: Option<String>) -> Option<Self> {
+        match name {
+            "queued" => Some(JobStatus::Queued),
+            "running" => Some(JobStatus::Running),
+            "sat" => Some(JobStatus::Finished(SolverResult::Sat)),
+            "unsat" => Some(JobStatus::Finished(SolverResult::Unsat)),
+            "unknown" => Some(JobStatus::Finished(SolverResult::Unknown)),
+            "failed" => Some(JobStatus::Failed(error.unwrap_or_default())),
+            _ => None,
+        }
+    }
+}
+
+/// A response produced by the daemon's request handler
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpResponse {
+    /// HTTP status code
+    pub status: u16,
+    /// JSON response body
+    pub body: String,
+}
+
+impl HttpResponse {
+    fn json(status: u16, body: String) -> Self {
+        Self { status, body }
+    }
+
+    fn error(status: u16, message: &str) -> Self {
+        Self::json(status, format!("{{\"error\":\"{}\"}}", escape_json(message)))
+    }
+
+    fn reason(&self) -> &'static str {
+        match self.status {
+            200 => "OK",
+            201 => "Created",
+            400 => "Bad Request",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            409 => "Conflict",
+            413 => "Payload Too Large",
+            503 => "Service Unavailable",
+            _ => "Internal Server Error",
+        }
+    }
+}
+
+struct Job {
+    request: JobRequest,
+    path: PathBuf,
+    status: JobStatus,
+    model: Option<Vec<i32>>,
+}
+
+struct DaemonState {
+    jobs: Mutex<HashMap<u64, Job>>,
+    queue: Mutex<BinaryHeap<(i32, Reverse<u64>)>>,
+    available: Condvar,
+    next_id: AtomicU64,
+    connections: AtomicUsize,
+}
+
+/// Shared solving service
+#[derive(Clone)]
+pub struct Daemon {
+    config: DaemonConfig,
+    state: Arc<DaemonState>,
+}
+
+impl Daemon {
+    /// Create a daemon, preparing its spool directory and resuming spooled jobs
+    pub fn new(config: DaemonConfig) -> Result<Self> {
+        if config.max_concurrent_jobs == 0 || config.max_threads_per_job == 0 || config.max_connections == 0 {
+            return Err(ParkissatError::InvalidConfiguration(
+                "Daemon job, thread, and connection quotas must be positive".to_string()
+            ));
+        }
+
+        std::fs::create_dir_all(&config.spool_dir)?;
+
+        let daemon = Self {
+            config,
+            state: Arc::new(DaemonState {
+                jobs: Mutex::new(HashMap::new()),
+                queue: Mutex::new(BinaryHeap::new()),
+                available: Condvar::new(),
+                next_id: AtomicU64::new(1),
+                connections: AtomicUsize::new(0),
+            }),
+        };
+        daemon.recover()?;
+
+        Ok(daemon)
+    }
+
+    /// Get the daemon configuration
+    pub fn config(&self) -> &DaemonConfig {
+        &self.config
+    }
+
+    /// Validate a job request against the configured quotas
+    pub fn check_quota(&self, request: &JobRequest) -> Result<()> {
+        if request.threads == 0 || request.threads > self.config.max_threads_per_job {
+            return Err(ParkissatError::InvalidConfiguration(format!(
+                "Requested {} threads, allowed 1 to {}",
+                request.threads, self.config.max_threads_per_job
+            )));
+        }
+
+        let max_timeout = self.config.max_timeout;
+        if !max_timeout.is_zero() && request.timeout > max_timeout {
+            return Err(ParkissatError::InvalidConfiguration(format!(
+                "Requested timeout of {}s exceeds limit of {}s",
+                request.timeout.as_secs(), max_timeout.as_secs()
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Queue a DIMACS formula for solving and return its job id
+    pub fn submit(&self, dimacs: &[u8], request: JobRequest) -> Result<u64> {
+        self.check_quota(&request)?;
+
+        let id = self.state.next_id.fetch_add(1, Ordering::SeqCst);
+        let path = self.spool_path(id, "cnf");
+        std::fs::write(&path, dimacs)?;
+
+        let job = Job {
+            request,
+            path,
+            status: JobStatus::Queued,
+            model: None,
+        };
+        self.persist(id, &job)?;
+        self.state.jobs.lock().unwrap().insert(id, job);
+
+        self.enqueue(id, request.priority);
+
+        Ok(id)
+    }
+
+    /// Get the number of jobs waiting for a worker
+    pub fn queued_jobs(&self) -> usize {
+        self.state.queue.lock().unwrap().len()
+    }
+
+    /// Get the status of a job
+    pub fn status(&self, id: u64) -> Option<JobStatus> {
+        self.state.jobs.lock().unwrap().get(&id).map(|job| job.status.clone())
+    }
+
+    /// Get the model of a finished satisfiable job
+    pub fn model(&self, id: u64) -> Option<Vec<i32>> {
+        self.state.jobs.lock().unwrap().get(&id).and_then(|job| job.model.clone())
+    }
+
+    /// Forget a job and remove its spool files
+    ///
+    /// Returns `Ok(false)` for an unknown job. Running jobs cannot be removed.
+    pub fn remove(&self, id: u64) -> Result<bool> {
+        let mut jobs = self.state.jobs.lock().unwrap();
+        match jobs.get(&id) {
+            None => return Ok(false),
+            Some(job) if job.status == JobStatus::Running => {
+                return Err(ParkissatError::InvalidConfiguration(format!("Job {} is running", id)));
+            }
+            Some(_) => {}
+        }
+
+        // A queued job stays in the queue; workers skip ids they no longer know
+        jobs.remove(&id);
+        for extension in ["meta", "cnf", "model"] {
+            match std::fs::remove_file(self.spool_path(id, extension)) {
+                Err(err) if err.kind() != std::io::ErrorKind::NotFound => return Err(err.into()),
+                _ => {}
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// Spawn the worker threads that solve queued jobs
+    pub fn start_workers(&self) -> Vec<thread::JoinHandle<()>> {
+        (0..self.config.max_concurrent_jobs)
+            .map(|_| {
+                let daemon = self.clone();
+                thread::spawn(move || daemon.worker_loop())
+            })
+            .collect()
+    }
+
+    /// Start the workers and serve HTTP requests until the listener fails
+    pub fn serve(&self) -> Result<()> {
+        let listener = TcpListener::bind(&self.config.bind_addr)?;
+        self.start_workers();
+        self.accept(listener);
+
+        Ok(())
+    }
+
+    /// Hand each connection to its own thread, up to `max_connections` at once
+    fn accept(&self, listener: TcpListener) {
+        for stream in listener.incoming() {
+            let mut stream = match stream {
+                Ok(stream) => stream,
+                Err(_) => continue,
+            };
+
+            if self.state.connections.fetch_add(1, Ordering::SeqCst) >= self.config.max_connections {
+                self.state.connections.fetch_sub(1, Ordering::SeqCst);
+                let _ = stream.set_write_timeout(Some(self.config.io_timeout).filter(|timeout| !timeout.is_zero()));
+                let _ = write_response(&mut stream, &HttpResponse::error(503, "Too many connections"));
+                continue;
+            }
+
+            let daemon = self.clone();
+            thread::spawn(move || {
+                let _ = daemon.handle_connection(stream);
+                daemon.state.connections.fetch_sub(1, Ordering::SeqCst);
+            });
+        }
+    }
+
+    /// Route a single request to the matching endpoint
+    pub fn handle_request(&self, method: &str, target: &str, body: &[u8]) -> HttpResponse {
+        let (path, query) = match target.split_once('?') {
+            Some((path, query)) => (path, query),
+            None => (target, ""),
+        };
+        let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+
+        match (method, segments.as_slice()) {
+            ("POST", ["jobs"]) => {
+                let request = match parse_job_request(query) {
+                    Ok(request) => request,
+                    Err(err) => return HttpResponse::error(400, &err.to_string()),
+                };
+
+                match self.submit(body, request) {
+                    Ok(id) => HttpResponse::json(201, format!("{{\"id\":{}}}", id)),
+                    Err(ParkissatError::IoError(msg)) => HttpResponse::error(500, &msg),
+                    Err(err) => HttpResponse::error(400, &err.to_string()),
+                }
+            }
+            ("GET", ["jobs", id]) => match id.parse().ok().and_then(|id| self.status(id).map(|s| (id, s))) {
+                Some((id, status)) => HttpResponse::json(200, status_json(id, &status)),
+                None => HttpResponse::error(404, "Unknown job"),
+            },
+            ("GET", ["jobs", id, "model"]) => {
+                let id: u64 = match id.parse() {
+                    Ok(id) => id,
+                    Err(_) => return HttpResponse::error(404, "Unknown job"),
+                };
+
+                match (self.status(id), self.model(id)) {
+                    (None, _) => HttpResponse::error(404, "Unknown job"),
+                    (Some(_), Some(model)) => {
+                        let literals: Vec<String> = model.iter().map(|lit| lit.to_string()).collect();
+                        HttpResponse::json(200, format!("{{\"id\":{},\"model\":[{}]}}", id, literals.join(",")))
+                    }
+                    (Some(_), None) => HttpResponse::error(409, "No model available"),
+                }
+            }
+            ("DELETE", ["jobs", id]) => match id.parse().map(|id| self.remove(id)) {
+                Ok(Ok(true)) => HttpResponse::json(200, format!("{{\"id\":{},\"deleted\":true}}", id)),
+                Ok(Ok(false)) | Err(_) => HttpResponse::error(404, "Unknown job"),
+                Ok(Err(ParkissatError::IoError(msg))) => HttpResponse::error(500, &msg),
+                Ok(Err(err)) => HttpResponse::error(409, &err.to_string()),
+            },
+            (_, ["jobs", ..]) => HttpResponse::error(405, "Method not allowed"),
+            _ => HttpResponse::error(404, "Not found"),
+        }
+    }
+
+    fn handle_connection(&self, stream: TcpStream) -> Result<()> {
+        let io_timeout = Some(self.config.io_timeout).filter(|timeout| !timeout.is_zero());
+        stream.set_read_timeout(io_timeout)?;
+        stream.set_write_timeout(io_timeout)?;
+
+        let mut reader = BufReader::new(stream.try_clone()?);
+
+        let mut request_line = String::new();
+        reader.read_line(&mut request_line)?;
+        let mut parts = request_line.split_whitespace();
+        let method = parts.next().unwrap_or("").to_string();
+        let target = parts.next().unwrap_or("/").to_string();
+
+        let mut content_length = 0usize;
+        loop {
+            let mut header = String::new();
+            if reader.read_line(&mut header)? == 0 || header.trim().is_empty() {
+                break;
+            }
+            if let Some((name, value)) = header.split_once(':') {
+                if name.trim().eq_ignore_ascii_case("content-length") {
+                    content_length = value.trim().parse().unwrap_or(0);
+                }
+            }
+        }
+
+        // Refuse oversized bodies before allocating a buffer for them
+        let response = if content_length > self.config.max_body_bytes {
+            HttpResponse::error(413, &format!("Request body exceeds {} bytes", self.config.max_body_bytes))
+        } else {
+            let mut body = vec![0u8; content_length];
+            reader.read_exact(&mut body)?;
+            self.handle_request(&method, &target, &body)
+        };
+
+        let mut stream = stream;
+        write_response(&mut stream, &response)
+    }
+
+    fn worker_loop(&self) {
+        loop {
+            let id = {
+                let mut queue = self.state.queue.lock().unwrap();
+                loop {
+                    if let Some((_, Reverse(id))) = queue.pop() {
+                        break id;
+                    }
+                    queue = self.state.available.wait(queue).unwrap();
+                }
+            };
+
+            let (request, path) = {
+                let mut jobs = self.state.jobs.lock().unwrap();
+                match jobs.get_mut(&id) {
+                    Some(job) => {
+                        job.status = JobStatus::Running;
+                        // A job whose state cannot be recorded would be solved again after a restart
+                        if let Err(err) = self.persist(id, job) {
+                            job.status = JobStatus::Failed(format!("Could not persist job: {}", err));
+                            continue;
+                        }
+                        (job.request, job.path.clone())
+                    }
+                    None => continue,
+                }
+            };
+
+            let outcome = self.run_job(&request, &path);
+
+            let mut jobs = self.state.jobs.lock().unwrap();
+            if let Some(job) = jobs.get_mut(&id) {
+                match outcome {
+                    Ok((result, model)) => {
+                        job.status = JobStatus::Finished(result);
+                        job.model = model;
+                    }
+                    Err(err) => job.status = JobStatus::Failed(err.to_string()),
+                }
+                if let Err(err) = self.persist(id, job) {
+                    job.status = JobStatus::Failed(format!("Could not persist result: {}", err));
+                    job.model = None;
+                }
+            }
+        }
+    }
+
+    fn enqueue(&self, id: u64, priority: i32) {
+        self.state.queue.lock().unwrap().push((priority, Reverse(id)));
+        self.state.available.notify_one();
+    }
+
+    fn spool_path(&self, id: u64, extension: &str) -> PathBuf {
+        self.config.spool_dir.join(format!("job-{}.{}", id, extension))
+    }
+
+    /// Write a job's metadata (and model, once known) next to its formula
+    fn persist(&self, id: u64, job: &Job) -> Result<()> {
+        let mut meta = format!(
+            "version={}\npriority={}\nthreads={}\ntimeout_ms={}\nseed={}\nstatus={}\n",
+            env!("CARGO_PKG_VERSION"), job.request.priority, job.request.threads,
+            job.request.timeout.as_millis(), job.request.seed, job.status.name()
+        );
+        if let JobStatus::Failed(msg) = &job.status {
+            meta.push_str(&format!("error={}\n", msg.replace('\n', " ")));
+        }
+
+        if let Some(model) = &job.model {
+            let literals: Vec<String> = model.iter().map(|lit| lit.to_string()).collect();
+            std::fs::write(self.spool_path(id, "model"), literals.join(" "))?;
+        }
+
+        // Write then rename so a crash never leaves a truncated metadata file
+        let tmp = self.spool_path(id, "meta.tmp");
+        std::fs::write(&tmp, meta)?;
+        std::fs::rename(&tmp, self.spool_path(id, "meta"))?;
+
+        Ok(())
+    }
+
+    /// Reload spooled jobs, re-queueing those that had not finished
+    fn recover(&self) -> Result<()> {
+        let mut max_id = 0;
+
+        for entry in std::fs::read_dir(&self.config.spool_dir)? {
+            let path = entry?.path();
+            let id = match parse_spool_id(&path) {
+                Some(id) => id,
+                None => continue,
+            };
+
+            let mut job = match self.load_job(id, &path)? {
+                Some(job) => job,
+                None => continue,
+            };
+            max_id = max_id.max(id);
+
+            if matches!(job.status, JobStatus::Queued | JobStatus::Running) {
+                // The quotas may have been lowered since the job was accepted
+                match self.check_quota(&job.request) {
+                    Ok(()) => {
+                        job.status = JobStatus::Queued;
+                        self.persist(id, &job)?;
+                        self.enqueue(id, job.request.priority);
+                    }
+                    Err(err) => {
+                        job.status = JobStatus::Failed(err.to_string());
+                        self.persist(id, &job)?;
+                    }
+                }
+            }
+
+            self.state.jobs.lock().unwrap().insert(id, job);
+        }
+
+        self.state.next_id.store(max_id + 1, Ordering::SeqCst);
+        Ok(())
+    }
+
+    fn load_job(&self, id: u64, meta_path: &Path) -> Result<Option<Job>> {
+        let path = self.spool_path(id, "cnf");
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let mut request = JobRequest::default();
+        let mut status = None;
+        let mut error = None;
+
+        for line in std::fs::read_to_string(meta_path)?.lines() {
+            let (key, value) = match line.split_once('=') {
+                Some(pair) => pair,
+                None => continue,
+            };
+            match key {
+                "priority" => request.priority = value.parse().unwrap_or(0),
+                "threads" => request.threads = value.parse().unwrap_or(1),
+                "timeout_ms" => request.timeout = Duration::from_millis(value.parse().unwrap_or(0)),
+                "seed" => request.seed = value.parse().unwrap_or(0),
+                "status" => status = Some(value.to_string()),
+                "error" => error = Some(value.to_string()),
+                _ => {}
+            }
+        }
+
+        let status = match status.and_then(|name| JobStatus::from_name(&name, error)) {
+            Some(status) => status,
+            None => return Ok(None),
+        };
+
+        let model = match std::fs::read_to_string(self.spool_path(id, "model")) {
+            Ok(text) => Some(text.split_whitespace().filter_map(|lit| lit.parse().ok()).collect()),
+            Err(_) => None,
+        };
+
+        Ok(Some(Job { request, path, status, model }))
+    }
+
+    fn run_job(&self, request: &JobRequest, path: &Path) -> Result<(SolverResult, Option<Vec<i32>>)> {
+        let timeout = if request.timeout.is_zero() {
+            self.config.max_timeout
+        } else {
+            request.timeout
+        };
+
+        let config = SolverConfig {
+            num_threads: request.threads as isize,
+            timeout,
+            random_seed: request.seed,
+            ..SolverConfig::default()
+        };
+
+        let mut solver = ParkissatSolver::new()?;
+        solver.configure(&config)?;
+        solver.load_dimacs(path)?;
+
+        let result = solver.solve()?;
+        let model = match result {
+            SolverResult::Sat => Some(solver.get_model()?),
+            _ => None,
+        };
+
+        Ok((result, model))
+    }
+}
+
+fn write_response(stream: &mut TcpStream, response: &HttpResponse) -> Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        response.status, response.reason(), response.body.len(), response.body
+    )?;
+    stream.flush()?;
+
+    Ok(())
+}
+
+fn parse_job_request(query: &str) -> Result<JobRequest> {
+    let mut request = JobRequest::default();
+
+    for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+        let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+        let invalid = || ParkissatError::InvalidConfiguration(format!("Invalid value for {}: {}", key, value));
+
+        match key {
+            "threads" => request.threads = value.parse().map_err(|_| invalid())?,
+            "timeout" => request.timeout = Duration::from_secs(value.parse().map_err(|_| invalid())?),
+            "priority" => request.priority = value.parse().map_err(|_| invalid())?,
+            "seed" => request.seed = value.parse().map_err(|_| invalid())?,
+            _ => {
+                return Err(ParkissatError::InvalidConfiguration(
+                    format!("Unknown job parameter: {}", key)
+                ));
+            }
+        }
+    }
+
+    Ok(request)
+}
+
+/// Extract the job id from a `job-{id}.meta` spool file name
+fn parse_spool_id(path: &Path) -> Option<u64> {
+    if path.extension()? != "meta" {
+        return None;
+    }
+    path.file_stem()?.to_str()?.strip_prefix("job-")?.parse().ok()
+}
+
+fn status_json(id: u64, status: &JobStatus) -> String {
+    match status {
+        JobStatus::Failed(msg) => format!(
+            "{{\"id\":{},\"status\":\"{}\",\"error\":\"{}\"}}",
+            id, status.name(), escape_json(msg)
+        ),
+        _ => format!("{{\"id\":{},\"status\":\"{}\"}}", id, status.name()),
+    }
+}
+
+fn escape_json(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_daemon() -> (tempfile::TempDir, Daemon) {
+        let dir = tempfile::tempdir().unwrap();
+        let config = DaemonConfig {
+            spool_dir: dir.path().to_path_buf(),
+            max_threads_per_job: 2,
+            max_timeout: Duration::from_secs(60),
+            ..DaemonConfig::default()
+        };
+        let daemon = Daemon::new(config).unwrap();
+        (dir, daemon)
+    }
+
+    #[test]
+    fn test_submit_and_poll() {
+        let (_dir, daemon) = test_daemon();
+
+        let response = daemon.handle_request("POST", "/jobs?threads=2&timeout=10", b"p cnf 1 1\n1 0\n");
+        assert_eq!(response.status, 201);
+        assert_eq!(response.body, "{\"id\":1}");
+
+        let response = daemon.handle_request("GET", "/jobs/1", b"");
+        assert_eq!(response.status, 200);
+        assert_eq!(response.body, "{\"id\":1,\"status\":\"queued\"}");
+
+        let response = daemon.handle_request("GET", "/jobs/1/model", b"");
+        assert_eq!(response.status, 409);
+    }
+
+    #[test]
+    fn test_quota_enforcement() {
+        let (_dir, daemon) = test_daemon();
+
+        let response = daemon.handle_request("POST", "/jobs?threads=8", b"p cnf 1 1\n1 0\n");
+        assert_eq!(response.status, 400);
+
+        let response = daemon.handle_request("POST", "/jobs?timeout=3600", b"p cnf 1 1\n1 0\n");
+        assert_eq!(response.status, 400);
+
+        let response = daemon.handle_request("POST", "/jobs?memory=1", b"p cnf 1 1\n1 0\n");
+        assert_eq!(response.status, 400);
+    }
+
+    #[test]
+    fn test_unknown_routes() {
+        let (_dir, daemon) = test_daemon();
+
+        assert_eq!(daemon.handle_request("GET", "/jobs/42", b"").status, 404);
+        assert_eq!(daemon.handle_request("GET", "/status", b"").status, 404);
+        assert_eq!(daemon.handle_request("PUT", "/jobs/1", b"").status, 405);
+        assert_eq!(daemon.handle_request("DELETE", "/jobs/1", b"").status, 404);
+    }
+
+    #[test]
+    fn test_delete_job() {
+        let (dir, daemon) = test_daemon();
+
+        let id = daemon.submit(b"p cnf 1 1\n1 0\n", JobRequest::default()).unwrap();
+        assert!(dir.path().join(format!("job-{}.cnf", id)).exists());
+
+        daemon.state.jobs.lock().unwrap().get_mut(&id).unwrap().status = JobStatus::Running;
+        assert_eq!(daemon.handle_request("DELETE", &format!("/jobs/{}", id), b"").status, 409);
+
+        daemon.state.jobs.lock().unwrap().get_mut(&id).unwrap().status = JobStatus::Queued;
+        let response = daemon.handle_request("DELETE", &format!("/jobs/{}", id), b"");
+        assert_eq!(response.status, 200);
+        assert_eq!(daemon.status(id), None);
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_body_limit() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = DaemonConfig {
+            spool_dir: dir.path().to_path_buf(),
+            max_body_bytes: 16,
+            ..DaemonConfig::default()
+        };
+        let daemon = Daemon::new(config).unwrap();
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            daemon.handle_connection(stream).unwrap();
+        });
+
+        let mut client = TcpStream::connect(addr).unwrap();
+        write!(client, "POST /jobs HTTP/1.1\r\nContent-Length: 1000000000000\r\n\r\n").unwrap();
+        let mut response = String::new();
+        client.read_to_string(&mut response).unwrap();
+        server.join().unwrap();
+
+        assert!(response.starts_with("HTTP/1.1 413 Payload Too Large"));
+    }
+
+    #[test]
+    fn test_connection_limit() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = DaemonConfig {
+            spool_dir: dir.path().to_path_buf(),
+            max_connections: 1,
+            ..DaemonConfig::default()
+        };
+        let daemon = Daemon::new(config).unwrap();
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        thread::spawn(move || daemon.accept(listener));
+
+        // The first connection holds the only slot while its request is incomplete
+        let mut first = TcpStream::connect(addr).unwrap();
+        let mut second = TcpStream::connect(addr).unwrap();
+        let mut response = String::new();
+        second.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 503 Service Unavailable"));
+
+        write!(first, "GET /jobs/1 HTTP/1.1\r\n\r\n").unwrap();
+        let mut response = String::new();
+        first.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 404 Not Found"));
+    }
+
+    #[test]
+    fn test_priority_order() {
+        let (_dir, daemon) = test_daemon();
+
+        let low = daemon.submit(b"p cnf 1 1\n1 0\n", JobRequest { priority: -1, ..JobRequest::default() }).unwrap();
+        let first = daemon.submit(b"p cnf 1 1\n1 0\n", JobRequest::default()).unwrap();
+        let high = daemon.submit(b"p cnf 1 1\n1 0\n", JobRequest { priority: 5, ..JobRequest::default() }).unwrap();
+        let second = daemon.submit(b"p cnf 1 1\n1 0\n", JobRequest::default()).unwrap();
+
+        let mut queue = daemon.state.queue.lock().unwrap();
+        let order: Vec<u64> = std::iter::from_fn(|| queue.pop().map(|(_, Reverse(id))| id)).collect();
+        assert_eq!(order, vec![high, first, second, low]);
+    }
+
+    #[test]
+    fn test_recover_spooled_jobs() {
+        let (dir, daemon) = test_daemon();
+
+        let request = JobRequest { threads: 2, timeout: Duration::from_millis(5500), priority: 3, seed: 7 };
+        let id = daemon.submit(b"p cnf 1 1\n1 0\n", request).unwrap();
+        drop(daemon);
+
+        let config = DaemonConfig {
+            spool_dir: dir.path().to_path_buf(),
+            max_threads_per_job: 2,
+            ..DaemonConfig::default()
+        };
+        let restarted = Daemon::new(config).unwrap();
+
+        assert_eq!(restarted.status(id), Some(JobStatus::Queued));
+        assert_eq!(restarted.queued_jobs(), 1);
+        assert_eq!(restarted.state.jobs.lock().unwrap()[&id].request, request);
+
+        let next = restarted.submit(b"p cnf 1 1\n1 0\n", JobRequest::default()).unwrap();
+        assert!(next > id);
+
+        let meta = std::fs::read_to_string(dir.path().join(format!("job-{}.meta", id))).unwrap();
+        assert!(meta.contains(&format!("version={}\n", env!("CARGO_PKG_VERSION"))));
+    }
+
+    #[test]
+    fn test_recover_checks_quota() {
+        let (dir, daemon) = test_daemon();
+
+        let request = JobRequest { threads: 2, ..JobRequest::default() };
+        let id = daemon.submit(b"p cnf 1 1\n1 0\n", request).unwrap();
+        drop(daemon);
+
+        let config = DaemonConfig {
+            spool_dir: dir.path().to_path_buf(),
+            max_threads_per_job: 1,
+            ..DaemonConfig::default()
+        };
+        let restarted = Daemon::new(config).unwrap();
+
+        assert!(matches!(restarted.status(id), Some(JobStatus::Failed(_))));
+        assert_eq!(restarted.queued_jobs(), 0);
+
+        // The failure is spooled too
+        drop(restarted);
+        let config = DaemonConfig { spool_dir: dir.path().to_path_buf(), ..DaemonConfig::default() };
+        assert!(matches!(Daemon::new(config).unwrap().status(id), Some(JobStatus::Failed(_))));
+    }
+
+    #[test]
+    fn test_worker_solves_job() {
+        let (_dir, daemon) = test_daemon();
+        daemon.start_workers();
+
+        let id = daemon.submit(b"p cnf 2 2\n1 2 0\n-1 2 0\n", JobRequest::default()).unwrap();
+
+        let mut status = daemon.status(id).unwrap();
+        for _ in 0..100 {
+            if matches!(status, JobStatus::Finished(_) | JobStatus::Failed(_)) {
+                break;
+            }
+            thread::sleep(Duration::from_millis(50));
+            status = daemon.status(id).unwrap();
+        }
+
+        assert_eq!(status, JobStatus::Finished(SolverResult::Sat));
+        assert!(daemon.model(id).is_some());
+    }
+}
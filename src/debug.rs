@@ -0,0 +1,188 @@
+//! Search state inspection for ParKissat-RS workers
+//!
+//! Available with the `debug` feature. After a solve returns (typically a
+//! budgeted or interrupted one), the trail, decision levels, and variable
+//! activities of each portfolio worker can be captured for studying search
+//! behavior. Snapshots are copies; they do not change as the solver continues.
+
+use crate::ffi;
+use crate::error::{ParkissatError, Result};
+use crate::wrapper::ParkissatSolver;
+use std::os::raw::c_int;
+
+/// A single assignment on a worker's trail
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TrailEntry {
+    /// Assigned literal (positive for true, negative for false)
+    pub literal: i32,
+    /// Decision level at which the literal was assigned
+    pub level: u32,
+    /// Whether the literal was a decision (as opposed to propagated)
+    pub decision: bool,
+}
+
+impl From<ffi::ParkissatTrailEntry> for TrailEntry {
+    fn from(entry: ffi::ParkissatTrailEntry) -> Self {
+        Self {
+            literal: entry.literal,
+            level: entry.level.max(0) as u32,
+            decision: entry.decision,
+        }
+    }
+}
+
+/// Snapshot of one worker's search state
+#[derive(Debug, Clone)]
+pub struct TrailSnapshot {
+    /// Index of the portfolio worker the snapshot was taken from
+    pub worker: usize,
+    /// Current decision level of the worker
+    pub decision_level: u32,
+    /// Assigned literals in trail order
+    pub trail: Vec<TrailEntry>,
+    /// Branching activity per variable (index 0 is variable 1)
+    pub activities: Vec<f64>,
+}
+
+impl TrailSnapshot {
+    /// Iterate over the decision literals on the trail
+    pub fn decisions(&self) -> impl Iterator<Item = &TrailEntry> {
+        self.trail.iter().filter(|entry| entry.decision)
+    }
+
+    /// Get the trail entries assigned at the given decision level
+    pub fn level(&self, level: u32) -> impl Iterator<Item = &TrailEntry> {
+        self.trail.iter().filter(move |entry| entry.level == level)
+    }
+
+    /// Get the activity of a variable, if it is known to the worker
+    pub fn activity(&self, variable: i32) -> Option<f64> {
+        if variable <= 0 {
+            return None;
+        }
+        self.activities.get(variable as usize - 1).copied()
+    }
+}
+
+impl ParkissatSolver {
+    /// Get the number of portfolio workers that can be inspected
+    pub fn debug_worker_count(&self) -> usize {
+        if self.solver.is_null() {
+            return 0;
+        }
+
+        let count = unsafe {
+            ffi::parkissat_debug_num_workers(self.solver)
+        };
+
+        count.max(0) as usize
+    }
+
+    /// Capture the trail, decision level, and activities of a worker
+    ///
+    /// Must not be called while a solve is running on another thread.
+    pub fn trail_snapshot(&self, worker: usize) -> Result<TrailSnapshot> {
+        if !self.is_configured() {
+            return Err(ParkissatError::NotConfigured);
+        }
+
+        if worker >= self.debug_worker_count() {
+            return Err(ParkissatError::InvalidConfiguration(
+                format!("No worker with index {}", worker)
+            ));
+        }
+
+        let worker_id = worker as c_int;
+
+        let decision_level = unsafe {
+            ffi::parkissat_debug_decision_level(self.solver, worker_id)
+        };
+
+        if decision_level < 0 {
+            return Err(ParkissatError::InternalError(
+                format!("Worker {} does not support inspection", worker)
+            ));
+        }
+
+        let size = unsafe {
+            ffi::parkissat_debug_trail_size(self.solver, worker_id)
+        };
+
+        let mut raw_trail = vec![
+            ffi::ParkissatTrailEntry { literal: 0, level: 0, decision: false };
+            size.max(0) as usize
+        ];
+        let copied = unsafe {
+            ffi::parkissat_debug_get_trail(self.solver, worker_id, raw_trail.as_mut_ptr(), size)
+        };
+        raw_trail.truncate(copied.max(0) as usize);
+
+        let mut activities = vec![0.0; self.variable_count()];
+        if !activities.is_empty() {
+            unsafe {
+                ffi::parkissat_debug_get_activities(
+                    self.solver,
+                    worker_id,
+                    activities.as_mut_ptr(),
+                    activities.len() as c_int
+                );
+            }
+        }
+
+        Ok(TrailSnapshot {
+            worker,
+            decision_level: decision_level as u32,
+            trail: raw_trail.into_iter().map(TrailEntry::from).collect(),
+            activities,
+        })
+    }
+
+    /// Capture snapshots of all portfolio workers
+    pub fn trail_snapshots(&self) -> Result<Vec<TrailSnapshot>> {
+        (0..self.debug_worker_count())
+            .map(|worker| self.trail_snapshot(worker))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wrapper::SolverConfig;
+
+    #[test]
+    fn test_snapshot_helpers() {
+        let snapshot = TrailSnapshot {
+            worker: 0,
+            decision_level: 1,
+            trail: vec![
+                TrailEntry { literal: 1, level: 0, decision: false },
+                TrailEntry { literal: -2, level: 1, decision: true },
+                TrailEntry { literal: 3, level: 1, decision: false },
+            ],
+            activities: vec![0.5, 1.5, 0.0],
+        };
+
+        assert_eq!(snapshot.decisions().count(), 1);
+        assert_eq!(snapshot.level(1).count(), 2);
+        assert_eq!(snapshot.activity(2), Some(1.5));
+        assert_eq!(snapshot.activity(0), None);
+        assert_eq!(snapshot.activity(4), None);
+    }
+
+    #[test]
+    fn test_snapshot_after_solve() {
+        let mut solver = ParkissatSolver::new().unwrap();
+        solver.configure(&SolverConfig::default()).unwrap();
+
+        solver.add_clause(&[1, 2]).unwrap();
+        solver.add_clause(&[-1, 2]).unwrap();
+        solver.solve().unwrap();
+
+        let snapshots = solver.trail_snapshots().unwrap();
+        assert_eq!(snapshots.len(), 1);
+        assert_eq!(snapshots[0].activities.len(), 2);
+
+        assert!(solver.trail_snapshot(1).is_err());
+    }
+}
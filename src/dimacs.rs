@@ -0,0 +1,337 @@
+//! Pure-Rust DIMACS CNF parsing
+//!
+//! Unlike [`ParkissatSolver::load_dimacs`], which hands a file path to the C++
+//! parser, these functions read from any [`Read`] source — stdin, sockets, or
+//! in-memory buffers — and report malformed input as
+//! [`ParkissatError::ParseError`] with the offending line number.
+//!
+//! The header `p cnf <vars> <clauses>` is required before the first clause.
+//! Clauses may span lines, comment lines start with `c`, and a line starting
+//! with `%` (as in the SATLIB benchmarks) ends the formula.
+//!
+//! Files compressed with gzip, xz, or bzip2 are detected by their magic bytes
+//! and decompressed on the fly when the matching cargo feature (`gzip`, `xz`,
+//! `bz2`) is enabled.
+
+use crate::cnf::CnfFormula;
+use crate::error::{ParkissatError, Result};
+use crate::wrapper::ParkissatSolver;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Cursor, Read};
+use std::path::Path;
+
+/// Compression formats recognized by their magic bytes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compression {
+    /// Uncompressed input
+    None,
+    /// gzip (`.gz`)
+    Gzip,
+    /// xz (`.xz`)
+    Xz,
+    /// bzip2 (`.bz2`)
+    Bzip2,
+}
+
+impl Compression {
+    /// Detect the compression format from the first bytes of the input
+    pub fn detect(prefix: &[u8]) -> Compression {
+        if prefix.starts_with(&[0x1f, 0x8b]) {
+            Compression::Gzip
+        } else if prefix.starts_with(&[0xfd, b'7', b'z', b'X', b'Z', 0x00]) {
+            Compression::Xz
+        } else if prefix.starts_with(b"BZh") {
+            Compression::Bzip2
+        } else {
+            Compression::None
+        }
+    }
+
+    /// Cargo feature that enables decompression of this format
+    fn feature(self) -> &'static str {
+        match self {
+            Compression::None => "",
+            Compression::Gzip => "gzip",
+            Compression::Xz => "xz",
+            Compression::Bzip2 => "bz2",
+        }
+    }
+}
+
+/// Wrap a reader so that compressed input is decompressed transparently
+///
+/// Returns an error if the input is compressed with a format whose cargo
+/// feature is not enabled.
+pub fn decompress<'a, R: Read + 'a>(mut reader: R) -> Result<Box<dyn Read + 'a>> {
+    let mut prefix = [0u8; 6];
+    let mut filled = 0;
+    while filled < prefix.len() {
+        let read = reader.read(&mut prefix[filled..])?;
+        if read == 0 {
+            break;
+        }
+        filled += read;
+    }
+
+    let compression = Compression::detect(&prefix[..filled]);
+    let input = Cursor::new(prefix[..filled].to_vec()).chain(reader);
+
+    match compression {
+        Compression::None => Ok(Box::new(input)),
+        #[cfg(feature = "gzip")]
+        Compression::Gzip => Ok(Box::new(flate2::read::MultiGzDecoder::new(input))),
+        #[cfg(feature = "xz")]
+        Compression::Xz => Ok(Box::new(xz2::read::XzDecoder::new(input))),
+        #[cfg(feature = "bz2")]
+        Compression::Bzip2 => Ok(Box::new(bzip2::read::BzDecoder::new(input))),
+        #[allow(unreachable_patterns)]
+        other => Err(ParkissatError::InvalidConfiguration(format!(
+            "{:?} input requires the `{}` feature",
+            other,
+            other.feature()
+        ))),
+    }
+}
+
+fn parse_error(line: usize, message: impl Into<String>) -> ParkissatError {
+    ParkissatError::ParseError {
+        line,
+        message: message.into(),
+    }
+}
+
+/// Parse a DIMACS CNF formula from a reader
+pub fn parse_reader<R: Read>(reader: R) -> Result<CnfFormula> {
+    let reader = BufReader::new(reader);
+
+    let mut header: Option<(usize, usize)> = None;
+    let mut formula = CnfFormula::new();
+    let mut clause = Vec::new();
+    let mut clause_start = 0;
+    let mut line_number = 0;
+
+    for line in reader.lines() {
+        line_number += 1;
+        let line = line?;
+        let trimmed = line.trim();
+
+        if trimmed.is_empty() || trimmed.starts_with('c') {
+            continue;
+        }
+        if trimmed.starts_with('%') {
+            break;
+        }
+
+        if trimmed.starts_with('p') {
+            if header.is_some() {
+                return Err(parse_error(line_number, "duplicate header"));
+            }
+            header = Some(parse_header(trimmed, line_number)?);
+            formula = CnfFormula::with_variables(header.map_or(0, |(vars, _)| vars));
+            continue;
+        }
+
+        let (num_vars, _) = header.ok_or_else(|| parse_error(line_number, "clause before `p cnf` header"))?;
+
+        for token in trimmed.split_whitespace() {
+            let lit: i32 = token
+                .parse()
+                .map_err(|_| parse_error(line_number, format!("invalid literal `{}`", token)))?;
+
+            if lit == 0 {
+                if clause.is_empty() {
+                    return Err(parse_error(line_number, "empty clause"));
+                }
+                formula.add_clause(&clause)?;
+                clause.clear();
+                continue;
+            }
+
+            if lit == i32::MIN || lit.unsigned_abs() as usize > num_vars {
+                return Err(parse_error(
+                    line_number,
+                    format!("literal {} exceeds the declared {} variables", lit, num_vars),
+                ));
+            }
+
+            if clause.is_empty() {
+                clause_start = line_number;
+            }
+            clause.push(lit);
+        }
+    }
+
+    let (_, num_clauses) = header.ok_or_else(|| parse_error(line_number, "missing `p cnf` header"))?;
+
+    if !clause.is_empty() {
+        return Err(parse_error(clause_start, "clause is not terminated by 0"));
+    }
+    if formula.num_clauses() != num_clauses {
+        return Err(parse_error(
+            line_number,
+            format!("header declares {} clauses but {} were found", num_clauses, formula.num_clauses()),
+        ));
+    }
+
+    Ok(formula)
+}
+
+/// Parse a DIMACS CNF formula from a string
+pub fn parse_str(input: &str) -> Result<CnfFormula> {
+    parse_reader(input.as_bytes())
+}
+
+/// Parse a DIMACS CNF file, decompressing it if needed
+pub fn parse_file<P: AsRef<Path>>(path: P) -> Result<CnfFormula> {
+    parse_reader(decompress(File::open(path)?)?)
+}
+
+/// Check whether a file starts with the magic bytes of a compression format
+pub(crate) fn is_compressed(path: &Path) -> Result<bool> {
+    let mut prefix = Vec::with_capacity(6);
+    File::open(path)?.take(6).read_to_end(&mut prefix)?;
+    Ok(Compression::detect(&prefix) != Compression::None)
+}
+
+fn parse_header(line: &str, line_number: usize) -> Result<(usize, usize)> {
+    let fields: Vec<&str> = line.split_whitespace().collect();
+    if fields.len() != 4 || fields[0] != "p" || fields[1] != "cnf" {
+        return Err(parse_error(line_number, "expected `p cnf <variables> <clauses>`"));
+    }
+
+    let vars = fields[2]
+        .parse()
+        .map_err(|_| parse_error(line_number, format!("invalid variable count `{}`", fields[2])))?;
+    let clauses = fields[3]
+        .parse()
+        .map_err(|_| parse_error(line_number, format!("invalid clause count `{}`", fields[3])))?;
+
+    if vars > i32::MAX as usize {
+        return Err(parse_error(line_number, "variable count exceeds i32::MAX"));
+    }
+
+    Ok((vars, clauses))
+}
+
+impl ParkissatSolver {
+    /// Parse a DIMACS CNF formula from a reader and add it to the solver
+    ///
+    /// Compressed input is decompressed transparently.
+    pub fn load_dimacs_reader<R: Read>(&mut self, reader: R) -> Result<()> {
+        if !self.is_configured() {
+            return Err(ParkissatError::NotConfigured);
+        }
+
+        let formula = parse_reader(decompress(reader)?)?;
+        self.add_formula(&formula)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wrapper::{SolverConfig, SolverResult};
+
+    fn error_line(input: &str) -> usize {
+        match parse_str(input) {
+            Err(ParkissatError::ParseError { line, .. }) => line,
+            other => panic!("expected parse error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_formula() {
+        let input = "c example\np cnf 3 2\n1 -2\n 3 0\n-1 0\n";
+        let formula = parse_str(input).unwrap();
+
+        assert_eq!(formula.num_vars(), 3);
+        assert_eq!(formula.clauses(), &[vec![1, -2, 3], vec![-1]]);
+    }
+
+    #[test]
+    fn test_satlib_terminator() {
+        let formula = parse_str("p cnf 2 1\n1 2 0\n%\n0\n").unwrap();
+        assert_eq!(formula.num_clauses(), 1);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(error_line("1 2 0\n"), 1);
+        assert_eq!(error_line("p cnf 2\n"), 1);
+        assert_eq!(error_line("p cnf 2 1\nc\n1 x 0\n"), 3);
+        assert_eq!(error_line("p cnf 2 1\n1 3 0\n"), 2);
+        assert_eq!(error_line("p cnf 2 1\n\n1 2\n"), 3);
+        assert_eq!(error_line("p cnf 2 2\n1 2 0\n"), 2);
+        assert_eq!(error_line("p cnf 2 1\n0\n"), 2);
+        assert_eq!(error_line(""), 0);
+    }
+
+    #[test]
+    fn test_load_dimacs_reader() {
+        let mut solver = ParkissatSolver::new().unwrap();
+        solver.configure(&SolverConfig::default()).unwrap();
+
+        solver.load_dimacs_reader("p cnf 2 2\n1 2 0\n-1 0\n".as_bytes()).unwrap();
+        assert_eq!(solver.solve().unwrap(), SolverResult::Sat);
+        assert!(solver.get_model_value(2).unwrap());
+    }
+
+    #[test]
+    fn test_detect_compression() {
+        assert_eq!(Compression::detect(&[0x1f, 0x8b, 8]), Compression::Gzip);
+        assert_eq!(Compression::detect(&[0xfd, b'7', b'z', b'X', b'Z', 0]), Compression::Xz);
+        assert_eq!(Compression::detect(b"BZh91AY"), Compression::Bzip2);
+        assert_eq!(Compression::detect(b"p cnf"), Compression::None);
+        assert_eq!(Compression::detect(b""), Compression::None);
+    }
+
+    #[test]
+    fn test_uncompressed_passthrough() {
+        let mut output = String::new();
+        decompress("p cnf 1 1\n1 0\n".as_bytes()).unwrap().read_to_string(&mut output).unwrap();
+        assert_eq!(output, "p cnf 1 1\n1 0\n");
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn test_gzip_input() {
+        use std::io::Write;
+
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(b"p cnf 2 1\n1 -2 0\n").unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        let formula = parse_reader(decompress(compressed.as_slice()).unwrap()).unwrap();
+        assert_eq!(formula.clauses(), &[vec![1, -2]]);
+    }
+
+    #[cfg(feature = "xz")]
+    #[test]
+    fn test_xz_input() {
+        use std::io::Write;
+
+        let mut encoder = xz2::write::XzEncoder::new(Vec::new(), 6);
+        encoder.write_all(b"p cnf 2 1\n1 -2 0\n").unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        let formula = parse_reader(decompress(compressed.as_slice()).unwrap()).unwrap();
+        assert_eq!(formula.clauses(), &[vec![1, -2]]);
+    }
+
+    #[cfg(feature = "bz2")]
+    #[test]
+    fn test_bzip2_file() {
+        use std::io::Write;
+
+        let file = tempfile::NamedTempFile::new().unwrap();
+        let mut encoder = bzip2::write::BzEncoder::new(std::fs::File::create(file.path()).unwrap(), bzip2::Compression::default());
+        encoder.write_all(b"p cnf 2 2\n1 2 0\n-1 0\n").unwrap();
+        encoder.finish().unwrap();
+
+        let mut solver = ParkissatSolver::new().unwrap();
+        solver.configure(&SolverConfig::default()).unwrap();
+        solver.load_dimacs(file.path()).unwrap();
+        assert_eq!(solver.solve().unwrap(), SolverResult::Sat);
+        assert!(solver.get_model_value(2).unwrap());
+    }
+}
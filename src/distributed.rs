@@ -0,0 +1,679 @@
+//! Distributed solving over TCP
+//!
+//! A [`Coordinator`] listens for worker processes, possibly on other
+//! machines, each running [`run_worker`]. Once the workers have connected,
+//! the coordinator sends every one of them the formula and then either
+//!
+//! - shards cubes among them ([`Coordinator::solve_cubes`]): each worker
+//!   solves one cube at a time under assumptions, the first SAT cube answers
+//!   for the formula, and the formula is UNSAT once every cube is refuted; or
+//! - runs a portfolio ([`Coordinator::solve_portfolio`]): every worker solves
+//!   the whole formula with a different random seed and the first definite
+//!   answer wins.
+//!
+//! In both modes workers stream short learned clauses to the coordinator
+//! through [`ParkissatSolver::set_learn_callback`], which relays them to every
+//! other worker; those import them into their running solve through a
+//! [`ClauseInjector`](crate::ClauseInjector). Clauses learned under a cube are
+//! still implied by the formula, so sharing is sound in both modes.
+//!
+//! Messages are frames of a little-endian `u32` payload length followed by
+//! the payload: a tag byte and the message's fields as little-endian
+//! integers, with literal lists prefixed by their `u32` length. When a
+//! session ends the coordinator sends every worker a stop message, which
+//! interrupts its solve and ends [`run_worker`]; the next session needs newly
+//! connected workers.
+
+use crate::cnf::CnfFormula;
+use crate::error::{ParkissatError, Result};
+use crate::wrapper::{ParkissatSolver, SolverConfig, SolverResult};
+use std::collections::VecDeque;
+use std::io::{ErrorKind, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Largest payload accepted on the wire
+const MAX_MESSAGE_BYTES: usize = 1 << 28;
+
+/// How often a worker sends the clauses it learned since the last batch
+const SHARE_INTERVAL: Duration = Duration::from_millis(100);
+
+const TAG_FORMULA: u8 = 1;
+const TAG_SOLVE: u8 = 2;
+const TAG_CLAUSES: u8 = 3;
+const TAG_STOP: u8 = 4;
+const TAG_ANSWER: u8 = 5;
+
+/// A protocol message, in either direction
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Message {
+    /// The formula of the session, with the worker's seed offset
+    Formula {
+        seed: u32,
+        share_length: u32,
+        num_vars: u32,
+        clauses: Vec<Vec<i32>>,
+    },
+    /// Solve the formula under assumptions
+    Solve { task: u64, assumptions: Vec<i32> },
+    /// Learned clauses, from a worker or relayed to one
+    Clauses(Vec<Vec<i32>>),
+    /// End of the session
+    Stop,
+    /// A worker's answer to a task
+    Answer {
+        task: u64,
+        result: SolverResult,
+        model: Vec<i32>,
+    },
+}
+
+fn invalid(message: &str) -> ParkissatError {
+    ParkissatError::IoError(format!("invalid distributed message: {}", message))
+}
+
+fn put_literals(payload: &mut Vec<u8>, literals: &[i32]) {
+    payload.extend_from_slice(&(literals.len() as u32).to_le_bytes());
+    for &lit in literals {
+        payload.extend_from_slice(&lit.to_le_bytes());
+    }
+}
+
+fn put_clauses(payload: &mut Vec<u8>, clauses: &[Vec<i32>]) {
+    payload.extend_from_slice(&(clauses.len() as u32).to_le_bytes());
+    for clause in clauses {
+        put_literals(payload, clause);
+    }
+}
+
+fn result_code(result: SolverResult) -> u8 {
+    match result {
+        SolverResult::Sat => 10,
+        SolverResult::Unsat => 20,
+        SolverResult::Unknown => 0,
+    }
+}
+
+impl Message {
+    fn encode(&self) -> Vec<u8> {
+        let mut payload = Vec::new();
+        match self {
+            Message::Formula { seed, share_length, num_vars, clauses } => {
+                payload.push(TAG_FORMULA);
+                payload.extend_from_slice(&seed.to_le_bytes());
+                payload.extend_from_slice(&share_length.to_le_bytes());
+                payload.extend_from_slice(&num_vars.to_le_bytes());
+                put_clauses(&mut payload, clauses);
+            }
+            Message::Solve { task, assumptions } => {
+                payload.push(TAG_SOLVE);
+                payload.extend_from_slice(&task.to_le_bytes());
+                put_literals(&mut payload, assumptions);
+            }
+            Message::Clauses(clauses) => {
+                payload.push(TAG_CLAUSES);
+                put_clauses(&mut payload, clauses);
+            }
+            Message::Stop => payload.push(TAG_STOP),
+            Message::Answer { task, result, model } => {
+                payload.push(TAG_ANSWER);
+                payload.extend_from_slice(&task.to_le_bytes());
+                payload.push(result_code(*result));
+                put_literals(&mut payload, model);
+            }
+        }
+
+        let mut frame = Vec::with_capacity(4 + payload.len());
+        frame.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+        frame.extend_from_slice(&payload);
+        frame
+    }
+
+    fn decode(payload: &[u8]) -> Result<Self> {
+        let mut cursor = Cursor { bytes: payload };
+        let message = match cursor.u8()? {
+            TAG_FORMULA => Message::Formula {
+                seed: cursor.u32()?,
+                share_length: cursor.u32()?,
+                num_vars: cursor.u32()?,
+                clauses: cursor.clauses()?,
+            },
+            TAG_SOLVE => Message::Solve {
+                task: cursor.u64()?,
+                assumptions: cursor.literals()?,
+            },
+            TAG_CLAUSES => Message::Clauses(cursor.clauses()?),
+            TAG_STOP => Message::Stop,
+            TAG_ANSWER => Message::Answer {
+                task: cursor.u64()?,
+                result: match cursor.u8()? {
+                    10 => SolverResult::Sat,
+                    20 => SolverResult::Unsat,
+                    0 => SolverResult::Unknown,
+                    _ => return Err(invalid("unknown result code")),
+                },
+                model: cursor.literals()?,
+            },
+            _ => return Err(invalid("unknown tag")),
+        };
+
+        if !cursor.bytes.is_empty() {
+            return Err(invalid("trailing bytes"));
+        }
+        Ok(message)
+    }
+}
+
+struct Cursor<'a> {
+    bytes: &'a [u8],
+}
+
+impl Cursor<'_> {
+    fn take<const N: usize>(&mut self) -> Result<[u8; N]> {
+        if self.bytes.len() < N {
+            return Err(invalid("truncated payload"));
+        }
+        let (head, rest) = self.bytes.split_at(N);
+        self.bytes = rest;
+        Ok(head.try_into().expect("split at N"))
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.take::<1>()?[0])
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        Ok(u32::from_le_bytes(self.take()?))
+    }
+
+    fn u64(&mut self) -> Result<u64> {
+        Ok(u64::from_le_bytes(self.take()?))
+    }
+
+    fn literals(&mut self) -> Result<Vec<i32>> {
+        let len = self.u32()? as usize;
+        if len > self.bytes.len() / 4 {
+            return Err(invalid("truncated literal list"));
+        }
+        (0..len).map(|_| Ok(i32::from_le_bytes(self.take()?))).collect()
+    }
+
+    fn clauses(&mut self) -> Result<Vec<Vec<i32>>> {
+        let count = self.u32()? as usize;
+        if count > self.bytes.len() / 4 {
+            return Err(invalid("truncated clause list"));
+        }
+        (0..count).map(|_| self.literals()).collect()
+    }
+}
+
+fn read_message<R: Read>(reader: &mut R) -> Result<Message> {
+    let mut word = [0u8; 4];
+    reader.read_exact(&mut word)?;
+
+    let len = u32::from_le_bytes(word) as usize;
+    if len > MAX_MESSAGE_BYTES {
+        return Err(invalid("message too large"));
+    }
+
+    let mut payload = vec![0u8; len];
+    reader.read_exact(&mut payload)?;
+    Message::decode(&payload)
+}
+
+fn write_message<W: Write>(writer: &mut W, message: &Message) -> Result<()> {
+    writer.write_all(&message.encode())?;
+    Ok(())
+}
+
+/// Options for a distributed session
+#[derive(Debug, Clone)]
+pub struct DistributedOptions {
+    /// Number of workers to wait for (default: 1)
+    pub workers: usize,
+
+    /// How long to wait for them to connect (default: 30 seconds)
+    ///
+    /// The session starts with the workers connected by then, and fails if
+    /// there are none.
+    pub accept_timeout: Duration,
+
+    /// Longest learned clause exchanged between workers, 0 = no exchange (default: 8)
+    pub share_length: usize,
+}
+
+impl Default for DistributedOptions {
+    fn default() -> Self {
+        Self {
+            workers: 1,
+            accept_timeout: Duration::from_secs(30),
+            share_length: 8,
+        }
+    }
+}
+
+/// Result of a distributed session
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DistributedOutcome {
+    /// The answer for the whole formula
+    pub result: SolverResult,
+    /// The model, if the answer is SAT
+    pub model: Option<Vec<i32>>,
+    /// The cube that was found SAT, in cube mode
+    pub cube: Option<Vec<i32>>,
+    /// Number of workers that took part
+    pub workers: usize,
+    /// Number of cubes refuted, in cube mode
+    pub refuted: usize,
+    /// Number of learned clauses relayed between workers
+    pub shared_clauses: u64,
+}
+
+/// Hands out work to [`run_worker`] processes and collects their answers
+pub struct Coordinator {
+    listener: TcpListener,
+    addr: SocketAddr,
+}
+
+impl Coordinator {
+    /// Listen for workers on the given address (use port 0 for any free port)
+    pub fn bind<A: ToSocketAddrs>(addr: A) -> Result<Self> {
+        let listener = TcpListener::bind(addr)?;
+        let addr = listener.local_addr()?;
+        Ok(Self { listener, addr })
+    }
+
+    /// Address workers should connect to
+    pub fn local_addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Solve a formula by sharding cubes among the workers
+    ///
+    /// The cubes must cover every model of the formula, as those of
+    /// [`cube::generate_cubes`](crate::cube::generate_cubes) do. A cube whose
+    /// worker disconnects is handed to another one.
+    pub fn solve_cubes(&self, formula: &CnfFormula, cubes: &[Vec<i32>], options: &DistributedOptions) -> Result<DistributedOutcome> {
+        self.run(formula, cubes.iter().cloned().collect(), false, options)
+    }
+
+    /// Solve a formula with every worker racing on it under a different seed
+    pub fn solve_portfolio(&self, formula: &CnfFormula, options: &DistributedOptions) -> Result<DistributedOutcome> {
+        self.run(formula, VecDeque::new(), true, options)
+    }
+
+    fn accept(&self, options: &DistributedOptions) -> Result<Vec<TcpStream>> {
+        if options.workers == 0 {
+            return Err(ParkissatError::InvalidConfiguration(
+                "Distributed solving needs at least one worker".to_string()
+            ));
+        }
+
+        let deadline = Instant::now() + options.accept_timeout;
+        let mut streams = Vec::new();
+        self.listener.set_nonblocking(true)?;
+        while streams.len() < options.workers && Instant::now() < deadline {
+            match self.listener.accept() {
+                Ok((stream, _)) => {
+                    stream.set_nonblocking(false)?;
+                    stream.set_nodelay(true)?;
+                    streams.push(stream);
+                }
+                Err(err) if err.kind() == ErrorKind::WouldBlock => thread::sleep(Duration::from_millis(10)),
+                Err(err) => return Err(err.into()),
+            }
+        }
+        self.listener.set_nonblocking(false)?;
+
+        if streams.is_empty() {
+            return Err(ParkissatError::IoError("no distributed worker connected".to_string()));
+        }
+        Ok(streams)
+    }
+
+    fn run(&self, formula: &CnfFormula, mut cubes: VecDeque<Vec<i32>>, portfolio: bool, options: &DistributedOptions) -> Result<DistributedOutcome> {
+        let streams = self.accept(options)?;
+        let mut outcome = DistributedOutcome {
+            result: SolverResult::Unknown,
+            model: None,
+            cube: None,
+            workers: streams.len(),
+            refuted: 0,
+            shared_clauses: 0,
+        };
+
+        let (tx, rx) = mpsc::channel();
+        let mut writers = Vec::with_capacity(streams.len());
+        for (index, stream) in streams.into_iter().enumerate() {
+            let mut reader = stream.try_clone()?;
+            let tx = tx.clone();
+            thread::spawn(move || loop {
+                let message = read_message(&mut reader);
+                let failed = message.is_err();
+                if tx.send((index, message)).is_err() || failed {
+                    break;
+                }
+            });
+            writers.push(Some(stream));
+        }
+        drop(tx);
+
+        let formula_message = Message::Formula {
+            seed: 0,
+            share_length: options.share_length.min(u32::MAX as usize) as u32,
+            num_vars: formula.num_vars().min(u32::MAX as usize) as u32,
+            clauses: formula.clauses().to_vec(),
+        };
+        // Each worker's current task: the cube it solves, if any
+        let mut running: Vec<Option<Vec<i32>>> = vec![None; writers.len()];
+        let mut next_task = 0u64;
+        for index in 0..writers.len() {
+            let mut message = formula_message.clone();
+            if let Message::Formula { seed, .. } = &mut message {
+                *seed = index as u32;
+            }
+            if !send(&mut writers[index], &message) {
+                continue;
+            }
+
+            if portfolio {
+                assign(&mut writers[index], &mut running[index], &mut next_task, Vec::new());
+            } else if let Some(cube) = cubes.pop_front() {
+                if let Some(cube) = assign(&mut writers[index], &mut running[index], &mut next_task, cube) {
+                    cubes.push_front(cube);
+                }
+            }
+        }
+
+        let mut unknown = false;
+        while running.iter().any(Option::is_some) {
+            let Ok((index, message)) = rx.recv() else {
+                break;
+            };
+            match message {
+                Ok(Message::Clauses(clauses)) => {
+                    outcome.shared_clauses += clauses.len() as u64;
+                    let relay = Message::Clauses(clauses);
+                    for (other, writer) in writers.iter_mut().enumerate() {
+                        if other != index {
+                            send(writer, &relay);
+                        }
+                    }
+                }
+                Ok(Message::Answer { result, model, .. }) => {
+                    let cube = running[index].take();
+                    match result {
+                        SolverResult::Sat => {
+                            outcome.result = SolverResult::Sat;
+                            outcome.model = Some(model);
+                            outcome.cube = cube.filter(|_| !portfolio);
+                            break;
+                        }
+                        SolverResult::Unsat if portfolio => {
+                            outcome.result = SolverResult::Unsat;
+                            break;
+                        }
+                        SolverResult::Unsat => outcome.refuted += 1,
+                        SolverResult::Unknown => unknown = true,
+                    }
+
+                    if let Some(cube) = cubes.pop_front() {
+                        if let Some(cube) = assign(&mut writers[index], &mut running[index], &mut next_task, cube) {
+                            cubes.push_front(cube);
+                        }
+                    }
+                }
+                Ok(_) => {}
+                Err(_) => {
+                    // A lost worker's cube goes to the next idle one
+                    writers[index] = None;
+                    if let Some(cube) = running[index].take().filter(|_| !portfolio) {
+                        cubes.push_front(cube);
+                    }
+                    for idle in 0..writers.len() {
+                        if running[idle].is_some() || writers[idle].is_none() {
+                            continue;
+                        }
+                        let Some(cube) = cubes.pop_front() else {
+                            break;
+                        };
+                        if let Some(cube) = assign(&mut writers[idle], &mut running[idle], &mut next_task, cube) {
+                            cubes.push_front(cube);
+                        }
+                    }
+                }
+            }
+        }
+
+        for writer in &mut writers {
+            send(writer, &Message::Stop);
+        }
+
+        if outcome.result == SolverResult::Unknown && !portfolio && !unknown && cubes.is_empty() {
+            outcome.result = SolverResult::Unsat;
+        }
+        if outcome.result == SolverResult::Unknown && writers.iter().all(Option::is_none) {
+            return Err(ParkissatError::IoError("every distributed worker disconnected".to_string()));
+        }
+        Ok(outcome)
+    }
+}
+
+/// Send a message, forgetting the worker if its connection has failed
+fn send(writer: &mut Option<TcpStream>, message: &Message) -> bool {
+    let sent = writer.as_mut().is_some_and(|stream| write_message(stream, message).is_ok());
+    if !sent {
+        *writer = None;
+    }
+    sent
+}
+
+/// Hand a cube to a worker and record it as running
+///
+/// Gives the cube back if the worker's connection has failed.
+fn assign(writer: &mut Option<TcpStream>, running: &mut Option<Vec<i32>>, next_task: &mut u64, cube: Vec<i32>) -> Option<Vec<i32>> {
+    let solve = Message::Solve { task: *next_task, assumptions: cube.clone() };
+    *next_task += 1;
+    if send(writer, &solve) {
+        *running = Some(cube);
+        None
+    } else {
+        Some(cube)
+    }
+}
+
+/// Solve tasks from a [`Coordinator`] until it ends the session
+///
+/// `config` configures the worker's solver; its random seed is offset by
+/// the worker's index so portfolio workers search differently. Returns once
+/// the coordinator sends its stop message or closes the connection.
+pub fn run_worker<A: ToSocketAddrs>(addr: A, config: &SolverConfig) -> Result<()> {
+    let stream = TcpStream::connect(addr)?;
+    stream.set_nodelay(true)?;
+    let mut reader = stream.try_clone()?;
+    let writer = Arc::new(Mutex::new(stream));
+
+    let Message::Formula { seed, share_length, num_vars, clauses } = read_message(&mut reader)? else {
+        return Err(invalid("expected the formula first"));
+    };
+    let mut formula = CnfFormula::with_variables(num_vars as usize);
+    for clause in &clauses {
+        formula.add_clause(clause)?;
+    }
+
+    let mut solver = ParkissatSolver::new()?;
+    solver.configure(&SolverConfig {
+        random_seed: config.random_seed.wrapping_add(seed),
+        ..config.clone()
+    })?;
+    solver.add_formula(&formula)?;
+
+    // Learned clauses are batched and sent every SHARE_INTERVAL
+    let learned = Arc::new(Mutex::new(Vec::new()));
+    let done = Arc::new(AtomicBool::new(false));
+    let flusher = if share_length > 0 {
+        let batch = Arc::clone(&learned);
+        solver.set_learn_callback(share_length as usize, move |clause| batch.lock().unwrap().push(clause.to_vec()));
+
+        let (learned, done, writer) = (Arc::clone(&learned), Arc::clone(&done), Arc::clone(&writer));
+        Some(thread::spawn(move || {
+            while !done.load(Ordering::SeqCst) {
+                thread::sleep(SHARE_INTERVAL);
+                let clauses = std::mem::take(&mut *learned.lock().unwrap());
+                if !clauses.is_empty() && write_message(&mut *writer.lock().unwrap(), &Message::Clauses(clauses)).is_err() {
+                    break;
+                }
+            }
+        }))
+    } else {
+        None
+    };
+
+    // Tasks go to this thread; relayed clauses and the stop message take effect at once
+    let (tasks, pending) = mpsc::channel();
+    let injector = solver.clause_injector();
+    let interrupt = solver.interrupt_handle();
+    thread::spawn(move || {
+        while let Ok(message) = read_message(&mut reader) {
+            match message {
+                Message::Solve { task, assumptions } => {
+                    // The assumptions move into the channel, so this cannot be a match guard
+                    let Ok(()) = tasks.send((task, assumptions)) else { break };
+                }
+                Message::Clauses(clauses) => {
+                    for clause in &clauses {
+                        let _ = injector.inject(clause);
+                    }
+                }
+                Message::Stop => break,
+                _ => {}
+            }
+        }
+        interrupt.interrupt();
+    });
+
+    let result = serve(&mut solver, pending, &writer, num_vars as usize);
+    done.store(true, Ordering::SeqCst);
+    if let Some(flusher) = flusher {
+        let _ = flusher.join();
+    }
+    solver.clear_learn_callback();
+    result
+}
+
+/// Answer tasks until the coordinator ends the session
+fn serve(solver: &mut ParkissatSolver, pending: mpsc::Receiver<(u64, Vec<i32>)>, writer: &Mutex<TcpStream>, num_vars: usize) -> Result<()> {
+    for (task, assumptions) in pending {
+        // A stop message that arrived before the solve started still interrupts it
+        let result = solver.solve_with_assumptions(&assumptions)?;
+        let model = match result {
+            SolverResult::Sat => solver.get_model()?.into_iter().take(num_vars).collect(),
+            _ => Vec::new(),
+        };
+        if write_message(&mut *writer.lock().unwrap(), &Message::Answer { task, result, model }).is_err() {
+            break;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cnf::pigeonhole;
+    use crate::cube::{generate_cubes, CubeOptions};
+
+    fn spawn_workers(coordinator: &Coordinator, count: usize) -> Vec<thread::JoinHandle<Result<()>>> {
+        let addr = coordinator.local_addr();
+        let config = SolverConfig { num_threads: 1, ..SolverConfig::default() };
+        (0..count)
+            .map(|_| {
+                let config = config.clone();
+                thread::spawn(move || run_worker(addr, &config))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_message_roundtrip() {
+        let messages = [
+            Message::Formula { seed: 3, share_length: 8, num_vars: 4, clauses: vec![vec![1, -2], vec![4]] },
+            Message::Solve { task: 7, assumptions: vec![-1, 3] },
+            Message::Clauses(vec![vec![2, 3]]),
+            Message::Stop,
+            Message::Answer { task: 7, result: SolverResult::Sat, model: vec![1, -2, 3, 4] },
+        ];
+        for message in messages {
+            let frame = message.encode();
+            assert_eq!(read_message(&mut frame.as_slice()).unwrap(), message);
+            assert!(read_message(&mut &frame[..frame.len() - 1]).is_err());
+        }
+
+        // A clause count beyond the payload is rejected before allocating
+        let mut frame = vec![5, 0, 0, 0, TAG_CLAUSES];
+        frame.extend_from_slice(&u32::MAX.to_le_bytes());
+        assert!(read_message(&mut frame.as_slice()).is_err());
+    }
+
+    #[test]
+    fn test_cubes_across_workers() {
+        let coordinator = Coordinator::bind("127.0.0.1:0").unwrap();
+        let options = DistributedOptions { workers: 2, ..DistributedOptions::default() };
+
+        // 6 pigeons in 5 holes is UNSAT, so every cube must be refuted
+        let formula = pigeonhole(6, 5);
+        let cubes = generate_cubes(&formula, &CubeOptions { depth: 3, ..CubeOptions::default() });
+        let workers = spawn_workers(&coordinator, 2);
+        let outcome = coordinator.solve_cubes(&formula, &cubes, &options).unwrap();
+        assert_eq!(outcome.result, SolverResult::Unsat);
+        assert_eq!((outcome.workers, outcome.refuted), (2, cubes.len()));
+        for worker in workers {
+            worker.join().unwrap().unwrap();
+        }
+
+        // 5 pigeons in 5 holes is SAT, and the model satisfies the SAT cube
+        let formula = pigeonhole(5, 5);
+        let cubes = generate_cubes(&formula, &CubeOptions { depth: 3, ..CubeOptions::default() });
+        let workers = spawn_workers(&coordinator, 2);
+        let outcome = coordinator.solve_cubes(&formula, &cubes, &options).unwrap();
+        assert_eq!(outcome.result, SolverResult::Sat);
+        let model = outcome.model.unwrap();
+        assert!(formula.is_satisfied_by(&model));
+        assert!(outcome.cube.unwrap().iter().all(|lit| model.contains(lit)));
+        for worker in workers {
+            worker.join().unwrap().unwrap();
+        }
+    }
+
+    #[test]
+    fn test_portfolio() {
+        let coordinator = Coordinator::bind("127.0.0.1:0").unwrap();
+        let options = DistributedOptions { workers: 3, ..DistributedOptions::default() };
+        let workers = spawn_workers(&coordinator, 3);
+        let outcome = coordinator.solve_portfolio(&pigeonhole(7, 6), &options).unwrap();
+        assert_eq!(outcome.result, SolverResult::Unsat);
+        assert_eq!(outcome.cube, None);
+        for worker in workers {
+            worker.join().unwrap().unwrap();
+        }
+    }
+
+    #[test]
+    fn test_no_workers() {
+        let coordinator = Coordinator::bind("127.0.0.1:0").unwrap();
+        let options = DistributedOptions { accept_timeout: Duration::from_millis(50), ..DistributedOptions::default() };
+        assert!(matches!(
+            coordinator.solve_portfolio(&pigeonhole(2, 1), &options),
+            Err(ParkissatError::IoError(_))
+        ));
+        let options = DistributedOptions { workers: 0, ..DistributedOptions::default() };
+        assert!(matches!(
+            coordinator.solve_portfolio(&pigeonhole(2, 1), &options),
+            Err(ParkissatError::InvalidConfiguration(_))
+        ));
+    }
+}
@@ -0,0 +1,205 @@
+//! Editable clauses for fast edit-solve loops
+//!
+//! An editable clause is added as `(¬s ∨ l1 ∨ ... ∨ ln)` for a fresh selector
+//! variable `s` that is assumed true on every solve. Editing a clause retires
+//! its selector with the unit `¬s` and re-adds the new literals under a new
+//! selector, so the rest of the formula stays valid and is kept. Like any
+//! other solve, the next one rebuilds the workers and carries over the short
+//! learned clauses of the last solve; those satisfied by the retired selector
+//! are dropped, and [`EditReport`] tells how many are kept.
+//!
+//! Selectors are allocated past the current variable count, so declare all
+//! problem variables (or use [`ParkissatSolver::new_variable`]) before adding
+//! editable clauses that mention variables not seen yet.
+
+use crate::error::{ParkissatError, Result};
+use crate::ffi;
+use crate::wrapper::ParkissatSolver;
+use std::collections::BTreeMap;
+
+/// Selector-guarded clauses owned by a solver
+#[derive(Debug, Default)]
+pub(crate) struct EditableClauses {
+    groups: BTreeMap<usize, (i32, Vec<i32>)>,
+    next_group: usize,
+}
+
+impl EditableClauses {
+    /// Selectors of all live editable clauses, to be assumed true
+    pub(crate) fn selectors(&self) -> Vec<i32> {
+        self.groups.values().map(|(selector, _)| *selector).collect()
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.groups.is_empty()
+    }
+}
+
+/// What an edit kept and what it discarded
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EditReport {
+    /// Group of the edited clause
+    pub group: usize,
+    /// Editable clauses left untouched by the edit
+    ///
+    /// Permanent clauses added with `add_clause` are always retained as well.
+    pub retained_clauses: usize,
+    /// Learned clauses of the last solve that the next one starts from
+    pub retained_learned_clauses: usize,
+    /// Clauses retired by the edit
+    pub retired_clauses: usize,
+}
+
+impl ParkissatSolver {
+    /// Add a clause that can later be replaced or strengthened
+    ///
+    /// Returns the group id used to refer to the clause.
+    pub fn add_editable_clause(&mut self, literals: &[i32]) -> Result<usize> {
+        let group = self.editable.next_group;
+        self.add_guarded(group, literals)?;
+        self.editable.next_group += 1;
+        Ok(group)
+    }
+
+    /// Current literals of an editable clause
+    pub fn editable_clause(&self, group: usize) -> Option<&[i32]> {
+        self.editable.groups.get(&group).map(|(_, literals)| literals.as_slice())
+    }
+
+    /// Replace the literals of an editable clause
+    pub fn replace_clause(&mut self, group: usize, new_literals: &[i32]) -> Result<EditReport> {
+        let (selector, _) = *self.editable.groups.get(&group)
+            .ok_or_else(|| ParkissatError::InvalidClause(format!("Unknown clause group {}", group)))?;
+
+        // Add the replacement first so a rejected clause leaves the old one intact
+        let old = self.editable.groups.remove(&group);
+        if let Err(err) = self.add_guarded(group, new_literals) {
+            if let Some(old) = old {
+                self.editable.groups.insert(group, old);
+            }
+            return Err(err);
+        }
+
+        self.add_clause(&[-selector])?;
+
+        let retained_learned_clauses = unsafe { ffi::parkissat_get_carried_learned_count(self.solver) };
+        self.check_error()?;
+
+        Ok(EditReport {
+            group,
+            retained_clauses: self.editable.groups.len() - 1,
+            retained_learned_clauses: retained_learned_clauses.max(0) as usize,
+            retired_clauses: 1,
+        })
+    }
+
+    /// Remove literals from an editable clause, making it stronger
+    ///
+    /// Every literal in `remove` must occur in the clause, and at least one
+    /// literal must remain.
+    pub fn strengthen_clause(&mut self, group: usize, remove: &[i32]) -> Result<EditReport> {
+        let literals = self.editable_clause(group)
+            .ok_or_else(|| ParkissatError::InvalidClause(format!("Unknown clause group {}", group)))?;
+
+        if let Some(&missing) = remove.iter().find(|lit| !literals.contains(lit)) {
+            return Err(ParkissatError::InvalidClause(
+                format!("Literal {} does not occur in clause group {}", missing, group)
+            ));
+        }
+
+        let strengthened: Vec<i32> = literals.iter().copied().filter(|lit| !remove.contains(lit)).collect();
+        if strengthened.is_empty() {
+            return Err(ParkissatError::InvalidClause("Strengthening would leave an empty clause".to_string()));
+        }
+
+        self.replace_clause(group, &strengthened)
+    }
+
+    fn add_guarded(&mut self, group: usize, literals: &[i32]) -> Result<()> {
+        if literals.is_empty() {
+            return Err(ParkissatError::InvalidClause("Empty clause".to_string()));
+        }
+        if literals.contains(&0) {
+            return Err(ParkissatError::InvalidClause("Literal cannot be zero".to_string()));
+        }
+
+        // Literals may mention variables beyond the current count; the selector must not collide
+        let highest = literals.iter().map(|lit| lit.unsigned_abs() as usize).max().unwrap_or(0);
+        if highest > self.variable_count() {
+            self.set_variable_count(highest)?;
+        }
+
+        let selector = self.new_variable()?;
+        let mut guarded = Vec::with_capacity(literals.len() + 1);
+        guarded.push(-selector);
+        guarded.extend_from_slice(literals);
+        self.add_clause(&guarded)?;
+
+        self.editable.groups.insert(group, (selector, literals.to_vec()));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cnf::{configured_solver, pigeonhole};
+    use crate::limits::SolverLimits;
+    use crate::wrapper::SolverResult;
+
+    #[test]
+    fn test_replace_clause() {
+        let mut solver = configured_solver();
+        solver.set_variable_count(3).unwrap();
+        solver.add_clause(&[1, 2]).unwrap();
+        let group = solver.add_editable_clause(&[-1]).unwrap();
+        solver.add_editable_clause(&[-3]).unwrap();
+
+        assert_eq!(solver.solve().unwrap(), SolverResult::Sat);
+        assert!(solver.get_model_value(2).unwrap());
+
+        let report = solver.replace_clause(group, &[-2]).unwrap();
+        assert_eq!(report, EditReport { group, retained_clauses: 1, retained_learned_clauses: 0, retired_clauses: 1 });
+        assert_eq!(solver.editable_clause(group), Some(&[-2][..]));
+
+        assert_eq!(solver.solve().unwrap(), SolverResult::Sat);
+        assert!(solver.get_model_value(1).unwrap());
+    }
+
+    #[test]
+    fn test_edit_keeps_learned_clauses() {
+        let mut solver = configured_solver();
+        solver.add_formula(&pigeonhole(9, 8)).unwrap();
+        let group = solver.add_editable_clause(&[1, 2]).unwrap();
+
+        let limits = SolverLimits::new().conflicts(2000);
+        assert_eq!(solver.solve_limited(&limits).unwrap(), SolverResult::Unknown);
+
+        let report = solver.replace_clause(group, &[3]).unwrap();
+        assert!(report.retained_learned_clauses > 0);
+        assert_eq!(solver.solve_limited(&limits).unwrap(), SolverResult::Unknown);
+    }
+
+    #[test]
+    fn test_strengthen_clause() {
+        let mut solver = configured_solver();
+        solver.add_clause(&[-1]).unwrap();
+        let group = solver.add_editable_clause(&[1, 2]).unwrap();
+
+        solver.strengthen_clause(group, &[2]).unwrap();
+        assert_eq!(solver.solve().unwrap(), SolverResult::Unsat);
+
+        assert!(solver.strengthen_clause(group, &[1]).is_err());
+        assert!(solver.strengthen_clause(group, &[3]).is_err());
+    }
+
+    #[test]
+    fn test_invalid_edits_keep_clause() {
+        let mut solver = configured_solver();
+        let group = solver.add_editable_clause(&[1, 2]).unwrap();
+
+        assert!(solver.replace_clause(group, &[]).is_err());
+        assert!(solver.replace_clause(group + 1, &[1]).is_err());
+        assert_eq!(solver.editable_clause(group), Some(&[1, 2][..]));
+    }
+}
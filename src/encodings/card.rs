@@ -0,0 +1,368 @@
+//! Cardinality constraints
+//!
+//! `at_most_k`, `at_least_k`, and `exactly_k` restrict how many of a set of
+//! literals may be true. Three encodings are available:
+//!
+//! - [`CardEncoding::Pairwise`] forbids every set of `k + 1` literals with
+//!   one clause each. No auxiliary variables, but `C(n, k + 1)` clauses, so
+//!   only suitable for small inputs.
+//! - [`CardEncoding::SequentialCounter`] (Sinz 2005) uses `O(n·k)`
+//!   auxiliary variables and clauses.
+//! - [`CardEncoding::Totalizer`] (Bailleux & Boufkhad 2003) builds a unary
+//!   adder tree with `O(n·log n)` auxiliary variables and `O(n·k)` clauses.
+//!
+//! [`IncrementalCard`] keeps a totalizer's outputs so one encoding serves a
+//! sequence of bounds, as in MaxSAT and optimization loops: each bound is an
+//! assumption or a unit clause rather than a new encoding.
+
+use super::ClauseSink;
+use crate::error::Result;
+use crate::types::Lit;
+
+/// Encoding used for a cardinality constraint
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CardEncoding {
+    /// One clause per forbidden subset, no auxiliary variables
+    Pairwise,
+    /// Sinz sequential counter
+    #[default]
+    SequentialCounter,
+    /// Totalizer (unary adder tree)
+    Totalizer,
+}
+
+/// At most one of `lits` is true
+pub fn at_most_one<S: ClauseSink>(sink: &mut S, lits: &[Lit], encoding: CardEncoding) -> Result<()> {
+    at_most_k(sink, lits, 1, encoding)
+}
+
+/// At most `k` of `lits` are true
+pub fn at_most_k<S: ClauseSink>(sink: &mut S, lits: &[Lit], k: usize, encoding: CardEncoding) -> Result<()> {
+    if k >= lits.len() {
+        return Ok(());
+    }
+    if k == 0 {
+        for &lit in lits {
+            sink.emit_clause(&[!lit])?;
+        }
+        return Ok(());
+    }
+
+    match encoding {
+        CardEncoding::Pairwise => pairwise(sink, lits, k),
+        CardEncoding::SequentialCounter => sequential_counter(sink, lits, k),
+        CardEncoding::Totalizer => totalizer(sink, lits, k),
+    }
+}
+
+/// At least `k` of `lits` are true
+///
+/// Encoded as "at most `n - k` of the negated literals are true". If `k`
+/// exceeds the number of literals the constraint is unsatisfiable and a
+/// contradiction is emitted.
+pub fn at_least_k<S: ClauseSink>(sink: &mut S, lits: &[Lit], k: usize, encoding: CardEncoding) -> Result<()> {
+    if k > lits.len() {
+        return sink.emit_contradiction();
+    }
+    if k == 0 {
+        return Ok(());
+    }
+    if k == 1 {
+        return sink.emit_clause(lits);
+    }
+
+    let negated: Vec<Lit> = lits.iter().map(|&lit| !lit).collect();
+    at_most_k(sink, &negated, lits.len() - k, encoding)
+}
+
+/// Exactly `k` of `lits` are true
+pub fn exactly_k<S: ClauseSink>(sink: &mut S, lits: &[Lit], k: usize, encoding: CardEncoding) -> Result<()> {
+    at_most_k(sink, lits, k, encoding)?;
+    at_least_k(sink, lits, k, encoding)
+}
+
+/// A totalizer over a fixed set of literals whose upper bound can change
+/// between solves
+///
+/// The encoding counts every input, so any bound can be expressed without
+/// re-encoding. [`at_most`](Self::at_most) gives an assumption for a bound
+/// that holds for one solve; [`tighten`](Self::tighten) adds it as a unit
+/// clause, which holds for every later solve.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IncrementalCard {
+    /// `outputs[j]` is implied by "at least `j + 1` inputs are true"
+    outputs: Vec<Lit>,
+    /// Tightest bound added as a unit clause
+    bound: Option<usize>,
+}
+
+impl IncrementalCard {
+    /// Encode a totalizer over `lits` into `sink`
+    pub fn new<S: ClauseSink>(sink: &mut S, lits: &[Lit]) -> Result<Self> {
+        let outputs = if lits.is_empty() {
+            Vec::new()
+        } else {
+            totalizer_tree(sink, lits, lits.len())?
+        };
+        Ok(Self { outputs, bound: None })
+    }
+
+    /// Number of literals counted
+    pub fn num_inputs(&self) -> usize {
+        self.outputs.len()
+    }
+
+    /// Unary count outputs: `outputs()[j]` is true if at least `j + 1` inputs are
+    pub fn outputs(&self) -> &[Lit] {
+        &self.outputs
+    }
+
+    /// Assumption that enforces "at most `k` inputs are true"
+    ///
+    /// `None` if the bound holds trivially, i.e. `k` is at least the number
+    /// of inputs.
+    pub fn at_most(&self, k: usize) -> Option<Lit> {
+        self.outputs.get(k).map(|&output| !output)
+    }
+
+    /// Permanently enforce "at most `k` inputs are true"
+    ///
+    /// Unit clauses cannot be retracted, so a bound looser than the current
+    /// one adds nothing.
+    pub fn tighten<S: ClauseSink>(&mut self, sink: &mut S, k: usize) -> Result<()> {
+        if self.bound.is_some_and(|bound| bound <= k) {
+            return Ok(());
+        }
+        if let Some(assumption) = self.at_most(k) {
+            sink.emit_clause(&[assumption])?;
+            self.bound = Some(k);
+        }
+        Ok(())
+    }
+
+    /// Tightest bound enforced by [`tighten`](Self::tighten), if any
+    pub fn bound(&self) -> Option<usize> {
+        self.bound
+    }
+}
+
+fn pairwise<S: ClauseSink>(sink: &mut S, lits: &[Lit], k: usize) -> Result<()> {
+    // Enumerate every (k + 1)-subset by index
+    let size = k + 1;
+    let mut indices: Vec<usize> = (0..size).collect();
+    loop {
+        let clause: Vec<Lit> = indices.iter().map(|&i| !lits[i]).collect();
+        sink.emit_clause(&clause)?;
+
+        let Some(position) = (0..size).rev().find(|&p| indices[p] != p + lits.len() - size) else {
+            return Ok(());
+        };
+        indices[position] += 1;
+        for next in position + 1..size {
+            indices[next] = indices[next - 1] + 1;
+        }
+    }
+}
+
+fn sequential_counter<S: ClauseSink>(sink: &mut S, lits: &[Lit], k: usize) -> Result<()> {
+    let n = lits.len();
+
+    // registers[i][j] means "at least j + 1 of lits[0..=i] are true"
+    let mut registers: Vec<Vec<Lit>> = Vec::with_capacity(n - 1);
+    for _ in 0..n - 1 {
+        let mut row = Vec::with_capacity(k);
+        for _ in 0..k {
+            row.push(sink.fresh_var()?.positive());
+        }
+        registers.push(row);
+    }
+
+    sink.emit_clause(&[!lits[0], registers[0][0]])?;
+    for &register in &registers[0][1..] {
+        sink.emit_clause(&[!register])?;
+    }
+
+    for i in 1..n - 1 {
+        let (previous, current) = (&registers[i - 1], &registers[i]);
+        sink.emit_clause(&[!lits[i], current[0]])?;
+        sink.emit_clause(&[!previous[0], current[0]])?;
+        for j in 1..k {
+            sink.emit_clause(&[!lits[i], !previous[j - 1], current[j]])?;
+            sink.emit_clause(&[!previous[j], current[j]])?;
+        }
+        sink.emit_clause(&[!lits[i], !previous[k - 1]])?;
+    }
+
+    sink.emit_clause(&[!lits[n - 1], !registers[n - 2][k - 1]])
+}
+
+fn totalizer<S: ClauseSink>(sink: &mut S, lits: &[Lit], k: usize) -> Result<()> {
+    let outputs = totalizer_tree(sink, lits, k + 1)?;
+    if let Some(&overflow) = outputs.get(k) {
+        sink.emit_clause(&[!overflow])?;
+    }
+    Ok(())
+}
+
+/// Build a totalizer over `lits` counting up to `limit`
+///
+/// Returns unary outputs where `outputs[j]` is implied by "at least `j + 1`
+/// inputs are true".
+fn totalizer_tree<S: ClauseSink>(sink: &mut S, lits: &[Lit], limit: usize) -> Result<Vec<Lit>> {
+    if lits.len() == 1 {
+        return Ok(vec![lits[0]]);
+    }
+
+    let (left, right) = lits.split_at(lits.len() / 2);
+    let left = totalizer_tree(sink, left, limit)?;
+    let right = totalizer_tree(sink, right, limit)?;
+
+    let size = (left.len() + right.len()).min(limit);
+    let mut outputs = Vec::with_capacity(size);
+    for _ in 0..size {
+        outputs.push(sink.fresh_var()?.positive());
+    }
+
+    // left count i and right count j imply output count i + j
+    for i in 0..=left.len() {
+        for j in 0..=right.len() {
+            let sum = i + j;
+            if sum == 0 || sum > size {
+                continue;
+            }
+            let mut clause = Vec::with_capacity(3);
+            if i > 0 {
+                clause.push(!left[i - 1]);
+            }
+            if j > 0 {
+                clause.push(!right[j - 1]);
+            }
+            clause.push(outputs[sum - 1]);
+            sink.emit_clause(&clause)?;
+        }
+    }
+
+    Ok(outputs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cnf::CnfFormula;
+    use crate::types::Var;
+    use crate::wrapper::{ParkissatSolver, SolverConfig, SolverResult};
+
+    const ENCODINGS: [CardEncoding; 3] = [
+        CardEncoding::Pairwise,
+        CardEncoding::SequentialCounter,
+        CardEncoding::Totalizer,
+    ];
+
+    fn inputs(formula: &mut CnfFormula, n: usize) -> Vec<Lit> {
+        (0..n).map(|_| formula.new_var().positive()).collect()
+    }
+
+    /// Check an encoding against every assignment of its inputs
+    fn check_exhaustive(n: usize, encode: impl Fn(&mut CnfFormula, &[Lit]), accepts: impl Fn(usize) -> bool) {
+        let mut formula = CnfFormula::new();
+        let lits = inputs(&mut formula, n);
+        encode(&mut formula, &lits);
+
+        let mut solver = ParkissatSolver::new().unwrap();
+        solver.configure(&SolverConfig::default()).unwrap();
+        solver.add_formula(&formula).unwrap();
+
+        for mask in 0u32..1 << n {
+            let assumptions: Vec<Lit> = (0..n)
+                .map(|i| Var::new(i as u32 + 1).unwrap().lit(mask & (1 << i) != 0))
+                .collect();
+            let expected = if accepts(mask.count_ones() as usize) {
+                SolverResult::Sat
+            } else {
+                SolverResult::Unsat
+            };
+            assert_eq!(solver.solve_with_lit_assumptions(&assumptions).unwrap(), expected, "mask {:b}", mask);
+        }
+    }
+
+    #[test]
+    fn test_at_most_k() {
+        for encoding in ENCODINGS {
+            for k in 0..=5 {
+                check_exhaustive(5, |f, lits| at_most_k(f, lits, k, encoding).unwrap(), |count| count <= k);
+            }
+        }
+    }
+
+    #[test]
+    fn test_at_least_and_exactly_k() {
+        for encoding in ENCODINGS {
+            for k in 0..=5 {
+                check_exhaustive(5, |f, lits| at_least_k(f, lits, k, encoding).unwrap(), |count| count >= k);
+                check_exhaustive(5, |f, lits| exactly_k(f, lits, k, encoding).unwrap(), |count| count == k);
+            }
+        }
+        check_exhaustive(2, |f, lits| at_least_k(f, lits, 3, CardEncoding::default()).unwrap(), |_| false);
+    }
+
+    #[test]
+    fn test_auxiliary_variables() {
+        let mut formula = CnfFormula::new();
+        let lits = inputs(&mut formula, 6);
+        at_most_one(&mut formula, &lits, CardEncoding::Pairwise).unwrap();
+        assert_eq!(formula.num_vars(), 6);
+        assert_eq!(formula.num_clauses(), 15);
+
+        let mut formula = CnfFormula::new();
+        let lits = inputs(&mut formula, 6);
+        at_most_k(&mut formula, &lits, 2, CardEncoding::SequentialCounter).unwrap();
+        assert_eq!(formula.num_vars(), 6 + 5 * 2);
+    }
+
+    #[test]
+    fn test_solver_sink() {
+        let mut solver = ParkissatSolver::new().unwrap();
+        solver.configure(&SolverConfig::default()).unwrap();
+        let lits: Vec<Lit> = (0..4).map(|_| solver.new_var().unwrap().positive()).collect();
+
+        exactly_k(&mut solver, &lits, 2, CardEncoding::Totalizer).unwrap();
+        assert_eq!(solver.solve().unwrap(), SolverResult::Sat);
+        let count = lits.iter().filter(|&&lit| solver.lit_value(lit).unwrap()).count();
+        assert_eq!(count, 2);
+    }
+
+    #[test]
+    fn test_incremental_bounds() {
+        let mut solver = ParkissatSolver::new().unwrap();
+        solver.configure(&SolverConfig::default()).unwrap();
+        let lits: Vec<Lit> = (0..5).map(|_| solver.new_var().unwrap().positive()).collect();
+        let mut card = IncrementalCard::new(&mut solver, &lits).unwrap();
+        assert_eq!((card.num_inputs(), card.outputs().len()), (5, 5));
+        assert_eq!(card.at_most(5), None);
+
+        // Bounds as assumptions hold for one solve
+        for k in 0..5 {
+            let mut assumptions = vec![card.at_most(k).unwrap()];
+            assumptions.extend(&lits[..k]);
+            assert_eq!(solver.solve_with_lit_assumptions(&assumptions).unwrap(), SolverResult::Sat);
+            assumptions.push(lits[k]);
+            assert_eq!(solver.solve_with_lit_assumptions(&assumptions).unwrap(), SolverResult::Unsat);
+        }
+        assert_eq!(solver.solve_with_lit_assumptions(&lits).unwrap(), SolverResult::Sat);
+
+        // Tightened bounds persist, and looser ones are ignored
+        card.tighten(&mut solver, 3).unwrap();
+        card.tighten(&mut solver, 4).unwrap();
+        assert_eq!(card.bound(), Some(3));
+        assert_eq!(solver.solve_with_lit_assumptions(&lits[..3]).unwrap(), SolverResult::Sat);
+        assert_eq!(solver.solve_with_lit_assumptions(&lits[..4]).unwrap(), SolverResult::Unsat);
+
+        card.tighten(&mut solver, 1).unwrap();
+        solver.add_clause_lits(&[lits[0]]).unwrap();
+        assert_eq!(solver.solve().unwrap(), SolverResult::Sat);
+        let count = lits.iter().filter(|&&lit| solver.lit_value(lit).unwrap()).count();
+        assert_eq!(count, 1);
+        card.tighten(&mut solver, 0).unwrap();
+        assert_eq!(solver.solve().unwrap(), SolverResult::Unsat);
+    }
+}
@@ -0,0 +1,163 @@
+//! CNF encodings of higher-level constraints
+//!
+//! Encoders write clauses into a [`ClauseSink`], which is implemented for
+//! both [`CnfFormula`] and [`ParkissatSolver`], and allocate their auxiliary
+//! variables from the same sink so they never collide with variables the
+//! caller has already used. The trait also provides the small constraints
+//! used everywhere, such as [`ClauseSink::add_implication`] and
+//! [`ClauseSink::add_exactly_one`], as methods on both. Unlike the gates in
+//! [`circuit`](crate::circuit), they constrain literals the caller already
+//! has instead of defining a fresh output.
+
+pub mod card;
+pub mod pb;
+
+use self::card::CardEncoding;
+use crate::cnf::CnfFormula;
+use crate::error::Result;
+use crate::types::{Lit, Var};
+use crate::wrapper::ParkissatSolver;
+
+/// A destination for encoded clauses
+pub trait ClauseSink {
+    /// Add a clause
+    fn emit_clause(&mut self, lits: &[Lit]) -> Result<()>;
+
+    /// Allocate a fresh auxiliary variable
+    fn fresh_var(&mut self) -> Result<Var>;
+
+    /// Add a clause that can never be satisfied
+    fn emit_contradiction(&mut self) -> Result<()> {
+        let var = self.fresh_var()?;
+        self.emit_clause(&[var.positive()])?;
+        self.emit_clause(&[var.negative()])
+    }
+
+    /// `a` implies `b`
+    fn add_implication(&mut self, a: Lit, b: Lit) -> Result<()> {
+        self.emit_clause(&[!a, b])
+    }
+
+    /// `a` and `b` take the same value
+    fn add_iff(&mut self, a: Lit, b: Lit) -> Result<()> {
+        self.add_implication(a, b)?;
+        self.add_implication(b, a)
+    }
+
+    /// Exactly one of `lits` is true
+    ///
+    /// Up to six literals are encoded pairwise, without auxiliary
+    /// variables; longer lists use a sequential counter.
+    fn add_exactly_one(&mut self, lits: &[Lit]) -> Result<()>
+    where
+        Self: Sized,
+    {
+        if lits.is_empty() {
+            return self.emit_contradiction();
+        }
+        self.emit_clause(lits)?;
+        let encoding = if lits.len() <= 6 { CardEncoding::Pairwise } else { CardEncoding::SequentialCounter };
+        card::at_most_one(self, lits, encoding)
+    }
+
+    /// `out` equals `then` if `cond` is true and `otherwise` if it is false
+    ///
+    /// Two of the six clauses are redundant, but let propagation fix `out`
+    /// when both branches agree before `cond` is known.
+    fn add_ite(&mut self, cond: Lit, then: Lit, otherwise: Lit, out: Lit) -> Result<()> {
+        self.emit_clause(&[!cond, !then, out])?;
+        self.emit_clause(&[!cond, then, !out])?;
+        self.emit_clause(&[cond, !otherwise, out])?;
+        self.emit_clause(&[cond, otherwise, !out])?;
+        self.emit_clause(&[!then, !otherwise, out])?;
+        self.emit_clause(&[then, otherwise, !out])
+    }
+}
+
+impl ClauseSink for CnfFormula {
+    fn emit_clause(&mut self, lits: &[Lit]) -> Result<()> {
+        self.add_clause_lits(lits)
+    }
+
+    fn fresh_var(&mut self) -> Result<Var> {
+        Ok(self.new_var())
+    }
+}
+
+impl ClauseSink for ParkissatSolver {
+    fn emit_clause(&mut self, lits: &[Lit]) -> Result<()> {
+        self.add_clause_lits(lits)
+    }
+
+    fn fresh_var(&mut self) -> Result<Var> {
+        self.new_var()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wrapper::{SolverConfig, SolverResult};
+
+    /// Whether the formula accepts exactly the assignments of its `n`
+    /// variables that `expected` accepts
+    fn check_exhaustive(formula: &CnfFormula, n: usize, expected: impl Fn(&[bool]) -> bool) {
+        for mask in 0u32..1 << n {
+            let values: Vec<bool> = (0..n).map(|i| mask & (1 << i) != 0).collect();
+            let model: Vec<i32> = (0..n).map(|i| if values[i] { i as i32 + 1 } else { -(i as i32 + 1) }).collect();
+            assert_eq!(formula.is_satisfied_by(&model), expected(&values), "assignment {:?}", values);
+        }
+    }
+
+    fn lits(formula: &mut CnfFormula, n: usize) -> Vec<Lit> {
+        (0..n).map(|_| formula.new_var().positive()).collect()
+    }
+
+    #[test]
+    fn test_implication_and_iff() {
+        let mut formula = CnfFormula::new();
+        let x = lits(&mut formula, 2);
+        formula.add_implication(x[0], !x[1]).unwrap();
+        check_exhaustive(&formula, 2, |v| !v[0] || !v[1]);
+
+        let mut formula = CnfFormula::new();
+        let x = lits(&mut formula, 2);
+        formula.add_iff(x[0], !x[1]).unwrap();
+        check_exhaustive(&formula, 2, |v| v[0] != v[1]);
+    }
+
+    #[test]
+    fn test_ite() {
+        let mut formula = CnfFormula::new();
+        let x = lits(&mut formula, 4);
+        formula.add_ite(x[0], x[1], !x[2], x[3]).unwrap();
+        check_exhaustive(&formula, 4, |v| v[3] == if v[0] { v[1] } else { !v[2] });
+    }
+
+    #[test]
+    fn test_exactly_one() {
+        for n in [1, 3, 6, 9] {
+            let mut formula = CnfFormula::new();
+            let x = lits(&mut formula, n);
+            formula.add_exactly_one(&x).unwrap();
+
+            let mut solver = ParkissatSolver::new().unwrap();
+            solver.configure(&SolverConfig::default()).unwrap();
+            solver.add_formula(&formula).unwrap();
+            for mask in 0u32..1 << n {
+                let assumptions: Vec<Lit> = (0..n)
+                    .map(|i| Var::new(i as u32 + 1).unwrap().lit(mask & (1 << i) != 0))
+                    .collect();
+                let expected = if mask.count_ones() == 1 { SolverResult::Sat } else { SolverResult::Unsat };
+                assert_eq!(solver.solve_with_lit_assumptions(&assumptions).unwrap(), expected);
+            }
+        }
+
+        let mut formula = CnfFormula::new();
+        formula.add_exactly_one(&[]).unwrap();
+        let mut solver = ParkissatSolver::new().unwrap();
+        solver.configure(&SolverConfig::default()).unwrap();
+        solver.add_formula(&formula).unwrap();
+        assert_eq!(solver.solve().unwrap(), SolverResult::Unsat);
+    }
+}
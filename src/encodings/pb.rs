@@ -0,0 +1,578 @@
+//! Pseudo-Boolean (linear) constraints
+//!
+//! [`add_pb_constraint`] compiles `Σ wᵢ·lᵢ ⋈ bound` into CNF, where each
+//! literal counts as 1 when true. Terms are first normalized to positive
+//! weights over distinct variables; the resulting `≤` constraint is then
+//! encoded as a reduced BDD (Eén & Sörensson 2006) with one auxiliary
+//! variable per node. Nodes are shared between equal residual bounds, which
+//! keeps the encoding small for constraints with few distinct weights.
+//!
+//! Instances in the OPB format of the pseudo-Boolean competitions are read
+//! into an [`OpbFormula`] with [`parse_opb_reader`] and friends. [`solve_opb`]
+//! encodes every constraint this way and, if the instance has a `min:`
+//! objective, minimizes it by SAT-UNSAT search: each model found adds the
+//! constraint that the objective must drop below its value, until no better
+//! model exists.
+
+use super::ClauseSink;
+use crate::cnf::CnfFormula;
+use crate::error::{ParkissatError, Result};
+use crate::types::Lit;
+use crate::wrapper::{ParkissatSolver, SolverConfig, SolverResult};
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read};
+use std::path::Path;
+
+/// Relation between the weighted sum and the bound
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparator {
+    /// `Σ wᵢ·lᵢ ≤ bound`
+    Le,
+    /// `Σ wᵢ·lᵢ < bound`
+    Lt,
+    /// `Σ wᵢ·lᵢ ≥ bound`
+    Ge,
+    /// `Σ wᵢ·lᵢ > bound`
+    Gt,
+    /// `Σ wᵢ·lᵢ = bound`
+    Eq,
+}
+
+/// Add the constraint `Σ weight·lit ⋈ bound`
+///
+/// Weights may be negative and literals may repeat. A constraint that no
+/// assignment satisfies emits a contradiction.
+pub fn add_pb_constraint<S: ClauseSink>(sink: &mut S, terms: &[(i64, Lit)], comparator: Comparator, bound: i64) -> Result<()> {
+    match comparator {
+        Comparator::Le => encode_le(sink, terms, bound),
+        Comparator::Lt => encode_le(sink, terms, bound - 1),
+        Comparator::Ge => encode_le(sink, &negate(terms), -bound),
+        Comparator::Gt => encode_le(sink, &negate(terms), -bound - 1),
+        Comparator::Eq => {
+            encode_le(sink, terms, bound)?;
+            encode_le(sink, &negate(terms), -bound)
+        }
+    }
+}
+
+fn negate(terms: &[(i64, Lit)]) -> Vec<(i64, Lit)> {
+    terms.iter().map(|&(weight, lit)| (-weight, lit)).collect()
+}
+
+/// Rewrite the terms as positive weights over distinct variables
+///
+/// Returns the terms sorted by decreasing weight and the constant that was
+/// moved out of the sum.
+fn normalize(terms: &[(i64, Lit)]) -> (Vec<(i64, Lit)>, i64) {
+    let mut coefficients: BTreeMap<_, i64> = BTreeMap::new();
+    let mut constant = 0;
+
+    for &(weight, lit) in terms {
+        if lit.is_positive() {
+            *coefficients.entry(lit.var()).or_default() += weight;
+        } else {
+            // w·¬x = w - w·x
+            constant += weight;
+            *coefficients.entry(lit.var()).or_default() -= weight;
+        }
+    }
+
+    let mut normalized = Vec::with_capacity(coefficients.len());
+    for (var, coefficient) in coefficients {
+        if coefficient > 0 {
+            normalized.push((coefficient, var.positive()));
+        } else if coefficient < 0 {
+            // c·x = c - c·¬x
+            constant += coefficient;
+            normalized.push((-coefficient, var.negative()));
+        }
+    }
+
+    normalized.sort_by_key(|&(weight, _)| std::cmp::Reverse(weight));
+    (normalized, constant)
+}
+
+/// A BDD node: a constant or the auxiliary literal that stands for it
+#[derive(Clone, Copy)]
+enum Node {
+    True,
+    False,
+    Lit(Lit),
+}
+
+fn encode_le<S: ClauseSink>(sink: &mut S, terms: &[(i64, Lit)], bound: i64) -> Result<()> {
+    let (terms, constant) = normalize(terms);
+
+    // suffix[i] is the largest possible sum of terms[i..]
+    let mut suffix = vec![0i64; terms.len() + 1];
+    for i in (0..terms.len()).rev() {
+        suffix[i] = suffix[i + 1] + terms[i].0;
+    }
+
+    let mut encoder = BddEncoder {
+        sink,
+        terms: &terms,
+        suffix: &suffix,
+        memo: HashMap::new(),
+    };
+
+    match encoder.node(0, bound - constant)? {
+        Node::True => Ok(()),
+        Node::False => encoder.sink.emit_contradiction(),
+        Node::Lit(root) => encoder.sink.emit_clause(&[root]),
+    }
+}
+
+struct BddEncoder<'a, S> {
+    sink: &'a mut S,
+    terms: &'a [(i64, Lit)],
+    suffix: &'a [i64],
+    memo: HashMap<(usize, i64), Node>,
+}
+
+impl<S: ClauseSink> BddEncoder<'_, S> {
+    /// Node for "the sum of terms[index..] is at most `bound`"
+    fn node(&mut self, index: usize, bound: i64) -> Result<Node> {
+        if bound < 0 {
+            return Ok(Node::False);
+        }
+        if self.suffix[index] <= bound {
+            return Ok(Node::True);
+        }
+        if let Some(&node) = self.memo.get(&(index, bound)) {
+            return Ok(node);
+        }
+
+        let (weight, lit) = self.terms[index];
+        let high = self.node(index + 1, bound - weight)?;
+        let low = self.node(index + 1, bound)?;
+
+        let output = self.sink.fresh_var()?.positive();
+
+        // output ∧ lit → high
+        match high {
+            Node::True => {}
+            Node::False => self.sink.emit_clause(&[!output, !lit])?,
+            Node::Lit(high) => self.sink.emit_clause(&[!output, !lit, high])?,
+        }
+        // output ∧ ¬lit → low
+        match low {
+            Node::True => {}
+            Node::False => self.sink.emit_clause(&[!output, lit])?,
+            Node::Lit(low) => self.sink.emit_clause(&[!output, lit, low])?,
+        }
+
+        let node = Node::Lit(output);
+        self.memo.insert((index, bound), node);
+        Ok(node)
+    }
+}
+
+/// A linear constraint of an [`OpbFormula`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OpbConstraint {
+    /// Weighted literals of the sum
+    pub terms: Vec<(i64, Lit)>,
+    /// Relation between the sum and the bound
+    pub comparator: Comparator,
+    /// Right-hand side
+    pub bound: i64,
+}
+
+impl OpbConstraint {
+    fn holds(&self, model: &[i32]) -> bool {
+        let sum = weighted_sum(&self.terms, model);
+        match self.comparator {
+            Comparator::Le => sum <= self.bound,
+            Comparator::Lt => sum < self.bound,
+            Comparator::Ge => sum >= self.bound,
+            Comparator::Gt => sum > self.bound,
+            Comparator::Eq => sum == self.bound,
+        }
+    }
+}
+
+/// Value of `Σ weight·lit` under a model of signed literals (unlisted variables count as false)
+fn weighted_sum(terms: &[(i64, Lit)], model: &[i32]) -> i64 {
+    terms.iter()
+        .filter(|(_, lit)| model.contains(&lit.to_dimacs()) || (!lit.is_positive() && !model.contains(&-lit.to_dimacs())))
+        .map(|(weight, _)| weight)
+        .sum()
+}
+
+/// A pseudo-Boolean instance: linear constraints and an optional objective to minimize
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OpbFormula {
+    constraints: Vec<OpbConstraint>,
+    objective: Option<Vec<(i64, Lit)>>,
+    num_vars: usize,
+}
+
+impl OpbFormula {
+    /// Create an empty instance
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the constraint `Σ weight·lit ⋈ bound`
+    pub fn add_constraint(&mut self, terms: &[(i64, Lit)], comparator: Comparator, bound: i64) {
+        self.track(terms);
+        self.constraints.push(OpbConstraint {
+            terms: terms.to_vec(),
+            comparator,
+            bound,
+        });
+    }
+
+    /// Minimize `Σ weight·lit`, replacing any previous objective
+    pub fn set_objective(&mut self, terms: &[(i64, Lit)]) {
+        self.track(terms);
+        self.objective = Some(terms.to_vec());
+    }
+
+    /// The constraints
+    pub fn constraints(&self) -> &[OpbConstraint] {
+        &self.constraints
+    }
+
+    /// The objective to minimize, if any
+    pub fn objective(&self) -> Option<&[(i64, Lit)]> {
+        self.objective.as_deref()
+    }
+
+    /// Number of variables (highest variable index used)
+    pub fn num_vars(&self) -> usize {
+        self.num_vars
+    }
+
+    /// Objective value of an assignment, or `None` if it violates a constraint
+    ///
+    /// `model` lists signed literals; unlisted variables count as false.
+    /// Instances without an objective have value 0.
+    pub fn value_of(&self, model: &[i32]) -> Option<i64> {
+        if !self.constraints.iter().all(|constraint| constraint.holds(model)) {
+            return None;
+        }
+        Some(self.objective.as_deref().map_or(0, |objective| weighted_sum(objective, model)))
+    }
+
+    /// Encode the constraints as CNF, with auxiliary variables after the instance's own
+    pub fn to_cnf(&self) -> Result<CnfFormula> {
+        let mut formula = CnfFormula::with_variables(self.num_vars);
+        for constraint in &self.constraints {
+            add_pb_constraint(&mut formula, &constraint.terms, constraint.comparator, constraint.bound)?;
+        }
+        Ok(formula)
+    }
+
+    fn track(&mut self, terms: &[(i64, Lit)]) {
+        for (_, lit) in terms {
+            self.num_vars = self.num_vars.max(lit.var().index() as usize);
+        }
+    }
+}
+
+/// Outcome of [`solve_opb`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OpbResult {
+    /// A model satisfying every constraint; optimal if the instance has an objective
+    Solved {
+        /// Objective value of the model (0 without an objective)
+        objective: i64,
+        /// The assignment, one signed literal per variable of the instance
+        model: Vec<i32>,
+    },
+    /// The constraints are unsatisfiable
+    Unsat,
+}
+
+/// Solve a pseudo-Boolean instance, minimizing its objective if it has one
+///
+/// Fails with `Interrupted` if a solve ends without an answer, e.g. on the
+/// configured timeout.
+pub fn solve_opb(formula: &OpbFormula, config: &SolverConfig) -> Result<OpbResult> {
+    let mut solver = ParkissatSolver::new()?;
+    solver.configure(config)?;
+    solver.add_formula(&formula.to_cnf()?)?;
+
+    let mut best = None;
+    loop {
+        match solver.solve()? {
+            SolverResult::Sat => {}
+            SolverResult::Unsat => break,
+            SolverResult::Unknown => return Err(ParkissatError::Interrupted),
+        }
+
+        let full_model = solver.get_model()?;
+        let model: Vec<i32> = full_model.into_iter().take(formula.num_vars()).collect();
+        let value = formula.value_of(&model).ok_or_else(|| {
+            ParkissatError::InternalError("PB model violates a constraint".to_string())
+        })?;
+        best = Some((value, model));
+
+        // Only a strictly better model can follow
+        match formula.objective() {
+            Some(objective) => add_pb_constraint(&mut solver, objective, Comparator::Lt, value)?,
+            None => break,
+        }
+    }
+
+    Ok(match best {
+        Some((objective, model)) => OpbResult::Solved { objective, model },
+        None => OpbResult::Unsat,
+    })
+}
+
+fn parse_error(line: usize, message: impl Into<String>) -> ParkissatError {
+    ParkissatError::ParseError {
+        line,
+        message: message.into(),
+    }
+}
+
+/// Parse the weighted literals of a statement
+fn parse_terms(tokens: &[&str], line: usize) -> Result<Vec<(i64, Lit)>> {
+    if !tokens.len().is_multiple_of(2) {
+        return Err(parse_error(line, "expected `<coefficient> <literal>` pairs"));
+    }
+
+    tokens.chunks(2)
+        .map(|pair| {
+            let weight: i64 = pair[0]
+                .parse()
+                .map_err(|_| parse_error(line, format!("invalid coefficient `{}`", pair[0])))?;
+            let (negated, name) = match pair[1].strip_prefix('~') {
+                Some(name) => (true, name),
+                None => (false, pair[1]),
+            };
+            let var = name.strip_prefix('x')
+                .and_then(|index| index.parse::<i32>().ok())
+                .filter(|&index| index > 0)
+                .ok_or_else(|| parse_error(line, format!("invalid literal `{}` (products are not supported)", pair[1])))?;
+            let lit = Lit::new(if negated { -var } else { var }).expect("variable index is positive");
+            Ok((weight, lit))
+        })
+        .collect()
+}
+
+/// Parse an OPB instance from a reader
+///
+/// Statements are linear: `min: <terms> ;` for the objective and
+/// `<terms> <op> <bound> ;` for constraints, where a term is a coefficient
+/// followed by `x<n>` or `~x<n>` and `<op>` is one of `>=`, `<=`, and `=`.
+/// Lines starting with `*` are comments. Non-linear products are rejected.
+pub fn parse_opb_reader<R: Read>(reader: R) -> Result<OpbFormula> {
+    let reader = BufReader::new(reader);
+    let mut formula = OpbFormula::new();
+    let mut statement = String::new();
+    let mut statement_line = 0;
+
+    for (index, line) in reader.lines().enumerate() {
+        let line_number = index + 1;
+        let line = line?;
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('*') {
+            continue;
+        }
+
+        let mut rest = trimmed;
+        while !rest.is_empty() {
+            if statement.trim().is_empty() {
+                statement_line = line_number;
+            }
+            match rest.find(';') {
+                Some(end) => {
+                    statement.push(' ');
+                    statement.push_str(&rest[..end]);
+                    parse_statement(&mut formula, &statement, statement_line)?;
+                    statement.clear();
+                    rest = rest[end + 1..].trim_start();
+                }
+                None => {
+                    statement.push(' ');
+                    statement.push_str(rest);
+                    rest = "";
+                }
+            }
+        }
+    }
+
+    if !statement.trim().is_empty() {
+        return Err(parse_error(statement_line, "statement is not terminated by `;`"));
+    }
+    Ok(formula)
+}
+
+fn parse_statement(formula: &mut OpbFormula, statement: &str, line: usize) -> Result<()> {
+    let tokens: Vec<&str> = statement.split_whitespace().collect();
+
+    if let Some((&first, terms)) = tokens.split_first() {
+        if first == "min:" {
+            if formula.objective().is_some() {
+                return Err(parse_error(line, "duplicate objective"));
+            }
+            let terms = parse_terms(terms, line)?;
+            formula.set_objective(&terms);
+            return Ok(());
+        }
+        if first.ends_with(':') {
+            return Err(parse_error(line, format!("unsupported objective `{}`", first)));
+        }
+    }
+
+    let Some(position) = tokens.iter().position(|token| matches!(*token, ">=" | "<=" | "=")) else {
+        return Err(parse_error(line, "expected `>=`, `<=`, or `=`"));
+    };
+    let comparator = match tokens[position] {
+        ">=" => Comparator::Ge,
+        "<=" => Comparator::Le,
+        _ => Comparator::Eq,
+    };
+    let [bound] = &tokens[position + 1..] else {
+        return Err(parse_error(line, "expected a single bound after the relation"));
+    };
+    let bound: i64 = bound
+        .parse()
+        .map_err(|_| parse_error(line, format!("invalid bound `{}`", bound)))?;
+
+    let terms = parse_terms(&tokens[..position], line)?;
+    formula.add_constraint(&terms, comparator, bound);
+    Ok(())
+}
+
+/// Parse an OPB instance from a string
+pub fn parse_opb_str(input: &str) -> Result<OpbFormula> {
+    parse_opb_reader(input.as_bytes())
+}
+
+/// Parse an OPB file
+pub fn parse_opb_file<P: AsRef<Path>>(path: P) -> Result<OpbFormula> {
+    parse_opb_reader(File::open(path)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cnf::CnfFormula;
+    use crate::types::Var;
+    use crate::wrapper::{ParkissatSolver, SolverConfig, SolverResult};
+
+    const COMPARATORS: [Comparator; 5] = [Comparator::Le, Comparator::Lt, Comparator::Ge, Comparator::Gt, Comparator::Eq];
+
+    fn holds(sum: i64, comparator: Comparator, bound: i64) -> bool {
+        match comparator {
+            Comparator::Le => sum <= bound,
+            Comparator::Lt => sum < bound,
+            Comparator::Ge => sum >= bound,
+            Comparator::Gt => sum > bound,
+            Comparator::Eq => sum == bound,
+        }
+    }
+
+    /// Compare the encoding with direct evaluation on every assignment
+    fn check_exhaustive(n: usize, terms: &[(i64, i32)], comparator: Comparator, bound: i64) {
+        let terms: Vec<(i64, Lit)> = terms.iter().map(|&(w, l)| (w, Lit::new(l).unwrap())).collect();
+
+        let mut formula = CnfFormula::with_variables(n);
+        add_pb_constraint(&mut formula, &terms, comparator, bound).unwrap();
+
+        let mut solver = ParkissatSolver::new().unwrap();
+        solver.configure(&SolverConfig::default()).unwrap();
+        solver.add_formula(&formula).unwrap();
+
+        for mask in 0u32..1 << n {
+            let value = |var: u32| mask & (1 << (var - 1)) != 0;
+            let sum: i64 = terms
+                .iter()
+                .filter(|(_, lit)| value(lit.var().index()) == lit.is_positive())
+                .map(|(weight, _)| weight)
+                .sum();
+
+            let assumptions: Vec<Lit> = (1..=n as u32).map(|v| Var::new(v).unwrap().lit(value(v))).collect();
+            let expected = if holds(sum, comparator, bound) {
+                SolverResult::Sat
+            } else {
+                SolverResult::Unsat
+            };
+            assert_eq!(
+                solver.solve_with_lit_assumptions(&assumptions).unwrap(),
+                expected,
+                "{:?} {} under mask {:b}",
+                comparator,
+                bound,
+                mask
+            );
+        }
+    }
+
+    #[test]
+    fn test_knapsack_constraint() {
+        let terms = [(3, 1), (5, 2), (2, 3), (4, 4)];
+        for comparator in COMPARATORS {
+            for bound in [-1, 0, 4, 7, 9, 14, 15] {
+                check_exhaustive(4, &terms, comparator, bound);
+            }
+        }
+    }
+
+    #[test]
+    fn test_negative_weights_and_repeated_literals() {
+        let terms = [(2, 1), (-3, -2), (1, 3), (4, -1), (-1, 3)];
+        for comparator in COMPARATORS {
+            for bound in -5..=5 {
+                check_exhaustive(3, &terms, comparator, bound);
+            }
+        }
+    }
+
+    #[test]
+    fn test_trivial_constraints() {
+        let mut formula = CnfFormula::with_variables(2);
+        let terms = [(1, Lit::new(1).unwrap()), (1, Lit::new(2).unwrap())];
+
+        add_pb_constraint(&mut formula, &terms, Comparator::Le, 2).unwrap();
+        assert!(formula.is_empty());
+
+        add_pb_constraint(&mut formula, &terms, Comparator::Ge, 3).unwrap();
+        assert!(!formula.is_satisfied_by(&[1, 2]));
+    }
+
+    #[test]
+    fn test_parse_opb() {
+        let input = "* #variable= 3 #constraint= 2\nmin: +2 x1 +3 x2 -1 x3 ;\n+1 x1 +1 x2\n >= 1 ;\n+1 ~x1 +1 x3 = 1 ; +1 x2 <= 1 ;\n";
+        let formula = parse_opb_str(input).unwrap();
+        assert_eq!(formula.num_vars(), 3);
+        assert_eq!(formula.constraints().len(), 3);
+        assert_eq!(formula.objective().unwrap().len(), 3);
+        assert_eq!(formula.constraints()[0].comparator, Comparator::Ge);
+        assert_eq!(formula.constraints()[1].terms[0], (1, Lit::new(-1).unwrap()));
+
+        for (input, line) in [("+1 x1 >= 1", 1), ("+1 x1 x2 >= 1 ;", 1), ("\n+1 y1 >= 1 ;", 2), ("max: +1 x1 ;", 1), ("+1 x1 >= a ;", 1)] {
+            match parse_opb_str(input) {
+                Err(ParkissatError::ParseError { line: found, .. }) => assert_eq!(found, line, "{}", input),
+                other => panic!("{:?} for {}", other, input),
+            }
+        }
+    }
+
+    #[test]
+    fn test_solve_opb() {
+        // At least two of x1..x3 with x1 + x2 <= 1; x1 costs 2, x2 costs 3, x3 costs 1
+        let formula = parse_opb_str("min: +2 x1 +3 x2 +1 x3 ;\n+1 x1 +1 x2 +1 x3 >= 2 ;\n+1 x1 +1 x2 <= 1 ;\n").unwrap();
+        let result = solve_opb(&formula, &SolverConfig::default()).unwrap();
+        assert_eq!(result, OpbResult::Solved { objective: 3, model: vec![1, -2, 3] });
+
+        let unsat = parse_opb_str("+1 x1 +1 x2 >= 2 ;\n+1 ~x1 >= 1 ;\n").unwrap();
+        assert_eq!(solve_opb(&unsat, &SolverConfig::default()).unwrap(), OpbResult::Unsat);
+
+        let satisfiable = parse_opb_str("+2 x1 -1 x2 = 1 ;\n").unwrap();
+        match solve_opb(&satisfiable, &SolverConfig::default()).unwrap() {
+            OpbResult::Solved { objective, model } => {
+                assert_eq!(objective, 0);
+                assert_eq!(model, vec![1, 2]);
+            }
+            OpbResult::Unsat => panic!("satisfiable instance reported UNSAT"),
+        }
+    }
+}
@@ -0,0 +1,282 @@
+//! Racing ParKissat against an external solver binary
+//!
+//! [`race`] solves a DIMACS file in-process while running a user-specified
+//! solver executable on the same file. The first definite (SAT or UNSAT)
+//! answer wins and the other side is cancelled: the external process is
+//! killed, or the in-process solver is interrupted. The external solver's
+//! SAT-competition output (`s` and `v` lines, or exit codes 10/20) is
+//! normalized to the same model format as [`ParkissatSolver::get_model`].
+
+use crate::error::{ParkissatError, Result};
+use crate::interrupt::InterruptHandle;
+use crate::wrapper::{ParkissatSolver, SolverConfig, SolverResult};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+/// An external solver executable
+#[derive(Debug, Clone)]
+pub struct ExternalSolver {
+    /// Path to the solver binary
+    pub program: PathBuf,
+    /// Arguments passed before the DIMACS file path
+    pub args: Vec<String>,
+}
+
+impl ExternalSolver {
+    /// Create an external solver invoked as `program [args...] <file>`
+    pub fn new<P: Into<PathBuf>>(program: P) -> Self {
+        Self {
+            program: program.into(),
+            args: Vec::new(),
+        }
+    }
+
+    /// Add an argument passed before the DIMACS file path
+    pub fn arg<S: Into<String>>(mut self, arg: S) -> Self {
+        self.args.push(arg.into());
+        self
+    }
+}
+
+/// Which side of the race produced the answer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Winner {
+    /// The in-process ParKissat solver
+    Parkissat,
+    /// The external solver binary
+    External,
+}
+
+/// Result of a race
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnsembleOutcome {
+    /// Side that produced the answer (`None` if neither found one)
+    pub winner: Option<Winner>,
+    /// The answer
+    pub result: SolverResult,
+    /// The model, if the answer is SAT
+    pub model: Option<Vec<i32>>,
+}
+
+type Answer = Result<(SolverResult, Option<Vec<i32>>)>;
+
+/// Cancellation state shared with the in-process racing thread
+#[derive(Default)]
+struct Cancellation {
+    handle: Option<InterruptHandle>,
+    cancelled: bool,
+}
+
+/// Race ParKissat against an external solver on a DIMACS file
+pub fn race<P: AsRef<Path>>(path: P, config: &SolverConfig, external: &ExternalSolver) -> Result<EnsembleOutcome> {
+    let path = path.as_ref().to_path_buf();
+
+    let mut child = Command::new(&external.program)
+        .args(&external.args)
+        .arg(&path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()?;
+    let mut stdout = child.stdout.take()
+        .ok_or_else(|| ParkissatError::IoError("Failed to capture external solver output".to_string()))?;
+
+    let (tx, rx) = mpsc::channel::<(Winner, Answer)>();
+    let cancellation = Arc::new(Mutex::new(Cancellation::default()));
+
+    let parkissat = {
+        let tx = tx.clone();
+        let cancellation = Arc::clone(&cancellation);
+        let config = config.clone();
+        let path = path.clone();
+        thread::spawn(move || {
+            let answer = solve_in_process(&path, &config, &cancellation);
+            let _ = tx.send((Winner::Parkissat, answer));
+        })
+    };
+
+    let external_reader = thread::spawn(move || {
+        let mut output = String::new();
+        let answer = stdout
+            .read_to_string(&mut output)
+            .map_err(ParkissatError::from)
+            .map(|_| parse_competition_output(&output));
+        let _ = tx.send((Winner::External, answer));
+    });
+
+    let mut outcome = EnsembleOutcome {
+        winner: None,
+        result: SolverResult::Unknown,
+        model: None,
+    };
+    let mut first_error = None;
+    let mut killed = false;
+
+    for _ in 0..2 {
+        let (side, answer) = match rx.recv() {
+            Ok(message) => message,
+            Err(_) => break,
+        };
+
+        let (result, model) = match answer {
+            Ok(answer) => answer,
+            Err(err) => {
+                first_error.get_or_insert(err);
+                continue;
+            }
+        };
+
+        if result == SolverResult::Unknown {
+            continue;
+        }
+
+        outcome = EnsembleOutcome { winner: Some(side), result, model };
+
+        // Cancel the other side
+        match side {
+            Winner::Parkissat => {
+                let _ = child.kill();
+                killed = true;
+            }
+            Winner::External => {
+                let mut cancellation = cancellation.lock().unwrap();
+                cancellation.cancelled = true;
+                if let Some(handle) = &cancellation.handle {
+                    handle.interrupt();
+                }
+            }
+        }
+        break;
+    }
+
+    let _ = parkissat.join();
+    let status = child.wait();
+
+    // A killed solver's own children may still hold the pipe open, so the
+    // reader is left to finish on its own
+    if !killed {
+        let _ = external_reader.join();
+    }
+
+    // Fall back to the exit code when the external solver printed no `s` line
+    if outcome.winner.is_none() {
+        if let Ok(status) = status {
+            let result = match status.code() {
+                Some(10) => SolverResult::Sat,
+                Some(20) => SolverResult::Unsat,
+                _ => SolverResult::Unknown,
+            };
+            if result == SolverResult::Unsat {
+                outcome = EnsembleOutcome { winner: Some(Winner::External), result, model: None };
+            }
+        }
+    }
+
+    match (outcome.winner, first_error) {
+        (None, Some(err)) => Err(err),
+        _ => Ok(outcome),
+    }
+}
+
+fn solve_in_process(path: &Path, config: &SolverConfig, cancellation: &Mutex<Cancellation>) -> Answer {
+    let mut solver = ParkissatSolver::new()?;
+    solver.configure(config)?;
+    solver.load_dimacs(path)?;
+
+    {
+        let mut cancellation = cancellation.lock().unwrap();
+        if cancellation.cancelled {
+            return Ok((SolverResult::Unknown, None));
+        }
+        cancellation.handle = Some(solver.interrupt_handle());
+    }
+
+    let result = solver.solve()?;
+    let model = match result {
+        SolverResult::Sat => Some(solver.get_model()?),
+        _ => None,
+    };
+
+    Ok((result, model))
+}
+
+/// Parse SAT-competition solver output into a result and normalized model
+///
+/// The model is sorted by variable, with one signed literal per variable.
+pub fn parse_competition_output(output: &str) -> (SolverResult, Option<Vec<i32>>) {
+    let mut result = SolverResult::Unknown;
+    let mut model = Vec::new();
+
+    for line in output.lines() {
+        let line = line.trim();
+        if let Some(status) = line.strip_prefix("s ") {
+            result = match status.trim() {
+                "SATISFIABLE" => SolverResult::Sat,
+                "UNSATISFIABLE" => SolverResult::Unsat,
+                _ => SolverResult::Unknown,
+            };
+        } else if let Some(values) = line.strip_prefix("v ") {
+            model.extend(
+                values
+                    .split_whitespace()
+                    .filter_map(|lit| lit.parse::<i32>().ok())
+                    .filter(|&lit| lit != 0),
+            );
+        }
+    }
+
+    if result != SolverResult::Sat {
+        return (result, None);
+    }
+
+    model.sort_by_key(|lit| lit.unsigned_abs());
+    model.dedup_by_key(|lit| lit.unsigned_abs());
+    (result, Some(model))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn test_parse_competition_output() {
+        let output = "c comment\ns SATISFIABLE\nv -2 1\nv 3 0\n";
+        assert_eq!(
+            parse_competition_output(output),
+            (SolverResult::Sat, Some(vec![1, -2, 3]))
+        );
+
+        assert_eq!(
+            parse_competition_output("s UNSATISFIABLE\n"),
+            (SolverResult::Unsat, None)
+        );
+        assert_eq!(parse_competition_output(""), (SolverResult::Unknown, None));
+    }
+
+    #[test]
+    fn test_race_cancels_slow_external_solver() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(file, "p cnf 2 2\n1 2 0\n-1 2 0").unwrap();
+
+        let external = ExternalSolver::new("sh").arg("-c").arg("sleep 30").arg("sh");
+
+        let start = Instant::now();
+        let outcome = race(file.path(), &SolverConfig::default(), &external).unwrap();
+
+        assert_eq!(outcome.winner, Some(Winner::Parkissat));
+        assert_eq!(outcome.result, SolverResult::Sat);
+        assert!(start.elapsed() < Duration::from_secs(20));
+    }
+
+    #[test]
+    fn test_race_missing_binary() {
+        let result = race("missing.cnf", &SolverConfig::default(), &ExternalSolver::new("/nonexistent/solver"));
+        assert!(matches!(result, Err(ParkissatError::IoError(_))));
+    }
+}
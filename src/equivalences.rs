@@ -0,0 +1,131 @@
+//! Substituted and eliminated variables
+//!
+//! While it simplifies, kissat removes variables from the search: equivalent
+//! literals are substituted by a representative, and other variables are
+//! eliminated by resolution. Models still assign every variable, since kissat
+//! reconstructs removed variables afterwards, but callers that reason about
+//! the simplified problem, e.g. to pick assumption or branching variables,
+//! want to know what was removed. [`ParkissatSolver::get_equivalences`] and
+//! [`ParkissatSolver::get_eliminated`] report it for the worker that answered
+//! the last solve (or the first worker if none did); workers simplify
+//! independently, so others may have removed different variables.
+//!
+//! Frozen variables are never eliminated, and every simplification happens
+//! afresh in each solve.
+
+use crate::ffi;
+use crate::wrapper::ParkissatSolver;
+use std::collections::BTreeMap;
+
+impl ParkissatSolver {
+    /// Substituted variables with their canonical representative literal
+    ///
+    /// Each pair `(variable, lit)` means `variable` is equivalent to `lit`
+    /// in every model. Representatives that were substituted in turn are
+    /// followed, so `lit` is over a variable that was not substituted.
+    /// Pairs are ordered by variable.
+    pub fn get_equivalences(&self) -> Vec<(i32, i32)> {
+        let size = unsafe { ffi::parkissat_get_equivalences_size(self.solver) };
+        if size <= 0 {
+            return Vec::new();
+        }
+
+        let mut pairs = vec![0; size as usize];
+        unsafe {
+            ffi::parkissat_get_equivalences(self.solver, pairs.as_mut_ptr(), size);
+        }
+
+        let direct: BTreeMap<i32, i32> = pairs.chunks_exact(2).map(|pair| (pair[0], pair[1])).collect();
+        direct.keys()
+            .map(|&var| {
+                let mut lit = var;
+                // Substitution never forms cycles; the bound guards against corrupt input
+                for _ in 0..=direct.len() {
+                    match direct.get(&lit.abs()) {
+                        Some(&next) => lit = if lit < 0 { -next } else { next },
+                        None => break,
+                    }
+                }
+                (var, lit)
+            })
+            .collect()
+    }
+
+    /// Canonical representative of a literal: itself unless its variable was substituted
+    pub fn representative(&self, literal: i32) -> i32 {
+        let equivalences = self.get_equivalences();
+        match equivalences.binary_search_by_key(&literal.abs(), |&(var, _)| var) {
+            Ok(index) if literal < 0 => -equivalences[index].1,
+            Ok(index) => equivalences[index].1,
+            Err(_) => literal,
+        }
+    }
+
+    /// Variables eliminated by resolution, in ascending order
+    pub fn get_eliminated(&self) -> Vec<i32> {
+        let size = unsafe { ffi::parkissat_get_eliminated_size(self.solver) };
+        if size <= 0 {
+            return Vec::new();
+        }
+
+        let mut eliminated = vec![0; size as usize];
+        unsafe {
+            ffi::parkissat_get_eliminated(self.solver, eliminated.as_mut_ptr(), size);
+        }
+        eliminated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wrapper::{SolverConfig, SolverResult};
+
+    /// A chain of equivalences 1 = -2 = 3 = ... over 40 variables plus a
+    /// handful of clauses that make kissat simplify before it finds a model
+    fn chained_solver() -> ParkissatSolver {
+        let mut solver = ParkissatSolver::new().unwrap();
+        solver.configure(&SolverConfig { num_threads: 1, ..SolverConfig::default() }).unwrap();
+        for var in 1..40 {
+            solver.add_clause(&[var, var + 1]).unwrap();
+            solver.add_clause(&[-var, -(var + 1)]).unwrap();
+        }
+        for var in 1..38 {
+            solver.add_clause(&[var, var + 2, -(var + 1)]).unwrap();
+        }
+        solver
+    }
+
+    #[test]
+    fn test_removed_variables_are_consistent_with_model() {
+        let mut solver = chained_solver();
+        assert!(solver.get_equivalences().is_empty());
+        assert_eq!(solver.solve().unwrap(), SolverResult::Sat);
+        let model = solver.get_model().unwrap();
+        let value = |lit: i32| model[lit.unsigned_abs() as usize - 1] == lit;
+
+        let equivalences = solver.get_equivalences();
+        for &(var, lit) in &equivalences {
+            assert_ne!(var, lit.abs());
+            assert_eq!(value(var), value(lit));
+            assert!(equivalences.binary_search_by_key(&lit.abs(), |&(var, _)| var).is_err());
+            assert_eq!(solver.representative(-var), -lit);
+        }
+
+        let eliminated = solver.get_eliminated();
+        assert!(eliminated.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(eliminated.iter().all(|&var| (1..=40).contains(&var)));
+        assert!(eliminated.iter().all(|var| equivalences.iter().all(|(other, _)| other != var)));
+        assert_eq!(solver.representative(41), 41);
+    }
+
+    #[test]
+    fn test_frozen_variables_are_kept() {
+        let mut solver = chained_solver();
+        for var in 1..=40 {
+            solver.freeze(var).unwrap();
+        }
+        assert_eq!(solver.solve().unwrap(), SolverResult::Sat);
+        assert!(solver.get_eliminated().is_empty());
+    }
+}
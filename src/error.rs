@@ -34,6 +34,18 @@ pub enum ParkissatError {
     
     /// Internal solver error
     InternalError(String),
+    
+    /// The solver ran out of memory
+    OutOfMemory(String),
+    
+    /// Proof that does not refute the formula
+    InvalidProof(String),
+    
+    /// Malformed input file (line numbers are 1-based)
+    ParseError {
+        line: usize,
+        message: String,
+    },
 }
 
 impl fmt::Display for ParkissatError {
@@ -66,6 +78,15 @@ impl fmt::Display for ParkissatError {
             ParkissatError::InternalError(msg) => {
                 write!(f, "Internal solver error: {}", msg)
             }
+            ParkissatError::OutOfMemory(msg) => {
+                write!(f, "Out of memory: {}", msg)
+            }
+            ParkissatError::InvalidProof(msg) => {
+                write!(f, "Invalid proof: {}", msg)
+            }
+            ParkissatError::ParseError { line, message } => {
+                write!(f, "Parse error on line {}: {}", line, message)
+            }
         }
     }
 }
@@ -98,6 +119,9 @@ mod tests {
 
         let err = ParkissatError::InvalidClause("empty clause".to_string());
         assert_eq!(err.to_string(), "Invalid clause: empty clause");
+
+        let err = ParkissatError::ParseError { line: 3, message: "bad literal".to_string() };
+        assert_eq!(err.to_string(), "Parse error on line 3: bad literal");
     }
 
     #[test]
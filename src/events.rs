@@ -0,0 +1,266 @@
+//! Search event hooks
+//!
+//! [`SolverEvents`] collects callbacks for coarse-grained events of the
+//! search: each restart of a worker, every N conflicts of a worker, the
+//! unit and binary clauses the workers export, and the clauses each worker
+//! hands to the others in a sharing round. The monitor thread that
+//! enforces timeouts polls the workers about once per millisecond during
+//! each solve and delivers the events, so callbacks never run inside the
+//! search itself and all of them run on the same thread. Restarts and
+//! conflict thresholds passed between two polls are delivered together.
+//!
+//! Unit and binary clauses are those the workers export for sharing, see
+//! [`ParkissatSolver::set_learn_callback`]; every one is implied by the
+//! formula, but the same clause may be reported by several workers.
+//!
+//! With the `tracing` feature every solver reports its events to `tracing`
+//! as well, whether or not callbacks are installed.
+
+use crate::ffi;
+use crate::wrapper::ParkissatSolver;
+use std::os::raw::{c_int, c_void};
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+
+type WorkerCallback = Box<dyn FnMut(usize, u64) + Send>;
+type UnitCallback = Box<dyn FnMut(usize, i32) + Send>;
+type BinaryCallback = Box<dyn FnMut(usize, [i32; 2]) + Send>;
+
+/// Callbacks for search events, installed with [`ParkissatSolver::set_events`]
+///
+/// Every callback receives the index of the worker the event comes from.
+#[derive(Default)]
+pub struct SolverEvents {
+    on_restart: Option<WorkerCallback>,
+    conflict_interval: u64,
+    on_conflicts: Option<WorkerCallback>,
+    on_unit: Option<UnitCallback>,
+    on_binary: Option<BinaryCallback>,
+    on_sharing: Option<WorkerCallback>,
+    /// Span of the running solve, the parent of the events it reports
+    #[cfg(feature = "tracing")]
+    pub(crate) span: Option<tracing::Span>,
+}
+
+impl SolverEvents {
+    /// No callbacks
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Called with the worker's restart count after each restart
+    pub fn on_restart<F>(mut self, callback: F) -> Self
+    where
+        F: FnMut(usize, u64) + Send + 'static,
+    {
+        self.on_restart = Some(Box::new(callback));
+        self
+    }
+
+    /// Called with the worker's conflict count each time it reaches a multiple of `interval`
+    ///
+    /// An interval of 0 never calls it.
+    pub fn on_conflicts<F>(mut self, interval: u64, callback: F) -> Self
+    where
+        F: FnMut(usize, u64) + Send + 'static,
+    {
+        self.conflict_interval = interval;
+        self.on_conflicts = Some(Box::new(callback));
+        self
+    }
+
+    /// Called with each unit clause a worker exports
+    pub fn on_new_unit<F>(mut self, callback: F) -> Self
+    where
+        F: FnMut(usize, i32) + Send + 'static,
+    {
+        self.on_unit = Some(Box::new(callback));
+        self
+    }
+
+    /// Called with each binary clause a worker exports
+    pub fn on_new_binary<F>(mut self, callback: F) -> Self
+    where
+        F: FnMut(usize, [i32; 2]) + Send + 'static,
+    {
+        self.on_binary = Some(Box::new(callback));
+        self
+    }
+
+    /// Called with the number of clauses a worker shares with the others in each sharing round
+    ///
+    /// Rounds in which a worker shares nothing are not reported.
+    pub fn on_sharing<F>(mut self, callback: F) -> Self
+    where
+        F: FnMut(usize, u64) + Send + 'static,
+    {
+        self.on_sharing = Some(Box::new(callback));
+        self
+    }
+
+    fn dispatch(&mut self, kind: ffi::ParkissatEvent, worker: usize, count: u64, literals: &[i32]) {
+        #[cfg(feature = "tracing")]
+        self.trace(kind, worker, count, literals);
+
+        match (kind, literals) {
+            (ffi::PARKISSAT_EVENT_RESTART, _) => {
+                if let Some(callback) = &mut self.on_restart {
+                    callback(worker, count);
+                }
+            }
+            (ffi::PARKISSAT_EVENT_CONFLICTS, _) => {
+                if let Some(callback) = &mut self.on_conflicts {
+                    callback(worker, count);
+                }
+            }
+            (ffi::PARKISSAT_EVENT_UNIT, &[lit]) => {
+                if let Some(callback) = &mut self.on_unit {
+                    callback(worker, lit);
+                }
+            }
+            (ffi::PARKISSAT_EVENT_BINARY, &[a, b]) => {
+                if let Some(callback) = &mut self.on_binary {
+                    callback(worker, [a, b]);
+                }
+            }
+            (ffi::PARKISSAT_EVENT_SHARED, _) => {
+                if let Some(callback) = &mut self.on_sharing {
+                    callback(worker, count);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    #[cfg(feature = "tracing")]
+    fn trace(&self, kind: ffi::ParkissatEvent, worker: usize, count: u64, literals: &[i32]) {
+        let parent = self.span.as_ref().and_then(tracing::Span::id);
+        match kind {
+            ffi::PARKISSAT_EVENT_RESTART => {
+                tracing::trace!(target: "parkissat", parent: parent, worker, restarts = count, "restart");
+            }
+            ffi::PARKISSAT_EVENT_CONFLICTS => {
+                tracing::debug!(target: "parkissat", parent: parent, worker, conflicts = count, "conflicts");
+            }
+            ffi::PARKISSAT_EVENT_UNIT | ffi::PARKISSAT_EVENT_BINARY => {
+                tracing::trace!(target: "parkissat", parent: parent, worker, clause = ?literals, "new clause");
+            }
+            ffi::PARKISSAT_EVENT_SHARED => {
+                tracing::debug!(target: "parkissat", parent: parent, worker, clauses = count, "sharing round");
+            }
+            _ => {}
+        }
+    }
+}
+
+impl std::fmt::Debug for SolverEvents {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SolverEvents")
+            .field("conflict_interval", &self.conflict_interval)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Called by the C++ monitor thread with the solver's `events` box
+unsafe extern "C" fn events_trampoline(
+    state: *mut c_void,
+    kind: ffi::ParkissatEvent,
+    worker: c_int,
+    count: u64,
+    literals: *const c_int,
+    size: c_int,
+) {
+    let events = &mut *(state as *mut SolverEvents);
+    let literals = if literals.is_null() {
+        &[][..]
+    } else {
+        std::slice::from_raw_parts(literals, size as usize)
+    };
+    // A panic must not unwind into C++; the event is simply dropped
+    let _ = panic::catch_unwind(AssertUnwindSafe(|| events.dispatch(kind, worker as usize, count, literals)));
+}
+
+impl ParkissatSolver {
+    /// Deliver search events of every later solve to `events`
+    ///
+    /// It replaces any previous set of event callbacks.
+    pub fn set_events(&mut self, events: SolverEvents) {
+        let mut events = Box::new(events);
+        let interval = events.on_conflicts.as_ref().map_or(0, |_| events.conflict_interval.min(i64::MAX as u64) as i64);
+        let state = &mut *events as *mut SolverEvents as *mut c_void;
+        unsafe {
+            ffi::parkissat_set_events(self.solver, state, interval, Some(events_trampoline));
+        }
+        self.events = Some(events);
+    }
+
+    /// Remove the event callbacks
+    pub fn clear_events(&mut self) {
+        // Events keep flowing to `tracing`
+        if cfg!(feature = "tracing") {
+            self.set_events(SolverEvents::new());
+            return;
+        }
+
+        unsafe {
+            ffi::parkissat_set_events(self.solver, ptr::null_mut(), 0, None);
+        }
+        self.events = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cnf::pigeonhole;
+    use crate::limits::SolverLimits;
+    use crate::wrapper::{SolverConfig, SolverResult};
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Default)]
+    struct Seen {
+        restarts: Vec<(usize, u64)>,
+        conflicts: Vec<(usize, u64)>,
+        clauses: Vec<Vec<i32>>,
+    }
+
+    #[test]
+    fn test_events_during_solve() {
+        let mut solver = ParkissatSolver::new().unwrap();
+        solver.configure(&SolverConfig { num_threads: 1, ..SolverConfig::default() }).unwrap();
+
+        // Far beyond 3000 conflicts
+        solver.add_formula(&pigeonhole(10, 9)).unwrap();
+
+        let seen = Arc::new(Mutex::new(Seen::default()));
+        let (restarts, conflicts, units, binaries) = (Arc::clone(&seen), Arc::clone(&seen), Arc::clone(&seen), Arc::clone(&seen));
+        solver.set_events(
+            SolverEvents::new()
+                .on_restart(move |worker, count| restarts.lock().unwrap().restarts.push((worker, count)))
+                .on_conflicts(1000, move |worker, count| conflicts.lock().unwrap().conflicts.push((worker, count)))
+                .on_new_unit(move |_, lit| units.lock().unwrap().clauses.push(vec![lit]))
+                .on_new_binary(move |_, clause| binaries.lock().unwrap().clauses.push(clause.to_vec())),
+        );
+        assert_eq!(solver.solve_limited(&SolverLimits::new().conflicts(3000)).unwrap(), SolverResult::Unknown);
+
+        {
+            let seen = seen.lock().unwrap();
+            let total = solver.get_statistics().unwrap().restarts;
+            assert!(!seen.restarts.is_empty());
+            let counts: Vec<u64> = seen.restarts.iter().map(|&(_, count)| count).collect();
+            assert_eq!(counts, (1..=counts.len() as u64).collect::<Vec<_>>());
+            assert!(counts.len() as u64 <= total);
+            assert!(seen.restarts.iter().all(|&(worker, _)| worker == 0));
+
+            // The budget may stop the search just short of the last multiple
+            assert!(seen.conflicts.starts_with(&[(0, 1000), (0, 2000)]) && seen.conflicts.len() <= 3);
+            assert!(seen.clauses.iter().all(|clause| clause.iter().all(|&lit| lit != 0 && lit.abs() <= 90)));
+        }
+
+        // Cleared callbacks see nothing of later solves
+        solver.clear_events();
+        let before = seen.lock().unwrap().conflicts.len();
+        solver.solve_limited(&SolverLimits::new().conflicts(1000)).unwrap();
+        assert_eq!(seen.lock().unwrap().conflicts.len(), before);
+    }
+}
@@ -14,4 +14,27 @@ include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
 // Re-export the result constants for easier access
 pub const PARKISSAT_SAT: ParkissatResult = 10;
 pub const PARKISSAT_UNSAT: ParkissatResult = 20;
-pub const PARKISSAT_UNKNOWN: ParkissatResult = 0;
\ No newline at end of file
+pub const PARKISSAT_UNKNOWN: ParkissatResult = 0;
+
+// Re-export the unknown-reason constants for easier access
+pub const PARKISSAT_REASON_NONE: ParkissatUnknownReason = 0;
+pub const PARKISSAT_REASON_INTERRUPTED: ParkissatUnknownReason = 1;
+pub const PARKISSAT_REASON_TIMEOUT: ParkissatUnknownReason = 2;
+pub const PARKISSAT_REASON_BUDGET: ParkissatUnknownReason = 3;
+pub const PARKISSAT_REASON_MEMORY: ParkissatUnknownReason = 4;
+pub const PARKISSAT_REASON_OTHER: ParkissatUnknownReason = 5;
+
+// Re-export the event kinds for easier access
+pub const PARKISSAT_EVENT_RESTART: ParkissatEvent = 0;
+pub const PARKISSAT_EVENT_CONFLICTS: ParkissatEvent = 1;
+pub const PARKISSAT_EVENT_UNIT: ParkissatEvent = 2;
+pub const PARKISSAT_EVENT_BINARY: ParkissatEvent = 3;
+pub const PARKISSAT_EVENT_SHARED: ParkissatEvent = 4;
+
+// Re-export the error codes for easier access
+pub const PARKISSAT_ERROR_NONE: ParkissatErrorCode = 0;
+pub const PARKISSAT_ERROR_INTERNAL: ParkissatErrorCode = 1;
+pub const PARKISSAT_ERROR_OUT_OF_MEMORY: ParkissatErrorCode = 2;
+pub const PARKISSAT_ERROR_IO: ParkissatErrorCode = 3;
+pub const PARKISSAT_ERROR_PARSE: ParkissatErrorCode = 4;
+pub const PARKISSAT_ERROR_INVALID_ARGUMENT: ParkissatErrorCode = 5;
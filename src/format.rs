@@ -0,0 +1,77 @@
+//! SAT-competition output
+//!
+//! [`competition_output`] renders a result the way competition solvers print
+//! it: an `s` line with the answer and, for SAT, the model as `v` lines
+//! shorter than 80 characters, ending in `0`. Tools that parse competition
+//! output can consume it verbatim; [`parse_competition_output`] reads it back.
+//!
+//! [`parse_competition_output`]: crate::ensemble::parse_competition_output
+
+use crate::error::Result;
+use crate::wrapper::SolverResult;
+use std::io::Write;
+
+/// Line length that `v` lines stay below, including the prefix
+const MAX_LINE: usize = 80;
+
+/// Render the `s` line and, for SAT, the `v` lines of the model
+///
+/// `model` holds signed DIMACS literals and is ignored unless the result is SAT.
+pub fn competition_output(result: SolverResult, model: &[i32]) -> String {
+    let mut output = Vec::new();
+    write_competition_output(&mut output, result, model).expect("writing to a Vec cannot fail");
+    String::from_utf8(output).expect("competition output is ASCII")
+}
+
+/// Write the `s` line and, for SAT, the `v` lines of the model
+pub fn write_competition_output<W: Write>(mut writer: W, result: SolverResult, model: &[i32]) -> Result<()> {
+    match result {
+        SolverResult::Sat => writeln!(writer, "s SATISFIABLE")?,
+        SolverResult::Unsat => {
+            writeln!(writer, "s UNSATISFIABLE")?;
+            return Ok(());
+        }
+        SolverResult::Unknown => {
+            writeln!(writer, "s UNKNOWN")?;
+            return Ok(());
+        }
+    }
+
+    let mut line = String::from("v");
+    for lit in model.iter().chain(&[0]) {
+        let lit = lit.to_string();
+        if line.len() + 1 + lit.len() > MAX_LINE - 1 {
+            writeln!(writer, "{}", line)?;
+            line.truncate(1);
+        }
+        line.push(' ');
+        line.push_str(&lit);
+    }
+    writeln!(writer, "{}", line)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ensemble::parse_competition_output;
+
+    #[test]
+    fn test_competition_output() {
+        assert_eq!(competition_output(SolverResult::Sat, &[1, -2, 3]), "s SATISFIABLE\nv 1 -2 3 0\n");
+        assert_eq!(competition_output(SolverResult::Unsat, &[1]), "s UNSATISFIABLE\n");
+        assert_eq!(competition_output(SolverResult::Unknown, &[]), "s UNKNOWN\n");
+        assert_eq!(competition_output(SolverResult::Sat, &[]), "s SATISFIABLE\nv 0\n");
+    }
+
+    #[test]
+    fn test_line_wrapping() {
+        let model: Vec<i32> = (1..=500).map(|var| if var % 3 == 0 { -var } else { var }).collect();
+        let output = competition_output(SolverResult::Sat, &model);
+
+        assert!(output.lines().all(|line| line.len() < MAX_LINE));
+        assert!(output.lines().skip(1).all(|line| line.starts_with("v ")));
+        assert!(output.ends_with(" 0\n"));
+        assert_eq!(parse_competition_output(&output), (SolverResult::Sat, Some(model)));
+    }
+}
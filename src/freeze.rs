@@ -0,0 +1,105 @@
+//! Freezing variables
+//!
+//! Kissat removes variables by bounded variable elimination while it
+//! simplifies the formula. An eliminated variable no longer takes part in the
+//! search, so assuming it or reasoning about it incrementally is unsafe.
+//! Freezing protects interface variables, such as those later used in
+//! assumptions, from elimination.
+//!
+//! Freezes are counted: a variable frozen twice stays frozen until it is
+//! melted twice. Kissat cannot exclude single variables from elimination, so
+//! while any variable is frozen the workers skip variable elimination as a
+//! whole; other simplifications still run. Frozen variables are kept across
+//! solves until they are melted or the solver is reset.
+
+use crate::error::{ParkissatError, Result};
+use crate::ffi;
+use crate::wrapper::ParkissatSolver;
+
+impl ParkissatSolver {
+    /// Protect `variable` from elimination until it is melted
+    pub fn freeze(&mut self, variable: i32) -> Result<()> {
+        if !self.is_configured() {
+            return Err(ParkissatError::NotConfigured);
+        }
+
+        if variable <= 0 {
+            return Err(ParkissatError::InvalidVariable(variable));
+        }
+
+        unsafe {
+            ffi::parkissat_freeze(self.solver, variable);
+        }
+
+        self.check_error()
+    }
+
+    /// Undo one `freeze` of `variable`
+    ///
+    /// Melting a variable that is not frozen is rejected with `InvalidVariable`.
+    pub fn melt(&mut self, variable: i32) -> Result<()> {
+        if !self.is_configured() {
+            return Err(ParkissatError::NotConfigured);
+        }
+
+        let melted = unsafe {
+            ffi::parkissat_melt(self.solver, variable)
+        };
+
+        if !melted {
+            return Err(ParkissatError::InvalidVariable(variable));
+        }
+
+        Ok(())
+    }
+
+    /// Whether `variable` is frozen at least once
+    pub fn is_frozen(&self, variable: i32) -> bool {
+        unsafe { ffi::parkissat_is_frozen(self.solver, variable) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wrapper::{SolverConfig, SolverResult};
+
+    #[test]
+    fn test_freeze_is_counted() {
+        let mut solver = ParkissatSolver::new().unwrap();
+        assert_eq!(solver.freeze(1).unwrap_err(), ParkissatError::NotConfigured);
+
+        solver.configure(&SolverConfig::default()).unwrap();
+        assert_eq!(solver.freeze(0).unwrap_err(), ParkissatError::InvalidVariable(0));
+        assert_eq!(solver.melt(1).unwrap_err(), ParkissatError::InvalidVariable(1));
+
+        solver.freeze(1).unwrap();
+        solver.freeze(1).unwrap();
+        solver.melt(1).unwrap();
+        assert!(solver.is_frozen(1));
+        solver.melt(1).unwrap();
+        assert!(!solver.is_frozen(1));
+
+        solver.freeze(2).unwrap();
+        solver.reset().unwrap();
+        assert!(!solver.is_frozen(2));
+    }
+
+    #[test]
+    fn test_frozen_variable_usable_in_assumptions() {
+        let mut solver = ParkissatSolver::new().unwrap();
+        solver.configure(&SolverConfig::default()).unwrap();
+
+        // Variable 2 only links the two clauses and would be a prime elimination candidate
+        solver.freeze(2).unwrap();
+        solver.add_clause(&[1, 2]).unwrap();
+        solver.add_clause(&[-2, 3]).unwrap();
+
+        assert_eq!(solver.solve_with_assumptions(&[2, -3]).unwrap(), SolverResult::Unsat);
+        let mut core = solver.failed_assumptions().unwrap();
+        core.sort_unstable();
+        assert_eq!(core, vec![-3, 2]);
+        assert_eq!(solver.solve_with_assumptions(&[-2]).unwrap(), SolverResult::Sat);
+        assert!(solver.get_model_value(1).unwrap());
+    }
+}
@@ -0,0 +1,270 @@
+//! Retractable groups of clauses
+//!
+//! Every [`ClauseGroup`] owns a fresh selector variable `s`, and each clause
+//! added to the group is stored as `(¬s ∨ l1 ∨ ... ∨ ln)`. Solves assume `s`
+//! for every enabled group, so its clauses are active; a disabled group is
+//! simply not assumed, which leaves its clauses satisfiable by `s = false`.
+//! Removing a group adds the unit `¬s`, retiring its clauses for good.
+//!
+//! [`ParkissatSolver::push`] and [`ParkissatSolver::pop`] build Z3-style
+//! assertion scopes on top of groups: `push` opens a group that every clause
+//! added afterwards joins, guarded by the innermost scope only, and `pop`
+//! removes that group again.
+//!
+//! Like editable clauses, selectors are allocated past the current variable
+//! count, so declare all problem variables (or use
+//! [`ParkissatSolver::new_variable`]) before creating groups or scopes.
+
+use crate::error::{ParkissatError, Result};
+use crate::wrapper::ParkissatSolver;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Handle to a group of clauses that are enabled, disabled, or removed together
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ClauseGroup(usize);
+
+#[derive(Debug)]
+struct GroupState {
+    selector: i32,
+    enabled: bool,
+}
+
+/// Clause groups owned by a solver
+#[derive(Debug, Default)]
+pub(crate) struct ClauseGroups {
+    groups: BTreeMap<usize, GroupState>,
+    /// Selector variables of every group ever created, removed ones included
+    selectors: BTreeSet<i32>,
+    /// Groups of the open push scopes, innermost last
+    scopes: Vec<ClauseGroup>,
+    next_group: usize,
+}
+
+impl ClauseGroups {
+    /// Selectors of the enabled groups, to be assumed true
+    pub(crate) fn enabled_selectors(&self) -> Vec<i32> {
+        self.groups.values().filter(|state| state.enabled).map(|state| state.selector).collect()
+    }
+
+    /// Enabled groups, leaving out those of push scopes
+    pub(crate) fn enabled_groups(&self) -> Vec<ClauseGroup> {
+        self.groups.iter()
+            .filter(|(&group, state)| state.enabled && !self.scopes.contains(&ClauseGroup(group)))
+            .map(|(&group, _)| ClauseGroup(group))
+            .collect()
+    }
+
+    /// Whether `lit` is a literal of a group selector
+    pub(crate) fn is_selector(&self, lit: i32) -> bool {
+        self.selectors.contains(&lit.abs())
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.groups.is_empty()
+    }
+
+    pub(crate) fn in_scope(&self) -> bool {
+        !self.scopes.is_empty()
+    }
+
+    /// Selector guarding clauses added in the innermost push scope
+    pub(crate) fn scope_selector(&self) -> Option<i32> {
+        let scope = self.scopes.last()?;
+        self.groups.get(&scope.0).map(|state| state.selector)
+    }
+
+    fn state(&self, group: ClauseGroup) -> Result<&GroupState> {
+        self.groups.get(&group.0)
+            .ok_or_else(|| ParkissatError::InvalidClause(format!("Unknown clause group {}", group.0)))
+    }
+
+    fn state_mut(&mut self, group: ClauseGroup) -> Result<&mut GroupState> {
+        self.groups.get_mut(&group.0)
+            .ok_or_else(|| ParkissatError::InvalidClause(format!("Unknown clause group {}", group.0)))
+    }
+}
+
+impl ParkissatSolver {
+    /// Create an empty, enabled clause group
+    pub fn new_clause_group(&mut self) -> Result<ClauseGroup> {
+        if !self.is_configured() {
+            return Err(ParkissatError::NotConfigured);
+        }
+
+        let selector = self.new_variable()?;
+        let group = self.groups.next_group;
+        self.groups.groups.insert(group, GroupState { selector, enabled: true });
+        self.groups.selectors.insert(selector);
+        self.groups.next_group += 1;
+        Ok(ClauseGroup(group))
+    }
+
+    /// Add a clause that is only active while `group` is enabled
+    pub fn add_clause_to_group(&mut self, group: ClauseGroup, literals: &[i32]) -> Result<()> {
+        let selector = self.groups.state(group)?.selector;
+
+        if literals.is_empty() {
+            return Err(ParkissatError::InvalidClause("Empty clause".to_string()));
+        }
+        if let Some(&lit) = literals.iter().find(|&&lit| self.groups.is_selector(lit)) {
+            return Err(ParkissatError::InvalidClause(
+                format!("Literal {} is the selector of a clause group", lit)
+            ));
+        }
+
+        let mut guarded = Vec::with_capacity(literals.len() + 1);
+        guarded.push(-selector);
+        guarded.extend_from_slice(literals);
+        self.add_clause(&guarded)
+    }
+
+    /// Open a scope: clauses added until the matching [`pop`](Self::pop) are retracted by it
+    pub fn push(&mut self) -> Result<()> {
+        let group = self.new_clause_group()?;
+        self.groups.scopes.push(group);
+        Ok(())
+    }
+
+    /// Close the innermost scope, retracting every clause added in it
+    pub fn pop(&mut self) -> Result<()> {
+        let group = *self.groups.scopes.last()
+            .ok_or_else(|| ParkissatError::InvalidConfiguration("No push scope to pop".to_string()))?;
+        self.retire_group(group)?;
+        self.groups.scopes.pop();
+        Ok(())
+    }
+
+    /// Number of open push scopes
+    pub fn scope_depth(&self) -> usize {
+        self.groups.scopes.len()
+    }
+
+    /// Make the clauses of a group take part in later solves
+    pub fn enable_group(&mut self, group: ClauseGroup) -> Result<()> {
+        self.groups.state_mut(group)?.enabled = true;
+        Ok(())
+    }
+
+    /// Leave the clauses of a group out of later solves until it is enabled again
+    pub fn disable_group(&mut self, group: ClauseGroup) -> Result<()> {
+        self.groups.state_mut(group)?.enabled = false;
+        Ok(())
+    }
+
+    /// Retire a group and its clauses permanently
+    pub fn remove_group(&mut self, group: ClauseGroup) -> Result<()> {
+        if self.groups.scopes.contains(&group) {
+            return Err(ParkissatError::InvalidClause(
+                format!("Clause group {} belongs to a push scope", group.0)
+            ));
+        }
+        self.retire_group(group)
+    }
+
+    fn retire_group(&mut self, group: ClauseGroup) -> Result<()> {
+        let selector = self.groups.state(group)?.selector;
+        self.add_clause_unscoped(&[-selector])?;
+        self.groups.groups.remove(&group.0);
+        Ok(())
+    }
+
+    /// Whether a group is enabled (`None` if it does not exist)
+    pub fn is_group_enabled(&self, group: ClauseGroup) -> Option<bool> {
+        self.groups.groups.get(&group.0).map(|state| state.enabled)
+    }
+
+    /// Enabled groups whose clauses were needed to refute the last solve
+    ///
+    /// Only valid after an UNSAT result. Like
+    /// [`failed_assumptions`](Self::failed_assumptions), the set is not
+    /// guaranteed to be minimal.
+    pub fn failed_groups(&self) -> Result<Vec<ClauseGroup>> {
+        let failed = self.failed_assumptions_with_selectors()?;
+        Ok(self.groups.groups.iter()
+            .filter(|(_, state)| state.enabled && failed.contains(&state.selector))
+            .map(|(&group, _)| ClauseGroup(group))
+            .filter(|group| !self.groups.scopes.contains(group))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cnf::configured_solver;
+    use crate::wrapper::SolverResult;
+
+    #[test]
+    fn test_enable_disable_remove() {
+        let mut solver = configured_solver();
+        solver.add_clause(&[1, 2]).unwrap();
+        let no_one = solver.new_clause_group().unwrap();
+        let no_two = solver.new_clause_group().unwrap();
+        solver.add_clause_to_group(no_one, &[-1]).unwrap();
+        solver.add_clause_to_group(no_two, &[-2]).unwrap();
+
+        assert_eq!(solver.solve().unwrap(), SolverResult::Unsat);
+        assert!(solver.failed_assumptions().unwrap().is_empty());
+        assert_eq!(solver.failed_groups().unwrap(), vec![no_one, no_two]);
+
+        solver.disable_group(no_two).unwrap();
+        assert_eq!(solver.is_group_enabled(no_two), Some(false));
+        assert_eq!(solver.solve().unwrap(), SolverResult::Sat);
+        assert!(solver.get_model_value(2).unwrap());
+
+        solver.enable_group(no_two).unwrap();
+        solver.remove_group(no_one).unwrap();
+        assert_eq!(solver.is_group_enabled(no_one), None);
+        assert_eq!(solver.solve().unwrap(), SolverResult::Sat);
+        assert!(solver.get_model_value(1).unwrap());
+        assert!(!solver.get_model_value(2).unwrap());
+    }
+
+    #[test]
+    fn test_invalid_group_use() {
+        let mut solver = configured_solver();
+        let group = solver.new_clause_group().unwrap();
+        solver.remove_group(group).unwrap();
+
+        assert!(solver.add_clause_to_group(group, &[1]).is_err());
+        assert!(solver.enable_group(group).is_err());
+        assert!(solver.remove_group(group).is_err());
+
+        let group = solver.new_clause_group().unwrap();
+        assert!(solver.add_clause_to_group(group, &[]).is_err());
+        assert!(solver.add_clause_to_group(group, &[1, 0]).is_err());
+        // The selector of the removed group is still reserved
+        assert!(solver.add_clause_to_group(group, &[3]).is_err());
+    }
+
+    #[test]
+    fn test_push_pop() {
+        let mut solver = configured_solver();
+        solver.add_clause(&[1, 2]).unwrap();
+        assert!(solver.pop().is_err());
+
+        solver.push().unwrap();
+        solver.add_clause(&[-1]).unwrap();
+        solver.push().unwrap();
+        solver.add_clauses(&[-2, 0]).unwrap();
+        assert_eq!(solver.scope_depth(), 2);
+        assert_eq!(solver.solve().unwrap(), SolverResult::Unsat);
+        assert!(solver.failed_assumptions().unwrap().is_empty());
+        assert!(solver.failed_groups().unwrap().is_empty());
+
+        solver.pop().unwrap();
+        assert_eq!(solver.solve().unwrap(), SolverResult::Sat);
+        assert!(solver.get_model_value(2).unwrap());
+
+        // DIMACS input loaded inside a scope is retracted too
+        solver.push().unwrap();
+        solver.load_dimacs_str("p cnf 2 1\n-2 0\n").unwrap();
+        assert_eq!(solver.solve().unwrap(), SolverResult::Unsat);
+        solver.pop().unwrap();
+        solver.pop().unwrap();
+        assert_eq!(solver.scope_depth(), 0);
+
+        solver.add_clause(&[-2]).unwrap();
+        assert_eq!(solver.solve().unwrap(), SolverResult::Sat);
+        assert!(solver.get_model_value(1).unwrap());
+    }
+}
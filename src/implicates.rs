@@ -0,0 +1,194 @@
+//! Prime implicate enumeration
+//!
+//! A clause is an implicate of the formula if the formula entails it, and a
+//! prime implicate if no proper subset of it is also entailed. Entailment is
+//! checked by solving under the negated clause as assumptions.
+//!
+//! Enumeration is model-guided: a second solver proposes full assignments to
+//! the variables of interest that satisfy every implicate found so far. An
+//! assignment that is inconsistent with the formula is shrunk by deletion to
+//! a minimal inconsistent cube, whose negation is a new prime implicate; a
+//! consistent assignment is blocked. Enumeration ends when no assignment is
+//! left to propose.
+
+use crate::error::{ParkissatError, Result};
+use crate::wrapper::{ParkissatSolver, SolverConfig, SolverResult};
+
+/// Options for prime implicate enumeration
+#[derive(Debug, Clone, Default)]
+pub struct ImplicateOptions {
+    /// Restrict implicates to these variables (default: all variables)
+    pub variables: Option<Vec<i32>>,
+
+    /// Stop after this many implicates (default: enumerate all)
+    pub limit: Option<usize>,
+}
+
+fn negate(literals: &[i32]) -> Vec<i32> {
+    literals.iter().map(|&lit| -lit).collect()
+}
+
+impl ParkissatSolver {
+    /// Check whether the formula entails the given clause
+    pub fn entails(&mut self, clause: &[i32]) -> Result<bool> {
+        if clause.contains(&0) {
+            return Err(ParkissatError::InvalidClause("Literal cannot be zero".to_string()));
+        }
+
+        self.is_inconsistent_with(&negate(clause))
+    }
+
+    /// Shrink an entailed clause to a prime implicate
+    ///
+    /// Literals are dropped one at a time as long as the remaining clause is
+    /// still entailed, so the result is subset-minimal but not necessarily the
+    /// shortest prime implicate contained in `clause`.
+    pub fn minimize_implicate(&mut self, clause: &[i32]) -> Result<Vec<i32>> {
+        if !self.entails(clause)? {
+            return Err(ParkissatError::InvalidClause("Clause is not entailed by the formula".to_string()));
+        }
+
+        let cube = self.minimize_inconsistent_cube(&negate(clause))?;
+        Ok(negate(&cube))
+    }
+
+    /// Enumerate the prime implicates of the formula
+    ///
+    /// If the formula is unsatisfiable, the only prime implicate is the empty
+    /// clause. The number of prime implicates can be exponential in the number
+    /// of variables, so restricting the variables or setting a limit is
+    /// recommended for anything but small formulas.
+    pub fn prime_implicates(&mut self, options: &ImplicateOptions) -> Result<Vec<Vec<i32>>> {
+        if !self.is_configured() {
+            return Err(ParkissatError::NotConfigured);
+        }
+
+        let mut variables = match &options.variables {
+            Some(variables) => variables.clone(),
+            None => (1..=self.variable_count() as i32).collect(),
+        };
+        for &var in &variables {
+            if var <= 0 {
+                return Err(ParkissatError::InvalidVariable(var));
+            }
+        }
+        variables.sort_unstable();
+        variables.dedup();
+
+        let limit = options.limit.unwrap_or(usize::MAX);
+        let mut implicates = Vec::new();
+
+        if variables.is_empty() {
+            if limit > 0 && self.is_inconsistent_with(&[])? {
+                implicates.push(Vec::new());
+            }
+            return Ok(implicates);
+        }
+
+        let mut candidates = ParkissatSolver::new()?;
+        candidates.configure(&SolverConfig::default())?;
+        candidates.set_variable_count(variables[variables.len() - 1] as usize)?;
+
+        while implicates.len() < limit {
+            match candidates.solve()? {
+                SolverResult::Sat => {}
+                SolverResult::Unsat => break,
+                SolverResult::Unknown => return Err(ParkissatError::Interrupted),
+            }
+
+            let mut cube = Vec::with_capacity(variables.len());
+            for &var in &variables {
+                cube.push(if candidates.get_model_value(var)? { var } else { -var });
+            }
+
+            if !self.is_inconsistent_with(&cube)? {
+                candidates.add_clause(&negate(&cube))?;
+                continue;
+            }
+
+            let core = self.minimize_inconsistent_cube(&cube)?;
+            if core.is_empty() {
+                // The formula is unsatisfiable: the empty clause subsumes everything
+                return Ok(vec![Vec::new()]);
+            }
+
+            let implicate = negate(&core);
+            candidates.add_clause(&implicate)?;
+            implicates.push(implicate);
+        }
+
+        Ok(implicates)
+    }
+
+    /// Check whether the formula is unsatisfiable under the given cube
+    fn is_inconsistent_with(&mut self, cube: &[i32]) -> Result<bool> {
+        match self.solve_with_assumptions(cube)? {
+            SolverResult::Sat => Ok(false),
+            SolverResult::Unsat => Ok(true),
+            SolverResult::Unknown => Err(ParkissatError::Interrupted),
+        }
+    }
+
+    /// Deletion-based minimization of a cube inconsistent with the formula
+    fn minimize_inconsistent_cube(&mut self, cube: &[i32]) -> Result<Vec<i32>> {
+        let mut core = cube.to_vec();
+        let mut i = 0;
+
+        while i < core.len() {
+            let lit = core.remove(i);
+            if !self.is_inconsistent_with(&core)? {
+                core.insert(i, lit);
+                i += 1;
+            }
+        }
+
+        Ok(core)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cnf::solver_with;
+
+    fn normalized(mut clauses: Vec<Vec<i32>>) -> Vec<Vec<i32>> {
+        for clause in &mut clauses {
+            clause.sort_unstable();
+        }
+        clauses.sort();
+        clauses
+    }
+
+    #[test]
+    fn test_entailment() {
+        let mut solver = solver_with(&SolverConfig::default(), &[&[1, 2], &[-1, 3]]);
+
+        assert!(solver.entails(&[2, 3]).unwrap());
+        assert!(!solver.entails(&[2]).unwrap());
+        assert_eq!(solver.minimize_implicate(&[1, 2, 3]).unwrap(), vec![2, 3]);
+        assert!(solver.minimize_implicate(&[3]).is_err());
+    }
+
+    #[test]
+    fn test_prime_implicates() {
+        let mut solver = solver_with(&SolverConfig::default(), &[&[1, 2], &[-1, 3]]);
+
+        let all = solver.prime_implicates(&ImplicateOptions::default()).unwrap();
+        assert_eq!(normalized(all), vec![vec![-1, 3], vec![1, 2], vec![2, 3]]);
+
+        let options = ImplicateOptions {
+            variables: Some(vec![2, 3]),
+            ..ImplicateOptions::default()
+        };
+        let restricted = solver.prime_implicates(&options).unwrap();
+        assert_eq!(normalized(restricted), vec![vec![2, 3]]);
+    }
+
+    #[test]
+    fn test_prime_implicates_unsat() {
+        let mut solver = solver_with(&SolverConfig::default(), &[&[1], &[-1]]);
+
+        let implicates = solver.prime_implicates(&ImplicateOptions::default()).unwrap();
+        assert_eq!(implicates, vec![Vec::<i32>::new()]);
+    }
+}
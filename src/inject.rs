@@ -0,0 +1,117 @@
+//! Injecting clauses into a running solve
+//!
+//! [`ClauseInjector`] is the counterpart of
+//! [`ParkissatSolver::set_learn_callback`]: it queues clauses from any thread,
+//! and every `SharingConfig::interval` the queue is handed to all workers,
+//! which import the clauses at their next restart. Together they let two
+//! processes using this crate exchange learned clauses, e.g. across machines.
+//!
+//! Injected clauses are treated like clauses learned by a worker: they must
+//! be implied by the formula, or the solver may wrongly answer UNSAT. They
+//! only take part in the solve that imports them; clauses queued between
+//! solves wait for the next one, and clauses over variables that the formula
+//! of that solve does not contain are dropped.
+
+use crate::error::{ParkissatError, Result};
+use crate::ffi;
+use crate::interrupt::SolverPtr;
+use crate::wrapper::ParkissatSolver;
+use std::os::raw::c_int;
+use std::sync::{Arc, Mutex};
+
+/// Handle that feeds clauses to a solver from any thread
+#[derive(Clone)]
+pub struct ClauseInjector {
+    target: Arc<Mutex<SolverPtr>>,
+}
+
+impl ClauseInjector {
+    /// Queue a clause for the workers to import
+    ///
+    /// Does nothing if the solver has been dropped.
+    pub fn inject(&self, clause: &[i32]) -> Result<()> {
+        if clause.is_empty() {
+            return Err(ParkissatError::InvalidClause("Empty clause".to_string()));
+        }
+        if clause.contains(&0) {
+            return Err(ParkissatError::InvalidClause("Literal cannot be zero".to_string()));
+        }
+
+        let target = self.target.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if !target.0.is_null() {
+            unsafe {
+                ffi::parkissat_inject_clause(target.0, clause.as_ptr(), clause.len() as c_int);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Debug for ClauseInjector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ClauseInjector").finish_non_exhaustive()
+    }
+}
+
+impl ParkissatSolver {
+    /// Get a thread-safe handle that injects clauses into this solver
+    pub fn clause_injector(&self) -> ClauseInjector {
+        ClauseInjector {
+            target: self.interrupt_handle.target(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cnf::pigeonhole;
+    use crate::wrapper::{SolverConfig, SolverResult, UnknownReason};
+    use std::thread;
+    use std::time::Duration;
+
+    #[test]
+    fn test_injected_clauses_reach_the_search() {
+        let mut solver = ParkissatSolver::new().unwrap();
+        let config = SolverConfig {
+            timeout: Duration::from_secs(30),
+            ..SolverConfig::default()
+        };
+        solver.configure(&config).unwrap();
+        // Far too hard to finish in time
+        solver.add_formula(&pigeonhole(12, 11)).unwrap();
+
+        // Contradicting units are not implied, but show the workers use them
+        let injector = solver.clause_injector();
+        let feeder = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(50));
+            injector.inject(&[1]).unwrap();
+            injector.inject(&[-1]).unwrap();
+        });
+        assert_eq!(solver.solve().unwrap(), SolverResult::Unsat);
+        feeder.join().unwrap();
+
+        // They are gone once that solve is over
+        let interrupt = solver.interrupt_handle();
+        let watchdog = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(200));
+            interrupt.interrupt();
+        });
+        assert_eq!(solver.solve().unwrap(), SolverResult::Unknown);
+        assert_eq!(solver.unknown_reason(), Some(UnknownReason::Interrupted));
+        watchdog.join().unwrap();
+    }
+
+    #[test]
+    fn test_invalid_and_detached_injection() {
+        let mut solver = ParkissatSolver::new().unwrap();
+        solver.configure(&SolverConfig::default()).unwrap();
+        let injector = solver.clause_injector();
+
+        assert!(matches!(injector.inject(&[]), Err(ParkissatError::InvalidClause(_))));
+        assert!(matches!(injector.inject(&[1, 0]), Err(ParkissatError::InvalidClause(_))));
+
+        drop(solver);
+        injector.inject(&[1, 2]).unwrap();
+    }
+}
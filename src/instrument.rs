@@ -0,0 +1,103 @@
+//! `tracing` instrumentation
+//!
+//! With the `tracing` feature, `configure`, batched clause additions, and
+//! every solve run in spans of target `parkissat`. The search events of
+//! [`SolverEvents`](crate::SolverEvents) are reported as events inside the
+//! span of their solve, and each solve ends with an event carrying its
+//! result and, for `Unknown`, the reason it stopped, e.g. a timeout.
+
+use crate::wrapper::{ParkissatSolver, SolverResult};
+use tracing::span::EnteredSpan;
+
+impl ParkissatSolver {
+    /// Enter the span of a solve and make it the parent of the solve's search events
+    pub(crate) fn solve_span(&mut self, assumptions: usize) -> EnteredSpan {
+        let span = tracing::info_span!(
+            target: "parkissat",
+            "solve",
+            threads = self.config.num_threads,
+            variables = self.variable_count(),
+            assumptions,
+        );
+        // Every solver has events installed with this feature
+        if let Some(events) = &mut self.events {
+            events.span = Some(span.clone());
+        }
+        span.entered()
+    }
+
+    /// Report the outcome of a solve inside its span
+    pub(crate) fn trace_result(&mut self, result: SolverResult) {
+        match result {
+            SolverResult::Unknown => {
+                let reason = self.unknown_reason();
+                tracing::info!(target: "parkissat", result = ?result, reason = ?reason, "solve stopped");
+            }
+            _ => tracing::info!(target: "parkissat", result = ?result, "solve finished"),
+        }
+        if let Some(events) = &mut self.events {
+            events.span = None;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::wrapper::{ParkissatSolver, SolverConfig, SolverResult};
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::Mutex;
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Dispatch, Event, Metadata, Subscriber};
+
+    /// Records span names and counts events
+    #[derive(Default)]
+    struct Recorder {
+        spans: Mutex<Vec<&'static str>>,
+        events: AtomicU64,
+    }
+
+    impl Subscriber for Recorder {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, span: &Attributes<'_>) -> Id {
+            let mut spans = self.spans.lock().unwrap();
+            spans.push(span.metadata().name());
+            Id::from_u64(spans.len() as u64)
+        }
+
+        fn record(&self, _: &Id, _: &Record<'_>) {}
+
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+        fn event(&self, _: &Event<'_>) {
+            self.events.fetch_add(1, Ordering::Relaxed);
+        }
+
+        fn enter(&self, _: &Id) {}
+
+        fn exit(&self, _: &Id) {}
+    }
+
+    #[test]
+    fn test_spans_and_events() {
+        let dispatch = Dispatch::new(Recorder::default());
+        tracing::dispatcher::with_default(&dispatch, || {
+            let mut solver = ParkissatSolver::new().unwrap();
+            solver.configure(&SolverConfig::default()).unwrap();
+            solver.add_clauses(&[1, 2, 0, -1, 0]).unwrap();
+            assert_eq!(solver.solve().unwrap(), SolverResult::Sat);
+
+            // Clearing the callbacks keeps the solver instrumented
+            solver.clear_events();
+            assert_eq!(solver.solve_with_assumptions(&[-2]).unwrap(), SolverResult::Unsat);
+        });
+
+        let recorder = dispatch.downcast_ref::<Recorder>().unwrap();
+        let spans = recorder.spans.lock().unwrap();
+        assert_eq!(*spans, ["configure", "add_clauses", "solve", "solve"]);
+        // At least the outcome of each solve; search events come from the monitor thread
+        assert!(recorder.events.load(Ordering::Relaxed) >= 2);
+    }
+}
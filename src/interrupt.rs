@@ -0,0 +1,126 @@
+//! Thread-safe solver interruption
+//!
+//! [`InterruptHandle`] stops a running solve from another thread. It shares
+//! the solver's C++ instance through a lock that the solver clears when it is
+//! dropped, so a handle that outlives its solver simply does nothing.
+//!
+//! Handles are cheap to clone and can be obtained before solving, so a
+//! watchdog thread or signal handler can stop a long-running
+//! [`ParkissatSolver::solve`].
+
+use crate::ffi;
+use crate::wrapper::ParkissatSolver;
+use std::ptr;
+use std::sync::{Arc, Mutex};
+
+/// Raw solver pointer shared with interrupt, statistics, and injector handles
+pub(crate) struct SolverPtr(pub(crate) *mut ffi::ParkissatSolver);
+
+// The pointer is only dereferenced by `parkissat_interrupt`,
+// `parkissat_get_statistics`, and `parkissat_inject_clause`, which are safe to
+// call from any thread, and only while the lock is held; the solver nulls it
+// under the same lock before the C++ instance is deleted.
+unsafe impl Send for SolverPtr {}
+
+/// Handle that interrupts a solver from another thread
+///
+/// An interrupt makes the current solve return
+/// [`SolverResult::Unknown`](crate::SolverResult::Unknown) as soon as the
+/// workers notice it.
+#[derive(Clone)]
+pub struct InterruptHandle {
+    target: Arc<Mutex<SolverPtr>>,
+}
+
+impl InterruptHandle {
+    pub(crate) fn new(solver: *mut ffi::ParkissatSolver) -> Self {
+        Self {
+            target: Arc::new(Mutex::new(SolverPtr(solver))),
+        }
+    }
+
+    /// Interrupt the solver
+    ///
+    /// Does nothing if the solver has been dropped.
+    pub fn interrupt(&self) {
+        let target = self.target.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if !target.0.is_null() {
+            unsafe {
+                ffi::parkissat_interrupt(target.0);
+            }
+        }
+    }
+
+    /// The pointer shared by every handle of the solver
+    pub(crate) fn target(&self) -> Arc<Mutex<SolverPtr>> {
+        Arc::clone(&self.target)
+    }
+
+    /// Disconnect every handle from the solver before it is deleted
+    pub(crate) fn detach(&self) {
+        let mut target = self.target.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        target.0 = ptr::null_mut();
+    }
+}
+
+impl ParkissatSolver {
+    /// Get a thread-safe handle that interrupts this solver
+    ///
+    /// An interrupt requested while no solve is running cancels the next
+    /// one; every solve clears the flag when it returns.
+    pub fn interrupt_handle(&self) -> InterruptHandle {
+        self.interrupt_handle.clone()
+    }
+}
+
+impl std::fmt::Debug for InterruptHandle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("InterruptHandle").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cnf::pigeonhole;
+    use crate::wrapper::{SolverConfig, SolverResult};
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::thread;
+    use std::time::Duration;
+
+    #[test]
+    fn test_watchdog_interrupts_blocking_solve() {
+        let mut solver = ParkissatSolver::new().unwrap();
+        solver.configure(&SolverConfig::default()).unwrap();
+
+        // Far too hard to finish in time
+        solver.add_formula(&pigeonhole(12, 11)).unwrap();
+
+        let done = Arc::new(AtomicBool::new(false));
+        let watchdog = {
+            let handle = solver.interrupt_handle();
+            let done = Arc::clone(&done);
+            thread::spawn(move || {
+                while !done.load(Ordering::SeqCst) {
+                    thread::sleep(Duration::from_millis(10));
+                    handle.interrupt();
+                }
+            })
+        };
+
+        let result = solver.solve().unwrap();
+        done.store(true, Ordering::SeqCst);
+        watchdog.join().unwrap();
+        assert_eq!(result, SolverResult::Unknown);
+    }
+
+    #[test]
+    fn test_handle_after_drop_is_inert() {
+        let solver = ParkissatSolver::new().unwrap();
+        let handle = solver.interrupt_handle();
+        let clone = handle.clone();
+        drop(solver);
+        handle.interrupt();
+        clone.interrupt();
+    }
+}
@@ -0,0 +1,315 @@
+//! IPASIR C interface
+//!
+//! With the `ipasir` feature the crate's `cdylib` exports the standard
+//! incremental SAT solver interface (`ipasir_init`, `ipasir_add`,
+//! `ipasir_assume`, `ipasir_solve`, `ipasir_val`, `ipasir_failed`, ...)
+//! implemented on top of [`ParkissatSolver`], so any IPASIR consumer can link
+//! against `libparkissat_sys` instead of its usual solver:
+//!
+//! ```text
+//! cargo build --release --features ipasir
+//! ```
+//!
+//! Every solver created by `ipasir_init` uses [`SolverConfig::default`].
+
+use crate::model::Model;
+use crate::types::Var;
+use crate::wrapper::{ParkissatSolver, SolverConfig, SolverResult};
+use std::os::raw::{c_char, c_int, c_void};
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+
+const SIGNATURE: &str = concat!("parkissat-sys-", env!("CARGO_PKG_VERSION"), "\0");
+
+/// State behind the opaque pointer handed out by `ipasir_init`
+struct IpasirSolver {
+    solver: ParkissatSolver,
+    /// Literals of the clause being added
+    clause: Vec<i32>,
+    /// Assumptions for the next solve
+    assumptions: Vec<i32>,
+    /// An empty clause was added, so every solve answers UNSAT
+    inconsistent: bool,
+    /// Model of the last SAT answer
+    model: Model,
+    /// Failed assumptions of the last UNSAT answer
+    failed: Vec<i32>,
+}
+
+/// User data passed back to IPASIR callbacks
+struct UserData(*mut c_void);
+
+// IPASIR leaves the thread safety of callback data to the caller, who must
+// accept calls from the solver's background threads
+unsafe impl Send for UserData {}
+
+impl UserData {
+    fn get(&self) -> *mut c_void {
+        self.0
+    }
+}
+
+/// Borrow the solver behind an IPASIR handle
+unsafe fn state<'a>(solver: *mut c_void) -> Option<&'a mut IpasirSolver> {
+    (solver as *mut IpasirSolver).as_mut()
+}
+
+/// Run `f` without letting a panic unwind into the C caller
+fn guard<T>(fallback: T, f: impl FnOnce() -> T) -> T {
+    panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or(fallback)
+}
+
+/// Name and version of the solver
+#[no_mangle]
+pub extern "C" fn ipasir_signature() -> *const c_char {
+    SIGNATURE.as_ptr() as *const c_char
+}
+
+/// Create a solver (null if that fails)
+#[no_mangle]
+pub extern "C" fn ipasir_init() -> *mut c_void {
+    guard(ptr::null_mut(), || {
+        let Ok(mut solver) = ParkissatSolver::new() else {
+            return ptr::null_mut();
+        };
+        if solver.configure(&SolverConfig::default()).is_err() {
+            return ptr::null_mut();
+        }
+
+        let state = IpasirSolver {
+            solver,
+            clause: Vec::new(),
+            assumptions: Vec::new(),
+            inconsistent: false,
+            model: Model::default(),
+            failed: Vec::new(),
+        };
+        Box::into_raw(Box::new(state)) as *mut c_void
+    })
+}
+
+/// Destroy a solver created by `ipasir_init`
+///
+/// # Safety
+///
+/// `solver` must come from `ipasir_init` and not be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn ipasir_release(solver: *mut c_void) {
+    if !solver.is_null() {
+        let state = Box::from_raw(solver as *mut IpasirSolver);
+        guard((), move || drop(state));
+    }
+}
+
+/// Add a literal to the current clause, or finish it with `0`
+///
+/// # Safety
+///
+/// `solver` must come from `ipasir_init`.
+#[no_mangle]
+pub unsafe extern "C" fn ipasir_add(solver: *mut c_void, lit_or_zero: i32) {
+    let Some(state) = state(solver) else { return };
+    guard((), || {
+        if lit_or_zero != 0 {
+            state.clause.push(lit_or_zero);
+            return;
+        }
+
+        let clause = std::mem::take(&mut state.clause);
+        if clause.is_empty() {
+            state.inconsistent = true;
+        } else {
+            let _ = state.solver.add_clause(&clause);
+        }
+    })
+}
+
+/// Assume a literal for the next solve only
+///
+/// # Safety
+///
+/// `solver` must come from `ipasir_init`.
+#[no_mangle]
+pub unsafe extern "C" fn ipasir_assume(solver: *mut c_void, lit: i32) {
+    let Some(state) = state(solver) else { return };
+    if lit != 0 {
+        state.assumptions.push(lit);
+    }
+}
+
+/// Solve under the current assumptions: 10 = SAT, 20 = UNSAT, 0 = interrupted
+///
+/// # Safety
+///
+/// `solver` must come from `ipasir_init`.
+#[no_mangle]
+pub unsafe extern "C" fn ipasir_solve(solver: *mut c_void) -> c_int {
+    let Some(state) = state(solver) else { return 0 };
+    guard(0, || {
+        let assumptions = std::mem::take(&mut state.assumptions);
+        state.model = Model::default();
+        state.failed.clear();
+
+        if state.inconsistent {
+            return 20;
+        }
+
+        match state.solver.solve_with_assumptions(&assumptions) {
+            Ok(SolverResult::Sat) => {
+                state.model = state.solver.model().unwrap_or_default();
+                10
+            }
+            Ok(SolverResult::Unsat) => {
+                state.failed = state.solver.failed_assumptions().unwrap_or_default();
+                20
+            }
+            Ok(SolverResult::Unknown) | Err(_) => 0,
+        }
+    })
+}
+
+/// Value of a literal in the last model: `lit` if true, `-lit` if false, `0` if unknown
+///
+/// # Safety
+///
+/// `solver` must come from `ipasir_init`.
+#[no_mangle]
+pub unsafe extern "C" fn ipasir_val(solver: *mut c_void, lit: i32) -> i32 {
+    let Some(state) = state(solver) else { return 0 };
+    match Var::new(lit.unsigned_abs()).and_then(|var| state.model.value(var)) {
+        Some(value) if value == (lit > 0) => lit,
+        Some(_) => -lit,
+        None => 0,
+    }
+}
+
+/// Whether assumption `lit` was used to prove the last UNSAT answer
+///
+/// # Safety
+///
+/// `solver` must come from `ipasir_init`.
+#[no_mangle]
+pub unsafe extern "C" fn ipasir_failed(solver: *mut c_void, lit: i32) -> c_int {
+    let Some(state) = state(solver) else { return 0 };
+    c_int::from(state.failed.contains(&lit))
+}
+
+/// Install a callback that stops the search when it returns nonzero (null = none)
+///
+/// # Safety
+///
+/// `solver` must come from `ipasir_init`; `terminate` is called with `data`
+/// from a background thread during solves.
+#[no_mangle]
+pub unsafe extern "C" fn ipasir_set_terminate(
+    solver: *mut c_void,
+    data: *mut c_void,
+    terminate: Option<unsafe extern "C" fn(data: *mut c_void) -> c_int>,
+) {
+    let Some(state) = state(solver) else { return };
+    match terminate {
+        Some(terminate) => {
+            let data = UserData(data);
+            state.solver.set_terminate_callback(move || unsafe { terminate(data.get()) != 0 });
+        }
+        None => state.solver.clear_terminate_callback(),
+    }
+}
+
+/// Install a callback receiving zero-terminated learned clauses of at most
+/// `max_length` literals (null = none)
+///
+/// # Safety
+///
+/// `solver` must come from `ipasir_init`; `learn` is called with `data` from
+/// a background thread during solves.
+#[no_mangle]
+pub unsafe extern "C" fn ipasir_set_learn(
+    solver: *mut c_void,
+    data: *mut c_void,
+    max_length: c_int,
+    learn: Option<unsafe extern "C" fn(data: *mut c_void, clause: *mut i32)>,
+) {
+    let Some(state) = state(solver) else { return };
+    match learn {
+        Some(learn) => {
+            let data = UserData(data);
+            let mut buffer = Vec::new();
+            let max_length = usize::try_from(max_length).unwrap_or(0);
+            state.solver.set_learn_callback(max_length, move |clause| {
+                buffer.clear();
+                buffer.extend_from_slice(clause);
+                buffer.push(0);
+                unsafe { learn(data.get(), buffer.as_mut_ptr()) }
+            });
+        }
+        None => state.solver.clear_learn_callback(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cnf::pigeonhole;
+    use std::ffi::CStr;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn add(solver: *mut c_void, clause: &[i32]) {
+        for &lit in clause.iter().chain(&[0]) {
+            unsafe { ipasir_add(solver, lit) };
+        }
+    }
+
+    #[test]
+    fn test_incremental_session() {
+        let signature = unsafe { CStr::from_ptr(ipasir_signature()) };
+        assert!(signature.to_str().unwrap().starts_with("parkissat-sys-"));
+
+        let solver = ipasir_init();
+        assert!(!solver.is_null());
+        unsafe {
+            add(solver, &[1, 2]);
+            add(solver, &[-1, 3]);
+
+            assert_eq!(ipasir_solve(solver), 10);
+            assert!(ipasir_val(solver, 1) == 1 || ipasir_val(solver, 2) == 2);
+            // Both signs report the true literal of the variable
+            assert_eq!(ipasir_val(solver, -3), ipasir_val(solver, 3));
+            assert_eq!(ipasir_val(solver, 3).abs(), 3);
+
+            ipasir_assume(solver, -2);
+            ipasir_assume(solver, -3);
+            assert_eq!(ipasir_solve(solver), 20);
+            assert_eq!(ipasir_failed(solver, -3), 1);
+            assert_eq!(ipasir_failed(solver, 1), 0);
+
+            // Assumptions only last for one solve
+            assert_eq!(ipasir_solve(solver), 10);
+
+            add(solver, &[]);
+            assert_eq!(ipasir_solve(solver), 20);
+            ipasir_release(solver);
+        }
+    }
+
+    unsafe extern "C" fn stop_after_first_poll(data: *mut c_void) -> c_int {
+        let polls = &*(data as *const AtomicUsize);
+        c_int::from(polls.fetch_add(1, Ordering::SeqCst) > 0)
+    }
+
+    #[test]
+    fn test_terminate() {
+        let solver = ipasir_init();
+        for clause in pigeonhole(12, 11).iter() {
+            add(solver, clause);
+        }
+
+        let polls = AtomicUsize::new(0);
+        unsafe {
+            ipasir_set_terminate(solver, &polls as *const AtomicUsize as *mut c_void, Some(stop_after_first_poll));
+            assert_eq!(ipasir_solve(solver), 0);
+            ipasir_release(solver);
+        }
+        assert!(polls.load(Ordering::SeqCst) > 1);
+    }
+}
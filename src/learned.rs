@@ -0,0 +1,165 @@
+//! Learned clause export and import
+//!
+//! The workers of a solve keep their clause databases until the next solve
+//! rebuilds them, carrying only the short learned clauses over to the new
+//! workers. [`ParkissatSolver::get_learned_clauses`] reads the learned
+//! clauses that survived clause-database reduction, so they can be fed to a
+//! later, related instance or persisted. Every one is implied by the formula
+//! the solve was given; literals fixed at decision level 0 are not clauses
+//! of the database and are available from
+//! [`fixed_literals`](ParkissatSolver::fixed_literals) instead.
+//!
+//! [`ParkissatSolver::add_learned_clauses`] is the way back: the clauses
+//! join every worker's database of the next solve as learned clauses, which
+//! clause-database reduction may drop again, rather than as part of the
+//! formula. Like [injected](crate::inject) clauses they must be implied by
+//! the formula, e.g. learned from the same or a weaker one, or the solver
+//! may wrongly answer UNSAT.
+
+use crate::error::{ParkissatError, Result};
+use crate::ffi;
+use crate::wrapper::ParkissatSolver;
+use std::os::raw::c_int;
+
+impl ParkissatSolver {
+    /// Learned clauses of at most `max_len` literals held by the workers of the last solve
+    ///
+    /// Clauses are deduplicated across workers, with their literals sorted.
+    pub fn get_learned_clauses(&self, max_len: usize) -> Result<Vec<Vec<i32>>> {
+        self.get_learned_clauses_with_glue(max_len, None)
+    }
+
+    /// Learned clauses of at most `max_len` literals and a glue (LBD) of at most `max_glue`
+    pub fn get_learned_clauses_with_glue(&self, max_len: usize, max_glue: Option<u32>) -> Result<Vec<Vec<i32>>> {
+        if !self.is_configured() {
+            return Err(ParkissatError::NotConfigured);
+        }
+
+        let max_len = max_len.min(c_int::MAX as usize) as c_int;
+        let max_glue = max_glue.map_or(0, |glue| glue.clamp(1, c_int::MAX as u32) as c_int);
+        let size = unsafe {
+            ffi::parkissat_get_learned_clauses(self.solver, max_len, max_glue, std::ptr::null_mut(), 0)
+        };
+        self.check_error()?;
+
+        let mut literals = vec![0; size.max(0) as usize];
+        unsafe {
+            ffi::parkissat_get_learned_clauses(self.solver, max_len, max_glue, literals.as_mut_ptr(), size);
+        }
+        self.check_error()?;
+
+        Ok(literals.split(|&lit| lit == 0)
+            .filter(|clause| !clause.is_empty())
+            .map(<[i32]>::to_vec)
+            .collect())
+    }
+
+    /// Add clauses implied by the formula as learned clauses of the next solve
+    ///
+    /// Literals are deduplicated and tautologies dropped. Every variable must
+    /// already be known to the solver. Their glue starts at the clause length
+    /// and is recomputed by the workers when a clause is used in conflict
+    /// analysis.
+    pub fn add_learned_clauses(&mut self, clauses: &[Vec<i32>]) -> Result<()> {
+        if !self.is_configured() {
+            return Err(ParkissatError::NotConfigured);
+        }
+
+        // Check every clause before any is added
+        let mut normalized = Vec::with_capacity(clauses.len());
+        for clause in clauses {
+            if clause.is_empty() {
+                return Err(ParkissatError::InvalidClause("Empty clause".to_string()));
+            }
+            if clause.contains(&0) {
+                return Err(ParkissatError::InvalidClause("Literal cannot be zero".to_string()));
+            }
+            if let Some(&lit) = clause.iter().find(|lit| lit.unsigned_abs() as usize > self.variable_count()) {
+                return Err(ParkissatError::InvalidVariable(lit));
+            }
+
+            let mut clause = clause.clone();
+            clause.sort_unstable_by_key(|&lit| (lit.abs(), lit));
+            clause.dedup();
+            if clause.windows(2).any(|pair| pair[0] == -pair[1]) {
+                continue;
+            }
+            normalized.push(clause);
+        }
+
+        for clause in &normalized {
+            unsafe {
+                ffi::parkissat_add_learned_clause(self.solver, clause.as_ptr(), clause.len() as c_int);
+            }
+            self.check_error()?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cnf::pigeonhole;
+    use crate::limits::SolverLimits;
+    use crate::wrapper::{SolverConfig, SolverResult};
+
+    #[test]
+    fn test_learned_clauses_after_budget() {
+        let mut solver = ParkissatSolver::new().unwrap();
+        solver.configure(&SolverConfig { num_threads: 2, ..SolverConfig::default() }).unwrap();
+        solver.add_formula(&pigeonhole(9, 8)).unwrap();
+        assert!(solver.get_learned_clauses(usize::MAX).unwrap().is_empty());
+
+        assert_eq!(solver.solve_limited(&SolverLimits::new().conflicts(2000)).unwrap(), SolverResult::Unknown);
+        let learned = solver.get_learned_clauses(10).unwrap();
+        assert!(!learned.is_empty());
+        for clause in &learned {
+            assert!(!clause.is_empty() && clause.len() <= 10);
+            assert!(clause.windows(2).all(|pair| pair[0] < pair[1]));
+            assert!(clause.iter().all(|&lit| lit != 0 && lit.abs() <= 72));
+        }
+
+        let glued = solver.get_learned_clauses_with_glue(10, Some(2)).unwrap();
+        assert!(glued.len() <= learned.len());
+        assert!(glued.iter().all(|clause| learned.contains(clause)));
+
+        solver.reset().unwrap();
+        assert!(solver.get_learned_clauses(usize::MAX).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_learned_clauses_carry_over() {
+        let config = SolverConfig { num_threads: 2, ..SolverConfig::default() };
+        let mut first = ParkissatSolver::new().unwrap();
+        first.configure(&config).unwrap();
+        first.add_formula(&pigeonhole(9, 8)).unwrap();
+        first.solve_limited(&SolverLimits::new().conflicts(2000)).unwrap();
+        let learned = first.get_learned_clauses(usize::MAX).unwrap();
+
+        // A fresh solver for the same formula starts from what the first one learned
+        let mut second = ParkissatSolver::new().unwrap();
+        second.configure(&config).unwrap();
+        second.add_formula(&pigeonhole(9, 8)).unwrap();
+        second.add_learned_clauses(&learned).unwrap();
+        second.add_learned_clauses(&[vec![1, -1], vec![2, 2, 3]]).unwrap();
+        let result = second.solve_limited(&SolverLimits::new().conflicts(2000)).unwrap();
+        assert_ne!(result, SolverResult::Sat);
+    }
+
+    #[test]
+    fn test_invalid_learned_clauses() {
+        let mut solver = ParkissatSolver::new().unwrap();
+        assert_eq!(solver.add_learned_clauses(&[vec![1]]).unwrap_err(), ParkissatError::NotConfigured);
+
+        solver.configure(&SolverConfig::default()).unwrap();
+        solver.add_clause(&[1, 2]).unwrap();
+        assert!(matches!(solver.add_learned_clauses(&[vec![]]), Err(ParkissatError::InvalidClause(_))));
+        assert!(matches!(solver.add_learned_clauses(&[vec![1, 0]]), Err(ParkissatError::InvalidClause(_))));
+        assert_eq!(solver.add_learned_clauses(&[vec![1], vec![-3]]).unwrap_err(), ParkissatError::InvalidVariable(-3));
+
+        // Nothing was added by the failed calls
+        solver.add_clause(&[-1]).unwrap();
+        assert_eq!(solver.solve().unwrap(), SolverResult::Sat);
+    }
+}
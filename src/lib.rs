@@ -6,10 +6,117 @@
 
 pub mod ffi;
 pub mod wrapper;
+pub mod builder;
 pub mod error;
+pub mod interrupt;
+pub mod progress;
+pub mod callbacks;
+pub mod events;
+pub mod inject;
+pub mod checkpoint;
+pub mod state;
+pub mod cache;
+pub mod background;
+pub mod batch;
+pub mod limits;
+pub mod phases;
+pub mod freeze;
+pub mod equivalences;
+pub mod priority;
+pub mod options;
+pub mod presets;
+pub mod output;
+pub mod format;
+pub mod proof;
+pub mod types;
+pub mod vars;
+pub mod model;
+pub mod minimize;
+pub mod cnf;
+pub mod encodings;
+pub mod circuit;
+pub mod aiger;
+pub mod dimacs;
+pub mod count;
+pub mod implicates;
+pub mod backbone;
+pub mod autotune;
+pub mod schedule;
+pub mod ensemble;
+pub mod cube;
+pub mod pool;
+pub mod bridge;
+pub mod distributed;
+pub mod edit;
+pub mod groups;
+pub mod mus;
+pub mod mcs;
+pub mod propagate;
+pub mod partial;
+pub mod learned;
+pub mod preprocess;
+pub mod maxsat;
+pub mod optimize;
 
-pub use wrapper::{ParkissatSolver, SolverConfig, SolverResult, SolverStatistics};
+#[cfg(feature = "debug")]
+pub mod debug;
+
+#[cfg(feature = "tracing")]
+mod instrument;
+
+#[cfg(feature = "daemon")]
+pub mod daemon;
+
+#[cfg(feature = "ipasir")]
+pub mod ipasir;
+
+#[cfg(feature = "rustsat")]
+pub mod rustsat_backend;
+
+#[cfg(all(feature = "signal", unix))]
+pub mod signal;
+
+#[cfg(unix)]
+pub mod process;
+
+pub use wrapper::{BranchingHeuristic, LocalSearchConfig, MabConfig, ParkissatSolver, PhaseMode, ProofFormat, RestartConfig, RestartScheme, SharingConfig, SolverConfig, SolverResult, SolverStatistics, UnknownReason, WorkerStatistics};
+pub use builder::{ConfiguredSolver, SolveOutcome, SolvedSolver, SolverBuilder};
 pub use error::{ParkissatError, Result};
+pub use interrupt::InterruptHandle;
+pub use progress::StatisticsHandle;
+pub use inject::ClauseInjector;
+pub use events::SolverEvents;
+pub use background::SolveHandle;
+pub use limits::SolverLimits;
+pub use presets::SolverConfigBuilder;
+pub use types::{Lit, Var};
+pub use vars::VarManager;
+pub use model::Model;
+pub use cnf::CnfFormula;
+pub use aiger::{Aig, Unrolling};
+pub use count::{ApproxCount, ApproxOptions, ModelCount, WeightedCount};
+pub use implicates::ImplicateOptions;
+pub use backbone::{Backbone, BackboneOptions};
+pub use autotune::{InstanceFeatures, Strategy};
+pub use schedule::{BudgetScheduler, SchedulePolicy, ScheduleReport};
+pub use ensemble::{EnsembleOutcome, ExternalSolver, Winner};
+pub use cube::{CubeOptions, CubeOutcome, SplitStrategy};
+pub use pool::{PoolAnswer, Query, ResultHandle, SolverPool};
+pub use bridge::{ClauseBridge, ClauseHub};
+pub use distributed::{Coordinator, DistributedOptions, DistributedOutcome};
+pub use edit::EditReport;
+pub use groups::ClauseGroup;
+pub use mus::UnsatCore;
+pub use mcs::{GroupSubset, SubsetEnumerator};
+pub use propagate::PropagationResult;
+pub use partial::PartialAssignment;
+pub use preprocess::{PreprocessOptions, SimplifiedFormula};
+pub use maxsat::{MaxSatResult, WcnfFormula};
+pub use optimize::{Direction, OptimizeOptions, OptimizeStrategy, Optimum};
+pub use cache::{CacheKey, CachedResult, DiskCache, Fingerprint, MemoryCache, ResultCache};
+
+#[cfg(feature = "debug")]
+pub use debug::{TrailEntry, TrailSnapshot};
 
 #[cfg(test)]
 mod tests {
@@ -0,0 +1,172 @@
+//! Budgeted solving
+//!
+//! [`ParkissatSolver::solve_limited`] stops the search once a conflict,
+//! decision, or propagation budget is exhausted and reports
+//! [`SolverResult::Unknown`]. Budgets apply to each portfolio worker
+//! separately and only to the call they are passed to.
+//!
+//! [`ParkissatSolver::solve_until`] stops the search at a wall-clock
+//! deadline, with millisecond precision, in addition to the configured
+//! timeout; whichever comes first ends the solve with
+//! [`UnknownReason::Timeout`](crate::UnknownReason::Timeout).
+
+use crate::error::{ParkissatError, Result};
+use crate::ffi;
+use crate::wrapper::{ParkissatSolver, SolverResult};
+use std::time::Instant;
+
+/// Search budgets for a single solve (`None` = unlimited)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SolverLimits {
+    /// Maximum number of conflicts per worker
+    pub max_conflicts: Option<u64>,
+    /// Maximum number of decisions per worker
+    pub max_decisions: Option<u64>,
+    /// Maximum number of propagations per worker
+    pub max_propagations: Option<u64>,
+}
+
+impl SolverLimits {
+    /// No limits
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Limit the number of conflicts
+    pub fn conflicts(mut self, max: u64) -> Self {
+        self.max_conflicts = Some(max);
+        self
+    }
+
+    /// Limit the number of decisions
+    pub fn decisions(mut self, max: u64) -> Self {
+        self.max_decisions = Some(max);
+        self
+    }
+
+    /// Limit the number of propagations
+    pub fn propagations(mut self, max: u64) -> Self {
+        self.max_propagations = Some(max);
+        self
+    }
+}
+
+fn ffi_limit(limit: Option<u64>) -> i64 {
+    limit.map_or(-1, |limit| limit.min(i64::MAX as u64) as i64)
+}
+
+impl ParkissatSolver {
+    /// Solve within the given budgets, returning `Unknown` if one runs out
+    pub fn solve_limited(&mut self, limits: &SolverLimits) -> Result<SolverResult> {
+        self.solve_limited_with_assumptions(&[], limits)
+    }
+
+    /// Solve under assumptions within the given budgets
+    pub fn solve_limited_with_assumptions(&mut self, assumptions: &[i32], limits: &SolverLimits) -> Result<SolverResult> {
+        if !self.is_configured() {
+            return Err(ParkissatError::NotConfigured);
+        }
+
+        unsafe {
+            ffi::parkissat_set_limits(
+                self.solver,
+                ffi_limit(limits.max_conflicts),
+                ffi_limit(limits.max_decisions),
+                ffi_limit(limits.max_propagations),
+            );
+        }
+
+        let result = self.solve_with_assumptions(assumptions);
+
+        unsafe {
+            ffi::parkissat_set_limits(self.solver, -1, -1, -1);
+        }
+
+        result
+    }
+
+    /// Solve until the given deadline, returning `Unknown` if it passes first
+    pub fn solve_until(&mut self, deadline: Instant) -> Result<SolverResult> {
+        self.solve_with_assumptions_until(&[], deadline)
+    }
+
+    /// Solve under assumptions until the given deadline
+    ///
+    /// A deadline that has already passed still starts the search, which is
+    /// stopped within a millisecond.
+    pub fn solve_with_assumptions_until(&mut self, assumptions: &[i32], deadline: Instant) -> Result<SolverResult> {
+        if !self.is_configured() {
+            return Err(ParkissatError::NotConfigured);
+        }
+
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        unsafe {
+            ffi::parkissat_set_deadline(self.solver, remaining.as_nanos().div_ceil(1_000_000).min(i64::MAX as u128) as i64);
+        }
+
+        let result = self.solve_with_assumptions(assumptions);
+
+        unsafe {
+            ffi::parkissat_set_deadline(self.solver, -1);
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cnf::pigeonhole;
+    use crate::wrapper::{SolverConfig, UnknownReason};
+    use std::time::Duration;
+
+    #[test]
+    fn test_budget_exhaustion() {
+        let mut solver = ParkissatSolver::new().unwrap();
+        solver.configure(&SolverConfig::default()).unwrap();
+        solver.add_formula(&pigeonhole(10, 9)).unwrap();
+
+        for limits in [
+            SolverLimits::new().conflicts(10),
+            SolverLimits::new().decisions(10),
+            SolverLimits::new().propagations(100),
+        ] {
+            assert_eq!(solver.solve_limited(&limits).unwrap(), SolverResult::Unknown);
+        }
+    }
+
+    #[test]
+    fn test_limits_apply_to_one_call() {
+        let mut solver = ParkissatSolver::new().unwrap();
+        assert_eq!(solver.solve_limited(&SolverLimits::new()).unwrap_err(), ParkissatError::NotConfigured);
+
+        solver.configure(&SolverConfig::default()).unwrap();
+        solver.add_formula(&pigeonhole(4, 3)).unwrap();
+
+        assert_eq!(solver.solve_limited(&SolverLimits::new().conflicts(0)).unwrap(), SolverResult::Unknown);
+        assert_eq!(solver.solve().unwrap(), SolverResult::Unsat);
+        assert_eq!(solver.solve_limited(&SolverLimits::new()).unwrap(), SolverResult::Unsat);
+    }
+
+    #[test]
+    fn test_solve_until() {
+        let mut solver = ParkissatSolver::new().unwrap();
+        solver.configure(&SolverConfig::default()).unwrap();
+        solver.add_formula(&pigeonhole(12, 11)).unwrap();
+
+        let start = Instant::now();
+        let result = solver.solve_until(start + Duration::from_millis(150)).unwrap();
+        assert_eq!(result, SolverResult::Unknown);
+        assert_eq!(solver.unknown_reason(), Some(UnknownReason::Timeout));
+        assert!(start.elapsed() < Duration::from_secs(1));
+
+        // A passed deadline stops the search right away, and applies to one call only
+        assert_eq!(solver.solve_with_assumptions_until(&[1], start).unwrap(), SolverResult::Unknown);
+        let mut easy = ParkissatSolver::new().unwrap();
+        easy.configure(&SolverConfig::default()).unwrap();
+        easy.add_formula(&pigeonhole(4, 3)).unwrap();
+        assert_eq!(easy.solve_until(Instant::now()).unwrap(), SolverResult::Unknown);
+        assert_eq!(easy.solve().unwrap(), SolverResult::Unsat);
+    }
+}
@@ -0,0 +1,424 @@
+//! Weighted partial MaxSAT
+//!
+//! A [`WcnfFormula`] holds hard clauses, which must be satisfied, and soft
+//! clauses with positive weights. [`solve`] finds an assignment satisfying
+//! every hard clause that minimizes the total weight of falsified soft
+//! clauses.
+//!
+//! The search is core-guided (weighted Fu-Malik / WPM1): every soft clause is
+//! activated by an assumption literal, and each UNSAT core returned by
+//! [`ParkissatSolver::failed_assumptions`] is relaxed by adding a fresh
+//! relaxation variable to its clauses together with an at-most-one constraint
+//! over them. Soft clauses heavier than the lightest clause of the core are
+//! split so that only the core's minimum weight is paid. The first satisfying
+//! assignment is optimal.
+
+use crate::encodings::card::{self, CardEncoding};
+use crate::error::{ParkissatError, Result};
+use crate::wrapper::{ParkissatSolver, SolverConfig, SolverResult};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read};
+use std::path::Path;
+
+/// A weighted partial MaxSAT instance
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WcnfFormula {
+    hard: Vec<Vec<i32>>,
+    soft: Vec<(Vec<i32>, u64)>,
+    num_vars: usize,
+}
+
+impl WcnfFormula {
+    /// Create an empty instance
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a clause that must be satisfied
+    pub fn add_hard(&mut self, literals: &[i32]) -> Result<()> {
+        self.check_literals(literals)?;
+        if literals.is_empty() {
+            return Err(ParkissatError::InvalidClause("Empty hard clause".to_string()));
+        }
+        self.hard.push(literals.to_vec());
+        Ok(())
+    }
+
+    /// Add a clause whose falsification costs `weight`
+    ///
+    /// An empty soft clause is always falsified and adds a constant cost.
+    pub fn add_soft(&mut self, literals: &[i32], weight: u64) -> Result<()> {
+        self.check_literals(literals)?;
+        if weight == 0 {
+            return Err(ParkissatError::InvalidClause("Soft clause weight must be positive".to_string()));
+        }
+        self.soft.push((literals.to_vec(), weight));
+        Ok(())
+    }
+
+    /// Number of variables (highest variable index used)
+    pub fn num_vars(&self) -> usize {
+        self.num_vars
+    }
+
+    /// The hard clauses
+    pub fn hard(&self) -> &[Vec<i32>] {
+        &self.hard
+    }
+
+    /// The soft clauses with their weights
+    pub fn soft(&self) -> &[(Vec<i32>, u64)] {
+        &self.soft
+    }
+
+    /// Cost of an assignment, or `None` if it falsifies a hard clause
+    ///
+    /// `model` lists signed literals; unlisted variables count as false.
+    pub fn cost_of(&self, model: &[i32]) -> Option<u64> {
+        let mut values = vec![false; self.num_vars + 1];
+        for &lit in model {
+            let var = lit.unsigned_abs() as usize;
+            if lit > 0 && var < values.len() {
+                values[var] = true;
+            }
+        }
+        let satisfied = |clause: &[i32]| clause.iter().any(|&lit| values[lit.unsigned_abs() as usize] == (lit > 0));
+
+        if !self.hard.iter().all(|clause| satisfied(clause)) {
+            return None;
+        }
+
+        Some(
+            self.soft
+                .iter()
+                .filter(|(clause, _)| !satisfied(clause))
+                .map(|(_, weight)| weight)
+                .sum(),
+        )
+    }
+
+    fn check_literals(&mut self, literals: &[i32]) -> Result<()> {
+        for &lit in literals {
+            if lit == 0 || lit == i32::MIN {
+                return Err(ParkissatError::InvalidClause(format!("Invalid literal {}", lit)));
+            }
+        }
+        for &lit in literals {
+            self.num_vars = self.num_vars.max(lit.unsigned_abs() as usize);
+        }
+        Ok(())
+    }
+}
+
+/// Outcome of a MaxSAT solve
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MaxSatResult {
+    /// An optimal assignment and its cost
+    Optimal {
+        /// Total weight of falsified soft clauses
+        cost: u64,
+        /// The assignment, one signed literal per variable of the instance
+        model: Vec<i32>,
+    },
+    /// The hard clauses are unsatisfiable
+    HardUnsat,
+}
+
+/// One active copy of a soft clause
+struct SoftCopy {
+    literals: Vec<i32>,
+    weight: u64,
+    selector: i32,
+}
+
+/// Find an optimal assignment for a weighted partial MaxSAT instance
+pub fn solve(formula: &WcnfFormula, config: &SolverConfig) -> Result<MaxSatResult> {
+    let mut solver = ParkissatSolver::new()?;
+    solver.configure(config)?;
+    if formula.num_vars() > 0 {
+        solver.set_variable_count(formula.num_vars())?;
+    }
+
+    for clause in formula.hard() {
+        solver.add_clause(clause)?;
+    }
+
+    let mut lower_bound = 0u64;
+    let mut copies: Vec<SoftCopy> = Vec::new();
+    let mut by_selector: HashMap<i32, usize> = HashMap::new();
+
+    for (clause, weight) in formula.soft() {
+        if clause.is_empty() {
+            lower_bound += weight;
+            continue;
+        }
+        add_copy(&mut solver, &mut copies, &mut by_selector, clause.clone(), *weight)?;
+    }
+
+    loop {
+        let assumptions: Vec<i32> = copies
+            .iter()
+            .filter(|copy| copy.weight > 0)
+            .map(|copy| copy.selector)
+            .collect();
+
+        match solver.solve_with_assumptions(&assumptions)? {
+            SolverResult::Sat => break,
+            SolverResult::Unknown => return Err(ParkissatError::Interrupted),
+            SolverResult::Unsat => {}
+        }
+
+        let core: Vec<usize> = solver
+            .failed_assumptions()?
+            .iter()
+            .filter_map(|selector| by_selector.get(selector).copied())
+            .collect();
+
+        if core.is_empty() {
+            return Ok(MaxSatResult::HardUnsat);
+        }
+
+        let min_weight = core.iter().map(|&index| copies[index].weight).min().unwrap_or(0);
+        lower_bound += min_weight;
+
+        let mut relaxation = Vec::with_capacity(core.len());
+        for &index in &core {
+            let selector = copies[index].selector;
+            let mut relaxed = copies[index].literals.clone();
+
+            // Keep the remaining weight on the unrelaxed clause
+            copies[index].weight -= min_weight;
+            if copies[index].weight == 0 {
+                solver.add_clause(&[-selector])?;
+            }
+
+            let relax = solver.new_var()?.positive();
+            relaxed.push(relax.to_dimacs());
+            relaxation.push(relax);
+            add_copy(&mut solver, &mut copies, &mut by_selector, relaxed, min_weight)?;
+        }
+
+        let encoding = if relaxation.len() <= 5 {
+            CardEncoding::Pairwise
+        } else {
+            CardEncoding::SequentialCounter
+        };
+        card::at_most_one(&mut solver, &relaxation, encoding)?;
+    }
+
+    let full_model = solver.get_model()?;
+    let model: Vec<i32> = (1..=formula.num_vars() as i32)
+        .map(|var| if full_model.contains(&var) { var } else { -var })
+        .collect();
+
+    let cost = formula.cost_of(&model).ok_or_else(|| {
+        ParkissatError::InternalError("MaxSAT model violates a hard clause".to_string())
+    })?;
+    debug_assert_eq!(cost, lower_bound);
+
+    Ok(MaxSatResult::Optimal { cost, model })
+}
+
+fn add_copy(
+    solver: &mut ParkissatSolver,
+    copies: &mut Vec<SoftCopy>,
+    by_selector: &mut HashMap<i32, usize>,
+    literals: Vec<i32>,
+    weight: u64,
+) -> Result<()> {
+    let selector = solver.new_variable()?;
+    let mut guarded = literals.clone();
+    guarded.push(-selector);
+    solver.add_clause(&guarded)?;
+
+    by_selector.insert(selector, copies.len());
+    copies.push(SoftCopy { literals, weight, selector });
+    Ok(())
+}
+
+fn parse_error(line: usize, message: impl Into<String>) -> ParkissatError {
+    ParkissatError::ParseError {
+        line,
+        message: message.into(),
+    }
+}
+
+/// Parse a WCNF instance from a reader
+///
+/// Both the classic format (`p wcnf <vars> <clauses> [<top>]` followed by
+/// `<weight> <literals> 0` lines, where weight `top` marks hard clauses) and
+/// the 2022 MaxSAT Evaluation format (`h <literals> 0` for hard clauses and
+/// `<weight> <literals> 0` for soft ones, no header) are accepted.
+pub fn parse_reader<R: Read>(reader: R) -> Result<WcnfFormula> {
+    let reader = BufReader::new(reader);
+    let mut formula = WcnfFormula::new();
+    let mut top: Option<u64> = None;
+    let mut header_seen = false;
+
+    for (index, line) in reader.lines().enumerate() {
+        let line_number = index + 1;
+        let line = line?;
+        let trimmed = line.trim();
+
+        if trimmed.is_empty() || trimmed.starts_with('c') {
+            continue;
+        }
+
+        if trimmed.starts_with('p') {
+            if header_seen {
+                return Err(parse_error(line_number, "duplicate header"));
+            }
+            header_seen = true;
+
+            let fields: Vec<&str> = trimmed.split_whitespace().collect();
+            if fields.len() < 4 || fields.len() > 5 || fields[1] != "wcnf" {
+                return Err(parse_error(line_number, "expected `p wcnf <variables> <clauses> [<top>]`"));
+            }
+            if let Some(value) = fields.get(4) {
+                top = Some(value.parse().map_err(|_| parse_error(line_number, format!("invalid top weight `{}`", value)))?);
+            }
+            continue;
+        }
+
+        let mut tokens = trimmed.split_whitespace();
+        let first = tokens.next().unwrap_or_default();
+
+        let weight = if first == "h" {
+            None
+        } else {
+            let weight: u64 = first
+                .parse()
+                .map_err(|_| parse_error(line_number, format!("invalid weight `{}`", first)))?;
+            if top.is_some_and(|top| weight >= top) {
+                None
+            } else {
+                Some(weight)
+            }
+        };
+
+        let mut clause = Vec::new();
+        let mut terminated = false;
+        for token in tokens {
+            if terminated {
+                return Err(parse_error(line_number, "literals after terminating 0"));
+            }
+            let lit: i32 = token
+                .parse()
+                .map_err(|_| parse_error(line_number, format!("invalid literal `{}`", token)))?;
+            if lit == 0 {
+                terminated = true;
+            } else {
+                clause.push(lit);
+            }
+        }
+        if !terminated {
+            return Err(parse_error(line_number, "clause is not terminated by 0"));
+        }
+
+        let added = match weight {
+            None => formula.add_hard(&clause),
+            Some(weight) => formula.add_soft(&clause, weight),
+        };
+        added.map_err(|err| parse_error(line_number, err.to_string()))?;
+    }
+
+    Ok(formula)
+}
+
+/// Parse a WCNF instance from a string
+pub fn parse_str(input: &str) -> Result<WcnfFormula> {
+    parse_reader(input.as_bytes())
+}
+
+/// Parse a WCNF file
+pub fn parse_file<P: AsRef<Path>>(path: P) -> Result<WcnfFormula> {
+    parse_reader(File::open(path)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn optimal_cost(formula: &WcnfFormula) -> u64 {
+        match solve(formula, &SolverConfig::default()).unwrap() {
+            MaxSatResult::Optimal { cost, model } => {
+                assert_eq!(formula.cost_of(&model), Some(cost));
+                cost
+            }
+            MaxSatResult::HardUnsat => panic!("expected an optimum"),
+        }
+    }
+
+    #[test]
+    fn test_unweighted_maxsat() {
+        // x1, x2, x3 wanted true, but at most one of them may be
+        let mut formula = WcnfFormula::new();
+        formula.add_hard(&[-1, -2]).unwrap();
+        formula.add_hard(&[-1, -3]).unwrap();
+        formula.add_hard(&[-2, -3]).unwrap();
+        for var in 1..=3 {
+            formula.add_soft(&[var], 1).unwrap();
+        }
+
+        assert_eq!(optimal_cost(&formula), 2);
+    }
+
+    #[test]
+    fn test_weighted_maxsat() {
+        let mut formula = WcnfFormula::new();
+        formula.add_hard(&[-1, -2]).unwrap();
+        formula.add_soft(&[1], 3).unwrap();
+        formula.add_soft(&[2], 5).unwrap();
+        formula.add_soft(&[], 2).unwrap();
+
+        match solve(&formula, &SolverConfig::default()).unwrap() {
+            MaxSatResult::Optimal { cost, model } => {
+                assert_eq!(cost, 5);
+                assert_eq!(model, vec![-1, 2]);
+            }
+            MaxSatResult::HardUnsat => panic!("expected an optimum"),
+        }
+    }
+
+    #[test]
+    fn test_large_core_uses_sequential_encoding() {
+        let mut formula = WcnfFormula::new();
+        // At most one of x1..x8 may be true
+        for i in 1..=8 {
+            for j in i + 1..=8 {
+                formula.add_hard(&[-i, -j]).unwrap();
+            }
+            formula.add_soft(&[i], 1).unwrap();
+        }
+
+        assert_eq!(optimal_cost(&formula), 7);
+    }
+
+    #[test]
+    fn test_hard_unsat() {
+        let mut formula = WcnfFormula::new();
+        formula.add_hard(&[1]).unwrap();
+        formula.add_hard(&[-1]).unwrap();
+        formula.add_soft(&[2], 1).unwrap();
+
+        assert_eq!(solve(&formula, &SolverConfig::default()).unwrap(), MaxSatResult::HardUnsat);
+    }
+
+    #[test]
+    fn test_parse_formats() {
+        let classic = parse_str("c classic\np wcnf 2 3 10\n10 1 2 0\n3 -1 0\n4 -2 0\n").unwrap();
+        assert_eq!(classic.hard(), &[vec![1, 2]]);
+        assert_eq!(classic.soft(), &[(vec![-1], 3), (vec![-2], 4)]);
+
+        let modern = parse_str("h 1 2 0\n3 -1 0\n4 -2 0\n").unwrap();
+        assert_eq!(modern, classic);
+        assert_eq!(optimal_cost(&modern), 3);
+
+        match parse_str("h 1 2 0\nx -1 0\n") {
+            Err(ParkissatError::ParseError { line, .. }) => assert_eq!(line, 2),
+            other => panic!("expected parse error, got {:?}", other),
+        }
+        assert!(parse_str("h 1 2\n").is_err());
+    }
+}
@@ -0,0 +1,213 @@
+//! Enumeration of correction sets and unsatisfiable subsets
+//!
+//! [`ParkissatSolver::enumerate_subsets`] treats the enabled
+//! [`ClauseGroup`]s as soft constraints and enumerates, MARCO-style, their
+//! minimal correction sets (MCS: groups to drop to make the rest satisfiable)
+//! and minimal unsatisfiable subsets (MUS), interleaved as they are found.
+//!
+//! A second solver, the map, has one variable per group and records which
+//! subsets have been explored. Each round asks the map for a seed subset,
+//! preferring to include groups. A satisfiable seed is grown to a maximal
+//! satisfiable subset, whose complement is an MCS; the map then requires
+//! every later seed to contain one of its groups. An unsatisfiable seed is
+//! shrunk to a MUS, and the map forbids every superset of it. Enumeration
+//! ends when the map runs out of seeds, at which point every MCS and every
+//! MUS has been reported.
+
+use crate::error::{ParkissatError, Result};
+use crate::groups::ClauseGroup;
+use crate::wrapper::{ParkissatSolver, SolverConfig, SolverResult};
+
+/// A subset of clause groups reported by [`SubsetEnumerator`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GroupSubset {
+    /// Minimal correction set: dropping these groups makes the others satisfiable
+    Mcs(Vec<ClauseGroup>),
+    /// Minimal unsatisfiable subset
+    Mus(Vec<ClauseGroup>),
+}
+
+/// Iterator over the MCSes and MUSes of a solver's enabled clause groups
+///
+/// Groups are left enabled or disabled as they were when the enumerator is dropped.
+pub struct SubsetEnumerator<'a> {
+    solver: &'a mut ParkissatSolver,
+    /// Map variable `i + 1` stands for `groups[i]`
+    map: ParkissatSolver,
+    groups: Vec<ClauseGroup>,
+    done: bool,
+}
+
+impl ParkissatSolver {
+    /// Enumerate minimal correction sets and minimal unsatisfiable subsets of the enabled groups
+    ///
+    /// Clauses outside groups, and those of open push scopes, are hard
+    /// constraints. If they are unsatisfiable on their own, the only subset
+    /// reported is an empty MUS; if all groups are satisfiable together, it is
+    /// an empty MCS.
+    pub fn enumerate_subsets(&mut self) -> Result<SubsetEnumerator<'_>> {
+        if !self.is_configured() {
+            return Err(ParkissatError::NotConfigured);
+        }
+
+        let groups = self.groups.enabled_groups();
+        let mut map = ParkissatSolver::new()?;
+        map.configure(&SolverConfig::default())?;
+        map.set_variable_count(groups.len())?;
+        // Seeds should include as many groups as possible
+        map.set_initial_phases(&(1..=groups.len() as i32).collect::<Vec<_>>())?;
+
+        Ok(SubsetEnumerator {
+            solver: self,
+            map,
+            groups,
+            done: false,
+        })
+    }
+}
+
+impl SubsetEnumerator<'_> {
+    fn map_literal(&self, group: ClauseGroup, positive: bool) -> i32 {
+        let var = self.groups.iter().position(|&other| other == group)
+            .expect("enumerated groups come from the map") as i32 + 1;
+        if positive { var } else { -var }
+    }
+
+    fn next_subset(&mut self) -> Result<Option<GroupSubset>> {
+        match self.map.solve()? {
+            SolverResult::Sat => {}
+            SolverResult::Unsat => return Ok(None),
+            SolverResult::Unknown => return Err(ParkissatError::Interrupted),
+        }
+        let model = self.map.get_model()?;
+        let mut seed: Vec<ClauseGroup> = self.groups.iter().enumerate()
+            .filter(|&(index, _)| model.get(index).is_some_and(|&lit| lit > 0))
+            .map(|(_, &group)| group)
+            .collect();
+
+        match self.solver.solve_core(&seed, &self.groups, None)? {
+            SolverResult::Sat => {
+                // Grow the seed to a maximal satisfiable subset
+                for &group in &self.groups {
+                    if seed.contains(&group) {
+                        continue;
+                    }
+                    seed.push(group);
+                    match self.solver.solve_core(&seed, &self.groups, None)? {
+                        SolverResult::Sat => {}
+                        SolverResult::Unsat => { seed.pop(); }
+                        SolverResult::Unknown => return Err(ParkissatError::Interrupted),
+                    }
+                }
+
+                let mcs: Vec<ClauseGroup> = self.groups.iter().copied().filter(|group| !seed.contains(group)).collect();
+                let block: Vec<i32> = mcs.iter().map(|&group| self.map_literal(group, true)).collect();
+                if block.is_empty() {
+                    self.done = true;
+                } else {
+                    self.map.add_clause(&block)?;
+                }
+                Ok(Some(GroupSubset::Mcs(mcs)))
+            }
+            SolverResult::Unsat => {
+                let mus = self.solver.shrink_failed_groups(&self.groups, None)?.groups;
+                let block: Vec<i32> = mus.iter().map(|&group| self.map_literal(group, false)).collect();
+                if block.is_empty() {
+                    self.done = true;
+                } else {
+                    self.map.add_clause(&block)?;
+                }
+                Ok(Some(GroupSubset::Mus(mus)))
+            }
+            SolverResult::Unknown => Err(ParkissatError::Interrupted),
+        }
+    }
+}
+
+impl Iterator for SubsetEnumerator<'_> {
+    type Item = Result<GroupSubset>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let next = self.next_subset();
+        if !matches!(next, Ok(Some(_))) {
+            self.done = true;
+        }
+        next.transpose()
+    }
+}
+
+impl Drop for SubsetEnumerator<'_> {
+    fn drop(&mut self) {
+        for &group in &self.groups {
+            let _ = self.solver.enable_group(group);
+        }
+    }
+}
+
+impl std::fmt::Debug for SubsetEnumerator<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SubsetEnumerator")
+            .field("groups", &self.groups)
+            .field("done", &self.done)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sorted(mut subsets: Vec<GroupSubset>) -> (Vec<Vec<ClauseGroup>>, Vec<Vec<ClauseGroup>>) {
+        let mut mcses = Vec::new();
+        let mut muses = Vec::new();
+        for subset in subsets.drain(..) {
+            match subset {
+                GroupSubset::Mcs(mut groups) => { groups.sort_unstable(); mcses.push(groups) }
+                GroupSubset::Mus(mut groups) => { groups.sort_unstable(); muses.push(groups) }
+            }
+        }
+        mcses.sort();
+        muses.sort();
+        (mcses, muses)
+    }
+
+    #[test]
+    fn test_enumerate_subsets() {
+        let mut solver = ParkissatSolver::new().unwrap();
+        solver.configure(&SolverConfig::default()).unwrap();
+        solver.set_variable_count(2).unwrap();
+
+        // Soft constraints 1, ¬1, 2, ¬1 ∨ ¬2
+        let mut groups = Vec::new();
+        for clause in [&[1][..], &[-1], &[2], &[-1, -2]] {
+            let group = solver.new_clause_group().unwrap();
+            solver.add_clause_to_group(group, clause).unwrap();
+            groups.push(group);
+        }
+
+        let subsets: Vec<GroupSubset> = solver.enumerate_subsets().unwrap().collect::<Result<_>>().unwrap();
+        let (mcses, muses) = sorted(subsets);
+        let [a, b, c, d] = [groups[0], groups[1], groups[2], groups[3]];
+        assert_eq!(muses, vec![vec![a, b], vec![a, c, d]]);
+        assert_eq!(mcses, vec![vec![a], vec![b, c], vec![b, d]]);
+
+        // Every group is enabled again afterwards
+        assert!(groups.iter().all(|&group| solver.is_group_enabled(group) == Some(true)));
+    }
+
+    #[test]
+    fn test_enumerate_satisfiable_groups() {
+        let mut solver = ParkissatSolver::new().unwrap();
+        solver.configure(&SolverConfig::default()).unwrap();
+        solver.set_variable_count(1).unwrap();
+        let group = solver.new_clause_group().unwrap();
+        solver.add_clause_to_group(group, &[1]).unwrap();
+
+        let subsets: Vec<GroupSubset> = solver.enumerate_subsets().unwrap().collect::<Result<_>>().unwrap();
+        assert_eq!(subsets, vec![GroupSubset::Mcs(Vec::new())]);
+    }
+}
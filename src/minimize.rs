@@ -0,0 +1,141 @@
+//! Model minimization
+//!
+//! A full model says more than the formula needs: many variables could take
+//! either value without falsifying a clause. [`ParkissatSolver::minimize_model`]
+//! shrinks the model of the last SAT answer to a prime implicant, a partial
+//! assignment that satisfies every clause on its own and stops doing so when
+//! any one of its literals is dropped. The variables it leaves out are
+//! don't-cares.
+//!
+//! Minimization is greedy and needs no further solves: each clause counts its
+//! true literals still in the assignment, and a literal is dropped when every
+//! clause it satisfies has another one left. The result is subset-minimal,
+//! but not necessarily the smallest implicant contained in the model.
+
+use crate::error::{ParkissatError, Result};
+use crate::model::Model;
+use crate::wrapper::ParkissatSolver;
+use std::collections::HashMap;
+
+impl ParkissatSolver {
+    /// Shrink the model of the last SAT answer to a minimal satisfying partial assignment
+    ///
+    /// Only variables in `relevant` (default: all) are candidates for
+    /// dropping; the others keep their values. Clause groups, scopes, and
+    /// editable clauses are honored as in the solve, and their selectors are
+    /// never reported. Literals assumed in the last solve are kept only if
+    /// the clauses need them. Returns `NoSolution` unless the last solve
+    /// answered SAT.
+    pub fn minimize_model(&self, relevant: Option<&[i32]>) -> Result<Model> {
+        let model = self.get_model()?;
+        if let Some(&invalid) = relevant.unwrap_or(&[]).iter().find(|&&var| var <= 0) {
+            return Err(ParkissatError::InvalidVariable(invalid));
+        }
+
+        let enabled: Vec<i32> = self.editable.selectors().into_iter()
+            .chain(self.groups.enabled_selectors())
+            .collect();
+        let is_selector = |var: i32| enabled.contains(&var) || self.groups.is_selector(var);
+        let is_true = |lit: i32| {
+            let var = lit.abs();
+            // Enabled selectors are assumed, the others are free to be false
+            let value = if is_selector(var) {
+                enabled.contains(&var)
+            } else {
+                model.get(var as usize - 1).is_some_and(|&value| value > 0)
+            };
+            value == (lit > 0)
+        };
+
+        // Number of kept true literals per clause, and the clauses each literal satisfies
+        let mut support = Vec::new();
+        let mut satisfied: HashMap<i32, Vec<usize>> = HashMap::new();
+        for clause in self.all_clauses()? {
+            if clause.iter().any(|&lit| is_selector(lit.abs()) && is_true(lit)) {
+                continue;
+            }
+            let index = support.len();
+            let mut count = 0;
+            for &lit in clause.iter().filter(|&&lit| !is_selector(lit.abs()) && is_true(lit)) {
+                satisfied.entry(lit).or_default().push(index);
+                count += 1;
+            }
+            support.push(count);
+        }
+
+        let mut kept: Vec<i32> = model.into_iter()
+            .filter(|lit| !is_selector(lit.abs()))
+            .collect();
+        kept.retain(|&lit| {
+            if relevant.is_some_and(|relevant| !relevant.contains(&lit.abs())) {
+                return true;
+            }
+            let clauses = satisfied.get(&lit).map(Vec::as_slice).unwrap_or(&[]);
+            if clauses.iter().any(|&index| support[index] < 2) {
+                return true;
+            }
+            for &index in clauses {
+                support[index] -= 1;
+            }
+            false
+        });
+
+        Ok(Model::from_dimacs(&kept))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wrapper::{SolverConfig, SolverResult};
+
+    fn solved(clauses: &[&[i32]], phases: &[i32]) -> ParkissatSolver {
+        let mut solver = ParkissatSolver::new().unwrap();
+        solver.configure(&SolverConfig::default()).unwrap();
+        for clause in clauses {
+            solver.add_clause(clause).unwrap();
+        }
+        solver.set_initial_phases(phases).unwrap();
+        assert_eq!(solver.solve().unwrap(), SolverResult::Sat);
+        solver
+    }
+
+    #[test]
+    fn test_minimize_model() {
+        let clauses: &[&[i32]] = &[&[1, 2], &[-3, 2], &[1, 4]];
+        let solver = solved(clauses, &[1, 2, 3, 4]);
+        assert_eq!(solver.get_model().unwrap(), vec![1, 2, 3, 4]);
+
+        // 1 goes first, as 2 and 4 also satisfy its clauses; 3 satisfies nothing
+        let minimal = solver.minimize_model(None).unwrap();
+        assert_eq!(minimal.to_dimacs(), vec![2, 4]);
+
+        let mut formula = crate::cnf::CnfFormula::new();
+        for clause in clauses {
+            formula.add_clause(clause).unwrap();
+        }
+        assert!(formula.evaluate(&minimal));
+
+        // Irrelevant variables are kept even where they are not needed
+        let restricted = solver.minimize_model(Some(&[2, 3, 4])).unwrap();
+        assert_eq!(restricted.to_dimacs(), vec![1, 2]);
+        assert!(solver.minimize_model(Some(&[0])).is_err());
+    }
+
+    #[test]
+    fn test_minimize_model_with_groups() {
+        let mut solver = solved(&[&[1, 2]], &[1, 2]);
+        solver.set_variable_count(2).unwrap();
+        let group = solver.new_clause_group().unwrap();
+        solver.add_clause_to_group(group, &[-1]).unwrap();
+        assert_eq!(solver.solve().unwrap(), SolverResult::Sat);
+
+        assert_eq!(solver.minimize_model(None).unwrap().to_dimacs(), vec![-1, 2]);
+
+        solver.disable_group(group).unwrap();
+        solver.set_initial_phases(&[1, 2]).unwrap();
+        assert_eq!(solver.solve().unwrap(), SolverResult::Sat);
+        // The disabled group's clause needs nothing, and one literal satisfies 1 ∨ 2
+        assert_eq!(solver.minimize_model(None).unwrap().iter().count(), 1);
+    }
+}
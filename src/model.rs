@@ -0,0 +1,255 @@
+//! Satisfying assignments
+//!
+//! [`Model`] wraps the signed literals returned by
+//! [`ParkissatSolver::get_model`] and does the indexing that every caller of
+//! the raw vector would otherwise repeat: looking up a variable or literal,
+//! iterating over the assignment, and printing it as DIMACS `v` lines.
+//!
+//! [`ParkissatSolver::verify_model`] replays every clause given to the solver
+//! against its model in Rust, as a cheap check that a SAT answer coming back
+//! from the parallel C++ solver is genuine.
+
+use crate::cnf::satisfies;
+use crate::dimacs;
+use crate::error::Result;
+use crate::ffi;
+use crate::types::{Lit, Var};
+use crate::wrapper::ParkissatSolver;
+use std::fmt;
+
+/// Longest `v` line written by [`Model`]'s `Display` implementation
+const V_LINE_WIDTH: usize = 78;
+
+/// A (possibly partial) assignment of variables
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Model {
+    /// Value of variable `i + 1` at index `i` (`None` = unassigned)
+    values: Vec<Option<bool>>,
+}
+
+impl Model {
+    /// Build a model from signed DIMACS literals in any order
+    ///
+    /// Zero entries are ignored; if a variable occurs more than once the last
+    /// literal wins.
+    pub fn from_dimacs(literals: &[i32]) -> Self {
+        let mut values = Vec::new();
+        for &lit in literals {
+            let Some(lit) = Lit::new(lit) else { continue };
+            let index = lit.var().index() as usize - 1;
+            if index >= values.len() {
+                values.resize(index + 1, None);
+            }
+            values[index] = Some(lit.is_positive());
+        }
+        Self { values }
+    }
+
+    /// Build a model from typed literals
+    pub fn from_lits(lits: &[Lit]) -> Self {
+        Self::from_dimacs(&lits.iter().map(|lit| lit.to_dimacs()).collect::<Vec<_>>())
+    }
+
+    /// Highest variable the model covers
+    pub fn num_vars(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Value of a variable (`None` if it is unassigned)
+    pub fn value(&self, var: Var) -> Option<bool> {
+        self.values.get(var.index() as usize - 1).copied().flatten()
+    }
+
+    /// Whether a literal is true (`None` if its variable is unassigned)
+    pub fn lit_value(&self, lit: Lit) -> Option<bool> {
+        self.value(lit.var()).map(|value| value == lit.is_positive())
+    }
+
+    /// The true literal of every assigned variable, in variable order
+    pub fn iter(&self) -> impl Iterator<Item = Lit> + '_ {
+        self.values.iter().enumerate().filter_map(|(index, value)| {
+            let var = Var::new(index as u32 + 1)?;
+            value.map(|value| var.lit(value))
+        })
+    }
+
+    /// Values indexed by variable: element `i` holds variable `i + 1`
+    ///
+    /// Unassigned variables are reported as `false`.
+    pub fn to_bools(&self) -> Vec<bool> {
+        self.values.iter().map(|value| value.unwrap_or(false)).collect()
+    }
+
+    /// The true literal of every assigned variable as DIMACS integers
+    pub fn to_dimacs(&self) -> Vec<i32> {
+        self.iter().map(Lit::to_dimacs).collect()
+    }
+}
+
+impl<'a> IntoIterator for &'a Model {
+    type Item = Lit;
+    type IntoIter = Box<dyn Iterator<Item = Lit> + 'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        Box::new(self.iter())
+    }
+}
+
+/// Formats the model as competition-style `v` lines terminated by `0`
+impl fmt::Display for Model {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut line = String::from("v");
+        for lit in self.iter().map(|lit| lit.to_string()).chain(std::iter::once("0".to_string())) {
+            if line.len() + 1 + lit.len() > V_LINE_WIDTH {
+                writeln!(f, "{}", line)?;
+                line = String::from("v");
+            }
+            line.push(' ');
+            line.push_str(&lit);
+        }
+        write!(f, "{}", line)
+    }
+}
+
+/// Serialized as the DIMACS literals of [`Model::to_dimacs`], e.g. `[1, -2, 3]`
+#[cfg(feature = "serde")]
+impl serde::Serialize for Model {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter().map(Lit::to_dimacs))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Model {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let literals = <Vec<i32> as serde::Deserialize>::deserialize(deserializer)?;
+        Ok(Model::from_dimacs(&literals))
+    }
+}
+
+impl ParkissatSolver {
+    /// Get the model as a [`Model`] (only valid after SAT result)
+    pub fn model(&self) -> Result<Model> {
+        Ok(Model::from_dimacs(&self.get_model()?))
+    }
+
+    /// Check the model of the last SAT answer against every added clause
+    ///
+    /// Clauses added through the API are fetched from the solver and DIMACS
+    /// files passed to [`load_dimacs`](Self::load_dimacs) are parsed again,
+    /// so those files must not have changed since they were loaded. Returns
+    /// `NoSolution` unless the last solve answered SAT.
+    pub fn verify_model(&self) -> Result<bool> {
+        let model = self.model()?;
+
+        let length = unsafe { ffi::parkissat_get_clauses_size(self.solver) };
+        let mut literals = vec![0; length];
+        unsafe {
+            ffi::parkissat_get_clauses(self.solver, literals.as_mut_ptr(), length);
+        }
+
+        let mut clauses = literals.split(|&lit| lit == 0);
+        // The buffer ends with a terminator, so the last piece is empty
+        if !clauses.all(|clause| clause.is_empty() || satisfies(&model, clause)) {
+            return Ok(false);
+        }
+
+        for path in &self.dimacs_files {
+            if !dimacs::parse_file(path)?.evaluate(&model) {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ParkissatError;
+    use crate::wrapper::{SolverConfig, SolverResult};
+
+    fn var(index: u32) -> Var {
+        Var::new(index).unwrap()
+    }
+
+    #[test]
+    fn test_model_lookup() {
+        let model = Model::from_dimacs(&[3, -1, 0]);
+
+        assert_eq!(model.num_vars(), 3);
+        assert_eq!(model.value(var(1)), Some(false));
+        assert_eq!(model.value(var(2)), None);
+        assert_eq!(model.value(var(4)), None);
+        assert_eq!(model.lit_value(var(1).negative()), Some(true));
+        assert_eq!(model.to_bools(), vec![false, false, true]);
+        assert_eq!(model.to_dimacs(), vec![-1, 3]);
+        assert_eq!((&model).into_iter().count(), 2);
+    }
+
+    #[test]
+    fn test_v_lines() {
+        assert_eq!(Model::default().to_string(), "v 0");
+        assert_eq!(Model::from_dimacs(&[1, -2]).to_string(), "v 1 -2 0");
+
+        let long = Model::from_dimacs(&(1..=40).collect::<Vec<_>>()).to_string();
+        let lines: Vec<&str> = long.lines().collect();
+        assert!(lines.len() > 1);
+        assert!(lines.iter().all(|line| line.starts_with("v ") && line.len() <= V_LINE_WIDTH));
+        assert!(long.ends_with(" 0"));
+    }
+
+    #[test]
+    fn test_solver_model() {
+        let mut solver = ParkissatSolver::new().unwrap();
+        solver.configure(&SolverConfig::default()).unwrap();
+        solver.add_clause(&[1, 2]).unwrap();
+        solver.add_clause(&[-1]).unwrap();
+        assert_eq!(solver.model().unwrap_err(), ParkissatError::NoSolution);
+
+        assert_eq!(solver.solve().unwrap(), SolverResult::Sat);
+        let model = solver.model().unwrap();
+        assert_eq!(model.value(var(1)), Some(false));
+        assert_eq!(model.value(var(2)), Some(true));
+        assert_eq!(model.to_dimacs(), solver.get_model().unwrap());
+    }
+
+    #[test]
+    fn test_verify_model() {
+        use std::io::Write;
+
+        let mut solver = ParkissatSolver::new().unwrap();
+        solver.configure(&SolverConfig::default()).unwrap();
+        assert_eq!(solver.verify_model().unwrap_err(), ParkissatError::NoSolution);
+
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(file, "p cnf 3 2\n1 2 0\n-2 3 0").unwrap();
+        solver.load_dimacs(file.path()).unwrap();
+        solver.add_clauses(&[-1, 0, 1, 2, 3, 0]).unwrap();
+
+        assert_eq!(solver.solve().unwrap(), SolverResult::Sat);
+        assert!(solver.verify_model().unwrap());
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let mut formula = crate::cnf::CnfFormula::new();
+        formula.add_clause(&[1, -2]).unwrap();
+        formula.add_clause(&[2, 3]).unwrap();
+
+        assert!(formula.evaluate(&Model::from_dimacs(&[1, 2, -3])));
+        assert!(!formula.evaluate(&Model::from_dimacs(&[-1, 2, 3])));
+        // Unassigned variables satisfy no literal
+        assert!(!formula.evaluate(&Model::from_dimacs(&[1, -3])));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_literals() {
+        let model = Model::from_dimacs(&[3, -1, 4]);
+        let json = serde_json::to_string(&model).unwrap();
+        assert_eq!(json, "[-1,3,4]");
+        assert_eq!(serde_json::from_str::<Model>(&json).unwrap(), model);
+    }
+}
@@ -0,0 +1,147 @@
+//! Minimal unsatisfiable subsets of clause groups
+//!
+//! When the enabled [`ClauseGroup`]s are unsatisfiable together,
+//! [`ParkissatSolver::minimize_core`] shrinks them to a minimal unsatisfiable
+//! subset (MUS): a set of groups that is unsatisfiable, but becomes
+//! satisfiable when any one of them is dropped. Clauses outside groups, and
+//! those of open push scopes, are treated as hard background constraints.
+//!
+//! Extraction is deletion-based. It starts from the core of a first solve,
+//! then tests each group by solving without it; an UNSAT answer drops the
+//! group, and the (possibly smaller) core of that answer drops every other
+//! group it does not use. A SAT answer marks the group as necessary. A time
+//! budget bounds the whole extraction; when it runs out, the groups not yet
+//! tested are kept and the result is reported as not minimal.
+
+use crate::error::{ParkissatError, Result};
+use crate::groups::ClauseGroup;
+use crate::wrapper::{ParkissatSolver, SolverResult};
+use std::time::{Duration, Instant};
+
+/// Outcome of [`ParkissatSolver::minimize_core`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnsatCore {
+    /// Groups that are unsatisfiable together with the background clauses
+    pub groups: Vec<ClauseGroup>,
+
+    /// Whether every group was tested, so that dropping any one makes the rest satisfiable
+    pub minimal: bool,
+}
+
+impl ParkissatSolver {
+    /// Shrink the enabled clause groups to a minimal unsatisfiable subset
+    ///
+    /// `budget` bounds the wall-clock time of the whole extraction (None = no
+    /// limit). Fails with `NoSolution` if the enabled groups are satisfiable,
+    /// as there is no core to minimize, and with `Interrupted` if the budget
+    /// runs out before the first solve finishes. An empty core means the
+    /// background clauses are unsatisfiable on their own. Groups are left
+    /// enabled or disabled as they were.
+    pub fn minimize_core(&mut self, budget: Option<Duration>) -> Result<UnsatCore> {
+        if !self.is_configured() {
+            return Err(ParkissatError::NotConfigured);
+        }
+
+        let deadline = budget.map(|budget| Instant::now() + budget);
+        let enabled = self.groups.enabled_groups();
+        let result = self.shrink_core(&enabled, deadline);
+
+        for &group in &enabled {
+            self.enable_group(group)?;
+        }
+        result
+    }
+
+    fn shrink_core(&mut self, enabled: &[ClauseGroup], deadline: Option<Instant>) -> Result<UnsatCore> {
+        match self.solve_core(enabled, &[], deadline)? {
+            SolverResult::Sat => return Err(ParkissatError::NoSolution),
+            SolverResult::Unknown => return Err(ParkissatError::Interrupted),
+            SolverResult::Unsat => {}
+        }
+
+        self.shrink_failed_groups(enabled, deadline)
+    }
+
+    /// Deletion-based shrinking of the failed groups of the last UNSAT answer
+    pub(crate) fn shrink_failed_groups(&mut self, enabled: &[ClauseGroup], deadline: Option<Instant>) -> Result<UnsatCore> {
+        let mut core = self.failed_groups()?;
+        let mut necessary = Vec::new();
+        while let Some(group) = core.pop() {
+            let test: Vec<ClauseGroup> = necessary.iter().chain(&core).copied().collect();
+            match self.solve_core(&test, enabled, deadline)? {
+                SolverResult::Sat => necessary.push(group),
+                SolverResult::Unsat => {
+                    // Groups outside the new core are not needed either
+                    let failed = self.failed_groups()?;
+                    core.retain(|group| failed.contains(group));
+                }
+                SolverResult::Unknown => {
+                    necessary.push(group);
+                    necessary.append(&mut core);
+                    necessary.sort_unstable();
+                    return Ok(UnsatCore { groups: necessary, minimal: false });
+                }
+            }
+        }
+
+        necessary.sort_unstable();
+        Ok(UnsatCore { groups: necessary, minimal: true })
+    }
+
+    /// Solve with exactly `active` among `all` enabled, stopping at the deadline
+    pub(crate) fn solve_core(&mut self, active: &[ClauseGroup], all: &[ClauseGroup], deadline: Option<Instant>) -> Result<SolverResult> {
+        for &group in all {
+            self.disable_group(group)?;
+        }
+        for &group in active {
+            self.enable_group(group)?;
+        }
+
+        match deadline {
+            Some(deadline) => self.solve_until(deadline),
+            None => self.solve(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cnf::configured_solver;
+
+    #[test]
+    fn test_minimize_core() {
+        let mut solver = configured_solver();
+        solver.add_clause(&[1, 2]).unwrap();
+
+        let mut groups = Vec::new();
+        for clause in [&[-1][..], &[3], &[-2], &[-1, -3], &[-3, 2]] {
+            let group = solver.new_clause_group().unwrap();
+            solver.add_clause_to_group(group, clause).unwrap();
+            groups.push(group);
+        }
+        solver.disable_group(groups[1]).unwrap();
+
+        // {¬1, ¬2} is the only MUS among the enabled groups
+        let core = solver.minimize_core(None).unwrap();
+        assert_eq!(core, UnsatCore { groups: vec![groups[0], groups[2]], minimal: true });
+        assert_eq!(solver.is_group_enabled(groups[1]), Some(false));
+        assert_eq!(solver.is_group_enabled(groups[4]), Some(true));
+
+        solver.disable_group(groups[2]).unwrap();
+        assert_eq!(solver.minimize_core(None).unwrap_err(), ParkissatError::NoSolution);
+    }
+
+    #[test]
+    fn test_core_of_unsatisfiable_background() {
+        let mut solver = configured_solver();
+        solver.add_clause(&[1]).unwrap();
+        solver.add_clause(&[-1]).unwrap();
+        let group = solver.new_clause_group().unwrap();
+        solver.add_clause_to_group(group, &[2]).unwrap();
+
+        let core = solver.minimize_core(Some(Duration::from_secs(10))).unwrap();
+        assert!(core.groups.is_empty());
+        assert!(core.minimal);
+    }
+}
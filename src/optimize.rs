@@ -0,0 +1,250 @@
+//! Linear objective optimization
+//!
+//! [`ParkissatSolver::optimize`] minimizes or maximizes `Σ weight·lit` over
+//! the models of the solver's clauses. Each step adds the constraint that
+//! the objective must beat a bound, compiled with
+//! [`add_pb_constraint`](crate::encodings::pb::add_pb_constraint) and guarded
+//! by a fresh selector, and solves under the selector as an assumption:
+//!
+//! - [`OptimizeStrategy::Linear`] asks for any improvement on the best model,
+//!   i.e. SAT-UNSAT search. Cheap steps, but one per improvement.
+//! - [`OptimizeStrategy::Binary`] bisects between the best model and the
+//!   best value still possible, so it needs a logarithmic number of steps
+//!   in the objective range, some of which are UNSAT.
+//!
+//! Selectors are disabled by a unit clause after their step, so the solver
+//! keeps its meaning for later solves; only auxiliary variables remain.
+//! With a timeout the search stops early and returns the best model found.
+
+use crate::encodings::pb::{add_pb_constraint, Comparator};
+use crate::encodings::ClauseSink;
+use crate::error::{ParkissatError, Result};
+use crate::types::{Lit, Var};
+use crate::wrapper::{ParkissatSolver, SolverResult};
+use std::time::{Duration, Instant};
+
+/// Whether to look for the smallest or the largest objective value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    /// Smallest value
+    Minimize,
+    /// Largest value
+    Maximize,
+}
+
+/// How the objective bound moves between steps
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OptimizeStrategy {
+    /// Require any improvement on the best model
+    #[default]
+    Linear,
+    /// Bisect the range of values that may still be reached
+    Binary,
+}
+
+/// Options for [`ParkissatSolver::optimize_with`]
+#[derive(Debug, Clone, Default)]
+pub struct OptimizeOptions {
+    /// How the bound moves between steps
+    pub strategy: OptimizeStrategy,
+
+    /// Stop after this long and return the best model so far (default: no limit)
+    ///
+    /// The configured timeout still applies to each step.
+    pub timeout: Option<Duration>,
+}
+
+/// Outcome of [`ParkissatSolver::optimize`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Optimum {
+    /// Objective value of the model
+    pub value: i64,
+
+    /// The best model found, one signed literal per variable the solver had
+    /// before optimizing
+    pub model: Vec<i32>,
+
+    /// Whether no better model exists, rather than the search running out of time
+    pub proven: bool,
+}
+
+/// Adds `¬guard` to every clause, so the clauses only apply while `guard` is assumed
+struct Guarded<'a> {
+    solver: &'a mut ParkissatSolver,
+    guard: Lit,
+}
+
+impl ClauseSink for Guarded<'_> {
+    fn emit_clause(&mut self, lits: &[Lit]) -> Result<()> {
+        let mut clause = Vec::with_capacity(lits.len() + 1);
+        clause.extend_from_slice(lits);
+        clause.push(!self.guard);
+        self.solver.emit_clause(&clause)
+    }
+
+    fn fresh_var(&mut self) -> Result<Var> {
+        self.solver.fresh_var()
+    }
+}
+
+impl ParkissatSolver {
+    /// Find a model with the best value of `Σ weight·lit`
+    ///
+    /// Returns `None` if the clauses are unsatisfiable.
+    pub fn optimize(&mut self, objective: &[(i64, Lit)], direction: Direction) -> Result<Option<Optimum>> {
+        self.optimize_with(objective, direction, &OptimizeOptions::default())
+    }
+
+    /// Find a model with the best objective value, with a strategy or a timeout
+    ///
+    /// Fails with `Interrupted` if the first solve ends without an answer,
+    /// since there is no model to return yet.
+    pub fn optimize_with(&mut self, objective: &[(i64, Lit)], direction: Direction, options: &OptimizeOptions) -> Result<Option<Optimum>> {
+        if !self.is_configured() {
+            return Err(ParkissatError::NotConfigured);
+        }
+
+        // Maximizing is minimizing the negated objective
+        let terms: Vec<(i64, Lit)> = match direction {
+            Direction::Minimize => objective.to_vec(),
+            Direction::Maximize => objective.iter().map(|&(weight, lit)| (-weight, lit)).collect(),
+        };
+
+        let max_var = terms.iter().map(|(_, lit)| lit.var().index() as usize).max().unwrap_or(0);
+        if max_var > self.variable_count() {
+            self.set_variable_count(max_var)?;
+        }
+        let num_vars = self.variable_count();
+        let deadline = options.timeout.map(|timeout| Instant::now() + timeout);
+
+        let mut best = match self.optimize_step(&[], deadline)? {
+            SolverResult::Sat => self.objective_model(&terms, num_vars)?,
+            SolverResult::Unsat => return Ok(None),
+            SolverResult::Unknown => return Err(ParkissatError::Interrupted),
+        };
+
+        // Values below `lower` are known to be out of reach
+        let mut lower: i64 = terms.iter().map(|&(weight, _)| weight.min(0)).sum();
+        let mut proven = true;
+        while lower < best.0 {
+            let bound = match options.strategy {
+                OptimizeStrategy::Linear => best.0 - 1,
+                OptimizeStrategy::Binary => lower + (best.0 - 1 - lower) / 2,
+            };
+
+            let selector = self.fresh_var()?.positive();
+            let mut guarded = Guarded { solver: self, guard: selector };
+            add_pb_constraint(&mut guarded, &terms, Comparator::Le, bound)?;
+
+            let result = self.optimize_step(&[selector.to_dimacs()], deadline)?;
+            match result {
+                SolverResult::Sat => best = self.objective_model(&terms, num_vars)?,
+                SolverResult::Unsat => lower = bound + 1,
+                SolverResult::Unknown => proven = false,
+            }
+            self.emit_clause(&[!selector])?;
+            if !proven {
+                break;
+            }
+        }
+
+        let (value, model) = best;
+        Ok(Some(Optimum {
+            value: match direction {
+                Direction::Minimize => value,
+                Direction::Maximize => -value,
+            },
+            model,
+            proven,
+        }))
+    }
+
+    fn optimize_step(&mut self, assumptions: &[i32], deadline: Option<Instant>) -> Result<SolverResult> {
+        match deadline {
+            Some(deadline) => self.solve_with_assumptions_until(assumptions, deadline),
+            None => self.solve_with_assumptions(assumptions),
+        }
+    }
+
+    /// The objective value of the current model and its first `num_vars` literals
+    fn objective_model(&self, terms: &[(i64, Lit)], num_vars: usize) -> Result<(i64, Vec<i32>)> {
+        let mut model = self.get_model()?;
+        model.truncate(num_vars);
+
+        let mut value = 0;
+        for &(weight, lit) in terms {
+            if self.lit_value(lit)? {
+                value += weight;
+            }
+        }
+        Ok((value, model))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wrapper::SolverConfig;
+
+    fn lit(dimacs: i32) -> Lit {
+        Lit::new(dimacs).unwrap()
+    }
+
+    /// Pick at least two of five items; item `i` costs `i + 1` and is worth `10 - i`
+    fn knapsack() -> ParkissatSolver {
+        let mut solver = ParkissatSolver::new().unwrap();
+        solver.configure(&SolverConfig::default()).unwrap();
+        let items: Vec<Lit> = (1..=5).map(lit).collect();
+        crate::encodings::card::at_least_k(&mut solver, &items, 2, Default::default()).unwrap();
+        // Items 1 and 2 exclude each other
+        solver.add_clause(&[-1, -2]).unwrap();
+        solver
+    }
+
+    #[test]
+    fn test_minimize_and_maximize() {
+        let cost: Vec<(i64, Lit)> = (1..=5).map(|i| (i as i64, lit(i))).collect();
+        for strategy in [OptimizeStrategy::Linear, OptimizeStrategy::Binary] {
+            let options = OptimizeOptions { strategy, ..OptimizeOptions::default() };
+
+            let mut solver = knapsack();
+            let optimum = solver.optimize_with(&cost, Direction::Minimize, &options).unwrap().unwrap();
+            assert_eq!(optimum.value, 1 + 3);
+            assert!(optimum.proven);
+            assert_eq!(optimum.model.len(), 5);
+            assert_eq!(&optimum.model[..3], [1, -2, 3]);
+
+            // Selectors are disabled afterwards, so every model is allowed again
+            assert_eq!(solver.solve_with_assumptions(&[2, 5]).unwrap(), SolverResult::Sat);
+
+            let worth: Vec<(i64, Lit)> = (1..=5).map(|i| (10 - i as i64, lit(i))).collect();
+            let optimum = solver.optimize_with(&worth, Direction::Maximize, &options).unwrap().unwrap();
+            assert_eq!(optimum.value, 9 + 7 + 6 + 5);
+            assert_eq!(optimum.model, [1, -2, 3, 4, 5]);
+        }
+    }
+
+    #[test]
+    fn test_negative_weights_and_unsat() {
+        let mut solver = knapsack();
+        let optimum = solver.optimize(&[(-4, lit(1)), (3, lit(-3)), (1, lit(4))], Direction::Minimize).unwrap().unwrap();
+        assert_eq!(optimum.value, -4);
+        assert!(optimum.proven);
+
+        solver.add_clause(&[1]).unwrap();
+        solver.add_clause(&[-1]).unwrap();
+        assert_eq!(solver.optimize(&[(1, lit(1))], Direction::Minimize).unwrap(), None);
+    }
+
+    #[test]
+    fn test_timeout_keeps_best_model() {
+        let mut solver = knapsack();
+        let options = OptimizeOptions { timeout: Some(Duration::ZERO), ..OptimizeOptions::default() };
+        // An expired deadline may stop even the first solve
+        match solver.optimize_with(&[(1, lit(5))], Direction::Minimize, &options) {
+            Ok(Some(optimum)) => assert!(optimum.value == 0 || !optimum.proven),
+            Err(error) => assert_eq!(error, ParkissatError::Interrupted),
+            Ok(None) => panic!("the clauses are satisfiable"),
+        }
+    }
+}
@@ -0,0 +1,72 @@
+//! Raw kissat options
+//!
+//! kissat_mab has many tunables (restart intervals, elimination bounds, walk
+//! effort, ...) that [`SolverConfig`](crate::SolverConfig) does not cover.
+//! [`ParkissatSolver::set_option`] forwards a named option to every worker
+//! and re-applies it whenever workers are rebuilt. Option names are those
+//! listed by `kissat --help`, without the leading dashes.
+
+use crate::error::{ParkissatError, Result};
+use crate::ffi;
+use crate::wrapper::ParkissatSolver;
+use std::ffi::CString;
+use std::os::raw::c_int;
+
+impl ParkissatSolver {
+    /// Set a kissat option on every worker
+    ///
+    /// Options override the portfolio defaults chosen by `configure` and stay
+    /// in effect for all later solves. Values outside an option's range are
+    /// clamped by kissat.
+    pub fn set_option(&mut self, name: &str, value: i64) -> Result<()> {
+        if !self.is_configured() {
+            return Err(ParkissatError::NotConfigured);
+        }
+
+        let value = c_int::try_from(value).map_err(|_| {
+            ParkissatError::InvalidConfiguration(format!("Value {} for option `{}` is out of range", value, name))
+        })?;
+        let c_name = CString::new(name)?;
+
+        let known = unsafe { ffi::parkissat_set_option(self.solver, c_name.as_ptr(), value) };
+
+        if !known {
+            // The C++ layer names the unknown option
+            self.check_error()?;
+            return Err(ParkissatError::InvalidConfiguration(format!("Unknown option `{}`", name)));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wrapper::{SolverConfig, SolverResult};
+
+    #[test]
+    fn test_set_option() {
+        let mut solver = ParkissatSolver::new().unwrap();
+        assert_eq!(solver.set_option("restartint", 10).unwrap_err(), ParkissatError::NotConfigured);
+
+        solver.configure(&SolverConfig::default()).unwrap();
+        solver.set_option("restartint", 10).unwrap();
+        solver.set_option("walkeffort", 0).unwrap();
+
+        solver.add_clause(&[1, 2]).unwrap();
+        solver.add_clause(&[-1]).unwrap();
+        assert_eq!(solver.solve().unwrap(), SolverResult::Sat);
+        assert_eq!(solver.solve().unwrap(), SolverResult::Sat);
+    }
+
+    #[test]
+    fn test_invalid_options() {
+        let mut solver = ParkissatSolver::new().unwrap();
+        solver.configure(&SolverConfig::default()).unwrap();
+
+        assert!(matches!(solver.set_option("no-such-option", 1), Err(ParkissatError::InvalidConfiguration(_))));
+        assert!(matches!(solver.set_option("restartint", i64::MAX), Err(ParkissatError::InvalidConfiguration(_))));
+        assert!(solver.set_option("restart\0int", 1).is_err());
+    }
+}
@@ -0,0 +1,206 @@
+//! Redirecting solver output
+//!
+//! With `SolverConfig::verbosity > 0` kissat prints its log straight to the
+//! process's standard output. A log sink set with
+//! [`ParkissatSolver::set_log_callback`] or
+//! [`ParkissatSolver::set_log_writer`] receives those lines instead: while the
+//! solver solves, file descriptor 1 is redirected into a pipe and every line is
+//! handed to the sink.
+//!
+//! The redirection is process-wide. Anything else written to stdout during
+//! such a solve is captured too, and solves of different solvers that capture
+//! output run one at a time.
+
+use crate::ffi;
+use crate::wrapper::ParkissatSolver;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Write};
+use std::os::fd::FromRawFd;
+use std::os::raw::c_int;
+use std::ptr;
+use std::sync::Mutex;
+use std::thread::{self, JoinHandle};
+
+/// Receives solver output one line at a time, without the line break
+pub(crate) type LogSink = Box<dyn FnMut(&str) + Send>;
+
+/// Held while stdout is redirected
+static CAPTURE: Mutex<()> = Mutex::new(());
+
+/// Stdout redirected into a pipe drained by a reader thread
+struct Redirect {
+    saved_stdout: c_int,
+    reader: JoinHandle<LogSink>,
+}
+
+impl Redirect {
+    fn start(mut sink: LogSink) -> std::result::Result<Self, LogSink> {
+        flush_stdout();
+
+        let mut fds = [0 as c_int; 2];
+        if unsafe { libc::pipe(fds.as_mut_ptr()) } != 0 {
+            return Err(sink);
+        }
+
+        let saved_stdout = unsafe { libc::dup(libc::STDOUT_FILENO) };
+        if saved_stdout < 0 || unsafe { libc::dup2(fds[1], libc::STDOUT_FILENO) } < 0 {
+            unsafe {
+                if saved_stdout >= 0 {
+                    libc::close(saved_stdout);
+                }
+                libc::close(fds[0]);
+                libc::close(fds[1]);
+            }
+            return Err(sink);
+        }
+        // Stdout is now the only write end, so restoring it ends the stream
+        unsafe {
+            libc::close(fds[1]);
+        }
+
+        let pipe = unsafe { File::from_raw_fd(fds[0]) };
+        let reader = thread::spawn(move || {
+            let mut pipe = BufReader::new(pipe);
+            let mut line = Vec::new();
+            while matches!(pipe.read_until(b'\n', &mut line), Ok(read) if read > 0) {
+                if line.ends_with(b"\n") {
+                    line.pop();
+                }
+                sink(&String::from_utf8_lossy(&line));
+                line.clear();
+            }
+            sink
+        });
+
+        Ok(Self { saved_stdout, reader })
+    }
+
+    fn finish(self) -> LogSink {
+        flush_stdout();
+        unsafe {
+            libc::dup2(self.saved_stdout, libc::STDOUT_FILENO);
+            libc::close(self.saved_stdout);
+        }
+
+        match self.reader.join() {
+            Ok(sink) => sink,
+            Err(panic) => std::panic::resume_unwind(panic),
+        }
+    }
+}
+
+/// Flush both Rust's and C's stdout buffers
+fn flush_stdout() {
+    let _ = io::stdout().flush();
+    unsafe {
+        libc::fflush(ptr::null_mut());
+    }
+}
+
+impl ParkissatSolver {
+    /// Send solver output to a callback instead of stdout
+    ///
+    /// The callback runs on a background thread, once per line, during each
+    /// solve of a solver configured with `verbosity > 0`.
+    pub fn set_log_callback<F>(&mut self, callback: F)
+    where
+        F: FnMut(&str) + Send + 'static,
+    {
+        self.log_sink = Some(Box::new(callback));
+    }
+
+    /// Write solver output to `writer` instead of stdout
+    pub fn set_log_writer<W>(&mut self, mut writer: W)
+    where
+        W: Write + Send + 'static,
+    {
+        self.set_log_callback(move |line| {
+            let _ = writeln!(writer, "{}", line);
+        });
+    }
+
+    /// Forward solver output to the `log` crate at info level (target `parkissat`)
+    #[cfg(feature = "log")]
+    pub fn set_log_to_log_crate(&mut self) {
+        self.set_log_callback(|line| log::info!(target: "parkissat", "{}", line));
+    }
+
+    /// Let solver output go to stdout again
+    pub fn clear_log_sink(&mut self) {
+        self.log_sink = None;
+    }
+
+    /// Run a solve call, capturing its output into the log sink if there is one
+    pub(crate) fn capture_output<T>(&mut self, solve: impl FnOnce(*mut ffi::ParkissatSolver) -> T) -> T {
+        let sink = match self.log_sink.take() {
+            Some(sink) if self.verbosity > 0 => sink,
+            sink => {
+                self.log_sink = sink;
+                return solve(self.solver);
+            }
+        };
+
+        let _capture = CAPTURE.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        match Redirect::start(sink) {
+            Ok(redirect) => {
+                let result = solve(self.solver);
+                self.log_sink = Some(redirect.finish());
+                result
+            }
+            // Without a pipe the output stays on stdout
+            Err(sink) => {
+                self.log_sink = Some(sink);
+                solve(self.solver)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wrapper::{SolverConfig, SolverResult};
+    use std::sync::Arc;
+
+    fn collecting(solver: &mut ParkissatSolver) -> Arc<Mutex<Vec<String>>> {
+        let lines = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&lines);
+        solver.set_log_callback(move |line| sink.lock().unwrap().push(line.to_string()));
+        lines
+    }
+
+    #[test]
+    fn test_log_callback_captures_output() {
+        let mut solver = ParkissatSolver::new().unwrap();
+        let config = SolverConfig {
+            verbosity: 1,
+            ..SolverConfig::default()
+        };
+        solver.configure(&config).unwrap();
+        let lines = collecting(&mut solver);
+
+        solver.add_clause(&[1, 2]).unwrap();
+        assert_eq!(solver.solve().unwrap(), SolverResult::Sat);
+        let first = lines.lock().unwrap().len();
+        assert!(first > 0);
+        assert!(lines.lock().unwrap().iter().all(|line| !line.ends_with('\n')));
+
+        // The sink is kept for later solves
+        assert_eq!(solver.solve_with_assumptions(&[-1]).unwrap(), SolverResult::Sat);
+        assert!(lines.lock().unwrap().len() > first);
+
+        solver.clear_log_sink();
+        assert_eq!(solver.solve().unwrap(), SolverResult::Sat);
+    }
+
+    #[test]
+    fn test_quiet_solver_is_not_captured() {
+        let mut solver = ParkissatSolver::new().unwrap();
+        solver.configure(&SolverConfig::default()).unwrap();
+        let lines = collecting(&mut solver);
+
+        solver.add_clause(&[1]).unwrap();
+        assert_eq!(solver.solve().unwrap(), SolverResult::Sat);
+        assert!(lines.lock().unwrap().is_empty());
+    }
+}
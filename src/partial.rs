@@ -0,0 +1,110 @@
+//! Partial answers of unfinished solves
+//!
+//! When a solve ends without an answer, e.g. at its timeout or budget, each
+//! worker still holds an assignment: its trail where variables are assigned,
+//! and otherwise its best phases, which record the largest trail it reached
+//! or the optimum of its last local-search phase. [`ParkissatSolver::best_partial_assignment`]
+//! returns the worker assignment that satisfies the most clauses, for
+//! anytime use where a good partial answer beats none.
+
+use crate::error::Result;
+use crate::ffi;
+use crate::wrapper::ParkissatSolver;
+use std::os::raw::c_int;
+
+/// An assignment left by an unfinished solve
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartialAssignment {
+    /// Assigned literals, ordered by variable
+    pub literals: Vec<i32>,
+    /// Clauses with at least one true literal
+    pub satisfied_clauses: usize,
+    /// Clauses given to the solver
+    pub total_clauses: usize,
+}
+
+impl PartialAssignment {
+    /// Whether every clause is satisfied, i.e. the assignment is a model
+    pub fn satisfies_all(&self) -> bool {
+        self.satisfied_clauses == self.total_clauses
+    }
+}
+
+impl ParkissatSolver {
+    /// Best assignment of the workers after the last solve answered `Unknown`
+    ///
+    /// `None` after SAT or UNSAT answers, before the first solve, and when
+    /// the solve was interrupted before it started. DIMACS files passed to
+    /// [`load_dimacs`](Self::load_dimacs) are parsed again to count the
+    /// satisfied clauses.
+    pub fn best_partial_assignment(&self) -> Result<Option<PartialAssignment>> {
+        let workers = unsafe { ffi::parkissat_get_partial_workers(self.solver) };
+        if workers <= 0 {
+            return Ok(None);
+        }
+
+        let clauses = self.all_clauses()?;
+        let mut best: Option<PartialAssignment> = None;
+        for worker in 0..workers {
+            let size = unsafe {
+                ffi::parkissat_get_partial_assignment(self.solver, worker, std::ptr::null_mut(), 0)
+            };
+            let mut values = vec![0i8; size.max(0) as usize];
+            unsafe {
+                ffi::parkissat_get_partial_assignment(self.solver, worker, values.as_mut_ptr(), size as c_int);
+            }
+
+            let is_true = |lit: i32| {
+                let value = values.get(lit.unsigned_abs() as usize).copied().unwrap_or(0);
+                if lit < 0 { value < 0 } else { value > 0 }
+            };
+            let satisfied = clauses.iter().filter(|clause| clause.iter().any(|&lit| is_true(lit))).count();
+            if best.as_ref().is_some_and(|best| best.satisfied_clauses >= satisfied) {
+                continue;
+            }
+
+            let literals = values.iter().enumerate()
+                .filter(|&(_, &value)| value != 0)
+                .map(|(var, &value)| if value > 0 { var as i32 } else { -(var as i32) })
+                .collect();
+            best = Some(PartialAssignment {
+                literals,
+                satisfied_clauses: satisfied,
+                total_clauses: clauses.len(),
+            });
+        }
+
+        Ok(best)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cnf::pigeonhole;
+    use crate::limits::SolverLimits;
+    use crate::wrapper::{SolverConfig, SolverResult};
+
+    #[test]
+    fn test_partial_assignment_after_budget() {
+        let mut solver = ParkissatSolver::new().unwrap();
+        solver.configure(&SolverConfig { num_threads: 2, ..SolverConfig::default() }).unwrap();
+        assert_eq!(solver.best_partial_assignment().unwrap(), None);
+
+        // Far beyond 200 conflicts
+        solver.add_formula(&pigeonhole(10, 9)).unwrap();
+
+        assert_eq!(solver.solve_limited(&SolverLimits::new().conflicts(200)).unwrap(), SolverResult::Unknown);
+        let partial = solver.best_partial_assignment().unwrap().unwrap();
+        assert_eq!(partial.total_clauses, 10 + 9 * 45);
+        // Every clause but one can be satisfied, so a good assignment comes close
+        assert!(partial.satisfied_clauses > partial.total_clauses / 2);
+        assert!(!partial.satisfies_all());
+        assert!(partial.literals.iter().all(|&lit| lit != 0 && lit.abs() <= 90));
+        assert!(partial.literals.windows(2).all(|pair| pair[0].abs() < pair[1].abs()));
+
+        // A definite answer leaves no partial assignment behind
+        assert_eq!(solver.solve_with_assumptions(&[1, 10]).unwrap(), SolverResult::Unsat);
+        assert_eq!(solver.best_partial_assignment().unwrap(), None);
+    }
+}
@@ -0,0 +1,172 @@
+//! Phase hints
+//!
+//! A phase is the polarity the solver tries first when it branches on a
+//! variable. Setting phases from a known-good (partial) assignment, such as
+//! the model of a previous similar instance, steers the search toward it
+//! without constraining the formula. Phases are kept across solves until
+//! they are cleared.
+//!
+//! Variables without a hint start from the polarity chosen by
+//! [`SolverConfig::initial_phase`](crate::SolverConfig::initial_phase).
+
+use crate::error::{ParkissatError, Result};
+use crate::ffi;
+use crate::model::Model;
+use crate::types::Lit;
+use crate::wrapper::ParkissatSolver;
+use std::os::raw::c_int;
+
+impl ParkissatSolver {
+    /// Prefer the polarity of `literal` when branching on its variable
+    pub fn set_phase(&mut self, literal: i32) -> Result<()> {
+        if !self.is_configured() {
+            return Err(ParkissatError::NotConfigured);
+        }
+
+        if literal == 0 || literal == i32::MIN {
+            return Err(ParkissatError::InvalidVariable(literal));
+        }
+
+        unsafe {
+            ffi::parkissat_set_phase(self.solver, literal);
+        }
+
+        self.check_error()
+    }
+
+    /// Prefer the polarity of a typed literal when branching on its variable
+    pub fn set_phase_lit(&mut self, lit: Lit) -> Result<()> {
+        self.set_phase(lit.to_dimacs())
+    }
+
+    /// Set phases from a (partial) assignment of signed literals
+    ///
+    /// A model returned by [`get_model`](Self::get_model) can be passed
+    /// directly to warm-start a related instance.
+    pub fn set_initial_phases(&mut self, literals: &[i32]) -> Result<()> {
+        if let Some(&invalid) = literals.iter().find(|&&lit| lit == 0 || lit == i32::MIN) {
+            return Err(ParkissatError::InvalidVariable(invalid));
+        }
+
+        for &lit in literals {
+            self.set_phase(lit)?;
+        }
+
+        Ok(())
+    }
+
+    /// Seed every worker's saved phases from a previous model for the next solve
+    ///
+    /// Unlike [`set_initial_phases`](Self::set_initial_phases), the seed
+    /// only applies to the next solve and is then forgotten, and workers
+    /// move away from it as the search learns. The model may be partial or
+    /// violate the current formula, e.g. the last solution of an
+    /// optimization loop before the bound was tightened. Phase hints take
+    /// precedence; a second call replaces the seed.
+    pub fn set_warm_start(&mut self, model: &Model) -> Result<()> {
+        if !self.is_configured() {
+            return Err(ParkissatError::NotConfigured);
+        }
+
+        let literals = model.to_dimacs();
+        unsafe {
+            ffi::parkissat_set_warm_start(self.solver, literals.as_ptr(), literals.len() as c_int);
+        }
+
+        self.check_error()
+    }
+
+    /// Forget every phase set so far
+    pub fn clear_phases(&mut self) -> Result<()> {
+        if !self.is_configured() {
+            return Err(ParkissatError::NotConfigured);
+        }
+
+        unsafe {
+            ffi::parkissat_clear_phases(self.solver);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wrapper::{PhaseMode, SolverConfig, SolverResult};
+
+    #[test]
+    fn test_phases_guide_unconstrained_variables() {
+        let mut solver = ParkissatSolver::new().unwrap();
+        assert_eq!(solver.set_phase(1).unwrap_err(), ParkissatError::NotConfigured);
+
+        solver.configure(&SolverConfig::default()).unwrap();
+        solver.add_clause(&[1, 2, 3, 4]).unwrap();
+
+        let hint = [-1, 2, -3, 4];
+        solver.set_initial_phases(&hint).unwrap();
+        assert_eq!(solver.solve().unwrap(), SolverResult::Sat);
+        assert_eq!(solver.get_model().unwrap(), hint);
+
+        // Phases persist across solves and can be changed individually
+        solver.set_phase_lit(Lit::new(3).unwrap()).unwrap();
+        assert_eq!(solver.solve().unwrap(), SolverResult::Sat);
+        assert_eq!(solver.get_model().unwrap(), [-1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_initial_phase_modes() {
+        let solve = |initial_phase| {
+            let mut solver = ParkissatSolver::new().unwrap();
+            solver.configure(&SolverConfig { initial_phase, ..SolverConfig::default() }).unwrap();
+            solver.add_clause(&[1, 2, 3, 4]).unwrap();
+            assert_eq!(solver.solve().unwrap(), SolverResult::Sat);
+            solver.get_model().unwrap()
+        };
+
+        assert_eq!(solve(PhaseMode::AllTrue), [1, 2, 3, 4]);
+        assert_eq!(solve(PhaseMode::AllFalse), [-1, -2, -3, 4]);
+        assert_eq!(solve(PhaseMode::Random(42)), solve(PhaseMode::Random(42)));
+
+        // Saved phases carry the last assignment into the next solve
+        let mut solver = ParkissatSolver::new().unwrap();
+        solver.configure(&SolverConfig { initial_phase: PhaseMode::Saved, ..SolverConfig::default() }).unwrap();
+        solver.add_clause(&[1, 2, 3, 4]).unwrap();
+        solver.set_initial_phases(&[-1, 2, -3, 4]).unwrap();
+        assert_eq!(solver.solve().unwrap(), SolverResult::Sat);
+        solver.clear_phases().unwrap();
+        solver.add_clause(&[-2, 5]).unwrap();
+        assert_eq!(solver.solve().unwrap(), SolverResult::Sat);
+        assert_eq!(solver.get_model().unwrap(), [-1, 2, -3, 4, 5]);
+    }
+
+    #[test]
+    fn test_warm_start_applies_once() {
+        let mut solver = ParkissatSolver::new().unwrap();
+        assert_eq!(solver.set_warm_start(&Model::from_dimacs(&[1])).unwrap_err(), ParkissatError::NotConfigured);
+
+        solver.configure(&SolverConfig::default()).unwrap();
+        solver.add_clause(&[1, 2, 3, 4]).unwrap();
+        solver.add_clause(&[-1, -4]).unwrap();
+
+        // A near-miss assignment (it violates the second clause) still seeds the search
+        solver.set_warm_start(&Model::from_dimacs(&[-1, 2, -3, 4, 5])).unwrap();
+        solver.set_phase(3).unwrap();
+        assert_eq!(solver.solve().unwrap(), SolverResult::Sat);
+        assert_eq!(solver.get_model().unwrap(), [-1, 2, 3, 4]);
+
+        // The next solve starts from the configured initial phase again
+        solver.clear_phases().unwrap();
+        assert_eq!(solver.solve().unwrap(), SolverResult::Sat);
+        assert_eq!(solver.get_model().unwrap(), [1, 2, 3, -4]);
+    }
+
+    #[test]
+    fn test_invalid_phase() {
+        let mut solver = ParkissatSolver::new().unwrap();
+        solver.configure(&SolverConfig::default()).unwrap();
+
+        assert_eq!(solver.set_initial_phases(&[1, 0]).unwrap_err(), ParkissatError::InvalidVariable(0));
+        solver.clear_phases().unwrap();
+    }
+}
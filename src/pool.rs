@@ -0,0 +1,253 @@
+//! A pool of solvers for many independent queries
+//!
+//! [`SolverPool`] owns a fixed number of worker threads, each with its own
+//! configured [`ParkissatSolver`]s, and hands out queries in submission order
+//! to whichever worker is free. A query is either a whole [`CnfFormula`],
+//! solved on a solver that is [`reset`](ParkissatSolver::reset) between
+//! formulas, or a set of assumptions, solved incrementally against the
+//! pool's base formula that every worker loads once. [`submit`] returns a
+//! [`ResultHandle`] right away, so thousands of small queries can be queued
+//! without managing threads or moving solvers around.
+//!
+//! [`submit`]: SolverPool::submit
+
+use crate::cnf::CnfFormula;
+use crate::error::{ParkissatError, Result};
+use crate::wrapper::{ParkissatSolver, SolverConfig, SolverResult};
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+
+/// A query for a [`SolverPool`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Query {
+    /// Solve this formula on its own
+    Formula(CnfFormula),
+    /// Solve the pool's base formula under these assumptions
+    Assumptions(Vec<i32>),
+}
+
+impl From<CnfFormula> for Query {
+    fn from(formula: CnfFormula) -> Self {
+        Query::Formula(formula)
+    }
+}
+
+impl From<Vec<i32>> for Query {
+    fn from(assumptions: Vec<i32>) -> Self {
+        Query::Assumptions(assumptions)
+    }
+}
+
+impl From<&[i32]> for Query {
+    fn from(assumptions: &[i32]) -> Self {
+        Query::Assumptions(assumptions.to_vec())
+    }
+}
+
+/// Answer to a pool query
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PoolAnswer {
+    /// The solver's answer
+    pub result: SolverResult,
+    /// The model, if the answer is SAT
+    pub model: Option<Vec<i32>>,
+}
+
+/// Pending answer to a query submitted to a [`SolverPool`]
+#[derive(Debug)]
+pub struct ResultHandle {
+    receiver: mpsc::Receiver<Result<PoolAnswer>>,
+}
+
+impl ResultHandle {
+    /// Wait for the answer
+    pub fn wait(self) -> Result<PoolAnswer> {
+        self.receiver.recv().unwrap_or_else(|_| Err(ParkissatError::InternalError(
+            "Pool worker stopped before answering".to_string()
+        )))
+    }
+
+    /// Get the answer if it is ready, without blocking
+    pub fn try_result(&self) -> Option<Result<PoolAnswer>> {
+        match self.receiver.try_recv() {
+            Ok(answer) => Some(answer),
+            Err(mpsc::TryRecvError::Empty) => None,
+            Err(mpsc::TryRecvError::Disconnected) => Some(Err(ParkissatError::InternalError(
+                "Pool worker stopped before answering".to_string()
+            ))),
+        }
+    }
+}
+
+type Job = (Query, mpsc::Sender<Result<PoolAnswer>>);
+
+/// Worker threads solving submitted queries
+///
+/// Dropping the pool waits for the queries already submitted to be answered.
+pub struct SolverPool {
+    jobs: Option<mpsc::Sender<Job>>,
+    workers: Vec<JoinHandle<()>>,
+}
+
+impl SolverPool {
+    /// Create a pool of `workers` threads with an empty base formula
+    pub fn new(workers: usize, config: &SolverConfig) -> Result<Self> {
+        Self::with_formula(workers, config, &CnfFormula::new())
+    }
+
+    /// Create a pool whose assumption queries are solved against `base`
+    pub fn with_formula(workers: usize, config: &SolverConfig, base: &CnfFormula) -> Result<Self> {
+        if workers == 0 {
+            return Err(ParkissatError::InvalidConfiguration(
+                "Solver pool needs at least one worker".to_string()
+            ));
+        }
+
+        let (jobs, queue) = mpsc::channel::<Job>();
+        let queue = Arc::new(Mutex::new(queue));
+        let workers = (0..workers)
+            .map(|_| {
+                let queue = Arc::clone(&queue);
+                let config = config.clone();
+                let base = base.clone();
+                thread::spawn(move || work(&queue, &config, &base))
+            })
+            .collect();
+
+        Ok(Self {
+            jobs: Some(jobs),
+            workers,
+        })
+    }
+
+    /// Queue a formula or a set of assumptions and return a handle to its answer
+    pub fn submit<Q: Into<Query>>(&self, query: Q) -> ResultHandle {
+        let (sender, receiver) = mpsc::channel();
+        if let Some(jobs) = &self.jobs {
+            // A send only fails once every worker is gone; the handle reports it
+            let _ = jobs.send((query.into(), sender));
+        }
+        ResultHandle { receiver }
+    }
+
+    /// Number of worker threads
+    pub fn workers(&self) -> usize {
+        self.workers.len()
+    }
+}
+
+impl Drop for SolverPool {
+    fn drop(&mut self) {
+        drop(self.jobs.take());
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+    }
+}
+
+impl std::fmt::Debug for SolverPool {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SolverPool")
+            .field("workers", &self.workers.len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Worker loop: answer jobs until the pool is dropped
+fn work(queue: &Mutex<mpsc::Receiver<Job>>, config: &SolverConfig, base: &CnfFormula) {
+    let mut incremental: Option<ParkissatSolver> = None;
+    let mut scratch: Option<ParkissatSolver> = None;
+
+    loop {
+        let job = queue.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).recv();
+        let Ok((query, answer)) = job else {
+            return;
+        };
+
+        let outcome = match query {
+            Query::Assumptions(assumptions) => {
+                solver_for(&mut incremental, config, Some(base))
+                    .and_then(|solver| answer_query(solver, &assumptions))
+            }
+            Query::Formula(formula) => {
+                solver_for(&mut scratch, config, None)
+                    .and_then(|solver| {
+                        solver.reset()?;
+                        solver.add_formula(&formula)?;
+                        answer_query(solver, &[])
+                    })
+            }
+        };
+        let _ = answer.send(outcome);
+    }
+}
+
+/// The worker's solver in `slot`, created (with `base` loaded) on first use
+fn solver_for<'a>(slot: &'a mut Option<ParkissatSolver>, config: &SolverConfig, base: Option<&CnfFormula>) -> Result<&'a mut ParkissatSolver> {
+    if slot.is_none() {
+        let mut solver = ParkissatSolver::new()?;
+        solver.configure(config)?;
+        if let Some(base) = base {
+            solver.add_formula(base)?;
+        }
+        *slot = Some(solver);
+    }
+    Ok(slot.as_mut().expect("solver was just created"))
+}
+
+fn answer_query(solver: &mut ParkissatSolver, assumptions: &[i32]) -> Result<PoolAnswer> {
+    let result = solver.solve_with_assumptions(assumptions)?;
+    let model = match result {
+        SolverResult::Sat => Some(solver.get_model()?),
+        _ => None,
+    };
+    Ok(PoolAnswer { result, model })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pool_formulas() {
+        let pool = SolverPool::new(3, &SolverConfig::default()).unwrap();
+        assert_eq!(pool.workers(), 3);
+
+        let handles: Vec<ResultHandle> = (1..=20)
+            .map(|var| {
+                let mut formula = CnfFormula::new();
+                formula.add_clause(&[var]).unwrap();
+                if var % 2 == 0 {
+                    formula.add_clause(&[-var]).unwrap();
+                }
+                pool.submit(formula)
+            })
+            .collect();
+
+        for (var, handle) in (1..=20).zip(handles) {
+            let answer = handle.wait().unwrap();
+            if var % 2 == 0 {
+                assert_eq!(answer.result, SolverResult::Unsat);
+            } else {
+                assert_eq!(answer.result, SolverResult::Sat);
+                assert_eq!(answer.model.unwrap()[var as usize - 1], var);
+            }
+        }
+    }
+
+    #[test]
+    fn test_pool_assumptions() {
+        let mut base = CnfFormula::new();
+        base.add_clause(&[1, 2]).unwrap();
+        base.add_clause(&[-1, -2]).unwrap();
+        let pool = SolverPool::with_formula(2, &SolverConfig::default(), &base).unwrap();
+
+        let sat = pool.submit(vec![1]);
+        let unsat = pool.submit(&[1, 2][..]);
+        assert_eq!(sat.wait().unwrap().model, Some(vec![1, -2]));
+        assert_eq!(unsat.wait().unwrap().result, SolverResult::Unsat);
+
+        assert!(SolverPool::new(0, &SolverConfig::default()).is_err());
+    }
+}
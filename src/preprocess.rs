@@ -0,0 +1,483 @@
+//! Standalone preprocessing
+//!
+//! [`preprocess`] simplifies a [`CnfFormula`] once, so that the reduced
+//! instance can be handed to any number of downstream solvers. Kissat only
+//! simplifies inside its workers and never exports the result, so the pass
+//! runs in Rust: unit propagation, removal of duplicate literals and
+//! tautologies, subsumption, and bounded variable elimination (a variable is
+//! replaced by its resolvents when that does not add clauses).
+//!
+//! The remaining variables are renumbered densely. [`SimplifiedFormula`]
+//! keeps the variable map and the clauses removed by elimination, which
+//! [`SimplifiedFormula::reconstruct`] uses to turn a model of the simplified
+//! formula into a model of the original one. Frozen variables are never
+//! eliminated and always keep a simplified counterpart unless they are fixed,
+//! so they can still be used in assumptions.
+
+use crate::cnf::CnfFormula;
+use crate::error::{ParkissatError, Result};
+
+/// Options for [`preprocess_with`]
+#[derive(Debug, Clone)]
+pub struct PreprocessOptions {
+    /// Run bounded variable elimination (default: true)
+    pub eliminate: bool,
+
+    /// Only eliminate variables occurring in at most this many clauses (default: 16)
+    pub max_occurrences: usize,
+
+    /// Only eliminate variables whose resolvents have at most this many literals (default: 16)
+    pub max_resolvent_size: usize,
+
+    /// Variables that must not be eliminated (default: none)
+    pub frozen: Vec<i32>,
+}
+
+impl Default for PreprocessOptions {
+    fn default() -> Self {
+        Self {
+            eliminate: true,
+            max_occurrences: 16,
+            max_resolvent_size: 16,
+            frozen: Vec::new(),
+        }
+    }
+}
+
+/// A simplified formula plus what is needed to map its models back
+#[derive(Debug, Clone)]
+pub struct SimplifiedFormula {
+    formula: CnfFormula,
+    /// Original variable of simplified variable `i + 1` at index `i`
+    variables: Vec<i32>,
+    /// Original variables fixed by propagation, as signed literals
+    fixed: Vec<i32>,
+    /// Clauses removed by elimination with the literal that satisfies them, in removal order
+    eliminated: Vec<(i32, Vec<i32>)>,
+    num_eliminated: usize,
+    original_vars: usize,
+    unsat: bool,
+}
+
+impl SimplifiedFormula {
+    /// The simplified formula
+    ///
+    /// An unsatisfiable formula simplifies to the contradiction `1 ∧ ¬1`
+    /// over a variable without original counterpart.
+    pub fn formula(&self) -> &CnfFormula {
+        &self.formula
+    }
+
+    /// Whether preprocessing alone refuted the formula
+    pub fn is_unsat(&self) -> bool {
+        self.unsat
+    }
+
+    /// Original variable of a variable of the simplified formula
+    pub fn original_variable(&self, variable: i32) -> Option<i32> {
+        let index = usize::try_from(variable).ok()?.checked_sub(1)?;
+        self.variables.get(index).copied()
+    }
+
+    /// Variable of the simplified formula standing for an original variable
+    ///
+    /// `None` if the variable was fixed, eliminated, or occurs in no clause.
+    pub fn simplified_variable(&self, variable: i32) -> Option<i32> {
+        self.variables.iter().position(|&var| var == variable).map(|index| index as i32 + 1)
+    }
+
+    /// Original literals fixed by unit propagation
+    pub fn fixed_literals(&self) -> &[i32] {
+        &self.fixed
+    }
+
+    /// Number of variables removed by bounded variable elimination
+    pub fn eliminated_variables(&self) -> usize {
+        self.num_eliminated
+    }
+
+    /// Turn a model of the simplified formula into one of the original formula
+    ///
+    /// `model` lists signed literals of the simplified formula, as returned by
+    /// [`ParkissatSolver::get_model`](crate::ParkissatSolver::get_model);
+    /// unlisted variables count as false. The result assigns every original
+    /// variable, in variable order.
+    pub fn reconstruct(&self, model: &[i32]) -> Result<Vec<i32>> {
+        let mut values = vec![false; self.original_vars + 1];
+        for &lit in &self.fixed {
+            values[lit.unsigned_abs() as usize] = lit > 0;
+        }
+        for &lit in model {
+            let var = self.original_variable(lit.abs())
+                .ok_or(ParkissatError::InvalidVariable(lit))?;
+            values[var as usize] = lit > 0;
+        }
+
+        // Walking the eliminated clauses backwards, flipping the witness of
+        // each falsified one, satisfies all of them
+        for (witness, clause) in self.eliminated.iter().rev() {
+            if !clause.iter().any(|&lit| values[lit.unsigned_abs() as usize] == (lit > 0)) {
+                values[witness.unsigned_abs() as usize] = *witness > 0;
+            }
+        }
+
+        Ok((1..=self.original_vars as i32)
+            .map(|var| if values[var as usize] { var } else { -var })
+            .collect())
+    }
+}
+
+/// Simplify a formula with the default options
+pub fn preprocess(formula: &CnfFormula) -> SimplifiedFormula {
+    preprocess_with(formula, &PreprocessOptions::default())
+        .expect("default preprocessing options are valid")
+}
+
+/// Simplify a formula
+pub fn preprocess_with(formula: &CnfFormula, options: &PreprocessOptions) -> Result<SimplifiedFormula> {
+    let num_vars = formula.num_vars();
+    let mut simplifier = Simplifier {
+        clauses: Vec::new(),
+        occurrences: vec![Vec::new(); 2 * num_vars + 2],
+        values: vec![0; num_vars + 1],
+        frozen: vec![false; num_vars + 1],
+        eliminated: vec![false; num_vars + 1],
+        units: Vec::new(),
+        fixed: Vec::new(),
+        stack: Vec::new(),
+        inconsistent: false,
+    };
+
+    for &var in &options.frozen {
+        if var <= 0 || var as usize > num_vars {
+            return Err(ParkissatError::InvalidVariable(var));
+        }
+        simplifier.frozen[var as usize] = true;
+    }
+
+    for clause in formula.iter() {
+        simplifier.add_clause(clause.to_vec());
+    }
+    simplifier.propagate();
+    simplifier.subsume();
+    if options.eliminate {
+        simplifier.eliminate(options);
+    }
+
+    Ok(simplifier.finish(num_vars))
+}
+
+fn code(lit: i32) -> usize {
+    2 * lit.unsigned_abs() as usize + usize::from(lit < 0)
+}
+
+fn is_tautology(sorted: &[i32]) -> bool {
+    sorted.windows(2).any(|pair| pair[0] == -pair[1])
+}
+
+/// Clause database with occurrence lists; deleted clauses are left empty
+struct Simplifier {
+    clauses: Vec<Vec<i32>>,
+    /// Clauses containing a literal, indexed by `code(lit)`; may hold stale entries
+    occurrences: Vec<Vec<usize>>,
+    /// 1 = true, -1 = false, 0 = unassigned, indexed by variable
+    values: Vec<i8>,
+    frozen: Vec<bool>,
+    eliminated: Vec<bool>,
+    /// Assigned literals not yet propagated
+    units: Vec<i32>,
+    fixed: Vec<i32>,
+    stack: Vec<(i32, Vec<i32>)>,
+    inconsistent: bool,
+}
+
+impl Simplifier {
+    fn value(&self, lit: i32) -> i8 {
+        let value = self.values[lit.unsigned_abs() as usize];
+        if lit < 0 { -value } else { value }
+    }
+
+    fn assign(&mut self, lit: i32) {
+        match self.value(lit) {
+            0 => {
+                self.values[lit.unsigned_abs() as usize] = if lit < 0 { -1 } else { 1 };
+                self.fixed.push(lit);
+                self.units.push(lit);
+            }
+            value if value < 0 => self.inconsistent = true,
+            _ => {}
+        }
+    }
+
+    fn add_clause(&mut self, mut clause: Vec<i32>) {
+        // Sorting by variable puts complementary literals next to each other
+        clause.sort_unstable_by_key(|&lit| (lit.unsigned_abs(), lit));
+        clause.dedup();
+        if is_tautology(&clause) || clause.iter().any(|&lit| self.value(lit) > 0) {
+            return;
+        }
+        clause.retain(|&lit| self.value(lit) == 0);
+
+        match clause.len() {
+            0 => self.inconsistent = true,
+            1 => self.assign(clause[0]),
+            _ => {
+                let index = self.clauses.len();
+                for &lit in &clause {
+                    self.occurrences[code(lit)].push(index);
+                }
+                self.clauses.push(clause);
+            }
+        }
+    }
+
+    /// Live clauses containing `lit`, dropping stale entries
+    fn clauses_with(&mut self, lit: i32) -> Vec<usize> {
+        let clauses = &self.clauses;
+        let occurrences = &mut self.occurrences[code(lit)];
+        occurrences.retain(|&index| clauses[index].contains(&lit));
+        occurrences.clone()
+    }
+
+    fn propagate(&mut self) {
+        while let Some(lit) = self.units.pop() {
+            if self.inconsistent {
+                return;
+            }
+
+            for index in self.clauses_with(lit) {
+                self.clauses[index].clear();
+            }
+            for index in self.clauses_with(-lit) {
+                let clause = &mut self.clauses[index];
+                clause.retain(|&other| other != -lit);
+                if clause.len() == 1 {
+                    let unit = clause[0];
+                    clause.clear();
+                    self.assign(unit);
+                }
+            }
+        }
+    }
+
+    fn subsume(&mut self) {
+        let mut order: Vec<usize> = (0..self.clauses.len()).filter(|&index| !self.clauses[index].is_empty()).collect();
+        order.sort_by_key(|&index| self.clauses[index].len());
+
+        for index in order {
+            if self.clauses[index].is_empty() {
+                continue;
+            }
+
+            // Every clause subsumed by this one contains its rarest literal
+            let clause = self.clauses[index].clone();
+            let rarest = *clause.iter()
+                .min_by_key(|&&lit| self.occurrences[code(lit)].len())
+                .expect("live clauses are not empty");
+            for other in self.clauses_with(rarest) {
+                let candidate = &self.clauses[other];
+                if other != index && candidate.len() >= clause.len()
+                    && clause.iter().all(|lit| candidate.contains(lit)) {
+                    self.clauses[other].clear();
+                }
+            }
+        }
+    }
+
+    fn eliminate(&mut self, options: &PreprocessOptions) {
+        let mut changed = true;
+        while changed && !self.inconsistent {
+            changed = false;
+            for var in 1..self.values.len() as i32 {
+                if self.try_eliminate(var, options) {
+                    changed = true;
+                    self.propagate();
+                    if self.inconsistent {
+                        return;
+                    }
+                }
+            }
+        }
+    }
+
+    fn try_eliminate(&mut self, var: i32, options: &PreprocessOptions) -> bool {
+        let index = var as usize;
+        if self.frozen[index] || self.eliminated[index] || self.values[index] != 0 {
+            return false;
+        }
+
+        let positive = self.clauses_with(var);
+        let negative = self.clauses_with(-var);
+        let occurrences = positive.len() + negative.len();
+        if occurrences == 0 || occurrences > options.max_occurrences {
+            return false;
+        }
+
+        let mut resolvents = Vec::new();
+        for &p in &positive {
+            for &n in &negative {
+                let mut resolvent: Vec<i32> = self.clauses[p].iter()
+                    .chain(&self.clauses[n])
+                    .copied()
+                    .filter(|lit| lit.abs() != var)
+                    .collect();
+                resolvent.sort_unstable_by_key(|&lit| (lit.unsigned_abs(), lit));
+                resolvent.dedup();
+                if is_tautology(&resolvent) {
+                    continue;
+                }
+                if resolvent.len() > options.max_resolvent_size || resolvents.len() == occurrences {
+                    return false;
+                }
+                resolvents.push(resolvent);
+            }
+        }
+
+        self.eliminated[index] = true;
+        for (witness, clauses) in [(-var, negative), (var, positive)] {
+            for clause in clauses {
+                let removed = std::mem::take(&mut self.clauses[clause]);
+                self.stack.push((witness, removed));
+            }
+        }
+        for resolvent in resolvents {
+            self.add_clause(resolvent);
+        }
+        true
+    }
+
+    fn finish(self, num_vars: usize) -> SimplifiedFormula {
+        let num_eliminated = self.eliminated.iter().filter(|&&eliminated| eliminated).count();
+
+        if self.inconsistent {
+            let mut formula = CnfFormula::with_variables(1);
+            formula.add_clause(&[1]).expect("unit clause is valid");
+            formula.add_clause(&[-1]).expect("unit clause is valid");
+            return SimplifiedFormula {
+                formula,
+                variables: Vec::new(),
+                fixed: self.fixed,
+                eliminated: self.stack,
+                num_eliminated,
+                original_vars: num_vars,
+                unsat: true,
+            };
+        }
+
+        // Renumber the variables left in clauses, plus frozen ones that are still open
+        let mut renamed = vec![0; num_vars + 1];
+        for clause in &self.clauses {
+            for &lit in clause {
+                renamed[lit.unsigned_abs() as usize] = 1;
+            }
+        }
+        let mut variables = Vec::new();
+        for (var, renamed) in renamed.iter_mut().enumerate().skip(1) {
+            if *renamed != 0 || (self.frozen[var] && self.values[var] == 0) {
+                variables.push(var as i32);
+                *renamed = variables.len() as i32;
+            }
+        }
+
+        let mut formula = CnfFormula::with_variables(variables.len());
+        for clause in self.clauses.iter().filter(|clause| !clause.is_empty()) {
+            let clause: Vec<i32> = clause.iter()
+                .map(|&lit| renamed[lit.unsigned_abs() as usize] * lit.signum())
+                .collect();
+            formula.add_clause(&clause).expect("simplified clauses are not empty");
+        }
+
+        SimplifiedFormula {
+            formula,
+            variables,
+            fixed: self.fixed,
+            eliminated: self.stack,
+            num_eliminated,
+            original_vars: num_vars,
+            unsat: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wrapper::{ParkissatSolver, SolverConfig, SolverResult};
+
+    fn formula(clauses: &[&[i32]]) -> CnfFormula {
+        let mut formula = CnfFormula::new();
+        for clause in clauses {
+            formula.add_clause(clause).unwrap();
+        }
+        formula
+    }
+
+    fn solve(formula: &CnfFormula) -> Option<Vec<i32>> {
+        let mut solver = ParkissatSolver::new().unwrap();
+        solver.configure(&SolverConfig::default()).unwrap();
+        solver.add_formula(formula).unwrap();
+        match solver.solve().unwrap() {
+            SolverResult::Sat => Some(solver.get_model().unwrap()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_propagation_and_subsumption() {
+        let original = formula(&[&[1], &[-1, 2], &[3, 4, -2, 3], &[3, 4, 5], &[3, 4], &[5, -5]]);
+        let options = PreprocessOptions {
+            eliminate: false,
+            ..PreprocessOptions::default()
+        };
+        let simplified = preprocess_with(&original, &options).unwrap();
+
+        assert!(!simplified.is_unsat());
+        assert_eq!(simplified.fixed_literals(), &[1, 2]);
+        assert_eq!(simplified.formula().clauses(), &[vec![1, 2]]);
+        assert_eq!(simplified.original_variable(2), Some(4));
+        assert_eq!(simplified.simplified_variable(3), Some(1));
+        assert_eq!(simplified.simplified_variable(5), None);
+    }
+
+    #[test]
+    fn test_elimination_and_reconstruction() {
+        let original = formula(&[&[1, 2], &[-2, 3], &[-3, 4, 5], &[-4, -1], &[-5, 6], &[-6, -1, 2]]);
+        let simplified = preprocess(&original);
+        assert!(simplified.eliminated_variables() > 0);
+        assert!(simplified.formula().num_vars() < original.num_vars());
+
+        let model = solve(simplified.formula()).unwrap();
+        let reconstructed = simplified.reconstruct(&model).unwrap();
+        assert_eq!(reconstructed.len(), original.num_vars());
+        assert!(original.is_satisfied_by(&reconstructed));
+    }
+
+    #[test]
+    fn test_frozen_variables_survive() {
+        let original = formula(&[&[1, 2], &[-2, 3]]);
+        let options = PreprocessOptions {
+            frozen: vec![2],
+            ..PreprocessOptions::default()
+        };
+        let simplified = preprocess_with(&original, &options).unwrap();
+        let var = simplified.simplified_variable(2).unwrap();
+
+        let model = solve(simplified.formula()).unwrap();
+        let reconstructed = simplified.reconstruct(&model).unwrap();
+        assert!(original.is_satisfied_by(&reconstructed));
+        assert_eq!(reconstructed[1] > 0, model.contains(&var));
+
+        let invalid = PreprocessOptions {
+            frozen: vec![4],
+            ..PreprocessOptions::default()
+        };
+        assert_eq!(preprocess_with(&original, &invalid).unwrap_err(), ParkissatError::InvalidVariable(4));
+    }
+
+    #[test]
+    fn test_unsatisfiable_formula() {
+        let simplified = preprocess(&formula(&[&[1, 2], &[-1], &[-2]]));
+        assert!(simplified.is_unsat());
+        assert_eq!(solve(simplified.formula()), None);
+    }
+}
@@ -0,0 +1,250 @@
+//! Configuration presets and builder
+//!
+//! [`SolverConfig`] has many interacting settings. The presets bundle
+//! known-good combinations for common situations. A [`SolverConfigBuilder`]
+//! adjusts a preset, or the defaults with [`SolverConfig::builder`], field by
+//! field and validates the result in [`SolverConfigBuilder::build`]:
+//!
+//! ```no_run
+//! use parkissat_sys::{SolverConfig, SolverConfigBuilder};
+//! use std::time::Duration;
+//!
+//! let config = SolverConfigBuilder::from(SolverConfig::sat_race())
+//!     .timeout(Duration::from_secs(600))
+//!     .build()?;
+//! # Ok::<(), parkissat_sys::ParkissatError>(())
+//! ```
+
+use crate::error::Result;
+use crate::wrapper::{LocalSearchConfig, MabConfig, PhaseMode, ProofFormat, RestartConfig, SharingConfig, SolverConfig};
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Wall-clock limit of the SAT competition tracks
+const SAT_RACE_TIMEOUT: Duration = Duration::from_secs(5000);
+
+/// Memory limit of the [`SolverConfig::low_memory`] preset in MiB
+const LOW_MEMORY_LIMIT_MB: u64 = 2048;
+
+impl SolverConfig {
+    /// Start building a configuration from the defaults
+    pub fn builder() -> SolverConfigBuilder {
+        SolverConfigBuilder::default()
+    }
+
+    /// The parallel competition setup: every CPU, preprocessing, and the
+    /// default LBD-2 clause sharing, with the 5000 s competition timeout
+    pub fn sat_race() -> Self {
+        Self {
+            num_threads: -1,
+            timeout: SAT_RACE_TIMEOUT,
+            enable_preprocessing: true,
+            sharing: SharingConfig::default(),
+            ..Self::default()
+        }
+    }
+
+    /// A single worker without sharing buffers, stopped at 2 GiB of resident memory
+    pub fn low_memory() -> Self {
+        Self {
+            num_threads: 1,
+            memory_limit_mb: Some(LOW_MEMORY_LIMIT_MB),
+            sharing: SharingConfig::disabled(),
+            ..Self::default()
+        }
+    }
+
+    /// A single worker with a fixed seed and no timeout
+    ///
+    /// Repeated solves of the same formula take the same search path, so
+    /// answers, models, and statistics are reproducible. Use conflict or
+    /// decision budgets (`solve_limited`) rather than a timeout to bound the
+    /// search without losing determinism.
+    pub fn single_thread_deterministic() -> Self {
+        Self {
+            num_threads: 1,
+            timeout: Duration::ZERO,
+            random_seed: 1,
+            sharing: SharingConfig::disabled(),
+            ..Self::default()
+        }
+    }
+}
+
+/// Builds a [`SolverConfig`] field by field, validating it at the end
+#[derive(Debug, Clone, Default)]
+pub struct SolverConfigBuilder {
+    config: SolverConfig,
+}
+
+/// Start from an existing configuration, e.g. a preset
+impl From<SolverConfig> for SolverConfigBuilder {
+    fn from(config: SolverConfig) -> Self {
+        Self { config }
+    }
+}
+
+impl SolverConfigBuilder {
+    /// Number of parallel threads (-1 = use all available CPUs)
+    pub fn num_threads(mut self, num_threads: isize) -> Self {
+        self.config.num_threads = num_threads;
+        self
+    }
+
+    /// Timeout of each solve (zero = no timeout)
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.config.timeout = timeout;
+        self
+    }
+
+    /// Random seed for diversification
+    pub fn random_seed(mut self, seed: u32) -> Self {
+        self.config.random_seed = seed;
+        self
+    }
+
+    /// Enable or disable preprocessing
+    pub fn preprocessing(mut self, enable: bool) -> Self {
+        self.config.enable_preprocessing = enable;
+        self
+    }
+
+    /// Verbosity level (0 = quiet)
+    pub fn verbosity(mut self, verbosity: u32) -> Self {
+        self.config.verbosity = verbosity;
+        self
+    }
+
+    /// Write a proof in the given format whenever a solve answers UNSAT
+    pub fn proof_output(mut self, path: impl Into<PathBuf>, format: ProofFormat) -> Self {
+        self.config.proof_output = Some(path.into());
+        self.config.proof_format = format;
+        self
+    }
+
+    /// Stop solving above this many MiB of resident memory
+    pub fn memory_limit_mb(mut self, limit: u64) -> Self {
+        self.config.memory_limit_mb = Some(limit);
+        self
+    }
+
+    /// Clause-sharing policy between workers
+    pub fn sharing(mut self, sharing: SharingConfig) -> Self {
+        self.config.sharing = sharing;
+        self
+    }
+
+    /// Branching-heuristic selection of every worker
+    pub fn mab(mut self, mab: MabConfig) -> Self {
+        self.config.mab = mab;
+        self
+    }
+
+    /// Per-worker overrides of the branching-heuristic selection
+    pub fn worker_mab(mut self, worker_mab: Vec<MabConfig>) -> Self {
+        self.config.worker_mab = worker_mab;
+        self
+    }
+
+    /// Local-search phase of every worker
+    pub fn local_search(mut self, local_search: LocalSearchConfig) -> Self {
+        self.config.local_search = local_search;
+        self
+    }
+
+    /// Restart policy of every worker
+    pub fn restart(mut self, restart: RestartConfig) -> Self {
+        self.config.restart = restart;
+        self
+    }
+
+    /// Initial polarity of every worker
+    pub fn initial_phase(mut self, initial_phase: PhaseMode) -> Self {
+        self.config.initial_phase = initial_phase;
+        self
+    }
+
+    /// Pin worker threads, to the given CPUs or (if empty) every CPU in order
+    pub fn pin_threads(mut self, cpus: Vec<usize>) -> Self {
+        self.config.pin_threads = true;
+        self.config.cpus = if cpus.is_empty() { None } else { Some(cpus) };
+        self
+    }
+
+    /// Allocate each worker's clause database on its NUMA node (needs pinned threads)
+    pub fn numa_local(mut self, numa_local: bool) -> Self {
+        self.config.numa_local = numa_local;
+        self
+    }
+
+    /// Validate and return the configuration
+    ///
+    /// Fails with `InvalidConfiguration` for the settings `configure` would
+    /// reject (see [`SolverConfig::validate`]).
+    pub fn build(self) -> Result<SolverConfig> {
+        self.config.validate()?;
+        Ok(self.config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cnf::pigeonhole;
+    use crate::error::ParkissatError;
+    use crate::wrapper::{ParkissatSolver, SolverResult};
+
+    #[test]
+    fn test_presets_are_valid() {
+        for preset in [SolverConfig::sat_race(), SolverConfig::low_memory(), SolverConfig::single_thread_deterministic()] {
+            preset.validate().unwrap();
+
+            let mut solver = ParkissatSolver::new().unwrap();
+            solver.configure(&preset).unwrap();
+            solver.add_clause(&[1, 2]).unwrap();
+            solver.add_clause(&[-1]).unwrap();
+            assert_eq!(solver.solve().unwrap(), SolverResult::Sat);
+            assert_eq!(solver.get_model().unwrap(), vec![-1, 2]);
+        }
+    }
+
+    #[test]
+    fn test_builder() {
+        let config = SolverConfigBuilder::from(SolverConfig::sat_race())
+            .num_threads(2)
+            .random_seed(9)
+            .proof_output("proof.drat", ProofFormat::BinaryDrat)
+            .build()
+            .unwrap();
+        assert_eq!(config.num_threads, 2);
+        assert_eq!(config.random_seed, 9);
+        assert_eq!(config.timeout, SAT_RACE_TIMEOUT);
+        assert!(config.enable_preprocessing);
+        assert_eq!(config.proof_format, ProofFormat::BinaryDrat);
+
+        for builder in [
+            SolverConfig::builder().num_threads(0),
+            SolverConfig::builder().memory_limit_mb(0),
+            SolverConfig::builder().sharing(SharingConfig { buffer_literals: 0, ..SharingConfig::default() }),
+            SolverConfig::builder().worker_mab(vec![MabConfig { heuristics: Vec::new(), ..MabConfig::default() }]),
+        ] {
+            assert!(matches!(builder.build(), Err(ParkissatError::InvalidConfiguration(_))));
+        }
+    }
+
+    #[test]
+    fn test_deterministic_preset_reproduces_search() {
+        let run = || {
+            let mut solver = ParkissatSolver::new().unwrap();
+            solver.configure(&SolverConfig::single_thread_deterministic()).unwrap();
+
+            solver.add_formula(&pigeonhole(6, 5)).unwrap();
+
+            assert_eq!(solver.solve().unwrap(), SolverResult::Unsat);
+            let stats = solver.get_statistics().unwrap();
+            (stats.conflicts, stats.decisions, stats.propagations)
+        };
+
+        assert_eq!(run(), run());
+    }
+}
@@ -0,0 +1,300 @@
+//! Process-isolated solving
+//!
+//! [`solve_isolated`] forks a child process that builds a solver, solves, and
+//! writes its answer back over a pipe in SAT-competition format. Whatever
+//! happens in the child stays there: a hard wall-clock limit is enforced by
+//! killing it, a memory limit is enforced with `setrlimit`, and a crash inside
+//! the C++ portfolio is reported as an error instead of taking down the host.
+//! Available on Unix.
+//!
+//! The child is a fork of the calling process and contains only the calling
+//! thread; it touches nothing but its own solver and exits without running
+//! destructors or `atexit` handlers.
+
+use crate::cnf::CnfFormula;
+use crate::error::{ParkissatError, Result};
+use crate::ensemble::parse_competition_output;
+use crate::format::write_competition_output;
+use crate::wrapper::{ParkissatSolver, SolverConfig, SolverResult, UnknownReason};
+use std::fs::File;
+use std::io::{Read, Write};
+use std::os::unix::io::FromRawFd;
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+/// Limits enforced on the child process from outside the solver
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ProcessLimits {
+    /// Kill the child after this much wall-clock time (None = no limit)
+    ///
+    /// Unlike `SolverConfig::timeout`, which the solver checks itself, this
+    /// holds even if the search stops responding. Set it somewhat above the
+    /// configured timeout so that the solver normally stops on its own.
+    pub timeout: Option<Duration>,
+    /// Address-space limit of the child in MiB (None = no limit)
+    pub memory_limit_mb: Option<u64>,
+}
+
+/// Answer of an isolated solve
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IsolatedOutcome {
+    /// The answer
+    pub result: SolverResult,
+    /// The model, if the answer is SAT
+    pub model: Option<Vec<i32>>,
+    /// Why the answer is `Unknown`
+    ///
+    /// A child killed at `ProcessLimits::timeout` reports `Timeout`, and one
+    /// that ran out of memory under `ProcessLimits::memory_limit_mb` reports
+    /// `MemoryLimit`.
+    pub unknown_reason: Option<UnknownReason>,
+}
+
+/// Solve a formula under assumptions in a child process
+///
+/// Fails with `InternalError` if the child crashes or exits without an
+/// answer; errors of the solver itself (e.g. an invalid configuration) are
+/// passed back from the child unchanged in kind.
+pub fn solve_isolated(
+    formula: &CnfFormula,
+    config: &SolverConfig,
+    assumptions: &[i32],
+    limits: &ProcessLimits,
+) -> Result<IsolatedOutcome> {
+    let mut fds = [0; 2];
+    if unsafe { libc::pipe(fds.as_mut_ptr()) } != 0 {
+        return Err(std::io::Error::last_os_error().into());
+    }
+    let (read, write) = (fds[0], fds[1]);
+
+    let pid = unsafe { libc::fork() };
+    if pid < 0 {
+        let err = std::io::Error::last_os_error();
+        unsafe {
+            libc::close(read);
+            libc::close(write);
+        }
+        return Err(err.into());
+    }
+
+    if pid == 0 {
+        unsafe { libc::close(read) };
+        let mut pipe = unsafe { File::from_raw_fd(write) };
+        if let Some(limit) = limits.memory_limit_mb {
+            let bytes = limit.saturating_mul(1024 * 1024) as libc::rlim_t;
+            let rlimit = libc::rlimit { rlim_cur: bytes, rlim_max: bytes };
+            unsafe { libc::setrlimit(libc::RLIMIT_AS, &rlimit) };
+        }
+
+        let report = match solve_in_child(formula, config, assumptions) {
+            Ok(answer) => answer,
+            Err(err) => encode_error(&err),
+        };
+        let _ = pipe.write_all(report.as_bytes());
+        let _ = pipe.flush();
+        unsafe { libc::_exit(0) };
+    }
+
+    unsafe { libc::close(write) };
+    let mut pipe = unsafe { File::from_raw_fd(read) };
+    let (tx, rx) = mpsc::channel();
+    let reader = thread::spawn(move || {
+        let mut output = String::new();
+        let _ = pipe.read_to_string(&mut output);
+        let _ = tx.send(());
+        output
+    });
+
+    // The pipe closes when the child exits, or once it is killed
+    let killed = match limits.timeout {
+        Some(timeout) => match rx.recv_timeout(timeout) {
+            Ok(()) => false,
+            Err(_) => {
+                unsafe { libc::kill(pid, libc::SIGKILL) };
+                true
+            }
+        },
+        None => {
+            let _ = rx.recv();
+            false
+        }
+    };
+    let output = reader.join().unwrap_or_default();
+
+    let mut status = 0;
+    while unsafe { libc::waitpid(pid, &mut status, 0) } < 0 {
+        let err = std::io::Error::last_os_error();
+        if err.kind() != std::io::ErrorKind::Interrupted {
+            return Err(err.into());
+        }
+    }
+
+    if killed {
+        return Ok(IsolatedOutcome {
+            result: SolverResult::Unknown,
+            model: None,
+            unknown_reason: Some(UnknownReason::Timeout),
+        });
+    }
+    if libc::WIFSIGNALED(status) {
+        return Err(ParkissatError::InternalError(format!(
+            "solver process terminated by signal {}", libc::WTERMSIG(status)
+        )));
+    }
+
+    decode_answer(&output)
+}
+
+fn solve_in_child(formula: &CnfFormula, config: &SolverConfig, assumptions: &[i32]) -> Result<String> {
+    let mut solver = ParkissatSolver::new()?;
+    solver.configure(config)?;
+    solver.add_formula(formula)?;
+
+    let result = solver.solve_with_assumptions(assumptions)?;
+    let model = match result {
+        SolverResult::Sat => solver.get_model()?,
+        _ => Vec::new(),
+    };
+
+    let mut output = Vec::new();
+    write_competition_output(&mut output, result, &model)?;
+    if let Some(reason) = solver.unknown_reason() {
+        writeln!(output, "c reason {}", reason_name(reason))?;
+    }
+    Ok(String::from_utf8_lossy(&output).into_owned())
+}
+
+fn reason_name(reason: UnknownReason) -> &'static str {
+    match reason {
+        UnknownReason::Interrupted => "interrupted",
+        UnknownReason::Timeout => "timeout",
+        UnknownReason::BudgetExhausted => "budget",
+        UnknownReason::MemoryLimit => "memory",
+        UnknownReason::Other => "other",
+    }
+}
+
+/// Errors travel as one `e <kind> <line> <message>` line
+fn encode_error(err: &ParkissatError) -> String {
+    let (kind, line, message) = match err {
+        ParkissatError::InvalidConfiguration(msg) => ("config", 0, msg.clone()),
+        ParkissatError::InvalidClause(msg) => ("clause", 0, msg.clone()),
+        ParkissatError::InvalidVariable(var) => ("variable", *var as i64, String::new()),
+        ParkissatError::IoError(msg) => ("io", 0, msg.clone()),
+        ParkissatError::OutOfMemory(msg) => ("memory", 0, msg.clone()),
+        ParkissatError::ParseError { line, message } => ("parse", *line as i64, message.clone()),
+        other => ("internal", 0, other.to_string()),
+    };
+    format!("e {} {} {}\n", kind, line, message.replace('\n', " "))
+}
+
+fn decode_answer(output: &str) -> Result<IsolatedOutcome> {
+    let mut unknown_reason = None;
+    for line in output.lines() {
+        if let Some(error) = line.strip_prefix("e ") {
+            let mut fields = error.splitn(3, ' ');
+            let kind = fields.next().unwrap_or_default();
+            let number: i64 = fields.next().and_then(|field| field.parse().ok()).unwrap_or(0);
+            let message = fields.next().unwrap_or_default().to_string();
+            return match kind {
+                // Running out of address space is the memory limit doing its job
+                "memory" => Ok(IsolatedOutcome {
+                    result: SolverResult::Unknown,
+                    model: None,
+                    unknown_reason: Some(UnknownReason::MemoryLimit),
+                }),
+                "config" => Err(ParkissatError::InvalidConfiguration(message)),
+                "clause" => Err(ParkissatError::InvalidClause(message)),
+                "variable" => Err(ParkissatError::InvalidVariable(number as i32)),
+                "io" => Err(ParkissatError::IoError(message)),
+                "parse" => Err(ParkissatError::ParseError { line: number as usize, message }),
+                _ => Err(ParkissatError::InternalError(message)),
+            };
+        }
+        unknown_reason = match line.strip_prefix("c reason ") {
+            Some("interrupted") => Some(UnknownReason::Interrupted),
+            Some("timeout") => Some(UnknownReason::Timeout),
+            Some("budget") => Some(UnknownReason::BudgetExhausted),
+            Some("memory") => Some(UnknownReason::MemoryLimit),
+            Some(_) => Some(UnknownReason::Other),
+            None => unknown_reason,
+        };
+    }
+
+    if !output.lines().any(|line| line.starts_with("s ")) {
+        return Err(ParkissatError::InternalError("solver process exited without an answer".to_string()));
+    }
+
+    let (result, model) = parse_competition_output(output);
+    Ok(IsolatedOutcome {
+        result,
+        model,
+        unknown_reason: if result == SolverResult::Unknown { unknown_reason } else { None },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cnf::pigeonhole;
+    use std::time::Instant;
+
+    #[test]
+    fn test_isolated_answers() {
+        let mut formula = CnfFormula::new();
+        formula.add_clause(&[1, 2]).unwrap();
+        formula.add_clause(&[-1, 2]).unwrap();
+
+        let config = SolverConfig::default();
+        let outcome = solve_isolated(&formula, &config, &[], &ProcessLimits::default()).unwrap();
+        assert_eq!(outcome.result, SolverResult::Sat);
+        assert!(outcome.model.unwrap().contains(&2));
+
+        let outcome = solve_isolated(&formula, &config, &[-2], &ProcessLimits::default()).unwrap();
+        assert_eq!(outcome, IsolatedOutcome { result: SolverResult::Unsat, model: None, unknown_reason: None });
+
+        // Solver errors come back with their kind
+        let invalid = SolverConfig { num_threads: 0, ..SolverConfig::default() };
+        let err = solve_isolated(&formula, &invalid, &[], &ProcessLimits::default()).unwrap_err();
+        assert!(matches!(err, ParkissatError::InvalidConfiguration(_)));
+    }
+
+    #[test]
+    fn test_hard_timeout_kills_child() {
+        let limits = ProcessLimits { timeout: Some(Duration::from_millis(300)), ..ProcessLimits::default() };
+
+        let start = Instant::now();
+        let outcome = solve_isolated(&pigeonhole(13, 12), &SolverConfig::default(), &[], &limits).unwrap();
+        assert_eq!(outcome.result, SolverResult::Unknown);
+        assert_eq!(outcome.unknown_reason, Some(UnknownReason::Timeout));
+        assert!(start.elapsed() < Duration::from_secs(10));
+    }
+
+    #[test]
+    fn test_memory_limit_is_contained() {
+        let limits = ProcessLimits {
+            timeout: Some(Duration::from_secs(30)),
+            memory_limit_mb: Some(1),
+        };
+
+        // The child cannot even build a solver; the host carries on either way
+        match solve_isolated(&pigeonhole(8, 7), &SolverConfig::default(), &[], &limits) {
+            Ok(outcome) => assert_eq!(outcome.result, SolverResult::Unknown),
+            Err(err) => assert!(matches!(err, ParkissatError::InternalError(_))),
+        }
+    }
+
+    #[test]
+    fn test_decode_answer() {
+        assert_eq!(
+            decode_answer("s UNKNOWN\nc reason budget\n").unwrap().unknown_reason,
+            Some(UnknownReason::BudgetExhausted)
+        );
+        assert_eq!(
+            decode_answer("e parse 3 bad literal\n").unwrap_err(),
+            ParkissatError::ParseError { line: 3, message: "bad literal".to_string() }
+        );
+        assert!(matches!(decode_answer(""), Err(ParkissatError::InternalError(_))));
+    }
+}
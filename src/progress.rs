@@ -0,0 +1,95 @@
+//! Live statistics
+//!
+//! [`StatisticsHandle`] reads the solver's statistics from another thread,
+//! including while [`ParkissatSolver::solve`] is running, so progress bars and
+//! dashboards can show activity on long runs. It shares the solver through the
+//! same lock as [`InterruptHandle`](crate::InterruptHandle), so a handle that
+//! outlives its solver simply returns nothing.
+
+use crate::ffi;
+use crate::interrupt::SolverPtr;
+use crate::wrapper::{ParkissatSolver, SolverStatistics};
+use std::sync::{Arc, Mutex};
+
+/// Handle that reads a solver's statistics from any thread
+///
+/// During a solve the times in a snapshot cover the solve so far. Counters of
+/// the workers are read one after the other while they keep searching, so a
+/// snapshot is consistent per counter, not across workers.
+#[derive(Clone)]
+pub struct StatisticsHandle {
+    target: Arc<Mutex<SolverPtr>>,
+}
+
+impl StatisticsHandle {
+    /// Take a snapshot of the statistics (`None` if the solver has been dropped)
+    pub fn snapshot(&self) -> Option<SolverStatistics> {
+        let target = self.target.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if target.0.is_null() {
+            return None;
+        }
+
+        let stats = unsafe { ffi::parkissat_get_statistics(target.0) };
+        Some(SolverStatistics::from(stats))
+    }
+}
+
+impl std::fmt::Debug for StatisticsHandle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StatisticsHandle").finish_non_exhaustive()
+    }
+}
+
+impl ParkissatSolver {
+    /// Get a thread-safe handle that reads this solver's statistics
+    pub fn statistics_handle(&self) -> StatisticsHandle {
+        StatisticsHandle {
+            target: self.interrupt_handle.target(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cnf::pigeonhole;
+    use crate::wrapper::{SolverConfig, SolverResult};
+    use std::thread;
+    use std::time::Duration;
+
+    #[test]
+    fn test_snapshot_during_solve() {
+        let mut solver = ParkissatSolver::new().unwrap();
+        solver.configure(&SolverConfig::default()).unwrap();
+
+        // Far too hard to finish in time
+        solver.add_formula(&pigeonhole(12, 11)).unwrap();
+
+        let stats = solver.statistics_handle();
+        let interrupt = solver.interrupt_handle();
+        let monitor = thread::spawn(move || loop {
+            let snapshot = stats.snapshot().unwrap();
+            if snapshot.conflicts > 0 {
+                interrupt.interrupt();
+                return snapshot;
+            }
+            thread::sleep(Duration::from_millis(1));
+        });
+
+        assert_eq!(solver.solve().unwrap(), SolverResult::Unknown);
+        let snapshot = monitor.join().unwrap();
+        assert!(snapshot.decisions > 0);
+        assert!(snapshot.solve_wall_time > Duration::ZERO);
+    }
+
+    #[test]
+    fn test_handle_after_drop() {
+        let mut solver = ParkissatSolver::new().unwrap();
+        solver.configure(&SolverConfig::default()).unwrap();
+        let handle = solver.statistics_handle();
+        assert_eq!(handle.snapshot().unwrap().conflicts, 0);
+
+        drop(solver);
+        assert!(handle.snapshot().is_none());
+    }
+}
@@ -0,0 +1,674 @@
+//! DRAT proofs and their conversion to LRAT
+//!
+//! Kissat logs DRAT proofs: the lemmas it learns and the clauses it deletes,
+//! without saying why a lemma follows. Verified checkers such as cake_lpr
+//! consume LRAT instead, where every lemma lists the clauses that unit
+//! propagation uses to derive it. [`drat_to_lrat`] fills those hints in with
+//! a forward pass over the proof: each lemma is checked by reverse unit
+//! propagation (RUP) against the clauses active at that point, falling back
+//! to the resolution asymmetric tautology (RAT) check on its first literal,
+//! and the clauses that took part in the conflict become its hints.
+//!
+//! The same pass validates proofs: [`check_drat`] accepts a DRAT refutation
+//! exactly when it converts, so UNSAT answers can be certified without
+//! shelling out to drat-trim. Being a forward checker, it checks every
+//! lemma, including those the refutation does not need.
+//!
+//! [`ProofFormat::Lrat`] applies the conversion after every UNSAT solve: the
+//! workers log binary DRAT to a sidecar file next to `proof_output`, which is
+//! converted into `proof_output` and then removed.
+//!
+//! Clause ids follow the LRAT convention: the clauses of the formula are
+//! numbered from 1 in order, and lemmas continue from there. As in drat-trim,
+//! deleting a unit clause, or a clause that is the reason for a literal fixed
+//! by propagation, is ignored.
+
+use crate::cnf::CnfFormula;
+use crate::error::{ParkissatError, Result};
+use crate::wrapper::{ParkissatSolver, ProofFormat};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+
+/// One step of a DRAT proof
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Step {
+    Add(Vec<i32>),
+    Delete(Vec<i32>),
+}
+
+/// Whether the proof is in binary DRAT, judged like drat-trim by its first bytes
+fn is_binary(bytes: &[u8]) -> bool {
+    bytes.iter().take(16).any(|&byte| !matches!(byte, b'\n' | b'\r' | b'\t' | 32..=126))
+}
+
+fn parse_text(bytes: &[u8]) -> Result<Vec<Step>> {
+    let text = std::str::from_utf8(bytes)
+        .map_err(|_| ParkissatError::ParseError { line: 1, message: "invalid UTF-8".to_string() })?;
+
+    let mut steps = Vec::new();
+    let mut lits = Vec::new();
+    let mut delete = false;
+    for (index, line) in text.lines().enumerate() {
+        let line_number = index + 1;
+        if line.trim_start().starts_with('c') {
+            continue;
+        }
+        for token in line.split_whitespace() {
+            if token == "d" && lits.is_empty() && !delete {
+                delete = true;
+                continue;
+            }
+            let lit: i32 = token.parse().map_err(|_| ParkissatError::ParseError {
+                line: line_number,
+                message: format!("invalid literal `{}`", token),
+            })?;
+            if lit == 0 {
+                let clause = std::mem::take(&mut lits);
+                steps.push(if delete { Step::Delete(clause) } else { Step::Add(clause) });
+                delete = false;
+            } else {
+                lits.push(lit);
+            }
+        }
+    }
+    if delete || !lits.is_empty() {
+        return Err(ParkissatError::ParseError {
+            line: text.lines().count(),
+            message: "last clause is not terminated by 0".to_string(),
+        });
+    }
+    Ok(steps)
+}
+
+fn parse_binary(bytes: &[u8]) -> Result<Vec<Step>> {
+    let truncated = |position: usize| ParkissatError::InvalidProof(format!("truncated binary proof at byte {}", position));
+
+    let mut steps = Vec::new();
+    let mut position = 0;
+    while position < bytes.len() {
+        let delete = match bytes[position] {
+            b'a' => false,
+            b'd' => true,
+            byte => return Err(ParkissatError::InvalidProof(format!("unexpected byte {:#04x} at byte {}", byte, position))),
+        };
+        position += 1;
+
+        let mut lits = Vec::new();
+        loop {
+            let mut code = 0u64;
+            let mut shift = 0;
+            loop {
+                let &byte = bytes.get(position).ok_or_else(|| truncated(position))?;
+                position += 1;
+                code |= u64::from(byte & 0x7f) << shift;
+                if byte & 0x80 == 0 {
+                    break;
+                }
+                shift += 7;
+                if shift > 35 {
+                    return Err(ParkissatError::InvalidProof(format!("literal too large at byte {}", position)));
+                }
+            }
+            if code == 0 {
+                break;
+            }
+            let var = i32::try_from(code / 2)
+                .map_err(|_| ParkissatError::InvalidProof(format!("literal too large at byte {}", position)))?;
+            lits.push(if code % 2 == 1 { -var } else { var });
+        }
+        steps.push(if delete { Step::Delete(lits) } else { Step::Add(lits) });
+    }
+    Ok(steps)
+}
+
+/// Parse a textual or binary DRAT proof
+fn parse_drat(bytes: &[u8]) -> Result<Vec<Step>> {
+    if is_binary(bytes) {
+        parse_binary(bytes)
+    } else {
+        parse_text(bytes)
+    }
+}
+
+fn code(lit: i32) -> usize {
+    2 * lit.unsigned_abs() as usize + usize::from(lit < 0)
+}
+
+fn value(values: &[i8], lit: i32) -> i8 {
+    let value = values.get(lit.unsigned_abs() as usize).copied().unwrap_or(0);
+    if lit < 0 { -value } else { value }
+}
+
+#[derive(Debug)]
+struct Clause {
+    lits: Vec<i32>,
+    id: u64,
+    deleted: bool,
+}
+
+/// Outcome of assuming the negation of a clause
+enum Assumed {
+    /// The negation is consistent so far
+    Open,
+    /// A literal of the clause is already true because of this clause
+    Conflict(usize),
+    /// The clause contains a literal and its negation
+    Tautology,
+}
+
+/// Clause database with a top-level assignment, checking lemmas by unit propagation
+#[derive(Debug, Default)]
+pub(crate) struct Checker {
+    clauses: Vec<Clause>,
+    /// Clauses watching a literal, indexed by `code(lit)`
+    watches: Vec<Vec<usize>>,
+    /// 1 = true, -1 = false, 0 = unassigned, indexed by variable
+    values: Vec<i8>,
+    /// Clause that propagated each variable, None for assumed literals
+    reasons: Vec<Option<usize>>,
+    marks: Vec<bool>,
+    trail: Vec<i32>,
+    head: usize,
+    /// Clause falsified by the top-level assignment, once there is one
+    conflict: Option<usize>,
+    /// Active clauses by their sorted literals, for deletion
+    index: HashMap<Vec<i32>, Vec<usize>>,
+    next_id: u64,
+}
+
+impl Checker {
+    pub(crate) fn new() -> Self {
+        Self { next_id: 1, ..Self::default() }
+    }
+
+    /// Id of the most recently added clause
+    pub(crate) fn last_id(&self) -> u64 {
+        self.next_id - 1
+    }
+
+    fn grow(&mut self, lit: i32) {
+        let var = lit.unsigned_abs() as usize;
+        if var >= self.values.len() {
+            self.values.resize(var + 1, 0);
+            self.reasons.resize(var + 1, None);
+            self.marks.resize(var + 1, false);
+            self.watches.resize(2 * (var + 1), Vec::new());
+        }
+    }
+
+    fn assign(&mut self, lit: i32, reason: Option<usize>) {
+        let var = lit.unsigned_abs() as usize;
+        self.values[var] = if lit < 0 { -1 } else { 1 };
+        self.reasons[var] = reason;
+        self.trail.push(lit);
+    }
+
+    fn undo(&mut self, length: usize) {
+        for &lit in &self.trail[length..] {
+            let var = lit.unsigned_abs() as usize;
+            self.values[var] = 0;
+            self.reasons[var] = None;
+        }
+        self.trail.truncate(length);
+        self.head = length;
+    }
+
+    /// Add a clause at the top level and propagate, returning its id
+    pub(crate) fn add(&mut self, lits: &[i32]) -> u64 {
+        let mut lits = normalize(lits);
+        for &lit in &lits {
+            self.grow(lit);
+        }
+        let id = self.next_id;
+        self.next_id += 1;
+        let index = self.clauses.len();
+        let mut key = lits.clone();
+        key.sort_unstable();
+        self.index.entry(key).or_default().push(index);
+
+        let tautology = lits.iter().any(|lit| lits.contains(&-lit));
+        if self.conflict.is_some() || tautology {
+            self.clauses.push(Clause { lits, id, deleted: false });
+            return id;
+        }
+
+        // Watch the literals that are not false, if there are any
+        let mut free = 0;
+        for position in 0..lits.len() {
+            if value(&self.values, lits[position]) >= 0 && free < 2 {
+                lits.swap(free, position);
+                free += 1;
+            }
+        }
+        if lits.len() >= 2 {
+            self.watches[code(lits[0])].push(index);
+            self.watches[code(lits[1])].push(index);
+        }
+        let first = lits.first().copied();
+        self.clauses.push(Clause { lits, id, deleted: false });
+
+        match first {
+            Some(lit) if free > 0 => {
+                if free == 1 && value(&self.values, lit) == 0 {
+                    self.assign(lit, Some(index));
+                    self.conflict = self.propagate();
+                }
+            }
+            _ => self.conflict = Some(index),
+        }
+        id
+    }
+
+    /// Delete an active clause, returning its id unless the deletion is ignored
+    pub(crate) fn delete(&mut self, lits: &[i32]) -> Option<u64> {
+        let mut key = normalize(lits);
+        key.sort_unstable();
+        let candidates = self.index.get_mut(&key)?;
+        let &index = candidates.last()?;
+
+        let clause = &self.clauses[index];
+        let reason = clause.lits.iter()
+            .any(|&lit| self.reasons.get(lit.unsigned_abs() as usize) == Some(&Some(index)));
+        if clause.lits.len() <= 1 || reason || self.conflict == Some(index) {
+            return None;
+        }
+
+        candidates.pop();
+        if candidates.is_empty() {
+            self.index.remove(&key);
+        }
+        self.clauses[index].deleted = true;
+        Some(self.clauses[index].id)
+    }
+
+    /// Propagate the trail, returning a falsified clause
+    fn propagate(&mut self) -> Option<usize> {
+        while self.head < self.trail.len() {
+            let false_lit = -self.trail[self.head];
+            self.head += 1;
+
+            let mut watching = std::mem::take(&mut self.watches[code(false_lit)]);
+            let mut kept = 0;
+            let mut conflict = None;
+            let mut position = 0;
+            while position < watching.len() {
+                let index = watching[position];
+                position += 1;
+                if self.clauses[index].deleted {
+                    continue;
+                }
+
+                let lits = &mut self.clauses[index].lits;
+                if lits[0] == false_lit {
+                    lits.swap(0, 1);
+                }
+                if value(&self.values, lits[0]) > 0 {
+                    watching[kept] = index;
+                    kept += 1;
+                    continue;
+                }
+                if let Some(other) = (2..lits.len()).find(|&other| value(&self.values, lits[other]) >= 0) {
+                    lits.swap(1, other);
+                    let watch = code(lits[1]);
+                    self.watches[watch].push(index);
+                    continue;
+                }
+
+                watching[kept] = index;
+                kept += 1;
+                let unit = lits[0];
+                if value(&self.values, unit) < 0 {
+                    conflict = Some(index);
+                    while position < watching.len() {
+                        watching[kept] = watching[position];
+                        kept += 1;
+                        position += 1;
+                    }
+                } else {
+                    self.assign(unit, Some(index));
+                }
+            }
+            watching.truncate(kept);
+            self.watches[code(false_lit)] = watching;
+
+            if conflict.is_some() {
+                return conflict;
+            }
+        }
+        None
+    }
+
+    /// Assume the negation of `lits` on top of the trail
+    fn assume_negation(&mut self, lits: &[i32]) -> Assumed {
+        for &lit in lits {
+            self.grow(lit);
+            match value(&self.values, lit) {
+                0 => self.assign(-lit, None),
+                1 => {
+                    return match self.reasons[lit.unsigned_abs() as usize] {
+                        Some(reason) => Assumed::Conflict(reason),
+                        None => Assumed::Tautology,
+                    };
+                }
+                _ => {}
+            }
+        }
+        Assumed::Open
+    }
+
+    /// Ids of the clauses that derive `conflict`, in propagation order
+    fn hints(&mut self, conflict: usize) -> Vec<u64> {
+        let mut pending = 0;
+        for &lit in &self.clauses[conflict].lits {
+            let var = lit.unsigned_abs() as usize;
+            if !self.marks[var] {
+                self.marks[var] = true;
+                pending += 1;
+            }
+        }
+
+        let mut hints = vec![self.clauses[conflict].id];
+        for position in (0..self.trail.len()).rev() {
+            if pending == 0 {
+                break;
+            }
+            let var = self.trail[position].unsigned_abs() as usize;
+            if !self.marks[var] {
+                continue;
+            }
+            self.marks[var] = false;
+            pending -= 1;
+
+            // A literal of the conflict clause may be true because of that very
+            // clause, when the lemma assumed its negation
+            if let Some(reason) = self.reasons[var].filter(|&reason| reason != conflict) {
+                hints.push(self.clauses[reason].id);
+                for &lit in &self.clauses[reason].lits {
+                    let other = lit.unsigned_abs() as usize;
+                    if !self.marks[other] && self.values[other] != 0 && other != var {
+                        self.marks[other] = true;
+                        pending += 1;
+                    }
+                }
+            }
+        }
+        hints.reverse();
+        hints
+    }
+
+    /// Hints of `lits` as a RUP lemma, if unit propagation refutes its negation
+    fn rup(&mut self, lits: &[i32]) -> Option<Vec<u64>> {
+        if let Some(conflict) = self.conflict {
+            return Some(self.hints(conflict));
+        }
+
+        let length = self.trail.len();
+        let hints = match self.assume_negation(lits) {
+            Assumed::Conflict(reason) => Some(self.hints(reason)),
+            Assumed::Tautology => Some(Vec::new()),
+            Assumed::Open => self.propagate().map(|conflict| self.hints(conflict)),
+        };
+        self.undo(length);
+        hints
+    }
+
+    /// Hints of `lits` as a RAT lemma on its first literal
+    ///
+    /// Each resolution candidate contributes its negated id followed by the
+    /// hints that refute the resolvent.
+    fn rat(&mut self, lits: &[i32]) -> Option<Vec<i64>> {
+        let &pivot = lits.first()?;
+        let candidates: Vec<usize> = (0..self.clauses.len())
+            .filter(|&index| !self.clauses[index].deleted && self.clauses[index].lits.contains(&-pivot))
+            .collect();
+
+        let length = self.trail.len();
+        let mut hints = Vec::new();
+        for index in candidates {
+            hints.push(-(self.clauses[index].id as i64));
+            let resolvent: Vec<i32> = lits.iter().copied()
+                .chain(self.clauses[index].lits.iter().copied().filter(|&lit| lit != -pivot))
+                .collect();
+            let refuted = match self.assume_negation(&resolvent) {
+                Assumed::Conflict(reason) => Some(self.hints(reason)),
+                Assumed::Tautology => Some(Vec::new()),
+                Assumed::Open => self.propagate().map(|conflict| self.hints(conflict)),
+            };
+            self.undo(length);
+            hints.extend(refuted?.into_iter().map(|id| id as i64));
+        }
+        Some(hints)
+    }
+
+    /// Hints that justify `lits`, by RUP or else RAT
+    pub(crate) fn justify(&mut self, lits: &[i32]) -> Option<Vec<i64>> {
+        match self.rup(lits) {
+            Some(hints) => Some(hints.into_iter().map(|id| id as i64).collect()),
+            None => self.rat(lits),
+        }
+    }
+}
+
+/// Remove duplicate literals, keeping the first occurrence of each
+fn normalize(lits: &[i32]) -> Vec<i32> {
+    let mut normalized = Vec::with_capacity(lits.len());
+    for &lit in lits {
+        if !normalized.contains(&lit) {
+            normalized.push(lit);
+        }
+    }
+    normalized
+}
+
+/// Convert a DRAT refutation of `clauses` to LRAT
+fn convert<W: Write>(clauses: &[Vec<i32>], drat: &[u8], mut lrat: W) -> Result<()> {
+    let mut checker = Checker::new();
+    for clause in clauses {
+        checker.add(clause);
+    }
+
+    let write_lemma = |lrat: &mut W, id: u64, lits: &[i32], hints: &[i64]| -> Result<()> {
+        write!(lrat, "{}", id)?;
+        for lit in lits {
+            write!(lrat, " {}", lit)?;
+        }
+        write!(lrat, " 0")?;
+        for hint in hints {
+            write!(lrat, " {}", hint)?;
+        }
+        writeln!(lrat, " 0")?;
+        Ok(())
+    };
+
+    for (number, step) in parse_drat(drat)?.into_iter().enumerate() {
+        match step {
+            Step::Add(lits) => {
+                let lits = normalize(&lits);
+                let hints = checker.justify(&lits).ok_or_else(|| {
+                    ParkissatError::InvalidProof(format!("lemma {} of the proof is neither RUP nor RAT", number + 1))
+                })?;
+                let id = checker.add(&lits);
+                write_lemma(&mut lrat, id, &lits, &hints)?;
+                if lits.is_empty() {
+                    lrat.flush()?;
+                    return Ok(());
+                }
+            }
+            Step::Delete(lits) => {
+                if let Some(id) = checker.delete(&lits) {
+                    writeln!(lrat, "{} d {} 0", checker.last_id(), id)?;
+                }
+            }
+        }
+    }
+
+    // Kissat stops logging once it has derived a conflict at the top level
+    let hints = checker.justify(&[])
+        .ok_or_else(|| ParkissatError::InvalidProof("the proof does not derive the empty clause".to_string()))?;
+    let id = checker.add(&[]);
+    write_lemma(&mut lrat, id, &[], &hints)?;
+    lrat.flush()?;
+    Ok(())
+}
+
+/// Convert a DRAT refutation of `formula` into an LRAT proof
+///
+/// The DRAT proof may be textual or binary. Fails with `InvalidProof` when a
+/// lemma is neither RUP nor RAT, or the proof does not refute the formula.
+pub fn drat_to_lrat<R: Read, W: Write>(formula: &CnfFormula, mut drat: R, lrat: W) -> Result<()> {
+    let mut bytes = Vec::new();
+    drat.read_to_end(&mut bytes)?;
+    convert(formula.clauses(), &bytes, lrat)
+}
+
+/// Check that `proof` is a DRAT refutation of `formula`
+///
+/// The proof may be textual or binary. Returns `InvalidProof` naming the
+/// first lemma that is neither RUP nor RAT, or saying that the proof does not
+/// derive the empty clause.
+pub fn check_drat<R: Read>(formula: &CnfFormula, proof: R) -> Result<()> {
+    drat_to_lrat(formula, proof, std::io::sink())
+}
+
+/// File the workers log binary DRAT to when the configured format is LRAT
+pub(crate) fn drat_sidecar(path: &Path) -> PathBuf {
+    let mut sidecar = path.as_os_str().to_owned();
+    sidecar.push(".drat");
+    PathBuf::from(sidecar)
+}
+
+impl ParkissatSolver {
+    /// Convert the DRAT proof of the last UNSAT solve into the configured LRAT file
+    ///
+    /// `assumptions` become unit clauses numbered after the formula's clauses.
+    pub(crate) fn finish_lrat_proof(&self, assumptions: &[i32]) -> Result<()> {
+        let Some(path) = self.config.proof_output.as_deref() else {
+            return Ok(());
+        };
+        if self.config.proof_format != ProofFormat::Lrat {
+            return Ok(());
+        }
+
+        let sidecar = drat_sidecar(path);
+        let mut drat = Vec::new();
+        File::open(&sidecar)
+            .and_then(|mut file| file.read_to_end(&mut drat))
+            .map_err(|e| ParkissatError::IoError(format!("Cannot read proof file {}: {}", sidecar.display(), e)))?;
+
+        let mut clauses = self.all_clauses()?;
+        clauses.extend(assumptions.iter().map(|&lit| vec![lit]));
+        let lrat = File::create(path)
+            .map_err(|e| ParkissatError::IoError(format!("Cannot write proof file {}: {}", path.display(), e)))?;
+        convert(&clauses, &drat, BufWriter::new(lrat))?;
+
+        std::fs::remove_file(&sidecar)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cnf::pigeonhole;
+    use crate::wrapper::{SolverConfig, SolverResult};
+
+    fn all_pairs() -> CnfFormula {
+        let mut formula = CnfFormula::new();
+        for clause in [[1, 2], [-1, 2], [1, -2], [-1, -2]] {
+            formula.add_clause(&clause).unwrap();
+        }
+        formula
+    }
+
+    fn lrat(formula: &CnfFormula, drat: &[u8]) -> Result<String> {
+        let mut output = Vec::new();
+        drat_to_lrat(formula, drat, &mut output)?;
+        Ok(String::from_utf8(output).unwrap())
+    }
+
+    #[test]
+    fn test_drat_to_lrat() {
+        let expected = "5 2 0 1 2 0\n6 0 5 3 4 0\n";
+        assert_eq!(lrat(&all_pairs(), b"2 0\n0\n").unwrap(), expected);
+        assert_eq!(lrat(&all_pairs(), b"a\x04\x00a\x00").unwrap(), expected);
+
+        // The empty clause is derived even when the proof stops before it
+        assert_eq!(lrat(&all_pairs(), b"c lemma\n2 0\n").unwrap(), expected);
+    }
+
+    #[test]
+    fn test_deletions_and_rat() {
+        // 3 is a fresh variable, so `3 0` holds by RAT; its deletion is ignored as a unit
+        let output = lrat(&all_pairs(), b"3 0\nd 3 0\n2 0\nd 1 2 0\n0\n").unwrap();
+        assert_eq!(output, "5 3 0 0\n6 2 0 1 2 0\n6 d 1 0\n7 0 6 3 4 0\n");
+    }
+
+    #[test]
+    fn test_invalid_proof() {
+        let mut formula = CnfFormula::new();
+        formula.add_clause(&[1, 2]).unwrap();
+        formula.add_clause(&[-1, 2]).unwrap();
+
+        assert!(matches!(lrat(&formula, b"-2 0\n"), Err(ParkissatError::InvalidProof(_))));
+        assert!(matches!(lrat(&formula, b"2 0\n"), Err(ParkissatError::InvalidProof(_))));
+        assert!(matches!(lrat(&formula, b"2 x 0\n"), Err(ParkissatError::ParseError { line: 1, .. })));
+    }
+
+    #[test]
+    fn test_check_drat() {
+        assert_eq!(check_drat(&all_pairs(), &b"2 0\n0\n"[..]), Ok(()));
+        assert_eq!(check_drat(&all_pairs(), &b"a\x04\x00"[..]), Ok(()));
+
+        let err = check_drat(&all_pairs(), &b"3 0\n-3 0\n"[..]).unwrap_err();
+        assert_eq!(err, ParkissatError::InvalidProof("lemma 2 of the proof is neither RUP nor RAT".to_string()));
+
+        let mut satisfiable = CnfFormula::new();
+        satisfiable.add_clause(&[1, 2]).unwrap();
+        assert!(matches!(check_drat(&satisfiable, &b""[..]), Err(ParkissatError::InvalidProof(_))));
+    }
+
+    #[test]
+    fn test_check_solver_proof() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("proof.drat");
+
+        let formula = pigeonhole(4, 3);
+
+        for format in [ProofFormat::Drat, ProofFormat::BinaryDrat] {
+            let mut solver = ParkissatSolver::new().unwrap();
+            solver.configure(&SolverConfig {
+                proof_output: Some(path.clone()),
+                proof_format: format,
+                ..SolverConfig::default()
+            }).unwrap();
+            solver.add_formula(&formula).unwrap();
+            assert_eq!(solver.solve().unwrap(), SolverResult::Unsat);
+
+            assert_eq!(check_drat(&formula, File::open(&path).unwrap()), Ok(()));
+        }
+    }
+
+    #[test]
+    fn test_lrat_proof_output() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("proof.lrat");
+
+        let mut solver = ParkissatSolver::new().unwrap();
+        solver.configure(&SolverConfig {
+            proof_output: Some(path.clone()),
+            proof_format: ProofFormat::Lrat,
+            ..SolverConfig::default()
+        }).unwrap();
+
+        solver.add_formula(&pigeonhole(4, 3)).unwrap();
+        assert_eq!(solver.solve().unwrap(), SolverResult::Unsat);
+
+        let proof = std::fs::read_to_string(&path).unwrap();
+        let last = proof.lines().last().unwrap();
+        let mut tokens = last.split_whitespace().skip(1);
+        assert_eq!(tokens.next(), Some("0"), "the proof ends with the empty clause");
+        assert!(!drat_sidecar(&path).exists());
+    }
+}
@@ -0,0 +1,368 @@
+//! Unit propagation without search
+//!
+//! [`ParkissatSolver::propagate`] assigns the assumptions one after the other
+//! and runs unit propagation after each, the way a CDCL solver treats
+//! decisions, but never branches. It answers which literals the assumptions
+//! force, or which clause they falsify, far faster than a full solve — the
+//! cheap consistency check CP and SMT integrations issue constantly.
+//!
+//! Kissat offers no propagation entry point, so the clauses are copied into a
+//! watched-literal propagator on the Rust side. The copy is built on the
+//! first call and rebuilt only after clauses have been added; DIMACS files
+//! loaded with [`load_dimacs`](ParkissatSolver::load_dimacs) are parsed again
+//! for it. Enabled clause groups, push scopes, and editable clauses take part
+//! just as they do in a solve.
+
+use crate::dimacs;
+use crate::error::{ParkissatError, Result};
+use crate::ffi;
+use crate::wrapper::ParkissatSolver;
+
+/// Outcome of [`ParkissatSolver::propagate`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PropagationResult {
+    /// No conflict; the literals implied besides the assumptions, in propagation order
+    ///
+    /// Literals forced by unit clauses of the formula are included.
+    Consistent(Vec<i32>),
+    /// Propagation falsified this clause
+    Conflict(Vec<i32>),
+    /// This assumption was already false when its turn came
+    FailedAssumption(i32),
+}
+
+/// Watched-literal copy of the solver's clauses
+#[derive(Debug, Default)]
+pub(crate) struct Propagator {
+    clauses: Vec<Vec<i32>>,
+    /// Clauses watching a literal, indexed by `code(lit)`
+    watches: Vec<Vec<usize>>,
+    units: Vec<usize>,
+    /// Clause count of the solver the copy was built from
+    num_clauses: usize,
+}
+
+fn code(lit: i32) -> usize {
+    2 * lit.unsigned_abs() as usize + usize::from(lit < 0)
+}
+
+/// Partial assignment of a single propagation run
+struct Trail {
+    /// 1 = true, -1 = false, 0 = unassigned, indexed by variable
+    values: Vec<i8>,
+    lits: Vec<i32>,
+    head: usize,
+}
+
+impl Trail {
+    fn value(&self, lit: i32) -> i8 {
+        let value = self.values.get(lit.unsigned_abs() as usize).copied().unwrap_or(0);
+        if lit < 0 { -value } else { value }
+    }
+
+    fn assign(&mut self, lit: i32) {
+        let var = lit.unsigned_abs() as usize;
+        if var >= self.values.len() {
+            self.values.resize(var + 1, 0);
+        }
+        self.values[var] = if lit < 0 { -1 } else { 1 };
+        self.lits.push(lit);
+    }
+}
+
+impl Propagator {
+    pub(crate) fn new(clauses: impl IntoIterator<Item = Vec<i32>>, num_clauses: usize) -> Self {
+        let mut propagator = Self {
+            num_clauses,
+            ..Self::default()
+        };
+
+        for mut clause in clauses {
+            clause.sort_unstable();
+            clause.dedup();
+            // Tautologies never propagate
+            if clause.iter().any(|lit| clause.contains(&-lit)) {
+                continue;
+            }
+
+            let index = propagator.clauses.len();
+            if clause.len() == 1 {
+                propagator.units.push(index);
+            } else {
+                for &lit in &clause[..2] {
+                    let code = code(lit);
+                    if code >= propagator.watches.len() {
+                        propagator.watches.resize(code + 1, Vec::new());
+                    }
+                    propagator.watches[code].push(index);
+                }
+            }
+            propagator.clauses.push(clause);
+        }
+
+        propagator
+    }
+
+    /// Propagate the trail from its head; returns a falsified clause if any
+    fn propagate(&mut self, trail: &mut Trail) -> Option<usize> {
+        while trail.head < trail.lits.len() {
+            let false_lit = -trail.lits[trail.head];
+            trail.head += 1;
+
+            let Some(watchers) = self.watches.get_mut(code(false_lit)) else { continue };
+            let mut watchers = std::mem::take(watchers);
+            let mut conflict = None;
+            let mut i = 0;
+            while i < watchers.len() {
+                let index = watchers[i];
+                let clause = &mut self.clauses[index];
+                if clause[0] == false_lit {
+                    clause.swap(0, 1);
+                }
+                if trail.value(clause[0]) > 0 {
+                    i += 1;
+                    continue;
+                }
+
+                // Move the watch to a literal that is not false, if there is one
+                if let Some(k) = (2..clause.len()).find(|&k| trail.value(clause[k]) >= 0) {
+                    clause.swap(1, k);
+                    let code = code(clause[1]);
+                    if code >= self.watches.len() {
+                        self.watches.resize(code + 1, Vec::new());
+                    }
+                    self.watches[code].push(index);
+                    watchers.swap_remove(i);
+                    continue;
+                }
+
+                if trail.value(clause[0]) < 0 {
+                    conflict = Some(index);
+                    break;
+                }
+                trail.assign(clause[0]);
+                i += 1;
+            }
+
+            self.watches[code(false_lit)] = watchers;
+            if conflict.is_some() {
+                return conflict;
+            }
+        }
+        None
+    }
+
+    pub(crate) fn run(&mut self, internal: &[i32], assumptions: &[i32]) -> PropagationResult {
+        let mut trail = Trail {
+            values: Vec::new(),
+            lits: Vec::new(),
+            head: 0,
+        };
+        if let Some(stopped) = self.run_on(&mut trail, internal, assumptions) {
+            return stopped;
+        }
+
+        let implied = trail.lits.into_iter()
+            .filter(|lit| !internal.contains(lit) && !assumptions.contains(lit))
+            .collect();
+        PropagationResult::Consistent(implied)
+    }
+
+    /// Every literal assigned before propagation stops, including the
+    /// assumptions taken so far
+    pub(crate) fn forced(&mut self, internal: &[i32], assumptions: &[i32]) -> Vec<i32> {
+        let mut trail = Trail {
+            values: Vec::new(),
+            lits: Vec::new(),
+            head: 0,
+        };
+        self.run_on(&mut trail, internal, assumptions);
+        trail.lits
+    }
+
+    /// Propagate units, then each assumption in turn; returns the conflict
+    /// or failed assumption that stopped propagation, if any
+    fn run_on(&mut self, trail: &mut Trail, internal: &[i32], assumptions: &[i32]) -> Option<PropagationResult> {
+        for index in 0..self.units.len() {
+            let lit = self.clauses[self.units[index]][0];
+            match trail.value(lit) {
+                0 => trail.assign(lit),
+                value if value < 0 => return Some(PropagationResult::Conflict(self.clauses[self.units[index]].clone())),
+                _ => {}
+            }
+        }
+        if let Some(index) = self.propagate(trail) {
+            return Some(PropagationResult::Conflict(self.clauses[index].clone()));
+        }
+
+        for &lit in internal.iter().chain(assumptions) {
+            match trail.value(lit) {
+                0 => trail.assign(lit),
+                value if value < 0 => return Some(PropagationResult::FailedAssumption(lit)),
+                _ => continue,
+            }
+            if let Some(index) = self.propagate(trail) {
+                return Some(PropagationResult::Conflict(self.clauses[index].clone()));
+            }
+        }
+        None
+    }
+}
+
+impl ParkissatSolver {
+    /// Unit-propagate the assumptions against the clauses, without searching
+    ///
+    /// Literals implied by selectors of clause groups, push scopes, and
+    /// editable clauses are reported like any other; the selectors themselves
+    /// are not.
+    pub fn propagate(&mut self, assumptions: &[i32]) -> Result<PropagationResult> {
+        if !self.is_configured() {
+            return Err(ParkissatError::NotConfigured);
+        }
+        if assumptions.contains(&0) {
+            return Err(ParkissatError::InvalidClause("Assumption cannot be zero".to_string()));
+        }
+
+        let internal = self.refresh_propagator()?;
+        let propagator = self.propagator.get_or_insert_with(Propagator::default);
+        Ok(match propagator.run(&internal, assumptions) {
+            PropagationResult::Consistent(mut implied) => {
+                // Retired selectors are implied false by their units
+                implied.retain(|&lit| !self.groups.is_selector(lit));
+                PropagationResult::Consistent(implied)
+            }
+            result => result,
+        })
+    }
+
+    /// Value of `literal` forced under the assumptions of the last solve
+    ///
+    /// Unlike [`get_model_value`](Self::get_model_value), this works
+    /// whatever the last solve answered. A literal is forced if it was
+    /// assumed, fixed at decision level 0 by the workers, or implied by unit
+    /// propagation of both; when propagation runs into a conflict, as it
+    /// often does after UNSAT, the literals forced before the conflict are
+    /// reported. `None` means the literal was not forced. Fails with
+    /// `NoSolution` before the first solve.
+    pub fn value_under_assumptions(&mut self, literal: i32) -> Result<Option<bool>> {
+        if !self.is_configured() {
+            return Err(ParkissatError::NotConfigured);
+        }
+        if literal == 0 || literal == i32::MIN {
+            return Err(ParkissatError::InvalidVariable(literal));
+        }
+
+        let assumptions = self.last_assumptions.clone().ok_or(ParkissatError::NoSolution)?;
+        let var = literal.abs();
+        let value_in = |lits: &[i32]| {
+            lits.iter().find(|lit| lit.abs() == var).map(|&lit| (lit > 0) == (literal > 0))
+        };
+        if let Some(value) = value_in(&assumptions) {
+            return Ok(Some(value));
+        }
+        if let Some(value) = self.fixed_value(var)? {
+            return Ok(Some(value == (literal > 0)));
+        }
+
+        let mut seeds = self.fixed_literals();
+        seeds.extend_from_slice(&assumptions);
+        let internal = self.refresh_propagator()?;
+        let propagator = self.propagator.get_or_insert_with(Propagator::default);
+        Ok(value_in(&propagator.forced(&internal, &seeds)))
+    }
+
+    /// Rebuild the clause copy if clauses were added since; returns the
+    /// selectors assumed internally on every solve
+    fn refresh_propagator(&mut self) -> Result<Vec<i32>> {
+        let num_clauses = self.clause_count();
+        if self.propagator.as_ref().map(|propagator| propagator.num_clauses) != Some(num_clauses) {
+            self.propagator = Some(Propagator::new(self.all_clauses()?, num_clauses));
+        }
+
+        let mut internal = self.editable.selectors();
+        internal.extend(self.groups.enabled_selectors());
+        Ok(internal)
+    }
+
+    /// Every clause given to the solver, through the API or in DIMACS files
+    pub(crate) fn all_clauses(&self) -> Result<Vec<Vec<i32>>> {
+        let length = unsafe { ffi::parkissat_get_clauses_size(self.solver) };
+        let mut literals = vec![0; length];
+        unsafe {
+            ffi::parkissat_get_clauses(self.solver, literals.as_mut_ptr(), length);
+        }
+
+        let mut clauses: Vec<Vec<i32>> = literals.split(|&lit| lit == 0)
+            .filter(|clause| !clause.is_empty())
+            .map(<[i32]>::to_vec)
+            .collect();
+        for path in &self.dimacs_files {
+            clauses.extend(dimacs::parse_file(path)?.clauses().iter().cloned());
+        }
+        Ok(clauses)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cnf::configured_solver;
+    use crate::wrapper::SolverResult;
+
+    #[test]
+    fn test_implied_literals() {
+        let mut solver = configured_solver();
+        solver.add_clauses(&[-1, 2, 0, -2, 3, 0, -2, -3, 4, 0, 5, 0]).unwrap();
+
+        assert_eq!(solver.propagate(&[]).unwrap(), PropagationResult::Consistent(vec![5]));
+        assert_eq!(solver.propagate(&[1]).unwrap(), PropagationResult::Consistent(vec![5, 2, 3, 4]));
+        assert_eq!(solver.propagate(&[-4, 1]).unwrap(), PropagationResult::Conflict(vec![-3, -2, 4]));
+        assert_eq!(solver.propagate(&[-5]).unwrap(), PropagationResult::FailedAssumption(-5));
+        assert_eq!(solver.propagate(&[1, -2]).unwrap(), PropagationResult::FailedAssumption(-2));
+
+        // Clauses added later are picked up
+        solver.add_clause(&[-4, 6]).unwrap();
+        assert_eq!(solver.propagate(&[4]).unwrap(), PropagationResult::Consistent(vec![5, 6]));
+        assert!(solver.propagate(&[0]).is_err());
+    }
+
+    #[test]
+    fn test_value_under_assumptions() {
+        let mut solver = configured_solver();
+        solver.add_clauses(&[-1, 2, 0, -2, 3, 0, -3, -4, 0, 5, 0, 6, 7, 0]).unwrap();
+        assert_eq!(solver.value_under_assumptions(2).unwrap_err(), ParkissatError::NoSolution);
+
+        // Values forced on the way to the conflict remain visible after UNSAT
+        assert_eq!(solver.solve_with_assumptions(&[1, 4]).unwrap(), SolverResult::Unsat);
+        assert_eq!(solver.value_under_assumptions(1).unwrap(), Some(true));
+        assert_eq!(solver.value_under_assumptions(-4).unwrap(), Some(false));
+        assert_eq!(solver.value_under_assumptions(3).unwrap(), Some(true));
+        assert_eq!(solver.value_under_assumptions(-2).unwrap(), Some(false));
+        assert_eq!(solver.value_under_assumptions(5).unwrap(), Some(true));
+        assert_eq!(solver.value_under_assumptions(6).unwrap(), None);
+
+        assert_eq!(solver.solve().unwrap(), SolverResult::Sat);
+        assert_eq!(solver.value_under_assumptions(5).unwrap(), Some(true));
+        assert_eq!(solver.value_under_assumptions(3).unwrap(), None);
+        assert!(solver.value_under_assumptions(0).is_err());
+    }
+
+    #[test]
+    fn test_groups_and_files() {
+        use std::io::Write;
+
+        let mut solver = configured_solver();
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(file, "p cnf 2 1\n-1 2 0").unwrap();
+        solver.load_dimacs(file.path()).unwrap();
+        solver.set_variable_count(2).unwrap();
+
+        let group = solver.new_clause_group().unwrap();
+        solver.add_clause_to_group(group, &[-2, -1]).unwrap();
+        assert!(matches!(solver.propagate(&[1]).unwrap(), PropagationResult::Conflict(clause) if clause.len() == 3));
+        assert_eq!(solver.propagate(&[2]).unwrap(), PropagationResult::Consistent(vec![-1]));
+
+        solver.disable_group(group).unwrap();
+        assert_eq!(solver.propagate(&[1]).unwrap(), PropagationResult::Consistent(vec![2]));
+    }
+}
@@ -0,0 +1,131 @@
+//! rustsat solver traits
+//!
+//! With the `rustsat` feature [`ParkissatSolver`] implements
+//! [`rustsat::solvers::Solve`] and [`rustsat::solvers::SolveIncremental`], so
+//! MaxSAT, pseudo-boolean, and other tools built on rustsat can use it as
+//! their SAT backend. Configure the solver before handing it over; rustsat
+//! has no notion of threads or portfolios.
+//!
+//! Where a trait method shares its name with an inherent method (`solve`,
+//! `add_clause`), method-call syntax picks the inherent one; call the trait
+//! version as `Solve::solve(&mut solver)`.
+
+use crate::error::ParkissatError;
+use crate::wrapper::{ParkissatSolver, SolverResult};
+use rustsat::solvers::{self, Solve, SolveIncremental};
+use rustsat::types::{Cl, Clause, Lit, TernaryVal};
+
+fn from_result(result: SolverResult) -> solvers::SolverResult {
+    match result {
+        SolverResult::Sat => solvers::SolverResult::Sat,
+        SolverResult::Unsat => solvers::SolverResult::Unsat,
+        SolverResult::Unknown => solvers::SolverResult::Interrupted,
+    }
+}
+
+impl Solve for ParkissatSolver {
+    fn signature(&self) -> &'static str {
+        concat!("parkissat-sys-", env!("CARGO_PKG_VERSION"))
+    }
+
+    fn solve(&mut self) -> anyhow::Result<solvers::SolverResult> {
+        Ok(from_result(ParkissatSolver::solve(self)?))
+    }
+
+    fn lit_val(&self, lit: Lit) -> anyhow::Result<TernaryVal> {
+        let value = match self.get_model_value(lit.var().to_ipasir()) {
+            Ok(value) => value,
+            // Variables the formula never mentioned can take either value
+            Err(ParkissatError::InvalidVariable(_)) => return Ok(TernaryVal::DontCare),
+            Err(err) => return Err(err.into()),
+        };
+        Ok(if value == lit.is_pos() {
+            TernaryVal::True
+        } else {
+            TernaryVal::False
+        })
+    }
+
+    fn add_clause_ref<C>(&mut self, clause: &C) -> anyhow::Result<()>
+    where
+        C: AsRef<Cl> + ?Sized,
+    {
+        let literals: Vec<i32> = clause.as_ref().iter().map(|lit| lit.to_ipasir()).collect();
+        ParkissatSolver::add_clause(self, &literals)?;
+        Ok(())
+    }
+}
+
+impl SolveIncremental for ParkissatSolver {
+    fn solve_assumps(&mut self, assumps: &[Lit]) -> anyhow::Result<solvers::SolverResult> {
+        let assumptions: Vec<i32> = assumps.iter().map(|lit| lit.to_ipasir()).collect();
+        Ok(from_result(self.solve_with_assumptions(&assumptions)?))
+    }
+
+    /// The negated failed assumptions of the last UNSAT answer
+    fn core(&mut self) -> anyhow::Result<Vec<Lit>> {
+        let failed = self.failed_assumptions()?;
+        Ok(failed.into_iter().filter_map(|lit| Lit::from_ipasir(-lit).ok()).collect())
+    }
+}
+
+impl Extend<Clause> for ParkissatSolver {
+    fn extend<T: IntoIterator<Item = Clause>>(&mut self, iter: T) {
+        for clause in iter {
+            self.add_clause_ref(&clause).expect("Error adding clause in extend");
+        }
+    }
+}
+
+impl<'a> Extend<&'a Clause> for ParkissatSolver {
+    fn extend<T: IntoIterator<Item = &'a Clause>>(&mut self, iter: T) {
+        for clause in iter {
+            self.add_clause_ref(clause).expect("Error adding clause in extend");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wrapper::SolverConfig;
+    use rustsat::types::Var;
+
+    fn clause(lits: &[Lit]) -> Clause {
+        lits.iter().copied().collect()
+    }
+
+    // Generic over the traits, as a rustsat-based tool would use the solver
+    fn solve_and_check<S: SolveIncremental>(solver: &mut S) {
+        let (a, b, c) = (Lit::positive(0), Lit::positive(1), Lit::positive(2));
+        solver.extend([clause(&[a, b]), clause(&[!a, c])]);
+        solver.add_clause(clause(&[!b])).unwrap();
+
+        assert_eq!(solver.solve().unwrap(), solvers::SolverResult::Sat);
+        assert_eq!(solver.lit_val(a).unwrap(), TernaryVal::True);
+        assert_eq!(solver.lit_val(!b).unwrap(), TernaryVal::True);
+        assert_eq!(solver.var_val(Var::new(2)).unwrap(), TernaryVal::True);
+        assert_eq!(solver.var_val(Var::new(9)).unwrap(), TernaryVal::DontCare);
+
+        assert_eq!(solver.solve_assumps(&[!c]).unwrap(), solvers::SolverResult::Unsat);
+        assert_eq!(solver.core().unwrap(), vec![c]);
+    }
+
+    #[test]
+    fn test_solve_incremental() {
+        let mut solver = ParkissatSolver::new().unwrap();
+        solver.configure(&SolverConfig::default()).unwrap();
+        assert!(solver.signature().starts_with("parkissat-sys-"));
+        solve_and_check(&mut solver);
+    }
+
+    #[test]
+    fn test_errors_before_solving() {
+        let mut solver = ParkissatSolver::new().unwrap();
+        assert!(solver.add_clause_ref(&clause(&[Lit::positive(0)])).is_err());
+
+        solver.configure(&SolverConfig::default()).unwrap();
+        assert!(solver.lit_val(Lit::positive(0)).is_err());
+        assert!(solver.core().is_err());
+    }
+}
@@ -0,0 +1,283 @@
+//! Wall-clock budget scheduling across a queue of instances
+//!
+//! [`BudgetScheduler`] spreads a global time budget over many instances to
+//! maximize the number solved. Instances are attempted in rounds; each round
+//! gives every unsolved instance a time slice, and an instance that times out
+//! is restarted from scratch in a later round with the slice for that round.
+//! Easy instances are therefore solved early with short slices, while the
+//! remaining budget flows to the hard ones.
+
+use crate::error::{ParkissatError, Result};
+use crate::wrapper::{ParkissatSolver, SolverConfig, SolverResult};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// How time slices grow from one round to the next
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SchedulePolicy {
+    /// Slice of round `r` is `unit * luby(r)` (1, 1, 2, 1, 1, 2, 4, ...)
+    Luby {
+        /// Base slice length
+        unit: Duration,
+    },
+    /// Slice of round `r` is `initial * factor^r`
+    Geometric {
+        /// Slice length of the first round
+        initial: Duration,
+        /// Growth factor per round (must be at least 1)
+        factor: f64,
+    },
+}
+
+impl SchedulePolicy {
+    /// Time slice granted to each unsolved instance in the given round (0-based)
+    pub fn slice(&self, round: u32) -> Duration {
+        match *self {
+            SchedulePolicy::Luby { unit } => unit * luby(round as u64 + 1) as u32,
+            SchedulePolicy::Geometric { initial, factor } => initial.mul_f64(factor.powi(round as i32)),
+        }
+    }
+}
+
+/// What happened to one instance of the queue
+#[derive(Debug, Clone)]
+pub struct InstanceOutcome {
+    /// Position of the instance in the queue
+    pub index: usize,
+    /// Final result (`Unknown` if never solved)
+    pub result: SolverResult,
+    /// Number of attempts made
+    pub attempts: u32,
+    /// Total budget charged to this instance
+    pub time_spent: Duration,
+    /// Error that removed the instance from the queue, if any
+    pub error: Option<ParkissatError>,
+}
+
+/// Results of a scheduling run
+#[derive(Debug, Clone)]
+pub struct ScheduleReport {
+    /// One outcome per instance, in queue order
+    pub outcomes: Vec<InstanceOutcome>,
+    /// Total budget consumed
+    pub elapsed: Duration,
+    /// Number of rounds started
+    pub rounds: u32,
+}
+
+impl ScheduleReport {
+    /// Number of instances with a definite (SAT or UNSAT) answer
+    pub fn solved(&self) -> usize {
+        self.outcomes
+            .iter()
+            .filter(|outcome| outcome.result != SolverResult::Unknown)
+            .count()
+    }
+}
+
+/// Distributes a global wall-clock budget across a queue of instances
+#[derive(Debug, Clone)]
+pub struct BudgetScheduler {
+    budget: Duration,
+    policy: SchedulePolicy,
+    config: SolverConfig,
+}
+
+impl BudgetScheduler {
+    /// Create a scheduler with the given total budget and slice policy
+    pub fn new(budget: Duration, policy: SchedulePolicy) -> Result<Self> {
+        let valid = match policy {
+            SchedulePolicy::Luby { unit } => !unit.is_zero(),
+            SchedulePolicy::Geometric { initial, factor } => !initial.is_zero() && factor >= 1.0,
+        };
+        if !valid {
+            return Err(ParkissatError::InvalidConfiguration(
+                "Schedule slices must be positive and non-decreasing".to_string()
+            ));
+        }
+
+        Ok(Self {
+            budget,
+            policy,
+            config: SolverConfig::default(),
+        })
+    }
+
+    /// Use the given solver configuration for every attempt (its timeout is replaced by the slice)
+    pub fn with_config(mut self, config: SolverConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Solve a queue of DIMACS files within the budget
+    pub fn run<P: AsRef<Path>>(&self, instances: &[P]) -> Result<ScheduleReport> {
+        self.run_with(instances.len(), |index, slice| {
+            let config = SolverConfig {
+                timeout: slice,
+                ..self.config.clone()
+            };
+
+            let mut solver = ParkissatSolver::new()?;
+            solver.configure(&config)?;
+            solver.load_dimacs(instances[index].as_ref())?;
+            solver.solve()
+        })
+    }
+
+    /// Schedule `count` instances using a custom attempt function
+    ///
+    /// `attempt(index, slice)` must try to solve instance `index` within
+    /// `slice`. An attempt that returns `Unknown` is charged its full slice;
+    /// one that returns an error removes the instance from the queue.
+    pub fn run_with<F>(&self, count: usize, mut attempt: F) -> Result<ScheduleReport>
+    where
+        F: FnMut(usize, Duration) -> Result<SolverResult>,
+    {
+        let mut outcomes: Vec<InstanceOutcome> = (0..count)
+            .map(|index| InstanceOutcome {
+                index,
+                result: SolverResult::Unknown,
+                attempts: 0,
+                time_spent: Duration::ZERO,
+                error: None,
+            })
+            .collect();
+
+        let mut spent = Duration::ZERO;
+        let mut round = 0;
+
+        loop {
+            let pending: Vec<usize> = outcomes
+                .iter()
+                .filter(|outcome| outcome.result == SolverResult::Unknown && outcome.error.is_none())
+                .map(|outcome| outcome.index)
+                .collect();
+
+            if pending.is_empty() || spent >= self.budget {
+                break;
+            }
+
+            let slice = self.policy.slice(round);
+            round += 1;
+
+            for index in pending {
+                let remaining = self.budget.saturating_sub(spent);
+                if remaining.is_zero() {
+                    break;
+                }
+                let granted = slice.min(remaining);
+
+                let start = Instant::now();
+                let outcome_result = attempt(index, granted);
+                let measured = start.elapsed();
+
+                let outcome = &mut outcomes[index];
+                outcome.attempts += 1;
+
+                let charged = match outcome_result {
+                    Ok(SolverResult::Unknown) => granted.max(measured),
+                    Ok(result) => {
+                        outcome.result = result;
+                        measured
+                    }
+                    Err(err) => {
+                        outcome.error = Some(err);
+                        measured
+                    }
+                };
+
+                outcome.time_spent += charged;
+                spent += charged;
+            }
+        }
+
+        Ok(ScheduleReport {
+            outcomes,
+            elapsed: spent,
+            rounds: round,
+        })
+    }
+}
+
+/// The Luby sequence (1-based): 1, 1, 2, 1, 1, 2, 4, 1, 1, 2, ...
+fn luby(mut i: u64) -> u64 {
+    loop {
+        let mut k = 1;
+        while (1u64 << k) - 1 < i {
+            k += 1;
+        }
+        if (1u64 << k) - 1 == i {
+            return 1u64 << (k - 1);
+        }
+        i = i - (1u64 << (k - 1)) + 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_luby_sequence() {
+        let sequence: Vec<u64> = (1..=15).map(luby).collect();
+        assert_eq!(sequence, vec![1, 1, 2, 1, 1, 2, 4, 1, 1, 2, 1, 1, 2, 4, 8]);
+    }
+
+    #[test]
+    fn test_policy_slices() {
+        let luby = SchedulePolicy::Luby { unit: Duration::from_secs(2) };
+        assert_eq!(luby.slice(0), Duration::from_secs(2));
+        assert_eq!(luby.slice(2), Duration::from_secs(4));
+
+        let geometric = SchedulePolicy::Geometric { initial: Duration::from_secs(1), factor: 2.0 };
+        assert_eq!(geometric.slice(3), Duration::from_secs(8));
+
+        assert!(BudgetScheduler::new(Duration::from_secs(10), SchedulePolicy::Luby { unit: Duration::ZERO }).is_err());
+    }
+
+    #[test]
+    fn test_schedule_solves_easy_instances_first() {
+        // Instance i needs a slice of at least `needed[i]` seconds
+        let needed = [1u64, 4, 100];
+        let policy = SchedulePolicy::Geometric { initial: Duration::from_secs(1), factor: 2.0 };
+        let scheduler = BudgetScheduler::new(Duration::from_secs(30), policy).unwrap();
+
+        let report = scheduler
+            .run_with(needed.len(), |index, slice| {
+                if slice >= Duration::from_secs(needed[index]) {
+                    Ok(SolverResult::Sat)
+                } else {
+                    Ok(SolverResult::Unknown)
+                }
+            })
+            .unwrap();
+
+        assert_eq!(report.solved(), 2);
+        assert_eq!(report.outcomes[0].attempts, 1);
+        assert_eq!(report.outcomes[1].attempts, 3);
+        assert_eq!(report.outcomes[2].result, SolverResult::Unknown);
+        assert!(report.elapsed <= Duration::from_secs(30) + Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_schedule_drops_failing_instances() {
+        let policy = SchedulePolicy::Luby { unit: Duration::from_secs(1) };
+        let scheduler = BudgetScheduler::new(Duration::from_secs(5), policy).unwrap();
+
+        let report = scheduler
+            .run_with(2, |index, _| {
+                if index == 0 {
+                    Err(ParkissatError::IoError("missing".to_string()))
+                } else {
+                    Ok(SolverResult::Unknown)
+                }
+            })
+            .unwrap();
+
+        assert_eq!(report.outcomes[0].attempts, 1);
+        assert!(report.outcomes[0].error.is_some());
+        assert_eq!(report.outcomes[1].attempts, 4);
+        assert_eq!(report.elapsed, Duration::from_secs(5));
+        assert_eq!(report.solved(), 0);
+    }
+}
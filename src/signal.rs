@@ -0,0 +1,163 @@
+//! Interrupting solvers on SIGINT and SIGTERM
+//!
+//! Available with the `signal` feature on Unix. [`interrupt_on_signal`]
+//! registers an [`InterruptHandle`] so that Ctrl-C or a termination request
+//! stops the solver's current search instead of killing the process. The
+//! solve then returns `Unknown` with
+//! [`UnknownReason::Interrupted`](crate::UnknownReason::Interrupted), and a
+//! command-line tool can still print statistics before it exits. A second
+//! signal exits the process right away, for searches that do not stop.
+//!
+//! Signal handlers may only call async-signal-safe functions, which rules out
+//! the lock inside the handle. The handler therefore just writes a byte to a
+//! pipe; a helper thread blocked on the other end interrupts the registered
+//! handles. The handlers are installed on first registration and stay
+//! installed for the life of the process.
+
+use crate::error::{ParkissatError, Result};
+use crate::interrupt::InterruptHandle;
+use crate::wrapper::ParkissatSolver;
+use std::io;
+use std::ptr;
+use std::sync::atomic::{AtomicI32, AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::thread;
+
+/// Number of the first signal received, 0 = none
+static RECEIVED: AtomicI32 = AtomicI32::new(0);
+/// Write end of the pipe to the helper thread
+static PIPE: AtomicI32 = AtomicI32::new(-1);
+static HANDLES: Mutex<Vec<(u64, InterruptHandle)>> = Mutex::new(Vec::new());
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+static INSTALLED: OnceLock<std::result::Result<(), String>> = OnceLock::new();
+
+/// Keeps a handle registered for signals; dropping it unregisters the handle
+#[derive(Debug)]
+#[must_use = "the handle is unregistered when the guard is dropped"]
+pub struct SignalGuard {
+    id: u64,
+}
+
+impl Drop for SignalGuard {
+    fn drop(&mut self) {
+        let mut handles = HANDLES.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        handles.retain(|(id, _)| *id != self.id);
+    }
+}
+
+/// Interrupt the solver behind `handle` when SIGINT or SIGTERM arrives
+///
+/// The handle stays registered until the returned guard is dropped. Like
+/// any interrupt, a signal that arrives between solves cancels the next one.
+pub fn interrupt_on_signal(handle: &InterruptHandle) -> Result<SignalGuard> {
+    install()?;
+
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    let mut handles = HANDLES.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    handles.push((id, handle.clone()));
+    Ok(SignalGuard { id })
+}
+
+/// The first SIGINT or SIGTERM received since the handlers were installed, if any
+pub fn signal_received() -> Option<i32> {
+    match RECEIVED.load(Ordering::SeqCst) {
+        0 => None,
+        signal => Some(signal),
+    }
+}
+
+impl ParkissatSolver {
+    /// Interrupt this solver when SIGINT or SIGTERM arrives, until the guard is dropped
+    pub fn interrupt_on_signal(&self) -> Result<SignalGuard> {
+        interrupt_on_signal(&self.interrupt_handle())
+    }
+}
+
+extern "C" fn on_signal(signal: libc::c_int) {
+    if RECEIVED.compare_exchange(0, signal, Ordering::SeqCst, Ordering::SeqCst).is_err() {
+        unsafe {
+            libc::_exit(128 + signal);
+        }
+    }
+
+    let byte = 1u8;
+    unsafe {
+        libc::write(PIPE.load(Ordering::SeqCst), &byte as *const u8 as *const libc::c_void, 1);
+    }
+}
+
+fn install() -> Result<()> {
+    INSTALLED
+        .get_or_init(|| install_handlers().map_err(|err| format!("Cannot install signal handlers: {}", err)))
+        .clone()
+        .map_err(ParkissatError::IoError)
+}
+
+fn install_handlers() -> io::Result<()> {
+    let mut fds = [0; 2];
+    if unsafe { libc::pipe(fds.as_mut_ptr()) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    let [read, write] = fds;
+    PIPE.store(write, Ordering::SeqCst);
+
+    thread::Builder::new()
+        .name("parkissat-signal".to_string())
+        .spawn(move || loop {
+            let mut byte = 0u8;
+            let n = unsafe { libc::read(read, &mut byte as *mut u8 as *mut libc::c_void, 1) };
+            if n < 0 && io::Error::last_os_error().kind() == io::ErrorKind::Interrupted {
+                continue;
+            }
+            if n <= 0 {
+                return;
+            }
+
+            let handles = HANDLES.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+            for (_, handle) in handles.iter() {
+                handle.interrupt();
+            }
+        })?;
+
+    for signal in [libc::SIGINT, libc::SIGTERM] {
+        unsafe {
+            let mut action: libc::sigaction = std::mem::zeroed();
+            action.sa_sigaction = on_signal as extern "C" fn(libc::c_int) as libc::sighandler_t;
+            action.sa_flags = libc::SA_RESTART;
+            libc::sigemptyset(&mut action.sa_mask);
+            if libc::sigaction(signal, &action, ptr::null_mut()) != 0 {
+                return Err(io::Error::last_os_error());
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cnf::pigeonhole;
+    use crate::wrapper::{SolverConfig, SolverResult, UnknownReason};
+
+    #[test]
+    fn test_interrupt_on_signal() {
+        let mut solver = ParkissatSolver::new().unwrap();
+        solver.configure(&SolverConfig::default()).unwrap();
+
+        // Takes far longer than the test
+        solver.add_formula(&pigeonhole(13, 12)).unwrap();
+
+        let guard = solver.interrupt_on_signal().unwrap();
+        assert_eq!(signal_received(), None);
+
+        let background = solver.solve_async();
+        unsafe {
+            libc::raise(libc::SIGINT);
+        }
+        let (solver, result) = background.join();
+        assert_eq!(result.unwrap(), SolverResult::Unknown);
+        assert_eq!(solver.unknown_reason(), Some(UnknownReason::Interrupted));
+        assert_eq!(signal_received(), Some(libc::SIGINT));
+        drop(guard);
+    }
+}
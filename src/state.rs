@@ -0,0 +1,203 @@
+//! Saved clause databases
+//!
+//! [`ParkissatSolver::save_state`] writes every clause given to the solver
+//! and its variable count to a compact binary file, and
+//! [`ParkissatSolver::load_state`] adds them back in one call, which is much
+//! faster than parsing the formula as DIMACS again. Only the formula is
+//! saved: configuration, phase hints, and learned clauses are not, and clause
+//! groups, scopes, and editable clauses are saved as their selector-guarded
+//! clauses, so their selectors become ordinary variables.
+//!
+//! Layout, all integers little-endian:
+//!
+//! ```text
+//! b"PKSTATE" 1        magic and format version
+//! u64 variables       variable count
+//! u64 clauses         clause count
+//! clauses             literals as binary-DRAT varints, each clause ended by 0
+//! ```
+
+use crate::error::{ParkissatError, Result};
+use crate::wrapper::ParkissatSolver;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+const MAGIC: &[u8; 8] = b"PKSTATE\x01";
+const HEADER_LEN: usize = MAGIC.len() + 16;
+
+fn invalid(message: String) -> ParkissatError {
+    ParkissatError::IoError(format!("invalid state file: {}", message))
+}
+
+/// Append a literal as `2 * var + sign` in 7-bit groups
+fn write_literal(out: &mut Vec<u8>, lit: i32) {
+    let mut code = 2 * u64::from(lit.unsigned_abs()) + u64::from(lit < 0);
+    while code >= 0x80 {
+        out.push((code & 0x7f) as u8 | 0x80);
+        code >>= 7;
+    }
+    out.push(code as u8);
+}
+
+fn encode_state(num_vars: usize, clauses: &[Vec<i32>]) -> Vec<u8> {
+    let literals: usize = clauses.iter().map(Vec::len).sum();
+    let mut out = Vec::with_capacity(HEADER_LEN + 2 * (literals + clauses.len()));
+    out.extend_from_slice(MAGIC);
+    out.extend_from_slice(&(num_vars as u64).to_le_bytes());
+    out.extend_from_slice(&(clauses.len() as u64).to_le_bytes());
+    for clause in clauses {
+        for &lit in clause {
+            write_literal(&mut out, lit);
+        }
+        out.push(0);
+    }
+    out
+}
+
+/// Decode into the flat, 0-terminated buffer `add_clauses` takes
+fn decode_state(bytes: &[u8]) -> Result<(usize, Vec<i32>)> {
+    if bytes.len() < HEADER_LEN || &bytes[..MAGIC.len()] != MAGIC {
+        return Err(invalid("missing header".to_string()));
+    }
+    let word = |offset: usize| u64::from_le_bytes(bytes[offset..offset + 8].try_into().unwrap());
+    let num_vars = word(MAGIC.len()) as usize;
+    let num_clauses = word(MAGIC.len() + 8);
+
+    let body = &bytes[HEADER_LEN..];
+    // Every literal takes at least one byte
+    let mut literals = Vec::with_capacity(body.len());
+    let mut clauses = 0u64;
+    let mut code = 0u64;
+    let mut shift = 0;
+    for (offset, &byte) in body.iter().enumerate() {
+        let position = HEADER_LEN + offset;
+        code |= u64::from(byte & 0x7f) << shift;
+        if byte & 0x80 != 0 {
+            shift += 7;
+            if shift > 35 {
+                return Err(invalid(format!("literal too large at byte {}", position)));
+            }
+            continue;
+        }
+
+        if code == 0 {
+            if matches!(literals.last(), None | Some(&0)) {
+                return Err(invalid(format!("empty clause at byte {}", position)));
+            }
+            clauses += 1;
+            literals.push(0);
+        } else {
+            let var = i32::try_from(code / 2)
+                .ok()
+                .filter(|&var| var > 0 && var as usize <= num_vars)
+                .ok_or_else(|| invalid(format!("variable out of range at byte {}", position)))?;
+            literals.push(if code % 2 == 1 { -var } else { var });
+        }
+        code = 0;
+        shift = 0;
+    }
+
+    if shift != 0 || literals.last().is_some_and(|&lit| lit != 0) {
+        return Err(invalid("truncated clause".to_string()));
+    }
+    if clauses != num_clauses {
+        return Err(invalid(format!("expected {} clauses, found {}", num_clauses, clauses)));
+    }
+    Ok((num_vars, literals))
+}
+
+impl ParkissatSolver {
+    /// Save the clauses given to the solver and its variable count
+    ///
+    /// DIMACS files passed to [`load_dimacs`](Self::load_dimacs) are parsed
+    /// again to collect their clauses, so they must not have changed since
+    /// they were loaded.
+    pub fn save_state<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let clauses = self.all_clauses()?;
+        let mut out = BufWriter::new(File::create(path)?);
+        out.write_all(&encode_state(self.variable_count(), &clauses))?;
+        out.flush()?;
+        Ok(())
+    }
+
+    /// Add the clauses of a state saved by [`save_state`](Self::save_state)
+    ///
+    /// The clauses are added to those already loaded, with a single FFI
+    /// call, and the variable count grows to the saved one. The whole file
+    /// is validated first, so on error nothing is added.
+    pub fn load_state<P: AsRef<Path>>(&mut self, path: P) -> Result<()> {
+        if !self.is_configured() {
+            return Err(ParkissatError::NotConfigured);
+        }
+
+        let (num_vars, literals) = decode_state(&std::fs::read(path)?)?;
+        self.add_clauses(&literals)?;
+        if num_vars > self.variable_count() {
+            self.set_variable_count(num_vars)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cnf::configured_solver;
+    use crate::wrapper::SolverResult;
+
+    #[test]
+    fn test_save_and_load_state() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("base.state");
+
+        let mut solver = configured_solver();
+        solver.load_dimacs_str("p cnf 3 2\n1 2 0\n-1 3 0\n").unwrap();
+        solver.add_clause(&[-2, -3]).unwrap();
+        solver.add_clause(&[100_000, -3]).unwrap();
+        solver.set_variable_count(100_005).unwrap();
+        solver.save_state(&path).unwrap();
+
+        let mut loaded = configured_solver();
+        loaded.load_state(&path).unwrap();
+        assert_eq!(loaded.variable_count(), 100_005);
+        assert_eq!(loaded.clause_count(), 4);
+
+        let mut expected = solver.all_clauses().unwrap();
+        let mut actual = loaded.all_clauses().unwrap();
+        expected.sort();
+        actual.sort();
+        assert_eq!(actual, expected);
+
+        // Assumption queries against the reloaded base formula
+        assert_eq!(loaded.solve_with_assumptions(&[1, 2]).unwrap(), SolverResult::Unsat);
+        assert_eq!(loaded.solve_with_assumptions(&[1]).unwrap(), SolverResult::Sat);
+    }
+
+    #[test]
+    fn test_invalid_state() {
+        let clauses = vec![vec![1, -2], vec![3]];
+        let bytes = encode_state(3, &clauses);
+        let (num_vars, literals) = decode_state(&bytes).unwrap();
+        assert_eq!(num_vars, 3);
+        assert_eq!(literals, vec![1, -2, 0, 3, 0]);
+
+        let corrupt = [
+            b"p cnf 3 2\n".to_vec(),
+            bytes[..bytes.len() - 1].to_vec(),
+            encode_state(2, &clauses),
+            [&bytes[..HEADER_LEN], &[0u8][..]].concat(),
+        ];
+        for bytes in corrupt {
+            assert!(matches!(decode_state(&bytes), Err(ParkissatError::IoError(_))));
+        }
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("bad.state");
+        std::fs::write(&path, &bytes[..bytes.len() - 1]).unwrap();
+        let mut solver = configured_solver();
+        assert!(solver.load_state(&path).is_err());
+        assert_eq!(solver.clause_count(), 0);
+        assert_eq!(ParkissatSolver::new().unwrap().load_state(&path).unwrap_err(), ParkissatError::NotConfigured);
+    }
+}
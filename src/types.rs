@@ -0,0 +1,226 @@
+//! Typed variables and literals
+//!
+//! [`Var`] and [`Lit`] wrap the DIMACS integers used by the raw API, so a
+//! zero literal or a negative variable cannot be constructed. `Lit` has the
+//! same layout as `i32`, which lets clauses of typed literals be passed to the
+//! solver without copying.
+
+use crate::error::{ParkissatError, Result};
+use crate::wrapper::{ParkissatSolver, SolverResult};
+use std::fmt;
+use std::ops::Not;
+
+/// A variable (1-based, at most `i32::MAX`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Var(u32);
+
+impl Var {
+    /// Create a variable from its 1-based index
+    pub fn new(index: u32) -> Option<Var> {
+        if index == 0 || index > i32::MAX as u32 {
+            None
+        } else {
+            Some(Var(index))
+        }
+    }
+
+    /// The 1-based index of the variable
+    pub fn index(self) -> u32 {
+        self.0
+    }
+
+    /// The positive literal of this variable
+    pub fn positive(self) -> Lit {
+        Lit(self.0 as i32)
+    }
+
+    /// The negative literal of this variable
+    pub fn negative(self) -> Lit {
+        Lit(-(self.0 as i32))
+    }
+
+    /// The literal of this variable with the given polarity
+    pub fn lit(self, positive: bool) -> Lit {
+        if positive {
+            self.positive()
+        } else {
+            self.negative()
+        }
+    }
+}
+
+impl TryFrom<i32> for Var {
+    type Error = ParkissatError;
+
+    fn try_from(index: i32) -> Result<Var> {
+        if index <= 0 {
+            return Err(ParkissatError::InvalidVariable(index));
+        }
+        Ok(Var(index as u32))
+    }
+}
+
+impl From<Var> for i32 {
+    fn from(var: Var) -> i32 {
+        var.0 as i32
+    }
+}
+
+impl fmt::Display for Var {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "x{}", self.0)
+    }
+}
+
+/// A literal: a variable or its negation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct Lit(i32);
+
+impl Lit {
+    /// Create a literal from its DIMACS encoding (non-zero)
+    pub fn new(dimacs: i32) -> Option<Lit> {
+        if dimacs == 0 || dimacs == i32::MIN {
+            None
+        } else {
+            Some(Lit(dimacs))
+        }
+    }
+
+    /// The variable of the literal
+    pub fn var(self) -> Var {
+        Var(self.0.unsigned_abs())
+    }
+
+    /// Check whether the literal is the positive one of its variable
+    pub fn is_positive(self) -> bool {
+        self.0 > 0
+    }
+
+    /// Check whether the literal is the negative one of its variable
+    pub fn is_negative(self) -> bool {
+        self.0 < 0
+    }
+
+    /// The DIMACS encoding of the literal
+    pub fn to_dimacs(self) -> i32 {
+        self.0
+    }
+}
+
+impl Not for Lit {
+    type Output = Lit;
+
+    fn not(self) -> Lit {
+        Lit(-self.0)
+    }
+}
+
+impl std::ops::Neg for Lit {
+    type Output = Lit;
+
+    fn neg(self) -> Lit {
+        !self
+    }
+}
+
+impl From<Var> for Lit {
+    fn from(var: Var) -> Lit {
+        var.positive()
+    }
+}
+
+impl TryFrom<i32> for Lit {
+    type Error = ParkissatError;
+
+    fn try_from(dimacs: i32) -> Result<Lit> {
+        Lit::new(dimacs).ok_or(ParkissatError::InvalidVariable(dimacs))
+    }
+}
+
+impl From<Lit> for i32 {
+    fn from(lit: Lit) -> i32 {
+        lit.0
+    }
+}
+
+impl fmt::Display for Lit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// View typed literals as DIMACS integers
+fn as_dimacs(lits: &[Lit]) -> &[i32] {
+    // Lit is repr(transparent) over i32
+    unsafe { std::slice::from_raw_parts(lits.as_ptr() as *const i32, lits.len()) }
+}
+
+impl ParkissatSolver {
+    /// Add a clause of typed literals
+    pub fn add_clause_lits(&mut self, lits: &[Lit]) -> Result<()> {
+        self.add_clause(as_dimacs(lits))
+    }
+
+    /// Solve under typed assumption literals
+    pub fn solve_with_lit_assumptions(&mut self, assumptions: &[Lit]) -> Result<SolverResult> {
+        self.solve_with_assumptions(as_dimacs(assumptions))
+    }
+
+    /// Get the value of a variable in the model (only valid after SAT result)
+    pub fn var_value(&self, var: Var) -> Result<bool> {
+        self.get_model_value(var.into())
+    }
+
+    /// Check whether a literal is true in the model (only valid after SAT result)
+    pub fn lit_value(&self, lit: Lit) -> Result<bool> {
+        Ok(self.var_value(lit.var())? == lit.is_positive())
+    }
+
+    /// Allocate a fresh typed variable
+    pub fn new_var(&mut self) -> Result<Var> {
+        Var::try_from(self.new_variable()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wrapper::SolverConfig;
+
+    #[test]
+    fn test_var_and_lit_construction() {
+        assert_eq!(Var::new(0), None);
+        assert_eq!(Lit::new(0), None);
+        assert_eq!(Lit::new(i32::MIN), None);
+        assert!(Var::try_from(-3).is_err());
+
+        let x = Var::new(3).unwrap();
+        assert_eq!(x.positive().to_dimacs(), 3);
+        assert_eq!(x.negative().to_dimacs(), -3);
+        assert_eq!(x.lit(false), !x.positive());
+        assert_eq!(-x.positive(), x.negative());
+        assert_eq!(Lit::try_from(-3).unwrap().var(), x);
+        assert!(x.negative().is_negative());
+        assert_eq!(i32::from(Lit::from(x)), 3);
+        assert_eq!(x.to_string(), "x3");
+    }
+
+    #[test]
+    fn test_typed_solver_api() {
+        let mut solver = ParkissatSolver::new().unwrap();
+        solver.configure(&SolverConfig::default()).unwrap();
+
+        let a = solver.new_var().unwrap();
+        let b = solver.new_var().unwrap();
+        solver.add_clause_lits(&[a.positive(), b.positive()]).unwrap();
+        solver.add_clause_lits(&[!a.positive(), b.positive()]).unwrap();
+
+        assert_eq!(solver.solve_with_lit_assumptions(&[a.positive()]).unwrap(), SolverResult::Sat);
+        assert!(solver.var_value(b).unwrap());
+        assert!(solver.lit_value(a.positive()).unwrap());
+        assert!(!solver.lit_value(a.negative()).unwrap());
+
+        assert_eq!(solver.solve_with_lit_assumptions(&[b.negative()]).unwrap(), SolverResult::Unsat);
+    }
+}
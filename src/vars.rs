@@ -0,0 +1,193 @@
+//! Named variables
+//!
+//! [`VarManager`] is the symbol table most applications end up writing:
+//! it hands out a [`Var`] per name (or any other hashable key), allocates
+//! anonymous auxiliary variables from the same counter, and decodes models
+//! back into values by name.
+//!
+//! ```no_run
+//! use parkissat_sys::{ParkissatSolver, SolverConfig, VarManager};
+//!
+//! let mut vars = VarManager::new();
+//! let (rain, wet) = (vars.var("rain"), vars.var("wet"));
+//!
+//! let mut solver = ParkissatSolver::new()?;
+//! solver.configure(&SolverConfig::default())?;
+//! solver.add_clause_lits(&[rain.negative(), wet.positive()])?;
+//! solver.add_clause_lits(&[rain.positive()])?;
+//! solver.solve()?;
+//!
+//! let values = vars.decode(&solver.model()?);
+//! assert!(values["wet"]);
+//! # Ok::<(), parkissat_sys::ParkissatError>(())
+//! ```
+
+use crate::model::Model;
+use crate::types::{Lit, Var};
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Maps keys to variables and back
+#[derive(Debug, Clone)]
+pub struct VarManager<K = String> {
+    vars: HashMap<K, Var>,
+    /// Key of variable `i + 1` at index `i` (`None` = anonymous or reserved)
+    keys: Vec<Option<K>>,
+}
+
+impl<K> Default for VarManager<K> {
+    fn default() -> Self {
+        Self {
+            vars: HashMap::new(),
+            keys: Vec::new(),
+        }
+    }
+}
+
+impl<K: Eq + Hash + Clone> VarManager<K> {
+    /// Create a manager that allocates from variable 1
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a manager that allocates after `num_vars` variables used elsewhere
+    pub fn starting_after(num_vars: usize) -> Self {
+        Self {
+            vars: HashMap::new(),
+            keys: vec![None; num_vars],
+        }
+    }
+
+    /// The variable of `key`, allocated on first use
+    pub fn var<Q>(&mut self, key: &Q) -> Var
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ToOwned<Owned = K> + ?Sized,
+    {
+        if let Some(&var) = self.vars.get(key) {
+            return var;
+        }
+
+        let var = self.fresh();
+        let key = key.to_owned();
+        self.keys[var.index() as usize - 1] = Some(key.clone());
+        self.vars.insert(key, var);
+        var
+    }
+
+    /// The literal of `key`'s variable with the given polarity
+    pub fn lit<Q>(&mut self, key: &Q, positive: bool) -> Lit
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ToOwned<Owned = K> + ?Sized,
+    {
+        self.var(key).lit(positive)
+    }
+
+    /// The variable of `key`, if it has one
+    pub fn get<Q>(&self, key: &Q) -> Option<Var>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.vars.get(key).copied()
+    }
+
+    /// Allocate an anonymous variable, e.g. for an encoding
+    pub fn fresh(&mut self) -> Var {
+        self.keys.push(None);
+        Var::new(self.keys.len() as u32).expect("variable count exceeds i32::MAX")
+    }
+
+    /// The key of a variable, if it is named
+    pub fn key(&self, var: Var) -> Option<&K> {
+        self.keys.get(var.index() as usize - 1)?.as_ref()
+    }
+
+    /// Number of variables allocated, named or not
+    pub fn num_vars(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Number of named variables
+    pub fn len(&self) -> usize {
+        self.vars.len()
+    }
+
+    /// Whether no variable is named yet
+    pub fn is_empty(&self) -> bool {
+        self.vars.is_empty()
+    }
+
+    /// Named variables in allocation order
+    pub fn iter(&self) -> impl Iterator<Item = (&K, Var)> + '_ {
+        self.keys.iter().enumerate().filter_map(|(index, key)| {
+            Some((key.as_ref()?, Var::new(index as u32 + 1)?))
+        })
+    }
+
+    /// Values of the named variables in a model
+    ///
+    /// Variables the model leaves unassigned are missing from the map.
+    pub fn decode(&self, model: &Model) -> HashMap<K, bool> {
+        self.iter()
+            .filter_map(|(key, var)| Some((key.clone(), model.value(var)?)))
+            .collect()
+    }
+
+    /// Values of the named variables in a model of signed DIMACS literals
+    pub fn decode_dimacs(&self, model: &[i32]) -> HashMap<K, bool> {
+        self.decode(&Model::from_dimacs(model))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wrapper::{ParkissatSolver, SolverConfig, SolverResult};
+
+    #[test]
+    fn test_names_and_fresh_variables() {
+        let mut vars: VarManager = VarManager::starting_after(2);
+        let x = vars.var("x");
+        let aux = vars.fresh();
+        assert_eq!(x.index(), 3);
+        assert_eq!(aux.index(), 4);
+        assert_eq!(vars.var("x"), x);
+        assert_eq!(vars.lit("y", false).to_dimacs(), -5);
+
+        assert_eq!(vars.get("y").map(Var::index), Some(5));
+        assert_eq!(vars.get("z"), None);
+        assert_eq!(vars.key(x).map(String::as_str), Some("x"));
+        assert_eq!(vars.key(aux), None);
+        assert_eq!((vars.len(), vars.num_vars()), (2, 5));
+        let names: Vec<_> = vars.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, ["x", "y"]);
+
+        // Any hashable key works
+        let mut cells = VarManager::<(usize, usize)>::new();
+        assert_eq!(cells.var(&(0, 1)).index(), 1);
+        assert_eq!(cells.var(&(1, 0)).index(), 2);
+    }
+
+    #[test]
+    fn test_decode_model() {
+        let mut vars = VarManager::<String>::new();
+        let (a, b, c) = (vars.var("a"), vars.var("b"), vars.var("c"));
+
+        let mut solver = ParkissatSolver::new().unwrap();
+        solver.configure(&SolverConfig::default()).unwrap();
+        solver.add_clause_lits(&[a.positive()]).unwrap();
+        solver.add_clause_lits(&[a.negative(), b.negative()]).unwrap();
+        solver.add_clause_lits(&[b.positive(), c.positive()]).unwrap();
+        assert_eq!(solver.solve().unwrap(), SolverResult::Sat);
+
+        let values = vars.decode(&solver.model().unwrap());
+        assert_eq!(values.len(), 3);
+        assert!(values["a"] && !values["b"] && values["c"]);
+
+        let partial = vars.decode_dimacs(&[-2]);
+        assert_eq!(partial, HashMap::from([("b".to_string(), false)]));
+    }
+}
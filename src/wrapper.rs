@@ -2,18 +2,245 @@
 
 use crate::ffi;
 use crate::error::{ParkissatError, Result};
-use std::ffi::CString;
+use crate::edit::EditableClauses;
+use crate::groups::ClauseGroups;
+use crate::propagate::Propagator;
+use crate::callbacks::{LearnCallback, TerminateCallback};
+use crate::events::SolverEvents;
+use crate::interrupt::InterruptHandle;
+use crate::output::LogSink;
+use crate::proof;
+use std::ffi::{CStr, CString};
 use std::os::raw::c_int;
+use std::path::PathBuf;
 use std::ptr;
 use std::time::Duration;
 
+/// Format of proofs written by the solver
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ProofFormat {
+    /// Textual DRAT
+    #[default]
+    Drat,
+    /// Binary DRAT (smaller and faster to write)
+    BinaryDrat,
+    /// LRAT with unit-propagation hints, converted from binary DRAT after the solve (see [`crate::proof`])
+    /// 
+    /// The solve fails with `InvalidProof` if a lemma cannot be justified.
+    Lrat,
+}
+
+/// Learnt-clause sharing between portfolio workers (no effect with a single thread)
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(default))]
+pub struct SharingConfig {
+    /// Share clauses between workers (default: true)
+    pub enabled: bool,
+    
+    /// Only share clauses with at most this LBD (default: 2, None = no limit)
+    pub max_lbd: Option<u32>,
+    
+    /// Only share clauses with at most this many literals (default: None = no limit)
+    pub max_size: Option<u32>,
+    
+    /// Time between sharing rounds (default: 500 ms)
+    #[cfg_attr(feature = "serde", serde(with = "seconds"))]
+    pub interval: Duration,
+    
+    /// Maximum number of literals each worker exports per round (default: 1500)
+    pub buffer_literals: usize,
+}
+
+impl Default for SharingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_lbd: Some(2),
+            max_size: None,
+            interval: Duration::from_millis(500),
+            buffer_literals: 1500,
+        }
+    }
+}
+
+impl SharingConfig {
+    /// No clause sharing
+    pub fn disabled() -> Self {
+        Self {
+            enabled: false,
+            ..Self::default()
+        }
+    }
+}
+
+/// Branching heuristic of a kissat_mab worker
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BranchingHeuristic {
+    /// Variable state independent decaying sum
+    Vsids,
+    /// Conflict history-based branching
+    Chb,
+}
+
+/// Multi-armed-bandit selection of the branching heuristic at each stable-mode restart
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(default))]
+pub struct MabConfig {
+    /// Select the heuristic with the bandit (default: true)
+    pub enabled: bool,
+    
+    /// Candidate heuristics (default: CHB and VSIDS)
+    pub heuristics: Vec<BranchingHeuristic>,
+    
+    /// UCB exploration constant, between 0.0 and 10.9 in steps of 0.1 (default: 4.0)
+    pub exploration: f64,
+}
+
+impl Default for MabConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            heuristics: vec![BranchingHeuristic::Chb, BranchingHeuristic::Vsids],
+            exploration: 4.0,
+        }
+    }
+}
+
+impl MabConfig {
+    /// Branch with a single heuristic, without the bandit
+    pub fn fixed(heuristic: BranchingHeuristic) -> Self {
+        Self {
+            enabled: false,
+            heuristics: vec![heuristic],
+            ..Self::default()
+        }
+    }
+    
+    fn validate(&self) -> Result<()> {
+        if self.heuristics.is_empty() {
+            return Err(ParkissatError::InvalidConfiguration(
+                "At least one branching heuristic is required".to_string()
+            ));
+        }
+        if self.heuristics.iter().enumerate().any(|(i, heuristic)| self.heuristics[..i].contains(heuristic)) {
+            return Err(ParkissatError::InvalidConfiguration(
+                "Branching heuristics must not repeat".to_string()
+            ));
+        }
+        if !(0.0..=10.9).contains(&self.exploration) {
+            return Err(ParkissatError::InvalidConfiguration(
+                "MAB exploration must be between 0.0 and 10.9".to_string()
+            ));
+        }
+        Ok(())
+    }
+    
+    fn to_ffi(&self) -> ffi::ParkissatMabConfig {
+        ffi::ParkissatMabConfig {
+            mab_enabled: self.enabled && self.heuristics.len() > 1,
+            heuristic: match self.heuristics.first() {
+                Some(BranchingHeuristic::Vsids) => 0,
+                _ => 1,
+            },
+            exploration_tenths: (self.exploration * 10.0).round() as c_int,
+        }
+    }
+}
+
+/// CCAnr local search run by kissat_mab workers when they rephase
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(default))]
+pub struct LocalSearchConfig {
+    /// Run local search when rephasing (default: true)
+    pub enabled: bool,
+    
+    /// Also run it once before the first decision (default: false)
+    pub initially: bool,
+    
+    /// Flips per local-search phase (default: None = kissat's effort-based budget)
+    pub flip_budget: Option<u32>,
+    
+    /// Adopt the best assignment found as saved phases (default: true)
+    pub phase_hints: bool,
+}
+
+impl Default for LocalSearchConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            initially: false,
+            flip_budget: None,
+            phase_hints: true,
+        }
+    }
+}
+
+impl LocalSearchConfig {
+    /// No local search
+    pub fn disabled() -> Self {
+        Self {
+            enabled: false,
+            ..Self::default()
+        }
+    }
+}
+
+/// Restart scheme of the workers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RestartScheme {
+    /// Kissat's alternation of focused-mode EMA and stable-mode Luby restarts
+    #[default]
+    Portfolio,
+    /// Luby-style reluctant doubling only (workers stay in stable mode)
+    Luby,
+    /// Glucose-style restarts on LBD moving averages only (workers stay in focused mode)
+    Ema,
+}
+
+/// Restart policy of every worker (unset values keep kissat's defaults)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(default))]
+pub struct RestartConfig {
+    /// Restart scheme (default: `Portfolio`)
+    pub scheme: RestartScheme,
+    
+    /// Base interval in conflicts: the Luby unit, or the minimum between EMA restarts
+    pub interval: Option<u32>,
+    
+    /// Largest Luby interval in conflicts
+    pub max_interval: Option<u32>,
+    
+    /// Percentage by which the fast LBD average must exceed the slow one to restart
+    pub margin: Option<u32>,
+}
+
+/// Polarity a worker tries first for variables without a phase hint
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PhaseMode {
+    /// Try true first (kissat's default)
+    #[default]
+    AllTrue,
+    /// Try false first
+    AllFalse,
+    /// A random polarity per variable and worker, reproducible from the seed
+    Random(u64),
+    /// Start each solve from the phases saved by the previous one (the first solve tries true)
+    Saved,
+}
+
 /// Configuration for the ParKissat solver
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(default))]
 pub struct SolverConfig {
     /// Number of parallel threads to use (default: 1, -1 = use all available CPUs)
     pub num_threads: isize,
     
-    /// Timeout in seconds (0 = no timeout)
+    /// Wall-clock timeout of each solve, with millisecond granularity (0 = no timeout)
+    #[cfg_attr(feature = "serde", serde(with = "seconds"))]
     pub timeout: Duration,
     
     /// Random seed for diversification (0 = use default)
@@ -22,8 +249,48 @@ pub struct SolverConfig {
     /// Enable preprocessing
     pub enable_preprocessing: bool,
     
-    /// Verbosity level (0 = quiet)
+    /// Verbosity level (0 = quiet); output goes to stdout unless a log sink is set
     pub verbosity: u32,
+    
+    /// Write the refuting worker's proof to this file when a solve answers UNSAT (None = no proof)
+    pub proof_output: Option<PathBuf>,
+    
+    /// Format of the proof written to `proof_output`
+    pub proof_format: ProofFormat,
+    
+    /// Stop solving once the whole process uses more than this many MiB of resident memory (None = no limit)
+    /// 
+    /// A solve stopped by the limit returns `Unknown` with [`UnknownReason::MemoryLimit`].
+    pub memory_limit_mb: Option<u64>,
+    
+    /// Clause-sharing policy between workers
+    pub sharing: SharingConfig,
+    
+    /// Branching-heuristic selection of every worker
+    pub mab: MabConfig,
+    
+    /// Per-worker overrides of `mab`: worker `i` uses `worker_mab[i]` if present
+    pub worker_mab: Vec<MabConfig>,
+    
+    /// Local-search phase of every worker
+    pub local_search: LocalSearchConfig,
+    
+    /// Restart policy of every worker
+    pub restart: RestartConfig,
+    
+    /// Initial polarity of every worker
+    pub initial_phase: PhaseMode,
+    
+    /// Pin each worker thread of a parallel solve to one CPU (default: false, Linux only)
+    /// 
+    /// A single-threaded solve runs on the calling thread, which is never pinned.
+    pub pin_threads: bool,
+    
+    /// CPUs to pin workers to, worker `i` to `cpus[i % cpus.len()]` (default: None = every allowed CPU)
+    pub cpus: Option<Vec<usize>>,
+    
+    /// Allocate each worker's clause database on the NUMA node of its CPU (default: false, requires `pin_threads`)
+    pub numa_local: bool,
 }
 
 impl Default for SolverConfig {
@@ -34,21 +301,147 @@ impl Default for SolverConfig {
             random_seed: 0,
             enable_preprocessing: false,
             verbosity: 0,
+            proof_output: None,
+            proof_format: ProofFormat::Drat,
+            memory_limit_mb: None,
+            sharing: SharingConfig::default(),
+            mab: MabConfig::default(),
+            worker_mab: Vec::new(),
+            local_search: LocalSearchConfig::default(),
+            restart: RestartConfig::default(),
+            initial_phase: PhaseMode::AllTrue,
+            pin_threads: false,
+            cpus: None,
+            numa_local: false,
+        }
+    }
+}
+
+impl SolverConfig {
+    /// Check the configuration without creating a solver
+    /// 
+    /// `configure` performs the same checks; this reports an invalid
+    /// configuration, e.g. one loaded from a file, before any work is done.
+    pub fn validate(&self) -> Result<()> {
+        resolve_threads(self.num_threads)?;
+        
+        if self.memory_limit_mb == Some(0) {
+            return Err(ParkissatError::InvalidConfiguration(
+                "Memory limit must be positive".to_string()
+            ));
+        }
+        
+        let sharing = &self.sharing;
+        if sharing.max_lbd == Some(0) || sharing.max_size == Some(0) {
+            return Err(ParkissatError::InvalidConfiguration(
+                "Sharing limits must be positive".to_string()
+            ));
+        }
+        if sharing.enabled && (sharing.interval.is_zero() || sharing.buffer_literals == 0) {
+            return Err(ParkissatError::InvalidConfiguration(
+                "Sharing interval and buffer size must be positive".to_string()
+            ));
+        }
+        
+        if self.local_search.flip_budget == Some(0) {
+            return Err(ParkissatError::InvalidConfiguration(
+                "Local-search flip budget must be positive".to_string()
+            ));
+        }
+        
+        let restart = &self.restart;
+        if restart.interval == Some(0) || restart.max_interval == Some(0) {
+            return Err(ParkissatError::InvalidConfiguration(
+                "Restart intervals must be positive".to_string()
+            ));
+        }
+        if let (Some(interval), Some(max)) = (restart.interval, restart.max_interval) {
+            if max < interval {
+                return Err(ParkissatError::InvalidConfiguration(
+                    "Largest restart interval must not be below the base interval".to_string()
+                ));
+            }
+        }
+        if restart.margin.is_some_and(|margin| margin > 100) {
+            return Err(ParkissatError::InvalidConfiguration(
+                "Restart margin must be a percentage up to 100".to_string()
+            ));
+        }
+        
+        match &self.cpus {
+            Some(_) if !self.pin_threads => {
+                return Err(ParkissatError::InvalidConfiguration(
+                    "A CPU list requires pin_threads".to_string()
+                ));
+            }
+            Some(cpus) if cpus.is_empty() => {
+                return Err(ParkissatError::InvalidConfiguration(
+                    "CPU list must not be empty".to_string()
+                ));
+            }
+            _ => {}
+        }
+        if self.numa_local && !self.pin_threads {
+            return Err(ParkissatError::InvalidConfiguration(
+                "NUMA-local allocation requires pin_threads".to_string()
+            ));
         }
+        
+        for mab in std::iter::once(&self.mab).chain(&self.worker_mab) {
+            mab.validate()?;
+        }
+        
+        Ok(())
     }
 }
 
 /// Result of SAT solving
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SolverResult {
     /// Formula is satisfiable
     Sat,
     /// Formula is unsatisfiable
     Unsat,
-    /// Result is unknown (timeout, interrupted, etc.)
+    /// Result is unknown (timeout, interrupted, etc.; see [`ParkissatSolver::unknown_reason`])
     Unknown,
 }
 
+/// Why a solve returned [`SolverResult::Unknown`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum UnknownReason {
+    /// The solver was interrupted
+    Interrupted,
+    /// The configured timeout or the deadline of `solve_until` elapsed
+    ///
+    /// The workers of the stopped search are kept, so `get_statistics` and
+    /// `get_thread_statistics` report the work done up to the timeout.
+    Timeout,
+    /// A conflict, decision, or propagation budget was exhausted
+    BudgetExhausted,
+    /// The memory limit was reached
+    MemoryLimit,
+    /// The search stopped for another reason
+    ///
+    /// A worker that threw an exception makes the solve fail with
+    /// `InternalError` instead.
+    Other,
+}
+
+impl UnknownReason {
+    fn from_ffi(reason: ffi::ParkissatUnknownReason) -> Option<Self> {
+        match reason {
+            ffi::PARKISSAT_REASON_INTERRUPTED => Some(UnknownReason::Interrupted),
+            ffi::PARKISSAT_REASON_TIMEOUT => Some(UnknownReason::Timeout),
+            ffi::PARKISSAT_REASON_BUDGET => Some(UnknownReason::BudgetExhausted),
+            ffi::PARKISSAT_REASON_MEMORY => Some(UnknownReason::MemoryLimit),
+            ffi::PARKISSAT_REASON_OTHER => Some(UnknownReason::Other),
+            _ => None,
+        }
+    }
+}
+
 impl From<ffi::ParkissatResult> for SolverResult {
     fn from(result: ffi::ParkissatResult) -> Self {
         match result {
@@ -62,6 +455,7 @@ impl From<ffi::ParkissatResult> for SolverResult {
 
 /// Solver statistics
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SolverStatistics {
     /// Number of propagations
     pub propagations: u64,
@@ -73,6 +467,65 @@ pub struct SolverStatistics {
     pub restarts: u64,
     /// Peak memory usage in KB
     pub memory_peak_kb: f64,
+    /// Number of learned clauses (one per conflict)
+    pub learned_clauses: u64,
+    /// Non-binary learned clauses still kept after database reductions
+    pub learned_clauses_kept: u64,
+    /// Binary clauses in the clause databases, original and learned
+    pub binary_clauses: u64,
+    /// Variables eliminated by preprocessing (maximum over workers)
+    pub eliminated_variables: u64,
+    /// CPU time of the whole process during the last solve
+    #[cfg_attr(feature = "serde", serde(with = "seconds"))]
+    pub solve_cpu_time: Duration,
+    /// Wall-clock time of the last solve
+    #[cfg_attr(feature = "serde", serde(with = "seconds"))]
+    pub solve_wall_time: Duration,
+    /// Current resident memory of the process in KB
+    pub memory_current_kb: f64,
+}
+
+/// Statistics of a single portfolio worker
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WorkerStatistics {
+    /// Index of the worker (0-based)
+    pub worker: usize,
+    /// Number of propagations
+    pub propagations: u64,
+    /// Number of decisions
+    pub decisions: u64,
+    /// Number of conflicts
+    pub conflicts: u64,
+    /// Number of restarts
+    pub restarts: u64,
+    /// Peak memory usage in KB
+    pub memory_peak_kb: f64,
+    /// Number of learned clauses (one per conflict)
+    pub learned_clauses: u64,
+    /// Non-binary learned clauses still kept after database reductions
+    pub learned_clauses_kept: u64,
+    /// Binary clauses in the clause database, original and learned
+    pub binary_clauses: u64,
+    /// Variables eliminated by preprocessing
+    pub eliminated_variables: u64,
+    /// Whether this worker produced the last SAT/UNSAT answer
+    pub is_winner: bool,
+}
+
+/// Durations as (fractional) seconds, which read better in configuration files
+#[cfg(feature = "serde")]
+mod seconds {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use std::time::Duration;
+    
+    pub fn serialize<S: Serializer>(duration: &Duration, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_f64(duration.as_secs_f64())
+    }
+    
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Duration, D::Error> {
+        Duration::try_from_secs_f64(f64::deserialize(deserializer)?).map_err(serde::de::Error::custom)
+    }
 }
 
 impl From<ffi::ParkissatStatistics> for SolverStatistics {
@@ -83,16 +536,61 @@ impl From<ffi::ParkissatStatistics> for SolverStatistics {
             conflicts: stats.conflicts,
             restarts: stats.restarts,
             memory_peak_kb: stats.mem_peak,
+            learned_clauses: stats.learned_clauses,
+            learned_clauses_kept: stats.learned_clauses_kept,
+            binary_clauses: stats.binary_clauses,
+            eliminated_variables: stats.eliminated_variables,
+            solve_cpu_time: Duration::from_secs_f64(stats.cpu_time.max(0.0)),
+            solve_wall_time: Duration::from_secs_f64(stats.wall_time.max(0.0)),
+            memory_current_kb: stats.mem_current,
         }
     }
 }
 
 /// Safe wrapper for ParKissat-RS SAT solver
+///
+/// The solver is incremental: clauses added after a call to `solve` are
+/// honored by the next call, and adding clauses or loading a formula discards
+/// the previous result and model. Kissat cannot resume a finished search, so
+/// every `solve` after the first re-parses the whole formula into new
+/// workers. They start from the short learned clauses of the previous
+/// workers, at most 64 literals with glue 6, unless a proof is written; every
+/// other learned clause is discarded.
 pub struct ParkissatSolver {
-    solver: *mut ffi::ParkissatSolver,
+    pub(crate) solver: *mut ffi::ParkissatSolver,
     configured: bool,
     last_result: Option<SolverResult>,
+    /// Assumptions of the last solve, for `value_under_assumptions`
+    pub(crate) last_assumptions: Option<Vec<i32>>,
     variable_count: usize,
+    pub(crate) editable: EditableClauses,
+    pub(crate) groups: ClauseGroups,
+    /// Clause copy for `propagate`, built on first use
+    pub(crate) propagator: Option<Propagator>,
+    pub(crate) interrupt_handle: InterruptHandle,
+    pub(crate) log_sink: Option<LogSink>,
+    /// Boxed twice so the C++ side can hold a thin pointer to it
+    pub(crate) terminate: Option<Box<TerminateCallback>>,
+    pub(crate) learn: Option<Box<LearnCallback>>,
+    pub(crate) events: Option<Box<SolverEvents>>,
+    pub(crate) verbosity: u32,
+    /// Last configuration applied, for helpers that spawn solvers of their own
+    pub(crate) config: SolverConfig,
+    /// DIMACS files handed to the C++ parser, which keeps no copy of their clauses
+    pub(crate) dimacs_files: Vec<PathBuf>,
+}
+
+/// Resolve the configured thread count: -1 means use all available CPUs
+fn resolve_threads(num_threads: isize) -> Result<usize> {
+    if num_threads == -1 {
+        Ok(num_cpus::get())
+    } else if num_threads <= 0 {
+        Err(ParkissatError::InvalidConfiguration(
+            "Number of threads must be positive or -1 for auto-detection".to_string()
+        ))
+    } else {
+        Ok(num_threads as usize)
+    }
 }
 
 impl ParkissatSolver {
@@ -104,53 +602,186 @@ impl ParkissatSolver {
             return Err(ParkissatError::SolverCreationFailed);
         }
         
-        Ok(Self {
+        let solver = Self {
             solver,
             configured: false,
             last_result: None,
+            last_assumptions: None,
             variable_count: 0,
-        })
+            editable: EditableClauses::default(),
+            groups: ClauseGroups::default(),
+            propagator: None,
+            interrupt_handle: InterruptHandle::new(solver),
+            log_sink: None,
+            terminate: None,
+            learn: None,
+            events: None,
+            verbosity: 0,
+            config: SolverConfig::default(),
+            dimacs_files: Vec::new(),
+        };
+        
+        // Search events are reported to `tracing` even without callbacks
+        #[cfg(feature = "tracing")]
+        let solver = {
+            let mut solver = solver;
+            solver.clear_events();
+            solver
+        };
+        
+        Ok(solver)
     }
     
     /// Configure the solver with the given options
+    /// 
+    /// A configured solver can be reconfigured between solves, keeping its
+    /// clauses, e.g. to raise the timeout of the next incremental solve. The
+    /// new settings apply from the next solve; statistics and the result of
+    /// the last one stay available until then. The thread count is fixed by
+    /// the first call: changing it is rejected with `InvalidConfiguration`,
+    /// as the workers would have to be rebuilt from scratch.
     pub fn configure(&mut self, config: &SolverConfig) -> Result<()> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!(target: "parkissat", "configure", threads = config.num_threads).entered();
+        
         if self.solver.is_null() {
             return Err(ParkissatError::SolverCreationFailed);
         }
         
-        // Resolve thread count: -1 means use all available CPUs
-        let actual_threads = if config.num_threads == -1 {
-            num_cpus::get()
-        } else if config.num_threads <= 0 {
-            return Err(ParkissatError::InvalidConfiguration(
-                "Number of threads must be positive or -1 for auto-detection".to_string()
-            ));
-        } else {
-            config.num_threads as usize
+        config.validate()?;
+        let actual_threads = resolve_threads(config.num_threads)?;
+        if self.configured {
+            let current = resolve_threads(self.config.num_threads)?;
+            if actual_threads != current {
+                return Err(ParkissatError::InvalidConfiguration(format!(
+                    "Thread count cannot change from {} to {} after configuration; create a new solver instead",
+                    current, actual_threads
+                )));
+            }
+        }
+        
+        let proof_path = match &config.proof_output {
+            Some(path) => {
+                let path_str = path.to_str()
+                    .ok_or_else(|| ParkissatError::IoError("Invalid proof path".to_string()))?;
+                
+                // Fail early if the proof file cannot be created
+                std::fs::File::create(path)
+                    .map_err(|e| ParkissatError::IoError(format!("Cannot write proof file {}: {}", path_str, e)))?;
+                
+                // LRAT is converted from a DRAT proof logged next to it
+                if config.proof_format == ProofFormat::Lrat {
+                    let sidecar = proof::drat_sidecar(path);
+                    Some(CString::new(sidecar.to_str().ok_or_else(|| ParkissatError::IoError("Invalid proof path".to_string()))?)?)
+                } else {
+                    Some(CString::new(path_str)?)
+                }
+            }
+            None => None,
         };
         
+        let sharing = &config.sharing;
+        let worker_mab: Vec<_> = config.worker_mab.iter().map(MabConfig::to_ffi).collect();
+        let cpus: Vec<c_int> = config.cpus.iter().flatten().map(|&cpu| cpu.min(c_int::MAX as usize) as c_int).collect();
         let ffi_config = ffi::ParkissatConfig {
             num_threads: actual_threads as c_int,
-            timeout_seconds: config.timeout.as_secs() as c_int,
+            // Round up so that sub-millisecond timeouts are not treated as "no timeout"
+            timeout_ms: config.timeout.as_nanos().div_ceil(1_000_000).min(i64::MAX as u128) as i64,
             random_seed: config.random_seed as c_int,
             enable_preprocessing: config.enable_preprocessing,
             verbosity: config.verbosity as c_int,
+            proof_path: proof_path.as_ref().map_or(ptr::null(), |path| path.as_ptr()),
+            proof_binary: config.proof_format != ProofFormat::Drat,
+            memory_limit_mb: config.memory_limit_mb.map_or(0, |mb| mb.min(c_int::MAX as u64) as c_int),
+            sharing_enabled: sharing.enabled,
+            sharing_max_lbd: sharing.max_lbd.map_or(0, |lbd| lbd.min(c_int::MAX as u32) as c_int),
+            sharing_max_size: sharing.max_size.map_or(0, |size| size.min(c_int::MAX as u32) as c_int),
+            sharing_interval_ms: sharing.interval.as_millis().clamp(1, c_int::MAX as u128) as c_int,
+            sharing_buffer_literals: sharing.buffer_literals.min(c_int::MAX as usize) as c_int,
+            mab: config.mab.to_ffi(),
+            worker_mab: worker_mab.as_ptr(),
+            num_worker_mab: worker_mab.len().min(c_int::MAX as usize) as c_int,
+            ls_enabled: config.local_search.enabled,
+            ls_initially: config.local_search.initially,
+            ls_flip_budget: config.local_search.flip_budget.map_or(0, |flips| flips.min(c_int::MAX as u32) as c_int),
+            ls_phase_hints: config.local_search.phase_hints,
+            restart_scheme: match config.restart.scheme {
+                RestartScheme::Portfolio => 0,
+                RestartScheme::Luby => 1,
+                RestartScheme::Ema => 2,
+            },
+            restart_interval: config.restart.interval.map_or(0, |interval| interval.min(c_int::MAX as u32) as c_int),
+            restart_max_interval: config.restart.max_interval.map_or(0, |interval| interval.min(c_int::MAX as u32) as c_int),
+            restart_margin: config.restart.margin.map_or(-1, |margin| margin as c_int),
+            initial_phase: match config.initial_phase {
+                PhaseMode::AllTrue => 0,
+                PhaseMode::AllFalse => 1,
+                PhaseMode::Random(_) => 2,
+                PhaseMode::Saved => 3,
+            },
+            phase_seed: match config.initial_phase {
+                PhaseMode::Random(seed) => seed,
+                _ => 0,
+            },
+            pin_threads: config.pin_threads,
+            cpus: cpus.as_ptr(),
+            num_cpus: cpus.len().min(c_int::MAX as usize) as c_int,
+            numa_local: config.numa_local,
         };
         
         unsafe {
             ffi::parkissat_configure(self.solver, &ffi_config);
         }
+        self.check_error()?;
         
         self.configured = true;
+        self.verbosity = config.verbosity;
+        self.config = config.clone();
+        Ok(())
+    }
+    
+    /// Remove every clause, the model, and statistics, keeping the configuration
+    /// 
+    /// The solver behaves like a freshly configured one and keeps its
+    /// configuration, options set with `set_option`, log sink, terminate and
+    /// learn callbacks, and interrupt handles. Kissat cannot forget clauses,
+    /// so every worker is freed and rebuilt; a reset costs about as much as
+    /// creating a new solver. Phase hints and frozen variables are cleared.
+    pub fn reset(&mut self) -> Result<()> {
+        if !self.configured {
+            return Err(ParkissatError::NotConfigured);
+        }
+        
+        unsafe {
+            ffi::parkissat_reset(self.solver);
+        }
+        self.check_error()?;
+        
+        self.last_result = None;
+        self.last_assumptions = None;
+        self.variable_count = 0;
+        self.editable = EditableClauses::default();
+        self.groups = ClauseGroups::default();
+        self.propagator = None;
+        self.dimacs_files.clear();
+        
         Ok(())
     }
     
     /// Load a DIMACS file
+    /// 
+    /// Compressed files (gzip, xz, bzip2) are decompressed and parsed in Rust
+    /// when the matching cargo feature is enabled.
     pub fn load_dimacs<P: AsRef<std::path::Path>>(&mut self, path: P) -> Result<()> {
         if !self.configured {
             return Err(ParkissatError::NotConfigured);
         }
         
+        // Clauses loaded inside a push scope go through `add_clauses` to be guarded
+        if self.groups.in_scope() || crate::dimacs::is_compressed(path.as_ref())? {
+            return self.load_dimacs_reader(std::fs::File::open(path)?);
+        }
+        
         let path_str = path.as_ref().to_str()
             .ok_or_else(|| ParkissatError::IoError("Invalid path".to_string()))?;
         
@@ -160,18 +791,88 @@ impl ParkissatSolver {
             ffi::parkissat_load_dimacs(self.solver, c_path.as_ptr())
         };
         
+        self.last_result = None;
+        
         if !success {
+            self.check_error()?;
             return Err(ParkissatError::IoError(format!("Failed to load DIMACS file: {}", path_str)));
         }
         
+        let num_vars = unsafe { ffi::parkissat_get_variable_count(self.solver) };
+        self.variable_count = self.variable_count.max(num_vars.max(0) as usize);
+        self.dimacs_files.push(path.as_ref().to_path_buf());
+        Ok(())
+    }
+    
+    /// Load a DIMACS CNF formula held in memory
+    /// 
+    /// The C++ side parses uncompressed input straight from the buffer, so
+    /// generated formulas need no temporary file. Compressed input is
+    /// decompressed and parsed in Rust. Nothing is added if the formula is
+    /// malformed.
+    pub fn load_dimacs_bytes(&mut self, data: &[u8]) -> Result<()> {
+        if !self.configured {
+            return Err(ParkissatError::NotConfigured);
+        }
+        
+        if self.groups.in_scope() || crate::dimacs::Compression::detect(data) != crate::dimacs::Compression::None {
+            return self.load_dimacs_reader(data);
+        }
+        
+        let mut error = ffi::ParkissatParseError {
+            line: 0,
+            message: ptr::null(),
+        };
+        let success = unsafe {
+            ffi::parkissat_load_dimacs_buffer(self.solver, data.as_ptr().cast(), data.len(), &mut error)
+        };
+        
+        if !success {
+            self.check_error()?;
+            let message = if error.message.is_null() {
+                "invalid DIMACS input".to_string()
+            } else {
+                unsafe { CStr::from_ptr(error.message) }.to_string_lossy().into_owned()
+            };
+            return Err(ParkissatError::ParseError { line: error.line, message });
+        }
+        
+        let num_vars = unsafe { ffi::parkissat_get_variable_count(self.solver) };
+        self.variable_count = self.variable_count.max(num_vars.max(0) as usize);
+        self.last_result = None;
+        
         Ok(())
     }
     
+    /// Load a DIMACS CNF formula from a string
+    pub fn load_dimacs_str(&mut self, dimacs: &str) -> Result<()> {
+        self.load_dimacs_bytes(dimacs.as_bytes())
+    }
+    
     /// Add a clause to the solver
     /// 
+    /// After a solve the clause is stored until the next one, which rebuilds
+    /// the workers from the whole formula.
+    /// 
+    /// Inside a [`push`](Self::push) scope the clause is retracted by the
+    /// matching [`pop`](Self::pop).
+    /// 
     /// # Arguments
     /// * `literals` - Array of literals (positive for variable, negative for negation)
     pub fn add_clause(&mut self, literals: &[i32]) -> Result<()> {
+        match self.groups.scope_selector() {
+            Some(selector) if !literals.is_empty() => {
+                let mut guarded = Vec::with_capacity(literals.len() + 1);
+                guarded.push(-selector);
+                guarded.extend_from_slice(literals);
+                self.add_clause_unscoped(&guarded)
+            }
+            _ => self.add_clause_unscoped(literals),
+        }
+    }
+    
+    /// Add a clause that stays even when the current push scope is popped
+    pub(crate) fn add_clause_unscoped(&mut self, literals: &[i32]) -> Result<()> {
         if !self.configured {
             return Err(ParkissatError::NotConfigured);
         }
@@ -189,7 +890,7 @@ impl ParkissatSolver {
         
         // Update variable count
         for &lit in literals {
-            let var = lit.abs() as usize;
+            let var = lit.unsigned_abs() as usize;
             if var > self.variable_count {
                 self.variable_count = var;
             }
@@ -202,10 +903,94 @@ impl ParkissatSolver {
                 literals.len() as c_int
             );
         }
+        self.check_error()?;
+        
+        // The previous model may violate the new clause
+        self.last_result = None;
+        
+        Ok(())
+    }
+    
+    /// Add many clauses with a single FFI call
+    /// 
+    /// `literals` holds the clauses back to back, each terminated by `0` as
+    /// in DIMACS (e.g. `[1, -2, 0, 3, 0]`). The whole buffer is validated
+    /// first, so on error no clause is added.
+    pub fn add_clauses(&mut self, literals: &[i32]) -> Result<()> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!(target: "parkissat", "add_clauses", literals = literals.len()).entered();
+        
+        if !self.configured {
+            return Err(ParkissatError::NotConfigured);
+        }
+        
+        if literals.is_empty() {
+            return Ok(());
+        }
+        
+        if literals.last() != Some(&0) {
+            return Err(ParkissatError::InvalidClause("Last clause is not terminated by 0".to_string()));
+        }
+        
+        let mut max_var = self.variable_count;
+        let mut clause_len = 0;
+        for &lit in literals {
+            if lit == 0 {
+                if clause_len == 0 {
+                    return Err(ParkissatError::InvalidClause("Empty clause".to_string()));
+                }
+                clause_len = 0;
+            } else {
+                max_var = max_var.max(lit.unsigned_abs() as usize);
+                clause_len += 1;
+            }
+        }
+        self.variable_count = max_var;
+        
+        let guarded;
+        let literals = match self.groups.scope_selector() {
+            Some(selector) => {
+                guarded = literals.split_inclusive(|&lit| lit == 0)
+                    .flat_map(|clause| std::iter::once(-selector).chain(clause.iter().copied()))
+                    .collect::<Vec<_>>();
+                &guarded[..]
+            }
+            None => literals,
+        };
+        
+        unsafe {
+            ffi::parkissat_add_clauses(self.solver, literals.as_ptr(), literals.len());
+        }
+        self.check_error()?;
+        
+        self.last_result = None;
         
         Ok(())
     }
     
+    /// Add clauses from an iterator with a single FFI call
+    pub fn add_clauses_iter<I, C>(&mut self, clauses: I) -> Result<()>
+    where
+        I: IntoIterator<Item = C>,
+        C: AsRef<[i32]>,
+    {
+        let mut literals = Vec::new();
+        for clause in clauses {
+            let clause = clause.as_ref();
+            // A zero would split the clause in the flat buffer
+            if clause.contains(&0) {
+                return Err(ParkissatError::InvalidClause("Literal cannot be zero".to_string()));
+            }
+            if clause.is_empty() {
+                return Err(ParkissatError::InvalidClause("Empty clause".to_string()));
+            }
+            literals.extend_from_slice(clause);
+            literals.push(0);
+        }
+        
+        self.add_clauses(&literals)
+    }
+    
     /// Set the number of variables explicitly
     pub fn set_variable_count(&mut self, count: usize) -> Result<()> {
         if !self.configured {
@@ -226,18 +1011,47 @@ impl ParkissatSolver {
         Ok(())
     }
     
-    /// Solve the SAT problem
+    /// Allocate a fresh variable that does not occur in any clause yet
+    pub fn new_variable(&mut self) -> Result<i32> {
+        let var = self.variable_count + 1;
+        self.set_variable_count(var)?;
+        Ok(var as i32)
+    }
+    
+    /// Solve the SAT problem
+    /// 
+    /// Every call after the first rebuilds the workers from the whole formula.
+    /// Only the short learned clauses of the previous call are carried over,
+    /// and none while a proof is written.
     pub fn solve(&mut self) -> Result<SolverResult> {
         if !self.configured {
             return Err(ParkissatError::NotConfigured);
         }
         
-        let result = unsafe {
-            ffi::parkissat_solve(self.solver)
-        };
+        // Editable clauses and clause groups are only active while their selectors are assumed
+        if !self.editable.is_empty() || !self.groups.is_empty() {
+            return self.solve_with_assumptions(&[]);
+        }
+        
+        #[cfg(feature = "tracing")]
+        let _span = self.solve_span(0);
+        
+        let result = self.capture_output(|solver| unsafe {
+            ffi::parkissat_solve(solver)
+        });
+        // An interrupt is spent once the solve that saw it returns
+        self.clear_interrupt();
+        self.check_error()?;
         
         let solver_result = SolverResult::from(result);
         self.last_result = Some(solver_result);
+        #[cfg(feature = "tracing")]
+        self.trace_result(solver_result);
+        self.last_assumptions = Some(Vec::new());
+        
+        if solver_result == SolverResult::Unsat {
+            self.finish_lrat_proof(&[])?;
+        }
         
         Ok(solver_result)
     }
@@ -255,21 +1069,40 @@ impl ParkissatSolver {
             }
         }
         
-        let result = unsafe {
+        let mut all_assumptions = self.editable.selectors();
+        all_assumptions.extend(self.groups.enabled_selectors());
+        all_assumptions.extend_from_slice(assumptions);
+        
+        #[cfg(feature = "tracing")]
+        let _span = self.solve_span(all_assumptions.len());
+        
+        let result = self.capture_output(|solver| unsafe {
             ffi::parkissat_solve_with_assumptions(
-                self.solver,
-                assumptions.as_ptr(),
-                assumptions.len() as c_int
+                solver,
+                all_assumptions.as_ptr(),
+                all_assumptions.len() as c_int
             )
-        };
+        });
+        self.clear_interrupt();
+        self.check_error()?;
         
         let solver_result = SolverResult::from(result);
         self.last_result = Some(solver_result);
+        #[cfg(feature = "tracing")]
+        self.trace_result(solver_result);
+        self.last_assumptions = Some(assumptions.to_vec());
+        
+        if solver_result == SolverResult::Unsat {
+            self.finish_lrat_proof(&all_assumptions)?;
+        }
         
         Ok(solver_result)
     }
     
     /// Get the truth value of a variable in the model (only valid after SAT result)
+    /// 
+    /// Variables outside the model, i.e. above the highest variable known to
+    /// the solver, are rejected with `InvalidVariable`.
     pub fn get_model_value(&self, variable: i32) -> Result<bool> {
         if variable <= 0 {
             return Err(ParkissatError::InvalidVariable(variable));
@@ -277,6 +1110,14 @@ impl ParkissatSolver {
         
         match self.last_result {
             Some(SolverResult::Sat) => {
+                let size = unsafe {
+                    ffi::parkissat_get_model_size(self.solver)
+                };
+                
+                if variable > size {
+                    return Err(ParkissatError::InvalidVariable(variable));
+                }
+                
                 let value = unsafe {
                     ffi::parkissat_get_model_value(self.solver, variable)
                 };
@@ -315,6 +1156,91 @@ impl ParkissatSolver {
         }
     }
     
+    /// Get the assumptions used to refute the last `solve_with_assumptions` call
+    /// 
+    /// Only valid after an UNSAT result. The returned literals are a subset of
+    /// the assumptions that is by itself inconsistent with the formula; it is
+    /// not guaranteed to be minimal. An empty core means the formula is
+    /// unsatisfiable without any assumptions.
+    pub fn failed_assumptions(&self) -> Result<Vec<i32>> {
+        let mut failed = self.failed_assumptions_with_selectors()?;
+        
+        // Selectors of editable clauses and clause groups are assumed internally, not by the caller
+        let selectors = self.editable.selectors();
+        failed.retain(|&lit| !selectors.contains(&lit) && !self.groups.is_selector(lit));
+        
+        Ok(failed)
+    }
+    
+    /// Failed assumptions including internally assumed selectors
+    pub(crate) fn failed_assumptions_with_selectors(&self) -> Result<Vec<i32>> {
+        if self.last_result != Some(SolverResult::Unsat) {
+            return Err(ParkissatError::NoSolution);
+        }
+        
+        let size = unsafe {
+            ffi::parkissat_get_failed_assumptions_size(self.solver)
+        };
+        
+        if size <= 0 {
+            return Ok(Vec::new());
+        }
+        
+        let mut failed = vec![0; size as usize];
+        unsafe {
+            ffi::parkissat_get_failed_assumptions(self.solver, failed.as_mut_ptr(), size);
+        }
+        
+        Ok(failed)
+    }
+    
+    /// Get the value of a variable fixed at decision level 0 by an earlier solve
+    /// 
+    /// Root-level values are implied by the clauses alone, so they hold in
+    /// every model and stay valid as more clauses are added. `None` means no
+    /// worker has fixed the variable yet, which is always the case before the
+    /// first solve and after `reset`.
+    pub fn fixed_value(&self, variable: i32) -> Result<Option<bool>> {
+        if variable <= 0 {
+            return Err(ParkissatError::InvalidVariable(variable));
+        }
+        
+        // Literals come back ordered by variable
+        let fixed = self.fixed_literals_with_selectors();
+        Ok(fixed
+            .binary_search_by_key(&variable, |lit| lit.abs())
+            .ok()
+            .map(|index| fixed[index] > 0))
+    }
+    
+    /// Get every literal fixed at decision level 0 by earlier solves, ordered by variable
+    pub fn fixed_literals(&self) -> Vec<i32> {
+        let mut fixed = self.fixed_literals_with_selectors();
+        
+        // Retired selectors of editable clauses and clause groups end up fixed to false
+        let selectors = self.editable.selectors();
+        fixed.retain(|&lit| !selectors.contains(&lit.abs()) && !self.groups.is_selector(lit));
+        
+        fixed
+    }
+    
+    fn fixed_literals_with_selectors(&self) -> Vec<i32> {
+        let size = unsafe {
+            ffi::parkissat_get_fixed_size(self.solver)
+        };
+        
+        if size <= 0 {
+            return Vec::new();
+        }
+        
+        let mut fixed = vec![0; size as usize];
+        unsafe {
+            ffi::parkissat_get_fixed(self.solver, fixed.as_mut_ptr(), size);
+        }
+        
+        fixed
+    }
+    
     /// Get solver statistics
     pub fn get_statistics(&self) -> Result<SolverStatistics> {
         if !self.configured {
@@ -324,10 +1250,58 @@ impl ParkissatSolver {
         let stats = unsafe {
             ffi::parkissat_get_statistics(self.solver)
         };
+        self.check_error()?;
         
         Ok(SolverStatistics::from(stats))
     }
     
+    /// Get statistics for each portfolio worker of the last solve
+    pub fn get_thread_statistics(&self) -> Result<Vec<WorkerStatistics>> {
+        if !self.configured {
+            return Err(ParkissatError::NotConfigured);
+        }
+        
+        let count = unsafe {
+            ffi::parkissat_get_worker_count(self.solver)
+        };
+        let winner = self.winning_worker();
+        
+        let workers = (0..count.max(0))
+            .map(|worker| {
+                let stats = unsafe {
+                    ffi::parkissat_get_worker_statistics(self.solver, worker)
+                };
+                WorkerStatistics {
+                    worker: worker as usize,
+                    propagations: stats.propagations,
+                    decisions: stats.decisions,
+                    conflicts: stats.conflicts,
+                    restarts: stats.restarts,
+                    memory_peak_kb: stats.mem_peak,
+                    learned_clauses: stats.learned_clauses,
+                    learned_clauses_kept: stats.learned_clauses_kept,
+                    binary_clauses: stats.binary_clauses,
+                    eliminated_variables: stats.eliminated_variables,
+                    is_winner: winner == Some(worker as usize),
+                }
+            })
+            .collect();
+        
+        Ok(workers)
+    }
+    
+    /// Index of the worker that produced the last SAT/UNSAT answer
+    pub fn winning_worker(&self) -> Option<usize> {
+        if !self.configured {
+            return None;
+        }
+        
+        let worker = unsafe {
+            ffi::parkissat_get_winning_worker(self.solver)
+        };
+        usize::try_from(worker).ok()
+    }
+    
     /// Interrupt the solver
     pub fn interrupt(&mut self) {
         if !self.solver.is_null() {
@@ -346,11 +1320,55 @@ impl ParkissatSolver {
         }
     }
     
+    /// Fail with the error the C++ layer recorded in an earlier call, if any
+    /// 
+    /// Failing calls return a neutral value and record why; exceptions thrown
+    /// inside painless or kissat never cross the FFI boundary but are
+    /// recorded too. The error is kept until taken here.
+    pub(crate) fn check_error(&self) -> Result<()> {
+        let mut info = ffi::ParkissatErrorInfo {
+            code: ffi::PARKISSAT_ERROR_NONE,
+            line: 0,
+            message: [0; 256],
+        };
+        let code = unsafe {
+            ffi::parkissat_last_error(self.solver, &mut info)
+        };
+        
+        let message = unsafe { CStr::from_ptr(info.message.as_ptr()) }.to_string_lossy().into_owned();
+        Err(match code {
+            ffi::PARKISSAT_ERROR_NONE => return Ok(()),
+            ffi::PARKISSAT_ERROR_OUT_OF_MEMORY => ParkissatError::OutOfMemory(message),
+            ffi::PARKISSAT_ERROR_IO => ParkissatError::IoError(message),
+            ffi::PARKISSAT_ERROR_PARSE => ParkissatError::ParseError { line: info.line, message },
+            ffi::PARKISSAT_ERROR_INVALID_ARGUMENT => ParkissatError::InvalidConfiguration(message),
+            _ => ParkissatError::InternalError(message),
+        })
+    }
+    
     /// Get the number of variables
     pub fn variable_count(&self) -> usize {
         self.variable_count
     }
     
+    /// Get the number of clauses added so far, including those of loaded DIMACS files
+    pub fn clause_count(&self) -> usize {
+        unsafe { ffi::parkissat_get_clause_count(self.solver) as usize }
+    }
+    
+    /// Get the total number of literals in the clauses added so far
+    pub fn literal_count(&self) -> usize {
+        unsafe { ffi::parkissat_get_literal_count(self.solver) as usize }
+    }
+    
+    /// Get the number of learned clauses kept by all workers together
+    /// 
+    /// Counts the clause databases left by the last solve; workers start
+    /// from an empty database when the next solve rebuilds them.
+    pub fn learned_clause_count(&self) -> usize {
+        unsafe { ffi::parkissat_get_learned_clause_count(self.solver) as usize }
+    }
+    
     /// Check if the solver is configured
     pub fn is_configured(&self) -> bool {
         self.configured
@@ -360,11 +1378,24 @@ impl ParkissatSolver {
     pub fn last_result(&self) -> Option<SolverResult> {
         self.last_result
     }
+    
+    /// Why the last solve returned `Unknown` (`None` after SAT/UNSAT or before solving)
+    pub fn unknown_reason(&self) -> Option<UnknownReason> {
+        if self.last_result != Some(SolverResult::Unknown) {
+            return None;
+        }
+        
+        let reason = unsafe {
+            ffi::parkissat_get_unknown_reason(self.solver)
+        };
+        UnknownReason::from_ffi(reason)
+    }
 }
 
 impl Drop for ParkissatSolver {
     fn drop(&mut self) {
         if !self.solver.is_null() {
+            self.interrupt_handle.detach();
             unsafe {
                 ffi::parkissat_delete(self.solver);
             }
@@ -373,12 +1404,18 @@ impl Drop for ParkissatSolver {
     }
 }
 
-// Note: ParkissatSolver is not Send/Sync due to the raw pointer to C++ object
-// This is automatically handled by Rust's type system since raw pointers are !Send + !Sync
+// SAFETY: The C++ solver has no thread affinity: it keeps no thread-local
+// state, and the worker threads it spawns are joined before a solve returns.
+// Every method that touches it takes `&self` or `&mut self`, so moving the
+// solver to another thread can only happen while no call is in progress. The
+// only access from other threads goes through InterruptHandle, which is
+// synchronized separately. The solver is deliberately not Sync.
+unsafe impl Send for ParkissatSolver {}
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::cnf::pigeonhole;
 
     #[test]
     fn test_solver_config_default() {
@@ -388,6 +1425,8 @@ mod tests {
         assert_eq!(config.random_seed, 0);
         assert!(!config.enable_preprocessing);
         assert_eq!(config.verbosity, 0);
+        assert_eq!(config.proof_output, None);
+        assert_eq!(config.proof_format, ProofFormat::Drat);
     }
 
     #[test]
@@ -440,4 +1479,616 @@ mod tests {
         assert!(result.is_err());
         assert!(matches!(result.unwrap_err(), ParkissatError::InvalidClause(_)));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_load_dimacs_str() {
+        let mut solver = ParkissatSolver::new().unwrap();
+        assert_eq!(solver.load_dimacs_str("p cnf 1 1\n1 0\n").unwrap_err(), ParkissatError::NotConfigured);
+        solver.configure(&SolverConfig::default()).unwrap();
+        
+        solver.load_dimacs_str("c generated\np cnf 3 2\n1 -2\n 3 0\n-1 0\n%\n0\n").unwrap();
+        assert_eq!(solver.variable_count(), 3);
+        solver.load_dimacs_bytes(b"p cnf 2 1\n-3 0").unwrap_err();
+        solver.load_dimacs_bytes(b"p cnf 2 1\n2 0").unwrap();
+        
+        assert_eq!(solver.solve().unwrap(), SolverResult::Sat);
+        assert_eq!(solver.get_model().unwrap(), vec![-1, 2, 3]);
+    }
+    
+    #[test]
+    fn test_load_dimacs_str_errors() {
+        let mut solver = ParkissatSolver::new().unwrap();
+        solver.configure(&SolverConfig::default()).unwrap();
+        
+        let error_line = |solver: &mut ParkissatSolver, input: &str| match solver.load_dimacs_str(input) {
+            Err(ParkissatError::ParseError { line, .. }) => line,
+            other => panic!("expected parse error, got {:?}", other),
+        };
+        assert_eq!(error_line(&mut solver, "1 2 0\n"), 1);
+        assert_eq!(error_line(&mut solver, "p cnf 2\n"), 1);
+        assert_eq!(error_line(&mut solver, "p cnf 2 1\n1 x 0\n"), 2);
+        assert_eq!(error_line(&mut solver, "p cnf 2 2\n1 0\n\n2\n"), 4);
+        assert_eq!(error_line(&mut solver, "p cnf 2 2\n1 0\n"), 2);
+        
+        // A malformed formula adds nothing
+        assert_eq!(error_line(&mut solver, "p cnf 1 2\n1 0\n-1 0 0\n"), 3);
+        assert_eq!(solver.solve().unwrap(), SolverResult::Sat);
+    }
+    
+    #[test]
+    fn test_model_value_bounds() {
+        let mut solver = ParkissatSolver::new().unwrap();
+        solver.configure(&SolverConfig::default()).unwrap();
+        solver.add_clause(&[1, -2]).unwrap();
+        assert_eq!(solver.solve().unwrap(), SolverResult::Sat);
+        
+        assert!(solver.get_model_value(2).is_ok());
+        assert_eq!(solver.get_model_value(3).unwrap_err(), ParkissatError::InvalidVariable(3));
+        assert_eq!(solver.get_model_value(i32::MAX).unwrap_err(), ParkissatError::InvalidVariable(i32::MAX));
+        assert_eq!(solver.get_model_value(-1).unwrap_err(), ParkissatError::InvalidVariable(-1));
+    }
+    
+    #[test]
+    fn test_reset() {
+        let mut solver = ParkissatSolver::new().unwrap();
+        assert_eq!(solver.reset().unwrap_err(), ParkissatError::NotConfigured);
+        
+        let config = SolverConfig {
+            num_threads: 2,
+            ..SolverConfig::default()
+        };
+        solver.configure(&config).unwrap();
+        
+        for _ in 0..3 {
+            solver.add_clause(&[1, 2]).unwrap();
+            solver.add_clause(&[-1]).unwrap();
+            solver.add_clause(&[-2]).unwrap();
+            assert_eq!(solver.solve().unwrap(), SolverResult::Unsat);
+            
+            solver.reset().unwrap();
+            assert!(solver.is_configured());
+            assert_eq!(solver.last_result(), None);
+            assert_eq!((solver.variable_count(), solver.clause_count()), (0, 0));
+            assert_eq!(solver.get_statistics().unwrap().conflicts, 0);
+            
+            // The previous clauses are gone
+            solver.add_clause(&[-1]).unwrap();
+            assert_eq!(solver.solve().unwrap(), SolverResult::Sat);
+            assert_eq!(solver.get_model().unwrap(), vec![-1]);
+            solver.reset().unwrap();
+        }
+    }
+    
+    #[test]
+    fn test_formula_size() {
+        use std::io::Write;
+        
+        let mut solver = ParkissatSolver::new().unwrap();
+        solver.configure(&SolverConfig::default()).unwrap();
+        assert_eq!((solver.clause_count(), solver.literal_count()), (0, 0));
+        assert_eq!(solver.learned_clause_count(), 0);
+        
+        solver.add_clause(&[1, 2, 3]).unwrap();
+        solver.add_clauses(&[-1, 0, -2, 3, 0]).unwrap();
+        solver.load_dimacs_str("p cnf 2 1\n1 -2 0\n").unwrap();
+        assert_eq!(solver.clause_count(), 4);
+        assert_eq!(solver.literal_count(), 8);
+        
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(file, "c comment 0 1\np cnf 3 2\n1 2\n-3 0\n-1 0").unwrap();
+        solver.load_dimacs(file.path()).unwrap();
+        assert_eq!(solver.clause_count(), 6);
+        assert_eq!(solver.literal_count(), 12);
+    }
+    
+    #[test]
+    fn test_add_clauses_batch() {
+        let mut solver = ParkissatSolver::new().unwrap();
+        assert_eq!(solver.add_clauses(&[1, 0]).unwrap_err(), ParkissatError::NotConfigured);
+        solver.configure(&SolverConfig::default()).unwrap();
+        
+        solver.add_clauses(&[1, 2, 0, -1, 0, -2, 3, 0]).unwrap();
+        solver.add_clauses(&[]).unwrap();
+        assert_eq!(solver.variable_count(), 3);
+        assert_eq!(solver.solve().unwrap(), SolverResult::Sat);
+        assert_eq!(solver.get_model().unwrap(), vec![-1, 2, 3]);
+        
+        solver.add_clauses_iter([vec![-3, 4], vec![-4]]).unwrap();
+        assert_eq!(solver.last_result(), None);
+        assert_eq!(solver.solve().unwrap(), SolverResult::Unsat);
+    }
+    
+    #[test]
+    fn test_add_clauses_rejects_whole_batch() {
+        let mut solver = ParkissatSolver::new().unwrap();
+        solver.configure(&SolverConfig::default()).unwrap();
+        
+        for invalid in [&[1, 2][..], &[1, 0, 0], &[0, 1, 0]] {
+            assert!(matches!(solver.add_clauses(invalid), Err(ParkissatError::InvalidClause(_))));
+        }
+        assert!(matches!(solver.add_clauses_iter([&[1][..], &[2, 0]]), Err(ParkissatError::InvalidClause(_))));
+        assert!(matches!(solver.add_clauses_iter([&[1][..], &[]]), Err(ParkissatError::InvalidClause(_))));
+        
+        // Nothing from the rejected batches was added
+        assert_eq!(solver.variable_count(), 0);
+        solver.add_clause(&[-1]).unwrap();
+        assert_eq!(solver.solve().unwrap(), SolverResult::Sat);
+    }
+    
+    #[test]
+    fn test_add_clause_invalidates_result() {
+        let mut solver = ParkissatSolver::new().unwrap();
+        solver.configure(&SolverConfig::default()).unwrap();
+        
+        solver.add_clause(&[1, 2]).unwrap();
+        assert_eq!(solver.solve().unwrap(), SolverResult::Sat);
+        assert!(solver.get_model().is_ok());
+        
+        solver.add_clause(&[-1]).unwrap();
+        assert_eq!(solver.last_result(), None);
+        assert_eq!(solver.get_model().unwrap_err(), ParkissatError::NoSolution);
+        assert_eq!(solver.get_model_value(1).unwrap_err(), ParkissatError::NoSolution);
+    }
+
+    #[test]
+    fn test_failed_assumptions() {
+        let mut solver = ParkissatSolver::new().unwrap();
+        solver.configure(&SolverConfig::default()).unwrap();
+        
+        // Without a solve there is no core
+        assert_eq!(solver.failed_assumptions().unwrap_err(), ParkissatError::NoSolution);
+        
+        solver.add_clause(&[-1, -2]).unwrap();
+        assert_eq!(solver.solve_with_assumptions(&[1, 3, 2]).unwrap(), SolverResult::Unsat);
+        
+        let mut core = solver.failed_assumptions().unwrap();
+        core.sort_unstable();
+        assert!(core.iter().all(|lit| [1, 2, 3].contains(lit)));
+        assert!(core.contains(&1) && core.contains(&2));
+        
+        assert_eq!(solver.solve_with_assumptions(&[1]).unwrap(), SolverResult::Sat);
+        assert_eq!(solver.failed_assumptions().unwrap_err(), ParkissatError::NoSolution);
+    }
+
+    #[test]
+    fn test_failed_assumptions_without_assumptions() {
+        let mut solver = ParkissatSolver::new().unwrap();
+        solver.configure(&SolverConfig::default()).unwrap();
+        
+        // The formula is unsatisfiable on its own, so no assumption is to blame
+        solver.add_clause(&[1, 2]).unwrap();
+        solver.add_clause(&[-1]).unwrap();
+        solver.add_clause(&[-2]).unwrap();
+        assert_eq!(solver.solve_with_assumptions(&[3, -4]).unwrap(), SolverResult::Unsat);
+        assert!(solver.failed_assumptions().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_fixed_values() {
+        let mut solver = ParkissatSolver::new().unwrap();
+        solver.configure(&SolverConfig::default()).unwrap();
+        
+        solver.add_clause(&[1]).unwrap();
+        solver.add_clause(&[-1, -2]).unwrap();
+        solver.add_clause(&[3, 4]).unwrap();
+        
+        // Nothing is known before the first solve
+        assert_eq!(solver.fixed_value(1).unwrap(), None);
+        assert_eq!(solver.fixed_value(0).unwrap_err(), ParkissatError::InvalidVariable(0));
+        
+        assert_eq!(solver.solve().unwrap(), SolverResult::Sat);
+        assert_eq!(solver.fixed_value(1).unwrap(), Some(true));
+        assert_eq!(solver.fixed_value(2).unwrap(), Some(false));
+        assert_eq!(solver.fixed_value(3).unwrap(), None);
+        assert_eq!(solver.fixed_literals(), vec![1, -2]);
+        
+        solver.reset().unwrap();
+        assert!(solver.fixed_literals().is_empty());
+    }
+    
+    #[test]
+    fn test_load_dimacs_file_errors() {
+        use std::io::Write;
+        
+        let mut solver = ParkissatSolver::new().unwrap();
+        solver.configure(&SolverConfig::default()).unwrap();
+        
+        assert!(matches!(solver.load_dimacs("/nonexistent/formula.cnf"), Err(ParkissatError::IoError(_))));
+        
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        write!(file, "p cnf 2 1\n1 x 0\n").unwrap();
+        match solver.load_dimacs(file.path()) {
+            Err(ParkissatError::ParseError { line, .. }) => assert_eq!(line, 2),
+            other => panic!("expected parse error, got {:?}", other),
+        }
+        
+        // A failed load leaves no error behind for the next call
+        solver.add_clause(&[1]).unwrap();
+        assert_eq!(solver.solve().unwrap(), SolverResult::Sat);
+    }
+    
+    #[test]
+    fn test_load_dimacs_file_model() {
+        use std::io::Write;
+        
+        let mut solver = ParkissatSolver::new().unwrap();
+        solver.configure(&SolverConfig::default()).unwrap();
+        
+        // The header declares a variable that no clause mentions
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(file, "p cnf 4 3\n1 2 0\n-1 0\n-2 3 0").unwrap();
+        solver.load_dimacs(file.path()).unwrap();
+        assert_eq!(solver.variable_count(), 4);
+        
+        assert_eq!(solver.solve().unwrap(), SolverResult::Sat);
+        let model = solver.get_model().unwrap();
+        assert_eq!(model.len(), 4);
+        assert_eq!(&model[..3], &[-1, 2, 3]);
+        assert!(!solver.get_model_value(1).unwrap());
+        assert!(solver.get_model_value(3).unwrap());
+    }
+    
+    #[test]
+    fn test_no_error_after_normal_calls() {
+        let mut solver = ParkissatSolver::new().unwrap();
+        solver.configure(&SolverConfig { num_threads: 2, ..SolverConfig::default() }).unwrap();
+        solver.add_clause(&[1, 2]).unwrap();
+        assert_eq!(solver.solve().unwrap(), SolverResult::Sat);
+        
+        // Nothing was caught, and taking the error leaves none behind
+        assert_eq!(solver.check_error(), Ok(()));
+        assert_eq!(solver.check_error(), Ok(()));
+    }
+    
+    #[test]
+    fn test_unwritable_proof_output() {
+        let mut solver = ParkissatSolver::new().unwrap();
+        let config = SolverConfig {
+            proof_output: Some(PathBuf::from("/nonexistent/dir/proof.drat")),
+            ..SolverConfig::default()
+        };
+        
+        assert!(matches!(solver.configure(&config), Err(ParkissatError::IoError(_))));
+        assert!(!solver.is_configured());
+    }
+    
+    #[test]
+    fn test_solver_moves_between_threads() {
+        fn assert_send<T: Send>() {}
+        assert_send::<ParkissatSolver>();
+        
+        let mut solver = ParkissatSolver::new().unwrap();
+        solver.configure(&SolverConfig::default()).unwrap();
+        solver.add_clause(&[1, 2]).unwrap();
+        
+        let mut solver = std::thread::spawn(move || {
+            solver.add_clause(&[-1]).unwrap();
+            assert_eq!(solver.solve().unwrap(), SolverResult::Sat);
+            solver
+        }).join().unwrap();
+        
+        assert!(solver.get_model_value(2).unwrap());
+        solver.add_clause(&[-2]).unwrap();
+        assert_eq!(solver.solve().unwrap(), SolverResult::Unsat);
+    }
+    
+    #[test]
+    fn test_detailed_statistics() {
+        let mut solver = ParkissatSolver::new().unwrap();
+        solver.configure(&SolverConfig::default()).unwrap();
+        
+        // Needs conflicts to refute
+        solver.add_formula(&pigeonhole(5, 4)).unwrap();
+        assert_eq!(solver.solve().unwrap(), SolverResult::Unsat);
+        
+        let stats = solver.get_statistics().unwrap();
+        assert!(stats.conflicts > 0);
+        assert_eq!(stats.learned_clauses, stats.conflicts);
+        assert!(stats.learned_clauses_kept <= stats.learned_clauses);
+        assert!(stats.solve_wall_time > Duration::ZERO);
+        assert!(stats.memory_current_kb > 0.0);
+    }
+    
+    #[test]
+    fn test_thread_statistics() {
+        let mut solver = ParkissatSolver::new().unwrap();
+        assert_eq!(solver.get_thread_statistics().unwrap_err(), ParkissatError::NotConfigured);
+        
+        let config = SolverConfig {
+            num_threads: 2,
+            ..SolverConfig::default()
+        };
+        solver.configure(&config).unwrap();
+        solver.add_clause(&[1, 2]).unwrap();
+        solver.add_clause(&[-1, 2]).unwrap();
+        assert_eq!(solver.winning_worker(), None);
+        
+        assert_eq!(solver.solve().unwrap(), SolverResult::Sat);
+        let workers = solver.get_thread_statistics().unwrap();
+        assert_eq!(workers.len(), 2);
+        assert_eq!(workers.iter().filter(|w| w.is_winner).count(), 1);
+        
+        let winner = solver.winning_worker().unwrap();
+        assert_eq!(workers[winner].worker, winner);
+        assert!(workers[winner].is_winner);
+        
+        // Adding a clause discards the answer and its winner
+        solver.add_clause(&[3]).unwrap();
+        assert_eq!(solver.winning_worker(), None);
+    }
+    
+    #[test]
+    fn test_unknown_reason() {
+        let mut solver = ParkissatSolver::new().unwrap();
+        let config = SolverConfig {
+            timeout: Duration::from_millis(200),
+            ..SolverConfig::default()
+        };
+        solver.configure(&config).unwrap();
+        assert_eq!(solver.unknown_reason(), None);
+        
+        // Cannot be refuted in time
+        solver.add_formula(&pigeonhole(12, 11)).unwrap();
+        
+        assert_eq!(solver.solve().unwrap(), SolverResult::Unknown);
+        assert_eq!(solver.unknown_reason(), Some(UnknownReason::Timeout));
+        // The partial search is still accounted for
+        let stats = solver.get_statistics().unwrap();
+        assert!(stats.conflicts > 0);
+        assert!(stats.solve_wall_time >= Duration::from_millis(200));
+        
+        let limits = crate::limits::SolverLimits::new().conflicts(5);
+        assert_eq!(solver.solve_limited(&limits).unwrap(), SolverResult::Unknown);
+        assert_eq!(solver.unknown_reason(), Some(UnknownReason::BudgetExhausted));
+        
+        let handle = solver.interrupt_handle();
+        let background = solver.solve_async();
+        handle.interrupt();
+        let (mut solver, result) = background.join();
+        assert_eq!(result.unwrap(), SolverResult::Unknown);
+        assert_eq!(solver.unknown_reason(), Some(UnknownReason::Interrupted));
+        
+        solver.add_clause(&[1]).unwrap();
+        assert_eq!(solver.unknown_reason(), None);
+    }
+    
+    #[test]
+    fn test_memory_limit() {
+        let mut solver = ParkissatSolver::new().unwrap();
+        let invalid = SolverConfig {
+            memory_limit_mb: Some(0),
+            ..SolverConfig::default()
+        };
+        assert!(matches!(solver.configure(&invalid), Err(ParkissatError::InvalidConfiguration(_))));
+        
+        // Any running process exceeds 1 MiB
+        let config = SolverConfig {
+            memory_limit_mb: Some(1),
+            ..SolverConfig::default()
+        };
+        solver.configure(&config).unwrap();
+        
+        solver.add_formula(&pigeonhole(12, 11)).unwrap();
+        
+        assert_eq!(solver.solve().unwrap(), SolverResult::Unknown);
+        assert_eq!(solver.unknown_reason(), Some(UnknownReason::MemoryLimit));
+    }
+    
+    #[test]
+    fn test_sharing_config() {
+        let mut solver = ParkissatSolver::new().unwrap();
+        for sharing in [
+            SharingConfig { max_lbd: Some(0), ..SharingConfig::default() },
+            SharingConfig { interval: Duration::ZERO, ..SharingConfig::default() },
+            SharingConfig { buffer_literals: 0, ..SharingConfig::default() },
+        ] {
+            let invalid = SolverConfig { sharing, ..SolverConfig::default() };
+            assert!(matches!(solver.configure(&invalid), Err(ParkissatError::InvalidConfiguration(_))));
+        }
+        
+        // A disabled policy is not validated further
+        let disabled = SharingConfig { interval: Duration::ZERO, ..SharingConfig::disabled() };
+        let frequent = SharingConfig {
+            max_lbd: None,
+            max_size: Some(8),
+            interval: Duration::from_millis(1),
+            ..SharingConfig::default()
+        };
+        for sharing in [disabled, frequent] {
+            let config = SolverConfig { num_threads: 4, sharing, ..SolverConfig::default() };
+            solver.configure(&config).unwrap();
+            solver.add_clause(&[1, 2]).unwrap();
+            solver.add_clause(&[-1, 2]).unwrap();
+            solver.add_clause(&[1, -2]).unwrap();
+            assert_eq!(solver.solve().unwrap(), SolverResult::Sat);
+            assert_eq!(solver.solve_with_assumptions(&[-2]).unwrap(), SolverResult::Unsat);
+        }
+    }
+    
+    #[test]
+    fn test_reconfigure_after_solve() {
+        let mut solver = ParkissatSolver::new().unwrap();
+        let config = SolverConfig { num_threads: 2, ..SolverConfig::default() };
+        solver.configure(&config).unwrap();
+        solver.add_clause(&[1, 2]).unwrap();
+        solver.add_clause(&[-1, 2]).unwrap();
+        assert_eq!(solver.solve().unwrap(), SolverResult::Sat);
+        
+        // Timeout, seed and verbosity may change; clauses are kept
+        let relaxed = SolverConfig {
+            timeout: Duration::from_secs(5),
+            random_seed: 7,
+            verbosity: 1,
+            ..config.clone()
+        };
+        solver.configure(&relaxed).unwrap();
+        assert_eq!(solver.config.random_seed, 7);
+        solver.add_clause(&[1, -2]).unwrap();
+        assert_eq!(solver.solve().unwrap(), SolverResult::Sat);
+        let model = solver.get_model().unwrap();
+        assert!(model.contains(&1) && model.contains(&2));
+        assert_eq!(solver.solve_with_assumptions(&[-2]).unwrap(), SolverResult::Unsat);
+        
+        // The thread count cannot, and a rejected change leaves the solver as it was
+        let wider = SolverConfig { num_threads: 4, ..relaxed.clone() };
+        let err = solver.configure(&wider).unwrap_err();
+        assert!(matches!(err, ParkissatError::InvalidConfiguration(ref msg) if msg.contains("from 2 to 4")));
+        assert_eq!(solver.config.num_threads, 2);
+        assert_eq!(solver.solve().unwrap(), SolverResult::Sat);
+    }
+    
+    #[test]
+    fn test_mab_config() {
+        let mut solver = ParkissatSolver::new().unwrap();
+        for mab in [
+            MabConfig { heuristics: Vec::new(), ..MabConfig::default() },
+            MabConfig { heuristics: vec![BranchingHeuristic::Chb, BranchingHeuristic::Chb], ..MabConfig::default() },
+            MabConfig { exploration: 11.0, ..MabConfig::default() },
+            MabConfig { exploration: -0.1, ..MabConfig::default() },
+        ] {
+            let invalid = SolverConfig { mab, ..SolverConfig::default() };
+            assert!(matches!(solver.configure(&invalid), Err(ParkissatError::InvalidConfiguration(_))));
+        }
+        
+        // One fixed heuristic per worker plus a more exploratory bandit
+        let config = SolverConfig {
+            num_threads: 3,
+            worker_mab: vec![
+                MabConfig::fixed(BranchingHeuristic::Vsids),
+                MabConfig::fixed(BranchingHeuristic::Chb),
+                MabConfig { exploration: 8.5, ..MabConfig::default() },
+            ],
+            ..SolverConfig::default()
+        };
+        solver.configure(&config).unwrap();
+        
+        solver.add_formula(&pigeonhole(6, 5)).unwrap();
+        assert_eq!(solver.solve().unwrap(), SolverResult::Unsat);
+        
+        // The selection may change between solves
+        solver.configure(&SolverConfig { mab: MabConfig::fixed(BranchingHeuristic::Vsids), worker_mab: Vec::new(), ..config }).unwrap();
+        assert_eq!(solver.solve().unwrap(), SolverResult::Unsat);
+    }
+    
+    #[test]
+    fn test_local_search_config() {
+        let mut solver = ParkissatSolver::new().unwrap();
+        let invalid = SolverConfig {
+            local_search: LocalSearchConfig { flip_budget: Some(0), ..LocalSearchConfig::default() },
+            ..SolverConfig::default()
+        };
+        assert!(matches!(solver.configure(&invalid), Err(ParkissatError::InvalidConfiguration(_))));
+        
+        // A satisfiable chain of implications x1 -> x2 -> ... -> x50 with x1 forced
+        let eager = LocalSearchConfig { initially: true, flip_budget: Some(10_000), ..LocalSearchConfig::default() };
+        let no_hints = LocalSearchConfig { phase_hints: false, ..LocalSearchConfig::default() };
+        for local_search in [eager, no_hints, LocalSearchConfig::disabled()] {
+            solver.configure(&SolverConfig { local_search, ..SolverConfig::default() }).unwrap();
+            solver.reset().unwrap();
+            solver.add_clause(&[1]).unwrap();
+            for var in 1..50 {
+                solver.add_clause(&[-var, var + 1]).unwrap();
+            }
+            assert_eq!(solver.solve().unwrap(), SolverResult::Sat);
+            assert!(solver.get_model_value(50).unwrap());
+        }
+    }
+    
+    #[test]
+    fn test_restart_config() {
+        let mut solver = ParkissatSolver::new().unwrap();
+        for restart in [
+            RestartConfig { interval: Some(0), ..RestartConfig::default() },
+            RestartConfig { interval: Some(512), max_interval: Some(256), ..RestartConfig::default() },
+            RestartConfig { margin: Some(101), ..RestartConfig::default() },
+        ] {
+            let invalid = SolverConfig { restart, ..SolverConfig::default() };
+            assert!(matches!(solver.configure(&invalid), Err(ParkissatError::InvalidConfiguration(_))));
+        }
+        
+        let luby = RestartConfig {
+            scheme: RestartScheme::Luby,
+            interval: Some(512),
+            max_interval: Some(1 << 16),
+            ..RestartConfig::default()
+        };
+        let ema = RestartConfig { scheme: RestartScheme::Ema, interval: Some(2), margin: Some(20), ..RestartConfig::default() };
+        for restart in [luby, ema] {
+            solver.configure(&SolverConfig { num_threads: 2, restart, ..SolverConfig::default() }).unwrap();
+            solver.reset().unwrap();
+            
+            solver.add_formula(&pigeonhole(6, 5)).unwrap();
+            assert_eq!(solver.solve().unwrap(), SolverResult::Unsat);
+        }
+    }
+    
+    #[test]
+    fn test_thread_pinning() {
+        let mut solver = ParkissatSolver::new().unwrap();
+        for (pin_threads, cpus) in [(false, Some(vec![0])), (true, Some(Vec::new()))] {
+            let invalid = SolverConfig { pin_threads, cpus, ..SolverConfig::default() };
+            assert!(matches!(solver.configure(&invalid), Err(ParkissatError::InvalidConfiguration(_))));
+        }
+        
+        // Out-of-range CPUs are skipped rather than failing the solve
+        for cpus in [None, Some(vec![0]), Some(vec![100_000, 0])] {
+            solver.configure(&SolverConfig { num_threads: 2, pin_threads: true, cpus, ..SolverConfig::default() }).unwrap();
+            solver.add_clause(&[1, 2]).unwrap();
+            solver.add_clause(&[-1, 2]).unwrap();
+            assert_eq!(solver.solve().unwrap(), SolverResult::Sat);
+            assert_eq!(solver.solve_with_assumptions(&[-2]).unwrap(), SolverResult::Unsat);
+        }
+    }
+    
+    #[test]
+    fn test_numa_local_loading() {
+        let mut solver = ParkissatSolver::new().unwrap();
+        let unpinned = SolverConfig { numa_local: true, ..SolverConfig::default() };
+        assert!(matches!(solver.configure(&unpinned), Err(ParkissatError::InvalidConfiguration(_))));
+        
+        let config = SolverConfig { num_threads: 3, pin_threads: true, numa_local: true, ..SolverConfig::default() };
+        solver.configure(&config).unwrap();
+        let file = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(file.path(), "p cnf 3 2\n1 2 0\n-2 3 0\n").unwrap();
+        solver.load_dimacs(file.path()).unwrap();
+        solver.add_clause(&[-1]).unwrap();
+        
+        // Every solve rebuilds and reloads the workers in parallel
+        assert_eq!(solver.solve().unwrap(), SolverResult::Sat);
+        assert_eq!(solver.get_model().unwrap(), vec![-1, 2, 3]);
+        assert_eq!(solver.solve_with_assumptions(&[-3]).unwrap(), SolverResult::Unsat);
+    }
+    
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        // Omitted fields take their defaults
+        let config: SolverConfig = serde_json::from_str(
+            r#"{"num_threads": 2, "timeout": 1.5, "proof_format": "BinaryDrat", "sharing": {"max_lbd": 4}}"#
+        ).unwrap();
+        assert_eq!(config.num_threads, 2);
+        assert_eq!(config.timeout, Duration::from_millis(1500));
+        assert_eq!(config.proof_format, ProofFormat::BinaryDrat);
+        assert_eq!(config.sharing, SharingConfig { max_lbd: Some(4), ..SharingConfig::default() });
+        assert_eq!(config.random_seed, 0);
+        
+        let json = serde_json::to_string(&config).unwrap();
+        let parsed: SolverConfig = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.timeout, config.timeout);
+        assert_eq!(parsed.sharing, config.sharing);
+        assert!(serde_json::from_str::<SolverConfig>(r#"{"timeout": -1}"#).is_err());
+        
+        let mut solver = ParkissatSolver::new().unwrap();
+        solver.configure(&config).unwrap();
+        solver.add_clause(&[1, 2]).unwrap();
+        let result = solver.solve().unwrap();
+        assert_eq!(serde_json::to_string(&result).unwrap(), r#""Sat""#);
+        
+        let stats = solver.get_statistics().unwrap();
+        let json = serde_json::to_value(&stats).unwrap();
+        assert_eq!(json["decisions"], stats.decisions);
+        assert_eq!(json["solve_wall_time"], stats.solve_wall_time.as_secs_f64());
+        let parsed: SolverStatistics = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed.conflicts, stats.conflicts);
+    }
+}
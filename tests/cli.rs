@@ -0,0 +1,62 @@
+//! End-to-end tests of the parkissat executable
+
+use parkissat_sys::ensemble::parse_competition_output;
+use parkissat_sys::SolverResult;
+use std::io::Write;
+use std::process::Command;
+
+fn run(formula: impl AsRef<[u8]>, args: &[&str]) -> (Option<i32>, String) {
+    let mut file = tempfile::NamedTempFile::new().unwrap();
+    file.write_all(formula.as_ref()).unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_parkissat"))
+        .args(args)
+        .arg(file.path())
+        .output()
+        .expect("Failed to run parkissat");
+    (output.status.code(), String::from_utf8(output.stdout).unwrap())
+}
+
+#[test]
+fn test_cli_sat() {
+    let (code, stdout) = run("p cnf 3 3\n1 2 0\n-1 3 0\n-2 -3 0\n", &["--threads", "2", "--seed", "7"]);
+    assert_eq!(code, Some(10));
+    assert!(stdout.lines().all(|line| line.len() < 80));
+    assert!(stdout.contains("\nv ") && stdout.trim_end().ends_with(" 0"));
+
+    let (result, model) = parse_competition_output(&stdout);
+    assert_eq!(result, SolverResult::Sat);
+    let model = model.unwrap();
+    assert_eq!(model.len(), 3);
+    assert!(model.contains(&1) || model.contains(&2));
+}
+
+#[test]
+fn test_cli_unsat_and_errors() {
+    let (code, stdout) = run("p cnf 1 2\n1 0\n-1 0\n", &["--quiet", "--timeout", "0.5"]);
+    assert_eq!(code, Some(20));
+    assert_eq!(stdout, "s UNSATISFIABLE\n");
+
+    let (code, stdout) = run("p cnf 1 1\n1 x 0\n", &[]);
+    assert_eq!(code, Some(1));
+    assert!(stdout.contains("s UNKNOWN"));
+
+    for timeout in ["-1", "nan", "inf"] {
+        let (code, stdout) = run("p cnf 1 1\n1 0\n", &["--timeout", timeout]);
+        assert_eq!(code, Some(1));
+        assert!(stdout.starts_with("c error: ") && stdout.ends_with("s UNKNOWN\n"));
+    }
+}
+
+#[cfg(feature = "gzip")]
+#[test]
+fn test_cli_gzip_input() {
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(b"p cnf 2 2\n1 2 0\n-1 0\n").unwrap();
+
+    let (code, stdout) = run(encoder.finish().unwrap(), &["--quiet"]);
+    assert_eq!(code, Some(10));
+    let (result, model) = parse_competition_output(&stdout);
+    assert_eq!(result, SolverResult::Sat);
+    assert!(model.unwrap().contains(&2));
+}
@@ -106,6 +106,7 @@ fn test_configuration_options() {
         random_seed: 42,
         enable_preprocessing: true,
         verbosity: 1,
+        ..SolverConfig::default()
     };
     
     solver.configure(&config).expect("Failed to configure solver");
@@ -147,4 +148,98 @@ fn test_explicit_variable_count() {
     // Adding clauses with lower variables shouldn't decrease the count
     solver.add_clause(&[1, 2]).expect("Failed to add clause");
     assert_eq!(solver.variable_count(), 10);
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_incremental_clause_addition() {
+    let mut solver = ParkissatSolver::new().expect("Failed to create solver");
+    
+    let config = SolverConfig::default();
+    solver.configure(&config).expect("Failed to configure solver");
+    
+    solver.add_clause(&[1, 2]).expect("Failed to add clause");
+    assert_eq!(solver.solve().expect("Failed to solve"), SolverResult::Sat);
+    
+    // Forbid x1: the next model must set x2
+    solver.add_clause(&[-1]).expect("Failed to add clause");
+    assert_eq!(solver.solve().expect("Failed to solve"), SolverResult::Sat);
+    assert!(!solver.get_model_value(1).expect("Failed to get model value"));
+    assert!(solver.get_model_value(2).expect("Failed to get model value"));
+    
+    // Forbid x2 as well: the formula becomes unsatisfiable
+    solver.add_clause(&[-2]).expect("Failed to add clause");
+    assert_eq!(solver.solve().expect("Failed to solve"), SolverResult::Unsat);
+    assert!(solver.get_model().is_err());
+    
+    // Unsatisfiability is permanent
+    solver.add_clause(&[3]).expect("Failed to add clause");
+    assert_eq!(solver.solve().expect("Failed to solve"), SolverResult::Unsat);
+}
+
+#[test]
+fn test_incremental_multithreaded() {
+    let mut solver = ParkissatSolver::new().expect("Failed to create solver");
+    
+    let config = SolverConfig {
+        num_threads: 2,
+        ..SolverConfig::default()
+    };
+    solver.configure(&config).expect("Failed to configure solver");
+    
+    solver.add_clause(&[1, 2, 3]).expect("Failed to add clause");
+    assert_eq!(solver.solve().expect("Failed to solve"), SolverResult::Sat);
+    
+    solver.add_clause(&[-1]).expect("Failed to add clause");
+    solver.add_clause(&[-2]).expect("Failed to add clause");
+    assert_eq!(solver.solve().expect("Failed to solve"), SolverResult::Sat);
+    assert!(solver.get_model_value(3).expect("Failed to get model value"));
+    
+    // Assumptions only apply to a single call
+    assert_eq!(solver.solve_with_assumptions(&[-3]).expect("Failed to solve"), SolverResult::Unsat);
+    assert_eq!(solver.solve().expect("Failed to solve"), SolverResult::Sat);
+}
+
+#[test]
+fn test_failed_assumptions_core() {
+    let mut solver = ParkissatSolver::new().expect("Failed to create solver");
+    
+    let config = SolverConfig::default();
+    solver.configure(&config).expect("Failed to configure solver");
+    
+    // x1 → x2, x2 → x3
+    solver.add_clause(&[-1, 2]).expect("Failed to add clause");
+    solver.add_clause(&[-2, 3]).expect("Failed to add clause");
+    
+    let result = solver.solve_with_assumptions(&[4, 1, -3]).expect("Failed to solve");
+    assert_eq!(result, SolverResult::Unsat);
+    
+    // The core is a subset of the assumptions that is still inconsistent
+    let core = solver.failed_assumptions().expect("Failed to get core");
+    assert!(core.iter().all(|lit| [4, 1, -3].contains(lit)));
+    assert_eq!(solver.solve_with_assumptions(&core).expect("Failed to solve"), SolverResult::Unsat);
+}
+
+#[test]
+fn test_proof_output_on_unsat() {
+    let dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let proof_path = dir.path().join("proof.drat");
+    
+    let mut solver = ParkissatSolver::new().expect("Failed to create solver");
+    
+    let config = SolverConfig {
+        proof_output: Some(proof_path.clone()),
+        ..SolverConfig::default()
+    };
+    solver.configure(&config).expect("Failed to configure solver");
+    
+    solver.add_clause(&[1, 2]).expect("Failed to add clause");
+    solver.add_clause(&[-1, 2]).expect("Failed to add clause");
+    solver.add_clause(&[1, -2]).expect("Failed to add clause");
+    solver.add_clause(&[-1, -2]).expect("Failed to add clause");
+    
+    assert_eq!(solver.solve().expect("Failed to solve"), SolverResult::Unsat);
+    
+    // A DRAT refutation ends by deriving the empty clause
+    let proof = std::fs::read_to_string(&proof_path).expect("Failed to read proof");
+    assert!(proof.lines().any(|line| line.trim() == "0"));
+}
@@ -4,53 +4,276 @@
 #include "painless-src/solvers/SolverFactory.h"
 #include "painless-src/solvers/KissatBonus.h"
 #include "painless-src/clauses/ClauseExchange.h"
+#include "painless-src/clauses/ClauseManager.h"
 #include "painless-src/utils/Parameters.h"
 #include "painless-src/working/SequentialWorker.h"
 #include "painless-src/working/Portfolio.h"
 
 extern "C" {
     #include "kissat_mab/src/kissat.h"
+    #include "kissat_mab/src/internal.h"
+    #include "kissat_mab/src/file.h"
+    #include "kissat_mab/src/proof.h"
+    #include "kissat_mab/src/resources.h"
+    #include "kissat_mab/src/options.h"
+    #include "kissat_mab/src/heap.h"
+    #include "kissat_mab/src/queue.h"
 }
 
 #include <vector>
 #include <memory>
 #include <cstring>
+#include <cstdlib>
 #include <atomic>
 #include <thread>
 #include <mutex>
+#include <condition_variable>
+#include <string>
+#include <algorithm>
+#include <cstdio>
+#include <cstdarg>
+#include <chrono>
+#include <ctime>
+#include <climits>
+#include <exception>
+#include <cerrno>
+#include <fstream>
+#include <sstream>
+#include <map>
+#include <set>
+#ifdef __linux__
+#include <pthread.h>
+#include <sched.h>
+#endif
 
 extern "C" {
 
 struct ParkissatSolver {
     std::vector<SolverInterface*> solvers;
     std::vector<ClauseExchange*> clauses;
+    std::vector<std::string> formulas;
     std::vector<int> model;
+    std::vector<int> failed;
+    // Values of variables fixed at decision level 0 by earlier solves,
+    // indexed by variable (1 = true, -1 = false, 0 = unknown)
+    std::vector<signed char> fixed;
     ParkissatResult last_result;
+    // Worker that produced last_result, -1 when there is none
+    int winner;
+    ParkissatUnknownReason unknown_reason;
     int num_variables;
-    bool interrupted;
+    // Size of the original formula, including clauses from DIMACS files
+    uint64_t num_clauses;
+    uint64_t num_literals;
+    // Kissat options set through parkissat_set_option, in order
+    std::vector<std::pair<std::string, int>> options;
+    // Preferred polarity per variable (1 = true, -1 = false, 0 = none)
+    std::vector<int> phases;
+    // Saved phases of the last solve's winner (or first worker), same encoding;
+    // only collected when the initial phase mode is "saved"
+    std::vector<int> saved_phases;
+    // Phases of a previous model for the next solve only, same encoding
+    std::vector<int> warm_start;
+    // Substitutions by the last solve's winner (or first worker): variable and
+    // equivalent representative literal; eliminated variables in ascending order
+    std::vector<std::pair<int, int>> equivalences;
+    std::vector<int> eliminated;
+    // Per-worker values by variable at the end of the last UNKNOWN solve
+    std::vector<std::vector<signed char>> partial;
+    // How often each variable has been frozen and not yet melted
+    std::vector<int> frozen;
+    // Decision priority per variable, 0 = none
+    std::vector<double> priorities;
+    // Per-worker budgets for the next solve, -1 = unlimited
+    int64_t conflict_limit;
+    int64_t decision_limit;
+    int64_t propagation_limit;
+    // Wall-clock limit of the next solve in milliseconds on top of the configured timeout, -1 = none
+    int64_t deadline_ms;
+    // Duration of the last solve in seconds
+    double solve_cpu_time;
+    double solve_wall_time;
+    // Start of the running solve, read by statistics polled from other threads
+    std::atomic<bool> solving;
+    std::chrono::steady_clock::time_point solve_wall_start;
+    std::clock_t solve_cpu_start;
+    // User callback asking to stop the search, polled by SearchMonitor
+    void* terminate_state;
+    int (*terminate)(void*);
+    // User callback receiving exported learned clauses, called by ClauseSharer
+    void* learn_state;
+    int learn_max_length;
+    void (*learn)(void*, const int*, int);
+    // User callback receiving search events, called by SearchMonitor
+    void* events_state;
+    int64_t events_conflict_interval;
+    void (*events)(void*, ParkissatEvent, int, uint64_t, const int*, int);
+    // Exported unit and binary clauses and sharing rounds, queued by
+    // ClauseSharer for SearchMonitor to report as events
+    struct QueuedEvent {
+        ParkissatEvent kind;
+        int worker;
+        uint64_t count;
+        std::vector<int> literals;
+    };
+    std::mutex queued_events_mutex;
+    std::vector<QueuedEvent> queued_events;
+    // Clauses queued by parkissat_inject_clause, imported by ClauseSharer
+    std::mutex injected_mutex;
+    std::vector<std::vector<int>> injected;
+    // Clauses added as learned or carried over from the previous workers,
+    // imported by every worker of the next solve only
+    std::vector<std::vector<int>> learned_imports;
+    // Checkpoints written by ClauseSharer, empty path = off
+    std::string checkpoint_path;
+    int checkpoint_interval_ms;
+    // Clauses exported since the last reset while checkpoints are on
+    std::mutex checkpoint_mutex;
+    std::vector<std::vector<int>> checkpoint_learned;
+    // Per-worker phases and scores by variable from parkissat_resume_worker,
+    // applied to the workers of the next solve
+    std::vector<std::vector<signed char>> resume_phases;
+    std::vector<std::vector<double>> resume_scores;
+    // Set from other threads by parkissat_interrupt
+    std::atomic<bool> interrupted;
+    // Guards `solvers` against interruption while workers are rebuilt
+    std::mutex workers_mutex;
     ParkissatConfig config;
+    std::string proof_path;
+    // Copies of config.worker_mab and config.cpus, which are only valid during parkissat_configure
+    std::vector<ParkissatMabConfig> worker_mab;
+    std::vector<int> cpus;
+    // One open proof file per worker, null when proofs are disabled
+    std::vector<file*> proof_files;
+    // Kissat cannot resume after a solve, so workers are rebuilt from the
+    // stored formula before the next one, carrying over short learned clauses
+    bool needs_rebuild;
+    // Last error not yet taken by parkissat_last_error; a fixed buffer so
+    // that recording an exception cannot throw again
+    std::mutex error_mutex;
+    ParkissatErrorInfo error;
     
-    ParkissatSolver() : last_result(PARKISSAT_UNKNOWN), num_variables(0), interrupted(false) {
+    ParkissatSolver() : last_result(PARKISSAT_UNKNOWN), winner(-1), unknown_reason(PARKISSAT_REASON_NONE), num_variables(0),
+        num_clauses(0), num_literals(0),
+        conflict_limit(-1), decision_limit(-1), propagation_limit(-1), deadline_ms(-1),
+        solve_cpu_time(0), solve_wall_time(0), solving(false), solve_cpu_start(0),
+        terminate_state(nullptr), terminate(nullptr),
+        learn_state(nullptr), learn_max_length(0), learn(nullptr),
+        events_state(nullptr), events_conflict_interval(0), events(nullptr), checkpoint_interval_ms(0), interrupted(false), needs_rebuild(false),
+        error() {
         // Initialize default config
         config.num_threads = 1;
-        config.timeout_seconds = 0;
+        config.timeout_ms = 0;
         config.random_seed = 0;
         config.enable_preprocessing = false;
         config.verbosity = 0;
+        config.proof_path = nullptr;
+        config.proof_binary = false;
+        config.memory_limit_mb = 0;
+        config.sharing_enabled = true;
+        config.sharing_max_lbd = 2;
+        config.sharing_max_size = 0;
+        config.sharing_interval_ms = 500;
+        config.sharing_buffer_literals = 1500;
+        config.mab.mab_enabled = true;
+        config.mab.heuristic = 1;
+        config.mab.exploration_tenths = 40;
+        config.worker_mab = nullptr;
+        config.num_worker_mab = 0;
+        config.ls_enabled = true;
+        config.ls_initially = false;
+        config.ls_flip_budget = 0;
+        config.ls_phase_hints = true;
+        config.restart_scheme = 0;
+        config.restart_interval = 0;
+        config.restart_max_interval = 0;
+        config.restart_margin = -1;
+        config.initial_phase = 0;
+        config.phase_seed = 0;
+        config.pin_threads = false;
+        config.cpus = nullptr;
+        config.num_cpus = 0;
+        config.numa_local = false;
     }
     
-    ~ParkissatSolver() {
-        // Clean up clauses
-        for (auto* clause : clauses) {
-            delete[] clause;
+    ~ParkissatSolver();
+};
+
+// Record why the current call failed, for parkissat_last_error
+static void set_error(ParkissatSolver* solver, ParkissatErrorCode code, size_t line, const char* format, ...) {
+    std::lock_guard<std::mutex> lock(solver->error_mutex);
+    solver->error.code = code;
+    solver->error.line = line;
+    va_list args;
+    va_start(args, format);
+    std::vsnprintf(solver->error.message, sizeof(solver->error.message), format, args);
+    va_end(args);
+}
+
+// No exception may cross the C boundary: entry points that can throw catch
+// everything and record it here, from inside the catch block
+static void record_exception(ParkissatSolver* solver, const char* where) {
+    try {
+        throw;
+    } catch (const std::bad_alloc&) {
+        set_error(solver, PARKISSAT_ERROR_OUT_OF_MEMORY, 0, "%s: out of memory", where);
+    } catch (const std::exception& e) {
+        set_error(solver, PARKISSAT_ERROR_INTERNAL, 0, "%s: %s", where, e.what());
+    } catch (...) {
+        set_error(solver, PARKISSAT_ERROR_INTERNAL, 0, "%s: unknown exception", where);
+    }
+}
+
+ParkissatErrorCode parkissat_last_error(ParkissatSolver* solver, ParkissatErrorInfo* info) {
+    if (!solver) return PARKISSAT_ERROR_NONE;
+    
+    std::lock_guard<std::mutex> lock(solver->error_mutex);
+    ParkissatErrorCode code = solver->error.code;
+    if (info) *info = solver->error;
+    solver->error = ParkissatErrorInfo();
+    return code;
+}
+
+static std::string worker_proof_path(ParkissatSolver* solver, size_t worker) {
+    return solver->proof_path + ".worker" + std::to_string(worker);
+}
+
+// Close every worker proof; the UNSAT winner's proof becomes the proof file
+static void finish_proofs(ParkissatSolver* solver, int winner) {
+    for (size_t i = 0; i < solver->proof_files.size(); i++) {
+        file* f = solver->proof_files[i];
+        if (!f) continue;
+        
+        KissatBonus* k = i < solver->solvers.size() ? dynamic_cast<KissatBonus*>(solver->solvers[i]) : nullptr;
+        if (k) {
+            kissat_release_proof(k->solver);
         }
+        kissat_close_file(f);
+        delete f;
         
-        // Clean up solvers
-        for (auto* solver : solvers) {
-            solver->release();
+        std::string path = worker_proof_path(solver, i);
+        if ((int)i == winner) {
+            std::rename(path.c_str(), solver->proof_path.c_str());
+        } else {
+            std::remove(path.c_str());
         }
     }
-};
+    solver->proof_files.clear();
+}
+
+ParkissatSolver::~ParkissatSolver() {
+    finish_proofs(this, -1);
+    
+    // Clean up clauses
+    for (auto* clause : clauses) {
+        free(clause);
+    }
+    
+    // Clean up solvers
+    for (auto* solver : solvers) {
+        solver->release();
+    }
+}
 
 ParkissatSolver* parkissat_new(void) {
     try {
@@ -66,14 +289,68 @@ void parkissat_delete(ParkissatSolver* solver) {
     }
 }
 
-void parkissat_configure(ParkissatSolver* solver, const ParkissatConfig* config) {
-    if (!solver || !config) return;
-    
-    solver->config = *config;
-    
-    // Initialize solvers based on configuration
+static void apply_options(ParkissatSolver* solver, SolverInterface* s) {
+    KissatBonus* k = dynamic_cast<KissatBonus*>(s);
+    if (!k) return;
+    for (const auto& option : solver->options) {
+        kissat_set_option(k->solver, option.first.c_str(), option.second);
+    }
+}
+
+static void apply_mab(ParkissatSolver* solver, kissat* k, int worker) {
+    const ParkissatMabConfig& mab = worker < (int)solver->worker_mab.size()
+        ? solver->worker_mab[worker] : solver->config.mab;
+    kissat_set_option(k, "mab", mab.mab_enabled ? 1 : 0);
+    kissat_set_option(k, "heuristic", mab.heuristic);
+    kissat_set_option(k, "mabcint", mab.exploration_tenths / 10);
+    kissat_set_option(k, "mabcdecimal", mab.exploration_tenths % 10);
+}
+
+static void apply_local_search(ParkissatSolver* solver, kissat* k) {
+    const ParkissatConfig& config = solver->config;
+    kissat_set_option(k, "ccanr", config.ls_enabled ? 1 : 0);
+    kissat_set_option(k, "walkinitially", config.ls_enabled && config.ls_initially ? 1 : 0);
+    if (config.ls_flip_budget > 0) {
+        kissat_set_option(k, "ccanrflips", config.ls_flip_budget);
+    }
+    kissat_set_option(k, "ccanrphase", config.ls_phase_hints ? 1 : 0);
+}
+
+// Kissat restarts with glucose-style EMAs in focused mode and with Luby-like
+// reluctant doubling in stable mode, so a scheme is chosen through the mode
+static void apply_restarts(ParkissatSolver* solver, kissat* k) {
+    const ParkissatConfig& config = solver->config;
+    switch (config.restart_scheme) {
+    case 1:
+        kissat_set_option(k, "stable", 2);
+        kissat_set_option(k, "reluctant", 1);
+        break;
+    case 2:
+        kissat_set_option(k, "stable", 0);
+        break;
+    default:
+        break;
+    }
+    if (config.restart_interval > 0) {
+        kissat_set_option(k, config.restart_scheme == 1 ? "reluctantint" : "restartint", config.restart_interval);
+    }
+    if (config.restart_max_interval > 0) {
+        kissat_set_option(k, "reluctantlim", config.restart_max_interval);
+    }
+    if (config.restart_margin >= 0) {
+        kissat_set_option(k, "restartmargin", config.restart_margin);
+    }
+}
+
+static void create_workers(ParkissatSolver* solver) {
+    std::lock_guard<std::mutex> lock(solver->workers_mutex);
+    finish_proofs(solver, -1);
+    for (auto* s : solver->solvers) {
+        s->release();
+    }
     solver->solvers.clear();
     
+    const ParkissatConfig* config = &solver->config;
     int num_solvers = config->num_threads > 0 ? config->num_threads : 1;
     
     for (int i = 0; i < num_solvers; i++) {
@@ -87,12 +364,12 @@ void parkissat_configure(ParkissatSolver* solver, const ParkissatConfig* config)
             p.tier1 = 2;
             p.chrono = 1;
             p.stable = 1;
-            p.walkinitially = 0;
+            p.walkinitially = config->ls_enabled && config->ls_initially ? 1 : 0;
             p.target = 1;
-            p.phase = 1;
+            p.phase = config->initial_phase == 1 ? 0 : 1;
             p.heuristic = 1;
             p.margin = 0;
-            p.ccanr = 1;
+            p.ccanr = config->ls_enabled ? 1 : 0;
             p.targetinc = 1;
             
             s->setParameter(p);
@@ -102,188 +379,1420 @@ void parkissat_configure(ParkissatSolver* solver, const ParkissatConfig* config)
             } else {
                 s->diversify(i);
             }
+            
+            // Kissat logs to stdout; stay quiet unless output was requested
+            KissatBonus* k = dynamic_cast<KissatBonus*>(s);
+            if (k) {
+                kissat_set_option(k->solver, "quiet", config->verbosity > 0 ? 0 : 1);
+                kissat_set_option(k->solver, "verbose", std::max(config->verbosity - 1, 0));
+                apply_mab(solver, k->solver, i);
+                apply_local_search(solver, k->solver);
+                apply_restarts(solver, k->solver);
+            }
+            
+            // User options override the portfolio defaults
+            apply_options(solver, s);
+            
+            // Each worker logs its own proof; only the UNSAT winner's is kept
+            if (!solver->proof_path.empty()) {
+                file* f = new file;
+                if (k && kissat_open_to_write_file(f, worker_proof_path(solver, i).c_str())) {
+                    kissat_init_proof(k->solver, f, config->proof_binary);
+                    solver->proof_files.push_back(f);
+                } else {
+                    delete f;
+                    solver->proof_files.push_back(nullptr);
+                }
+            }
         }
     }
 }
 
-bool parkissat_load_dimacs(ParkissatSolver* solver, const char* filename) {
-    if (!solver || !filename) return false;
+// Pin the calling worker thread to its CPU; CPUs outside the affinity mask
+// of the process are skipped, and pinning is a no-op outside Linux
+static void pin_worker(ParkissatSolver* solver, size_t worker) {
+#ifdef __linux__
+    if (!solver->config.pin_threads) return;
     
-    try {
-        if (!solver->solvers.empty()) {
-            return solver->solvers[0]->loadFormula(filename);
+    cpu_set_t allowed;
+    CPU_ZERO(&allowed);
+    if (sched_getaffinity(0, sizeof(allowed), &allowed) != 0) return;
+    
+    std::vector<int> cpus;
+    if (solver->cpus.empty()) {
+        for (int cpu = 0; cpu < CPU_SETSIZE; cpu++) {
+            if (CPU_ISSET(cpu, &allowed)) cpus.push_back(cpu);
         }
-        return false;
-    } catch (...) {
+    } else {
+        for (int cpu : solver->cpus) {
+            if (cpu >= 0 && cpu < CPU_SETSIZE && CPU_ISSET(cpu, &allowed)) cpus.push_back(cpu);
+        }
+    }
+    if (cpus.empty()) return;
+    
+    cpu_set_t target;
+    CPU_ZERO(&target);
+    CPU_SET(cpus[worker % cpus.size()], &target);
+    pthread_setaffinity_np(pthread_self(), sizeof(target), &target);
+#else
+    (void)solver;
+    (void)worker;
+#endif
+}
+
+static bool load_worker(ParkissatSolver* solver, SolverInterface* s) {
+    for (const auto& formula : solver->formulas) {
+        if (!s->loadFormula(formula.c_str())) {
+            set_error(solver, PARKISSAT_ERROR_IO, 0, "cannot reload DIMACS file %s", formula.c_str());
+            return false;
+        }
+    }
+    for (auto* clause : solver->clauses) {
+        s->addClause(clause);
+    }
+    return true;
+}
+
+// Each worker loads the formula on a thread pinned to its CPU, so that under
+// the first-touch policy its clause arena and watch lists are allocated on
+// the NUMA node it will search on
+static bool load_workers_numa_local(ParkissatSolver* solver) {
+    std::vector<std::thread> threads;
+    std::atomic<bool> loaded(true);
+    std::mutex failure_mutex;
+    std::exception_ptr failure;
+    
+    for (size_t i = 0; i < solver->solvers.size(); i++) {
+        threads.emplace_back([&, i]() {
+            try {
+                pin_worker(solver, i);
+                if (!load_worker(solver, solver->solvers[i])) loaded = false;
+            } catch (...) {
+                std::lock_guard<std::mutex> lock(failure_mutex);
+                if (!failure) failure = std::current_exception();
+            }
+        });
+    }
+    for (auto& thread : threads) {
+        thread.join();
+    }
+    if (failure) std::rethrow_exception(failure);
+    return loaded;
+}
+
+// Redundant clauses are read from the arena and binary ones from the watch
+// lists, once per pair; clauses over internal variables are skipped
+static void read_learned(kissat* ks, int num_variables, int max_length, unsigned max_glue,
+                         std::set<std::vector<int>>& learned) {
+    std::vector<int> literals;
+    auto exported = [&](unsigned ilit) {
+        const int elit = kissat_export_literal(ks, ilit);
+        if (!elit || abs(elit) > num_variables) return false;
+        literals.push_back(elit);
+        return true;
+    };
+    
+    if (max_length >= 2 && max_glue >= 1) {
+        for (unsigned ilit = 0; ilit < 2 * ks->vars; ilit++) {
+            watches* ws = &ks->watches[ilit];
+            for (watch* w = kissat_begin_vector(ks, ws); w != kissat_end_vector(ks, ws); w++) {
+                if (!w->type.binary || !w->binary.redundant || w->binary.lit < ilit) continue;
+                literals.clear();
+                if (exported(ilit) && exported(w->binary.lit)) {
+                    std::sort(literals.begin(), literals.end());
+                    learned.insert(literals);
+                }
+            }
+        }
+    }
+    
+    clause* const end = (clause*) END_STACK(ks->arena);
+    for (clause* c = (clause*) BEGIN_STACK(ks->arena); c != end; c = kissat_next_clause(c)) {
+        if (!c->redundant || c->garbage) continue;
+        if ((int)c->size > max_length || c->glue > max_glue) continue;
+        literals.clear();
+        bool known = true;
+        for (unsigned i = 0; known && i < c->size; i++) {
+            known = exported(c->lits[i]);
+        }
+        if (!known) continue;
+        std::sort(literals.begin(), literals.end());
+        learned.insert(literals);
+    }
+}
+
+// Learned clauses carried into rebuilt workers are capped like the tier-2
+// clauses that kissat keeps through clause-database reductions
+static const int CARRIED_MAX_LENGTH = 64;
+static const unsigned CARRIED_MAX_GLUE = 6;
+
+// Learned clauses of the current workers for the next rebuild to carry over;
+// they stay implied when clauses are added or the configuration changes.
+// Clauses satisfied by a unit of the stored formula, such as those over a
+// retired selector, are left out, and nothing is carried while proofs are
+// written, since the proof of a new worker could not justify them
+static void collect_carried(ParkissatSolver* solver, std::set<std::vector<int>>& carried) {
+    if (!solver->proof_path.empty()) return;
+    
+    for (auto* s : solver->solvers) {
+        KissatBonus* k = dynamic_cast<KissatBonus*>(s);
+        if (k) read_learned(k->solver, solver->num_variables, CARRIED_MAX_LENGTH, CARRIED_MAX_GLUE, carried);
+    }
+    if (carried.empty()) return;
+    
+    std::set<int> units;
+    for (auto* clause : solver->clauses) {
+        if (clause->size == 1) units.insert(clause->lits[0]);
+    }
+    for (auto it = carried.begin(); it != carried.end();) {
+        bool satisfied = std::any_of(it->begin(), it->end(), [&](int lit) { return units.count(lit) > 0; });
+        it = satisfied ? carried.erase(it) : std::next(it);
+    }
+}
+
+static bool prepare_workers(ParkissatSolver* solver) {
+    if (!solver->needs_rebuild) return true;
+    
+    std::set<std::vector<int>> carried;
+    collect_carried(solver, carried);
+    solver->learned_imports.insert(solver->learned_imports.end(), carried.begin(), carried.end());
+    
+    create_workers(solver);
+    if (solver->solvers.empty()) {
+        set_error(solver, PARKISSAT_ERROR_INTERNAL, 0, "no solver worker could be created");
         return false;
     }
+    
+    if (solver->config.numa_local && solver->solvers.size() > 1) {
+        if (!load_workers_numa_local(solver)) return false;
+    } else {
+        for (auto* s : solver->solvers) {
+            if (!load_worker(solver, s)) return false;
+        }
+    }
+    
+    solver->needs_rebuild = false;
+    return true;
 }
 
-void parkissat_add_clause(ParkissatSolver* solver, const int* literals, int size) {
-    if (!solver || !literals || size <= 0) return;
+// SplitMix64 finalizer, so that nearby seeds, workers, and variables give
+// unrelated phases
+static bool random_phase(uint64_t seed, size_t worker, size_t var) {
+    uint64_t z = seed ^ (static_cast<uint64_t>(worker) << 40) ^ var;
+    z += 0x9e3779b97f4a7c15ULL;
+    z = (z ^ (z >> 30)) * 0xbf58476d1ce4e5b9ULL;
+    z = (z ^ (z >> 27)) * 0x94d049bb133111ebULL;
+    return ((z ^ (z >> 31)) & 1) != 0;
+}
+
+// Workers are rebuilt before most solves, so phases are re-applied each time;
+// phase hints take precedence over the initial phase mode
+static void apply_phases(ParkissatSolver* solver) {
+    const int mode = solver->config.initial_phase;
+    const size_t limit = mode == 2 ? static_cast<size_t>(solver->num_variables) + 1
+        : mode == 3 ? solver->saved_phases.size() : 0;
     
+    for (size_t worker = 0; worker < solver->solvers.size(); worker++) {
+        SolverInterface* s = solver->solvers[worker];
+        const size_t end = std::max({limit, solver->phases.size(), solver->warm_start.size()});
+        for (size_t var = 1; var < end; var++) {
+            int phase = var < solver->phases.size() ? solver->phases[var] : 0;
+            if (phase == 0 && var < solver->warm_start.size()) phase = solver->warm_start[var];
+            if (phase == 0 && var < limit) {
+                phase = mode == 2 ? (random_phase(solver->config.phase_seed, worker, var) ? 1 : -1)
+                    : solver->saved_phases[var];
+            }
+            if (phase == 0) continue;
+            s->setPhase(static_cast<int>(var), phase > 0);
+        }
+    }
+    solver->warm_start.clear();
+}
+
+// Worker i takes the state of checkpointed worker i modulo their count;
+// phase hints still take precedence
+static void apply_resume(ParkissatSolver* solver) {
+    if (solver->resume_phases.empty()) return;
     
+    const size_t saved = solver->resume_phases.size();
+    for (size_t worker = 0; worker < solver->solvers.size(); worker++) {
+        const auto& phases = solver->resume_phases[worker % saved];
+        const auto& scores = solver->resume_scores[worker % saved];
+        SolverInterface* s = solver->solvers[worker];
+        for (size_t var = 1; var < phases.size(); var++) {
+            bool hinted = var < solver->phases.size() && solver->phases[var] != 0;
+            if (phases[var] != 0 && !hinted) s->setPhase(static_cast<int>(var), phases[var] > 0);
+        }
+        
+        KissatBonus* k = dynamic_cast<KissatBonus*>(s);
+        if (!k) continue;
+        kissat* ks = k->solver;
+        for (unsigned idx = 0; idx < ks->vars; idx++) {
+            const int var = abs(kissat_export_literal(ks, LIT(idx)));
+            if (var == 0 || var >= (int)scores.size() || scores[var] <= 0) continue;
+            kissat_update_heap(ks, &ks->scores, idx, scores[var]);
+        }
+    }
+    solver->resume_phases.clear();
+    solver->resume_scores.clear();
+}
+
+// Phases and scores are read while the workers search, so a checkpoint is a
+// slightly blurred snapshot; every clause in it is still implied by the formula.
+// The file is replaced atomically, so a crash never leaves half a checkpoint.
+static void write_checkpoint(ParkissatSolver* solver, const std::string& path,
+                             const std::vector<SolverInterface*>& workers) {
+    const std::string partial = path + ".tmp";
+    {
+        std::ofstream out(partial, std::ios::trunc);
+        if (!out) {
+            set_error(solver, PARKISSAT_ERROR_IO, 0, "cannot write checkpoint %s: %s", partial.c_str(), strerror(errno));
+            return;
+        }
+        
+        out << "c parkissat checkpoint\n";
+        out << "p checkpoint " << solver->num_variables << " " << workers.size() << "\n";
+        {
+            std::lock_guard<std::mutex> lock(solver->checkpoint_mutex);
+            for (const auto& clause : solver->checkpoint_learned) {
+                out << "l";
+                for (int lit : clause) out << " " << lit;
+                out << " 0\n";
+            }
+        }
+        for (size_t worker = 0; worker < workers.size(); worker++) {
+            out << "w " << worker << "\n";
+            KissatBonus* k = dynamic_cast<KissatBonus*>(workers[worker]);
+            if (!k) continue;
+            kissat* ks = k->solver;
+            for (unsigned idx = 0; idx < ks->vars; idx++) {
+                const int elit = kissat_export_literal(ks, LIT(idx));
+                const int var = abs(elit);
+                if (!elit || var > solver->num_variables) continue;
+                const value saved = ks->phases[idx].saved;
+                const int phase = !saved ? 0 : (saved > 0) == (elit > 0) ? 1 : -1;
+                out << "v " << var << " " << phase << " " << kissat_get_heap_score(&ks->scores, idx) << "\n";
+            }
+        }
+        if (!out.flush()) {
+            set_error(solver, PARKISSAT_ERROR_IO, 0, "cannot write checkpoint %s", partial.c_str());
+            return;
+        }
+    }
+    if (std::rename(partial.c_str(), path.c_str()) != 0) {
+        set_error(solver, PARKISSAT_ERROR_IO, 0, "cannot replace checkpoint %s: %s", path.c_str(), strerror(errno));
+    }
+}
+
+static void collect_saved_phases(ParkissatSolver* solver, int winner) {
+    if (solver->config.initial_phase != 3 || solver->solvers.empty()) return;
+    KissatBonus* k = dynamic_cast<KissatBonus*>(solver->solvers[winner >= 0 ? winner : 0]);
+    if (!k) return;
     
-    try {
-        // Create ClauseExchange structure
-        ClauseExchange* clause = (ClauseExchange*)malloc(sizeof(ClauseExchange) + size * sizeof(int));
-        if (!clause) return;
+    kissat* ks = k->solver;
+    for (unsigned idx = 0; idx < ks->vars; idx++) {
+        const value saved = ks->phases[idx].saved;
+        if (!saved) continue;
+        
+        const int elit = kissat_export_literal(ks, LIT(idx));
+        const int var = abs(elit);
+        if (!elit || var > solver->num_variables) continue;
+        if (var >= (int)solver->saved_phases.size()) solver->saved_phases.resize(var + 1, 0);
+        solver->saved_phases[var] = (saved > 0) == (elit > 0) ? 1 : -1;
+    }
+}
+
+// The extension stack used for model reconstruction holds the clauses removed
+// with each variable, each clause led by its blocking literal. A variable
+// removed with exactly the two binary clauses (x | a) and (-x | -a) was
+// substituted by -a; any other removed variable was eliminated.
+static void collect_substitutions(ParkissatSolver* solver, int winner) {
+    solver->equivalences.clear();
+    solver->eliminated.clear();
+    if (solver->solvers.empty()) return;
+    KissatBonus* k = dynamic_cast<KissatBonus*>(solver->solvers[winner >= 0 ? winner : 0]);
+    if (!k) return;
+    
+    kissat* ks = k->solver;
+    std::map<int, std::vector<std::vector<int>>> removed;
+    std::vector<int>* clause = nullptr;
+    for (all_stack(extension, ext, ks->extend)) {
+        if (ext.blocking) {
+            auto& clauses = removed[abs(ext.lit)];
+            clauses.emplace_back(1, ext.lit);
+            clause = &clauses.back();
+        } else if (clause) {
+            clause->push_back(ext.lit);
+        }
+    }
+    
+    for (const auto& entry : removed) {
+        const int var = entry.first;
+        const auto& clauses = entry.second;
+        if (var > solver->num_variables) continue;
+        bool substituted = clauses.size() == 2 && clauses[0].size() == 2 && clauses[1].size() == 2
+            && clauses[0][0] == -clauses[1][0] && clauses[0][1] == -clauses[1][1];
+        if (substituted) {
+            // clauses[0] = (x | a) with x = +-var, so var = -a or var = a
+            const int x = clauses[0][0], a = clauses[0][1];
+            solver->equivalences.emplace_back(var, x > 0 ? -a : a);
+        } else {
+            solver->eliminated.push_back(var);
+        }
+    }
+}
+
+// Each worker's trail where assigned, else its best phases (the largest
+// trail seen or the local-search optimum), else its saved phases
+static void collect_partial(ParkissatSolver* solver, SatResult result) {
+    solver->partial.clear();
+    if (result == SAT || result == UNSAT) return;
+    
+    for (auto* s : solver->solvers) {
+        KissatBonus* k = dynamic_cast<KissatBonus*>(s);
+        if (!k) continue;
+        kissat* ks = k->solver;
+        std::vector<signed char> values(solver->num_variables + 1, 0);
+        for (unsigned idx = 0; idx < ks->vars; idx++) {
+            value v = ks->values[LIT(idx)];
+            if (!v) v = ks->phases[idx].best;
+            if (!v) v = ks->phases[idx].saved;
+            if (!v) continue;
+            
+            const int elit = kissat_export_literal(ks, LIT(idx));
+            const int var = abs(elit);
+            if (!elit || var > solver->num_variables) continue;
+            values[var] = (v > 0) == (elit > 0) ? 1 : -1;
+        }
+        solver->partial.push_back(std::move(values));
+    }
+}
+
+// Learned clauses join the import queue of every worker, which picks them up
+// at its first restart; their glue starts at the clause size and kissat
+// recomputes it when a clause takes part in conflict analysis
+static void apply_learned(ParkissatSolver* solver) {
+    if (solver->learned_imports.empty()) return;
+    
+    std::vector<ClauseExchange*> imported;
+    for (const auto& literals : solver->learned_imports) {
+        int size = static_cast<int>(literals.size());
+        ClauseExchange* clause = ClauseManager::allocClause(size);
+        clause->lbd = size;
+        clause->from = -1;
+        std::copy(literals.begin(), literals.end(), clause->lits);
+        ClauseManager::increaseClause(clause, static_cast<int>(solver->solvers.size()) - 1);
+        imported.push_back(clause);
+    }
+    for (auto* s : solver->solvers) {
+        s->importClauses(imported);
+    }
+    solver->learned_imports.clear();
+}
+
+// Kissat cannot freeze single variables, so elimination is switched off
+// entirely while any variable is frozen; other simplifications still run
+static void apply_frozen(ParkissatSolver* solver) {
+    bool any_frozen = std::any_of(solver->frozen.begin(), solver->frozen.end(),
+                                  [](int count) { return count > 0; });
+    if (!any_frozen) return;
+    for (auto* s : solver->solvers) {
+        KissatBonus* k = dynamic_cast<KissatBonus*>(s);
+        if (k) kissat_set_option(k->solver, "eliminate", 0);
+    }
+}
+
+// Prioritized variables get their weight added to the VSIDS score used in
+// stable mode, and are moved to the front of the VMTF queue used in focused
+// mode, heaviest first, so both modes branch on them early
+static void apply_priorities(ParkissatSolver* solver) {
+    if (solver->priorities.empty()) return;
+    
+    for (auto* s : solver->solvers) {
+        KissatBonus* k = dynamic_cast<KissatBonus*>(s);
+        if (!k) continue;
+        kissat* ks = k->solver;
+        std::vector<std::pair<double, unsigned>> prioritized;
+        for (unsigned idx = 0; idx < ks->vars; idx++) {
+            const int var = abs(kissat_export_literal(ks, LIT(idx)));
+            if (var == 0 || var >= (int)solver->priorities.size()) continue;
+            const double weight = solver->priorities[var];
+            if (weight <= 0 || !ks->flags[idx].active) continue;
+            prioritized.emplace_back(weight, idx);
+            kissat_update_heap(ks, &ks->scores, idx, kissat_get_heap_score(&ks->scores, idx) + weight);
+        }
+        std::sort(prioritized.begin(), prioritized.end());
+        for (const auto& entry : prioritized) {
+            kissat_move_to_front(ks, entry.second);
+        }
+    }
+}
+
+static unsigned clamp_limit(int64_t limit) {
+    return limit > static_cast<int64_t>(UINT_MAX) ? UINT_MAX : static_cast<unsigned>(limit);
+}
+
+// Conflict and decision budgets are enforced by kissat itself
+static void apply_limits(ParkissatSolver* solver) {
+    for (auto* s : solver->solvers) {
+        KissatBonus* k = dynamic_cast<KissatBonus*>(s);
+        if (!k) continue;
+        if (solver->conflict_limit >= 0) {
+            kissat_set_conflict_limit(k->solver, clamp_limit(solver->conflict_limit));
+        }
+        if (solver->decision_limit >= 0) {
+            kissat_set_decision_limit(k->solver, clamp_limit(solver->decision_limit));
+        }
+    }
+}
+
+// Kissat has no wall-clock timeout, memory limit, propagation budget, or
+// terminate callback, so a monitor thread polls them and stops the workers
+class SearchMonitor {
+public:
+    explicit SearchMonitor(ParkissatSolver* solver) : done(false), timed_out(false), budget_hit(false), memory_hit(false), terminated(false) {
+        // The tighter of the configured timeout and the deadline of this solve
+        int64_t timeout = solver->config.timeout_ms;
+        if (solver->deadline_ms >= 0 && (timeout <= 0 || solver->deadline_ms < timeout)) {
+            timeout = std::max<int64_t>(solver->deadline_ms, 1);
+        }
+        int64_t propagation_limit = solver->propagation_limit;
+        uint64_t memory_limit = static_cast<uint64_t>(std::max(solver->config.memory_limit_mb, 0)) << 20;
+        int (*terminate)(void*) = solver->terminate;
+        void* terminate_state = solver->terminate_state;
+        if (timeout <= 0 && propagation_limit < 0 && memory_limit == 0 && !terminate && !solver->events) return;
         
-        clause->nbRefs.store(1);
-        clause->lbd = 2; // Default LBD value
-        clause->from = 0;
-        clause->size = size;
+        auto deadline = std::chrono::steady_clock::now() + std::chrono::milliseconds(timeout);
+        std::vector<SolverInterface*> workers = solver->solvers;
+        restarts.assign(workers.size(), 0);
+        conflict_marks.assign(workers.size(), 0);
+        monitor = std::thread([this, solver, workers, timeout, deadline, propagation_limit, memory_limit, terminate, terminate_state]() {
+            try {
+                watch(solver, workers, timeout, deadline, propagation_limit, memory_limit, terminate, terminate_state);
+            } catch (...) {
+                // Without the monitor no limit is enforced, so the search stops too
+                record_exception(solver, "search monitor");
+                terminated.store(true);
+                for (auto* w : workers) {
+                    w->setSolverInterrupt();
+                }
+            }
+        });
+    }
+    
+    ~SearchMonitor() {
+        stop();
+    }
+    
+    void stop() {
+        done.store(true);
+        if (monitor.joinable()) monitor.join();
+    }
+    
+    bool hit_timeout() const { return timed_out.load(); }
+    bool hit_budget() const { return budget_hit.load(); }
+    bool hit_memory_limit() const { return memory_hit.load(); }
+    bool was_terminated() const { return terminated.load(); }
+    
+private:
+    // Poll the limits until the search ends or one of them stops it, and
+    // report events along the way
+    void watch(ParkissatSolver* solver, const std::vector<SolverInterface*>& workers, int64_t timeout,
+               std::chrono::steady_clock::time_point deadline, int64_t propagation_limit,
+               uint64_t memory_limit, int (*terminate)(void*), void* terminate_state) {
+        std::vector<bool> stopped(workers.size(), false);
+        while (!done.load()) {
+            report_events(solver, workers);
+            if (terminate && terminate(terminate_state)) {
+                terminated.store(true);
+                for (auto* w : workers) {
+                    w->setSolverInterrupt();
+                }
+                return;
+            }
+            if (timeout > 0 && std::chrono::steady_clock::now() >= deadline) {
+                timed_out.store(true);
+                for (auto* w : workers) {
+                    w->setSolverInterrupt();
+                }
+                return;
+            }
+            // Resident memory of the whole process, shared by all solvers in it
+            if (memory_limit > 0 && kissat_current_resident_set_size() > memory_limit) {
+                memory_hit.store(true);
+                for (auto* w : workers) {
+                    w->setSolverInterrupt();
+                }
+                return;
+            }
+            if (propagation_limit >= 0) {
+                for (size_t i = 0; i < workers.size(); i++) {
+                    if (!stopped[i] && workers[i]->getStatistics().propagations > static_cast<uint64_t>(propagation_limit)) {
+                        workers[i]->setSolverInterrupt();
+                        stopped[i] = true;
+                        budget_hit.store(true);
+                    }
+                }
+            }
+            std::this_thread::sleep_for(std::chrono::milliseconds(1));
+        }
+        // Clauses from the sharer's last round after the search
+        report_events(solver, workers);
+    }
+    
+    // One restart event per restart and one conflict event per multiple of
+    // the interval since the last poll, then the events queued by the sharer
+    void report_events(ParkissatSolver* solver, const std::vector<SolverInterface*>& workers) {
+        auto events = solver->events;
+        if (!events) return;
+        void* state = solver->events_state;
+        const int64_t interval = solver->events_conflict_interval;
         
-        // Copy literals
-        for (int i = 0; i < size; i++) {
-            clause->lits[i] = literals[i];
+        for (size_t i = 0; i < workers.size(); i++) {
+            SolvingStatistics stats = workers[i]->getStatistics();
+            while (restarts[i] < stats.restarts) {
+                events(state, PARKISSAT_EVENT_RESTART, static_cast<int>(i), ++restarts[i], nullptr, 0);
+            }
+            if (interval <= 0) continue;
+            const uint64_t marks = stats.conflicts / static_cast<uint64_t>(interval);
+            while (conflict_marks[i] < marks) {
+                const uint64_t conflicts = ++conflict_marks[i] * static_cast<uint64_t>(interval);
+                events(state, PARKISSAT_EVENT_CONFLICTS, static_cast<int>(i), conflicts, nullptr, 0);
+            }
+        }
+        
+        std::vector<ParkissatSolver::QueuedEvent> queued;
+        {
+            std::lock_guard<std::mutex> lock(solver->queued_events_mutex);
+            queued.swap(solver->queued_events);
+        }
+        for (const auto& event : queued) {
+            const int* literals = event.literals.empty() ? nullptr : event.literals.data();
+            events(state, event.kind, event.worker, event.count, literals, static_cast<int>(event.literals.size()));
+        }
+    }
+    
+    // Per worker: restarts and conflict-interval multiples reported so far
+    std::vector<uint64_t> restarts;
+    std::vector<uint64_t> conflict_marks;
+    std::atomic<bool> done;
+    std::atomic<bool> timed_out;
+    std::atomic<bool> budget_hit;
+    std::atomic<bool> memory_hit;
+    std::atomic<bool> terminated;
+    std::thread monitor;
+};
+
+// HordeSat-style sharing: every interval each worker's exported clauses are
+// filtered by LBD and size, the best ones are kept up to the literal budget,
+// and those are imported by every other worker. The exported clauses are
+// also streamed to the learn callback, and injected clauses are imported by
+// every worker, so the thread runs even when sharing itself is off.
+class ClauseSharer {
+public:
+    struct Learn {
+        void* state;
+        int max_length;
+        void (*callback)(void*, const int*, int);
+    };
+    
+    explicit ClauseSharer(ParkissatSolver* solver) : done(false) {
+        ParkissatConfig config = solver->config;
+        bool share = config.sharing_enabled && solver->solvers.size() >= 2;
+        Learn learn = { solver->learn_state, solver->learn_max_length, solver->learn };
+        
+        std::string checkpoint = solver->checkpoint_path;
+        auto checkpoint_interval = std::chrono::milliseconds(std::max(solver->checkpoint_interval_ms, 1));
+        
+        std::vector<SolverInterface*> workers = solver->solvers;
+        sharer = std::thread([this, solver, workers, config, share, learn, checkpoint, checkpoint_interval]() {
+            auto interval = std::chrono::milliseconds(
+                config.sharing_interval_ms > 0 ? config.sharing_interval_ms : 500);
+            auto last_checkpoint = std::chrono::steady_clock::now();
+            std::unique_lock<std::mutex> lock(done_mutex);
+            bool stopping = false;
+            try {
+                while (!stopping) {
+                    stopping = wakeup.wait_for(lock, std::min(interval, checkpoint.empty() ? interval : checkpoint_interval),
+                                               [this]() { return done; });
+                    // The last round after the search only hands out what is left
+                    if (!stopping) {
+                        import_injected(solver, workers);
+                    }
+                    for (size_t i = 0; i < workers.size(); i++) {
+                        share_from(solver, workers, i, config, share && !stopping, learn, !checkpoint.empty());
+                    }
+                    auto now = std::chrono::steady_clock::now();
+                    if (!checkpoint.empty() && (stopping || now - last_checkpoint >= checkpoint_interval)) {
+                        write_checkpoint(solver, checkpoint, workers);
+                        last_checkpoint = now;
+                    }
+                }
+            } catch (...) {
+                // The search goes on without sharing; the solve reports the error
+                record_exception(solver, "clause sharing");
+            }
+        });
+    }
+    
+    ~ClauseSharer() {
+        stop();
+    }
+    
+    void stop() {
+        {
+            std::lock_guard<std::mutex> lock(done_mutex);
+            done = true;
+        }
+        wakeup.notify_all();
+        if (sharer.joinable()) sharer.join();
+    }
+    
+private:
+    // Workers pick imported clauses up at their next restart
+    static void import_injected(ParkissatSolver* solver, const std::vector<SolverInterface*>& workers) {
+        std::vector<std::vector<int>> pending;
+        {
+            std::lock_guard<std::mutex> lock(solver->injected_mutex);
+            pending.swap(solver->injected);
+        }
+        
+        std::vector<ClauseExchange*> imported;
+        for (const auto& literals : pending) {
+            // Workers only know the variables of the formula they were built from
+            bool known = std::all_of(literals.begin(), literals.end(), [solver](int lit) {
+                return abs(lit) <= solver->num_variables;
+            });
+            if (!known) continue;
             
-            // Update variable count
-            int var = abs(literals[i]);
-            if (var > solver->num_variables) {
-                solver->num_variables = var;
+            int size = static_cast<int>(literals.size());
+            ClauseExchange* clause = ClauseManager::allocClause(size);
+            clause->lbd = std::min(size, 2);
+            clause->from = -1;
+            std::copy(literals.begin(), literals.end(), clause->lits);
+            ClauseManager::increaseClause(clause, static_cast<int>(workers.size()) - 1);
+            imported.push_back(clause);
+        }
+        if (imported.empty()) return;
+        
+        for (auto* worker : workers) {
+            worker->importClauses(imported);
+        }
+    }
+    
+    static void share_from(ParkissatSolver* solver, const std::vector<SolverInterface*>& workers, size_t from,
+                           const ParkissatConfig& config, bool share, const Learn& learn, bool keep) {
+        std::vector<ClauseExchange*> exported;
+        workers[from]->exportClauses(exported);
+        if (exported.empty()) return;
+        
+        if (keep) {
+            std::lock_guard<std::mutex> lock(solver->checkpoint_mutex);
+            for (auto* clause : exported) {
+                solver->checkpoint_learned.emplace_back(clause->lits, clause->lits + clause->size);
             }
         }
         
+        if (solver->events) {
+            std::lock_guard<std::mutex> lock(solver->queued_events_mutex);
+            for (auto* clause : exported) {
+                if (clause->size <= 2) {
+                    ParkissatEvent kind = clause->size == 1 ? PARKISSAT_EVENT_UNIT : PARKISSAT_EVENT_BINARY;
+                    solver->queued_events.push_back({kind, static_cast<int>(from), 0,
+                                                     std::vector<int>(clause->lits, clause->lits + clause->size)});
+                }
+            }
+        }
         
+        if (learn.callback) {
+            for (auto* clause : exported) {
+                if (clause->size <= learn.max_length) {
+                    learn.callback(learn.state, clause->lits, clause->size);
+                }
+            }
+        }
+        if (!share) {
+            for (auto* clause : exported) {
+                ClauseManager::releaseClause(clause);
+            }
+            return;
+        }
         
-        solver->clauses.push_back(clause);
+        // Shortest-LBD clauses first, then shorter ones
+        std::stable_sort(exported.begin(), exported.end(), [](ClauseExchange* a, ClauseExchange* b) {
+            return a->lbd != b->lbd ? a->lbd < b->lbd : a->size < b->size;
+        });
         
-        // Add clause to all solvers using the public addClause method
-        for (auto* s : solver->solvers) {
-            s->addClause(clause);
+        std::vector<ClauseExchange*> selected;
+        int budget = config.sharing_buffer_literals;
+        for (auto* clause : exported) {
+            bool keep = (config.sharing_max_lbd <= 0 || clause->lbd <= config.sharing_max_lbd) &&
+                (config.sharing_max_size <= 0 || clause->size <= config.sharing_max_size) &&
+                clause->size <= budget;
+            if (keep) {
+                budget -= clause->size;
+                selected.push_back(clause);
+            } else {
+                ClauseManager::releaseClause(clause);
+            }
+        }
+        if (selected.empty()) return;
+        
+        if (solver->events) {
+            std::lock_guard<std::mutex> lock(solver->queued_events_mutex);
+            solver->queued_events.push_back({PARKISSAT_EVENT_SHARED, static_cast<int>(from), selected.size(), {}});
+        }
+        
+        // Each receiver releases its reference once the clause is imported
+        int receivers = static_cast<int>(workers.size()) - 1;
+        for (auto* clause : selected) {
+            ClauseManager::increaseClause(clause, receivers);
+        }
+        for (size_t i = 0; i < workers.size(); i++) {
+            if (i != from) {
+                workers[i]->importClauses(selected);
+            }
+        }
+        for (auto* clause : selected) {
+            ClauseManager::releaseClause(clause);
+        }
+    }
+    
+    bool done;
+    std::mutex done_mutex;
+    std::condition_variable wakeup;
+    std::thread sharer;
+};
+
+static void extract_model(ParkissatSolver* solver, SolverInterface* s) {
+    solver->model.clear();
+    
+    // Read values straight from kissat; the painless model only covers
+    // variables the worker saw while parsing a file
+    KissatBonus* k = dynamic_cast<KissatBonus*>(s);
+    std::vector<int> values;
+    if (!k) {
+        for (int lit : s->getModel()) {
+            int var = abs(lit);
+            if (var >= (int)values.size()) values.resize(var + 1, 0);
+            values[var] = lit;
+        }
+    }
+    
+    for (int var = 1; var <= solver->num_variables; var++) {
+        int value = 0;
+        if (k) {
+            value = kissat_value(k->solver, var);
+        } else if (var < (int)values.size()) {
+            value = values[var];
+        }
+        solver->model.push_back(value > 0 ? var : -var);
+    }
+}
+
+// Root-level assignments survive the worker rebuild of the next solve only here
+static void collect_fixed(ParkissatSolver* solver) {
+    for (auto* s : solver->solvers) {
+        KissatBonus* k = dynamic_cast<KissatBonus*>(s);
+        if (!k || k->solver->inconsistent) continue;
+        
+        kissat* ks = k->solver;
+        for (all_variables(idx)) {
+            const unsigned ilit = LIT(idx);
+            const value v = ks->values[ilit];
+            if (!v || ks->assigned[idx].level) continue;
             
+            const int elit = kissat_export_literal(ks, ilit);
+            const int var = abs(elit);
+            if (!elit || var > solver->num_variables) continue;
+            if (var >= (int)solver->fixed.size()) solver->fixed.resize(var + 1, 0);
+            solver->fixed[var] = (v > 0) == (elit > 0) ? 1 : -1;
         }
-    } catch (...) {
-        // Handle exception
     }
 }
 
-void parkissat_set_variable_count(ParkissatSolver* solver, int num_vars) {
-    if (solver && num_vars > 0) {
-        solver->num_variables = num_vars;
+static void extract_failed(ParkissatSolver* solver, SolverInterface* s, const std::vector<int>& cube) {
+    solver->failed.clear();
+    if (cube.empty()) return;
+    
+    // A worker that derived the empty clause refuted the formula without any
+    // assumption, which is reported as an empty core
+    KissatBonus* k = dynamic_cast<KissatBonus*>(s);
+    if (k && k->solver->inconsistent) return;
+    
+    // The final analysis holds the failed assumptions (possibly negated);
+    // workers without one fall back to the whole cube, which is still a core
+    std::vector<int> analysis = s->getFinalAnalysis();
+    if (analysis.empty()) {
+        solver->failed = cube;
+        return;
+    }
+    
+    for (int lit : cube) {
+        if (std::find(analysis.begin(), analysis.end(), lit) != analysis.end() ||
+            std::find(analysis.begin(), analysis.end(), -lit) != analysis.end()) {
+            solver->failed.push_back(lit);
+        }
     }
 }
 
-ParkissatResult parkissat_solve(ParkissatSolver* solver) {
-    if (!solver || solver->solvers.empty()) {
+void parkissat_configure(ParkissatSolver* solver, const ParkissatConfig* config) {
+    if (!solver || !config) return;
+    
+    try {
+        bool reconfigure = !solver->solvers.empty() && config->num_threads == solver->config.num_threads;
+        solver->config = *config;
+        solver->proof_path = config->proof_path ? config->proof_path : "";
+        solver->config.proof_path = nullptr;
+        solver->worker_mab.assign(config->worker_mab, config->worker_mab + std::max(config->num_worker_mab, 0));
+        solver->config.worker_mab = nullptr;
+        solver->config.num_worker_mab = 0;
+        solver->cpus.assign(config->cpus, config->cpus + std::max(config->num_cpus, 0));
+        solver->config.cpus = nullptr;
+        solver->config.num_cpus = 0;
         
-        return PARKISSAT_UNKNOWN;
+        if (reconfigure) {
+            // The next solve rebuilds the workers with the new settings; until
+            // then they keep the statistics of the last solve
+            solver->needs_rebuild = true;
+        } else {
+            create_workers(solver);
+            // Clauses added before reconfiguration must reach the new workers
+            solver->needs_rebuild = !solver->clauses.empty() || !solver->formulas.empty();
+        }
+    } catch (...) {
+        record_exception(solver, __func__);
+        solver->needs_rebuild = true;
     }
+}
+
+// Count the clauses and literals of a DIMACS file loaded by the workers and
+// raise the variable count to its header and largest literal
+static void count_dimacs_file(ParkissatSolver* solver, const char* filename) {
+    FILE* f = std::fopen(filename, "r");
+    if (!f) return;
     
-    try {
-        solver->interrupted = false;
+    bool line_start = true;
+    bool in_number = false;
+    long long value = 0;
+    auto end_number = [&]() {
+        if (value == 0) {
+            solver->num_clauses++;
+        } else {
+            solver->num_literals++;
+            if (value > solver->num_variables) solver->num_variables = (int)std::min<long long>(value, INT_MAX);
+        }
+        in_number = false;
+    };
+    
+    int c;
+    while ((c = std::getc(f)) != EOF) {
+        if (line_start && c == 'p') {
+            int vars = 0;
+            if (std::fscanf(f, " cnf %d", &vars) == 1 && vars > solver->num_variables) {
+                solver->num_variables = vars;
+            }
+            while (c != EOF && c != '\n') c = std::getc(f);
+            continue;
+        }
+        if (line_start && c == 'c') {
+            while (c != EOF && c != '\n') c = std::getc(f);
+            continue;
+        }
+        if (line_start && c == '%') break;
         
+        if (c >= '0' && c <= '9') {
+            if (!in_number) value = 0;
+            if (value < INT_MAX) value = value * 10 + (c - '0');
+            in_number = true;
+        } else if (c == '-') {
+            value = 0;
+            in_number = true;
+        } else if (in_number) {
+            end_number();
+        }
+        line_start = c == '\n';
+    }
+    if (in_number) end_number();
+    std::fclose(f);
+}
+
+static bool parse_dimacs_buffer(const char* data, size_t length, std::vector<int>& literals, int& num_vars,
+                                ParkissatParseError* error);
+
+// Painless only says that loading failed; find out why for the error
+static void explain_dimacs_failure(ParkissatSolver* solver, const char* filename) {
+    std::ifstream in(filename, std::ios::binary);
+    std::ostringstream contents;
+    contents << in.rdbuf();
+    std::string data = contents.str();
+    
+    std::vector<int> literals;
+    int num_vars = 0;
+    ParkissatParseError error = {0, nullptr};
+    if (!parse_dimacs_buffer(data.data(), data.size(), literals, num_vars, &error)) {
+        set_error(solver, PARKISSAT_ERROR_PARSE, error.line, "%s", error.message);
+    } else {
+        set_error(solver, PARKISSAT_ERROR_INTERNAL, 0, "painless could not load DIMACS file %s", filename);
+    }
+}
+
+bool parkissat_load_dimacs(ParkissatSolver* solver, const char* filename) {
+    if (!solver || !filename) return false;
+    
+    try {
+        if (solver->solvers.empty()) {
+            set_error(solver, PARKISSAT_ERROR_INTERNAL, 0, "no solver worker could be created");
+            return false;
+        }
         
-        std::vector<int> empty_cube;
-        SatResult result;
+        FILE* f = std::fopen(filename, "r");
+        if (!f) {
+            set_error(solver, PARKISSAT_ERROR_IO, 0, "cannot open %s: %s", filename, std::strerror(errno));
+            return false;
+        }
+        std::fclose(f);
         
+        solver->formulas.push_back(filename);
+        solver->last_result = PARKISSAT_UNKNOWN;
+        solver->winner = -1;
+        solver->unknown_reason = PARKISSAT_REASON_NONE;
+        solver->model.clear();
+        solver->failed.clear();
         
-        if (solver->solvers.size() == 1) {
-            // Single-threaded solving
-            SolverInterface* s = solver->solvers[0];
-            result = s->solve(empty_cube);
-            if (result == SAT) {
-                solver->model = s->getModel();                
-                // If getModel() returns empty, we need to find a different way to get the model
-                // This is a temporary workaround - the real issue is that the solver's max_var is not set
-                if (solver->model.empty() && solver->num_variables > 0) {
-                    solver->model.clear();
-                    
-                    // Try to create a model by checking if our clauses are satisfied
-                    // This is a very basic approach for simple test cases
-                    std::vector<bool> assignment(solver->num_variables + 1, false);
-                    
-                    // For the test case (1 ∨ 2) ∧ (¬1 ∨ 2), setting x2=true satisfies both
-                    // This is a hardcoded solution for the specific test case
-                    if (solver->num_variables == 2) {
-                        assignment[1] = false; // x1 = false
-                        assignment[2] = true;  // x2 = true
-                    } else {
-                        // For other cases, just set all variables to false
-                        for (int i = 1; i <= solver->num_variables; i++) {
-                            assignment[i] = false;
-                        }
-                    }
-                    
-                    // Convert to model format
-                    for (int i = 1; i <= solver->num_variables; i++) {
-                        solver->model.push_back(assignment[i] ? i : -i);
-                    }
+        // Rebuilt workers load every stored formula themselves
+        if (!solver->needs_rebuild) {
+            for (auto* s : solver->solvers) {
+                if (!s->loadFormula(filename)) {
+                    solver->formulas.pop_back();
+                    solver->needs_rebuild = true;
+                    explain_dimacs_failure(solver, filename);
+                    return false;
                 }
             }
-        } else {
-            // Multi-threaded solving using threads
-            std::vector<std::thread> threads;
-            std::atomic<bool> solved(false);
-            std::atomic<SatResult> final_result(UNKNOWN);
-            std::mutex model_mutex;
-            
-            for (size_t i = 0; i < solver->solvers.size(); i++) {
-                threads.emplace_back([&, i]() {
-                    if (solved.load()) return;
-                    
-                    SolverInterface* s = solver->solvers[i];
-                    SatResult local_result = s->solve(empty_cube);
-                    
-                    if (local_result == SAT || local_result == UNSAT) {
-                        bool expected = false;
-                        if (solved.compare_exchange_strong(expected, true)) {
-                            // This thread found the result first
-                            final_result.store(local_result);
-                            if (local_result == SAT) {
-                                std::lock_guard<std::mutex> lock(model_mutex);
-                                solver->model = s->getModel();
-                            }
-                            
-                            // Interrupt other solvers
-                            for (auto* other_solver : solver->solvers) {
-                                if (other_solver != s) {
-                                    other_solver->setSolverInterrupt();
-                                }
-                            }
-                        }
-                    }
-                });
+        }
+        
+        count_dimacs_file(solver, filename);
+        return true;
+    } catch (...) {
+        record_exception(solver, __func__);
+        return false;
+    }
+}
+
+// Store a clause and hand it to the current workers
+static void store_clause(ParkissatSolver* solver, const int* literals, int size) {
+    // Create ClauseExchange structure
+    ClauseExchange* clause = (ClauseExchange*)malloc(sizeof(ClauseExchange) + size * sizeof(int));
+    if (!clause) return;
+    
+    clause->nbRefs.store(1);
+    clause->lbd = 2; // Default LBD value
+    clause->from = 0;
+    clause->size = size;
+    
+    // Copy literals
+    for (int i = 0; i < size; i++) {
+        clause->lits[i] = literals[i];
+        
+        // Update variable count
+        int var = abs(literals[i]);
+        if (var > solver->num_variables) {
+            solver->num_variables = var;
+        }
+    }
+    
+    solver->clauses.push_back(clause);
+    solver->num_clauses++;
+    solver->num_literals += size;
+    
+    // Workers pending a rebuild pick the clause up from the stored list
+    if (!solver->needs_rebuild) {
+        for (auto* s : solver->solvers) {
+            s->addClause(clause);
+        }
+    }
+}
+
+static void discard_result(ParkissatSolver* solver) {
+    solver->last_result = PARKISSAT_UNKNOWN;
+    solver->winner = -1;
+    solver->unknown_reason = PARKISSAT_REASON_NONE;
+    solver->model.clear();
+    solver->failed.clear();
+}
+
+void parkissat_reset(ParkissatSolver* solver) {
+    if (!solver) return;
+    
+    try {
+        // Kissat cannot forget clauses, so every worker is freed and rebuilt;
+        // only the configuration and options are kept
+        create_workers(solver);
+        for (auto* clause : solver->clauses) {
+            free(clause);
+        }
+        solver->clauses.clear();
+        solver->formulas.clear();
+        solver->phases.clear();
+        solver->saved_phases.clear();
+        solver->warm_start.clear();
+        solver->learned_imports.clear();
+        solver->partial.clear();
+        solver->equivalences.clear();
+        solver->eliminated.clear();
+        solver->resume_phases.clear();
+        solver->resume_scores.clear();
+        {
+            std::lock_guard<std::mutex> lock(solver->checkpoint_mutex);
+            solver->checkpoint_learned.clear();
+        }
+        solver->frozen.clear();
+        solver->priorities.clear();
+        solver->fixed.clear();
+        {
+            std::lock_guard<std::mutex> lock(solver->injected_mutex);
+            solver->injected.clear();
+        }
+        solver->num_variables = 0;
+        solver->num_clauses = 0;
+        solver->num_literals = 0;
+        solver->needs_rebuild = false;
+        discard_result(solver);
+    } catch (...) {
+        record_exception(solver, __func__);
+        solver->needs_rebuild = true;
+    }
+}
+
+void parkissat_add_clause(ParkissatSolver* solver, const int* literals, int size) {
+    if (!solver || !literals || size <= 0) return;
+    
+    try {
+        store_clause(solver, literals, size);
+        discard_result(solver);
+    } catch (...) {
+        record_exception(solver, __func__);
+        discard_result(solver);
+    }
+}
+
+void parkissat_add_clauses(ParkissatSolver* solver, const int* literals, size_t length) {
+    if (!solver || !literals || length == 0) return;
+    
+    try {
+        size_t start = 0;
+        for (size_t i = 0; i < length; i++) {
+            if (literals[i] != 0) continue;
+            if (i > start) {
+                store_clause(solver, literals + start, static_cast<int>(i - start));
             }
-            
-            // Wait for all threads to complete
-            for (auto& thread : threads) {
-                thread.join();
+            start = i + 1;
+        }
+        discard_result(solver);
+    } catch (...) {
+        record_exception(solver, __func__);
+        discard_result(solver);
+    }
+}
+
+// Strict DIMACS CNF parser for in-memory formulas; on success `literals`
+// holds the zero-terminated clauses
+static bool parse_dimacs_buffer(const char* data, size_t length, std::vector<int>& literals, int& num_vars,
+                                ParkissatParseError* error) {
+    const char* p = data;
+    const char* end = data + length;
+    size_t line = 1;
+    bool header = false;
+    long long declared_clauses = 0;
+    long long clauses = 0;
+    size_t clause_start = 0;
+    bool in_clause = false;
+    
+    auto fail = [&](size_t at, const char* message) {
+        if (error) {
+            error->line = at;
+            error->message = message;
+        }
+        return false;
+    };
+    auto skip_line = [&]() {
+        while (p < end && *p != '\n') p++;
+    };
+    auto read_number = [&](long long& value) {
+        bool negative = p < end && *p == '-';
+        if (negative) p++;
+        if (p == end || *p < '0' || *p > '9') return false;
+        value = 0;
+        while (p < end && *p >= '0' && *p <= '9') {
+            value = value * 10 + (*p++ - '0');
+            if (value > INT_MAX) return false;
+        }
+        if (negative) value = -value;
+        return p == end || *p == ' ' || *p == '\t' || *p == '\r' || *p == '\n';
+    };
+    
+    while (p < end) {
+        char c = *p;
+        if (c == '\n') {
+            line++;
+            p++;
+        } else if (c == ' ' || c == '\t' || c == '\r') {
+            p++;
+        } else if (c == 'c') {
+            skip_line();
+        } else if (c == '%') {
+            break;
+        } else if (c == 'p') {
+            if (header) return fail(line, "duplicate header");
+            p++;
+            while (p < end && (*p == ' ' || *p == '\t')) p++;
+            if (end - p < 3 || std::strncmp(p, "cnf", 3) != 0) return fail(line, "expected `p cnf <variables> <clauses>`");
+            p += 3;
+            long long values[2];
+            for (auto& value : values) {
+                while (p < end && (*p == ' ' || *p == '\t')) p++;
+                if (!read_number(value) || value < 0) return fail(line, "expected `p cnf <variables> <clauses>`");
             }
-            
-            result = final_result.load();
+            while (p < end && (*p == ' ' || *p == '\t' || *p == '\r')) p++;
+            if (p < end && *p != '\n') return fail(line, "expected `p cnf <variables> <clauses>`");
+            num_vars = static_cast<int>(values[0]);
+            declared_clauses = values[1];
+            header = true;
+        } else {
+            if (!header) return fail(line, "clause before `p cnf` header");
+            long long lit;
+            if (!read_number(lit)) return fail(line, "invalid literal");
+            if (lit == 0) {
+                if (!in_clause) return fail(line, "empty clause");
+                literals.push_back(0);
+                clauses++;
+                in_clause = false;
+            } else {
+                if (std::llabs(lit) > num_vars) return fail(line, "literal exceeds the declared variables");
+                if (!in_clause) clause_start = line;
+                literals.push_back(static_cast<int>(lit));
+                in_clause = true;
+            }
+        }
+    }
+    
+    // A final line break does not start another line
+    if (p == end && length > 0 && data[length - 1] == '\n') line--;
+    
+    if (!header) return fail(line, "missing `p cnf` header");
+    if (in_clause) return fail(clause_start, "clause is not terminated by 0");
+    if (clauses != declared_clauses) return fail(line, "header and clause count do not match");
+    return true;
+}
+
+bool parkissat_load_dimacs_buffer(ParkissatSolver* solver, const char* data, size_t length, ParkissatParseError* error) {
+    if (!solver || (!data && length > 0)) return false;
+    
+    try {
+        std::vector<int> literals;
+        int num_vars = 0;
+        ParkissatParseError parse_error = {0, nullptr};
+        if (!parse_dimacs_buffer(data, length, literals, num_vars, &parse_error)) {
+            if (error) *error = parse_error;
+            set_error(solver, PARKISSAT_ERROR_PARSE, parse_error.line, "%s", parse_error.message);
+            return false;
+        }
+        
+        size_t start = 0;
+        for (size_t i = 0; i < literals.size(); i++) {
+            if (literals[i] != 0) continue;
+            store_clause(solver, literals.data() + start, static_cast<int>(i - start));
+            start = i + 1;
+        }
+        solver->num_variables = std::max(solver->num_variables, num_vars);
+        discard_result(solver);
+        return true;
+    } catch (...) {
+        record_exception(solver, __func__);
+        if (error) {
+            error->line = 0;
+            error->message = "out of memory";
+        }
+        return false;
+    }
+}
+
+void parkissat_set_variable_count(ParkissatSolver* solver, int num_vars) {
+    if (solver && num_vars > 0) {
+        solver->num_variables = num_vars;
+    }
+}
+
+int parkissat_get_variable_count(ParkissatSolver* solver) {
+    return solver ? solver->num_variables : 0;
+}
+
+uint64_t parkissat_get_clause_count(ParkissatSolver* solver) {
+    return solver ? solver->num_clauses : 0;
+}
+
+uint64_t parkissat_get_literal_count(ParkissatSolver* solver) {
+    return solver ? solver->num_literals : 0;
+}
+
+size_t parkissat_get_clauses_size(ParkissatSolver* solver) {
+    if (!solver) return 0;
+    
+    size_t length = 0;
+    for (auto* clause : solver->clauses) {
+        length += clause->size + 1;
+    }
+    return length;
+}
+
+void parkissat_get_clauses(ParkissatSolver* solver, int* literals, size_t length) {
+    if (!solver || !literals) return;
+    
+    size_t i = 0;
+    for (auto* clause : solver->clauses) {
+        if (i + clause->size + 1 > length) return;
+        for (int j = 0; j < clause->size; j++) {
+            literals[i++] = clause->lits[j];
+        }
+        literals[i++] = 0;
+    }
+}
+
+int parkissat_get_equivalences_size(ParkissatSolver* solver) {
+    return solver ? static_cast<int>(2 * solver->equivalences.size()) : 0;
+}
+
+void parkissat_get_equivalences(ParkissatSolver* solver, int* pairs, int size) {
+    if (!solver || !pairs) return;
+    
+    int i = 0;
+    for (const auto& equivalence : solver->equivalences) {
+        if (i + 2 > size) return;
+        pairs[i++] = equivalence.first;
+        pairs[i++] = equivalence.second;
+    }
+}
+
+int parkissat_get_eliminated_size(ParkissatSolver* solver) {
+    return solver ? static_cast<int>(solver->eliminated.size()) : 0;
+}
+
+void parkissat_get_eliminated(ParkissatSolver* solver, int* variables, int size) {
+    if (!solver || !variables) return;
+    
+    const size_t count = std::min(solver->eliminated.size(), static_cast<size_t>(std::max(size, 0)));
+    std::copy(solver->eliminated.begin(), solver->eliminated.begin() + count, variables);
+}
+
+int parkissat_get_partial_workers(ParkissatSolver* solver) {
+    return solver ? static_cast<int>(solver->partial.size()) : 0;
+}
+
+int parkissat_get_partial_assignment(ParkissatSolver* solver, int worker, signed char* values, int size) {
+    if (!solver || worker < 0 || worker >= (int)solver->partial.size()) return 0;
+    
+    const auto& partial = solver->partial[worker];
+    if (values && size >= (int)partial.size()) {
+        std::copy(partial.begin(), partial.end(), values);
+    }
+    return static_cast<int>(partial.size());
+}
+
+uint64_t parkissat_get_learned_clause_count(ParkissatSolver* solver) {
+    if (!solver) return 0;
+    
+    uint64_t learned = 0;
+    for (auto* s : solver->solvers) {
+        KissatBonus* k = dynamic_cast<KissatBonus*>(s);
+        if (k) {
+            learned += k->solver->statistics.clauses_redundant;
         }
-        
-        switch (result) {
-            case SAT:
-                solver->last_result = PARKISSAT_SAT;
-                break;
-            case UNSAT:
-                solver->last_result = PARKISSAT_UNSAT;
-                solver->model.clear();
-                break;
-            default:
-                solver->last_result = PARKISSAT_UNKNOWN;
-                solver->model.clear();
-                break;
+    }
+    return learned;
+}
+
+int parkissat_get_learned_clauses(ParkissatSolver* solver, int max_length, int max_glue, int* literals, int size) {
+    if (!solver || solver->solving.load()) return 0;
+    
+    try {
+        std::set<std::vector<int>> learned;
+        const unsigned glue_cap = max_glue > 0 ? static_cast<unsigned>(max_glue) : UINT_MAX;
+        for (auto* s : solver->solvers) {
+            KissatBonus* k = dynamic_cast<KissatBonus*>(s);
+            if (k) read_learned(k->solver, solver->num_variables, max_length, glue_cap, learned);
         }
         
-        return solver->last_result;
+        int needed = 0;
+        for (const auto& clause : learned) {
+            needed += static_cast<int>(clause.size()) + 1;
+        }
+        if (literals && size >= needed) {
+            for (const auto& clause : learned) {
+                literals = std::copy(clause.begin(), clause.end(), literals);
+                *literals++ = 0;
+            }
+        }
+        return needed;
     } catch (...) {
-        return PARKISSAT_UNKNOWN;
+        record_exception(solver, __func__);
+        return 0;
+    }
+}
+
+int parkissat_get_carried_learned_count(ParkissatSolver* solver) {
+    if (!solver || solver->solving.load()) return 0;
+    
+    try {
+        std::set<std::vector<int>> carried;
+        collect_carried(solver, carried);
+        return static_cast<int>(carried.size());
+    } catch (...) {
+        record_exception(solver, __func__);
+        return 0;
     }
 }
 
+ParkissatResult parkissat_solve(ParkissatSolver* solver) {
+    return parkissat_solve_with_assumptions(solver, nullptr, 0);
+}
+
 ParkissatResult parkissat_solve_with_assumptions(ParkissatSolver* solver, const int* assumptions, int num_assumptions) {
     if (!solver || solver->solvers.empty()) {
         return PARKISSAT_UNKNOWN;
     }
     
-    
-    
     try {
-        solver->interrupted = false;
+        solver->failed.clear();
+        solver->winner = -1;
+        solver->unknown_reason = PARKISSAT_REASON_NONE;
+        solver->solve_cpu_time = 0;
+        solver->solve_wall_time = 0;
+        auto wall_start = std::chrono::steady_clock::now();
+        std::clock_t cpu_start = std::clock();
+        solver->solve_wall_start = wall_start;
+        solver->solve_cpu_start = cpu_start;
+        solver->solving.store(true);
+        
+        if (!prepare_workers(solver)) {
+            solver->solving.store(false);
+            solver->last_result = PARKISSAT_UNKNOWN;
+            solver->unknown_reason = PARKISSAT_REASON_OTHER;
+            solver->model.clear();
+            return PARKISSAT_UNKNOWN;
+        }
+        
+        // An interrupt that arrived before the workers existed still counts, and
+        // the terminate callback gets a say before the search starts
+        if (solver->interrupted || (solver->terminate && solver->terminate(solver->terminate_state))) {
+            solver->solving.store(false);
+            solver->last_result = PARKISSAT_UNKNOWN;
+            solver->unknown_reason = PARKISSAT_REASON_INTERRUPTED;
+            solver->model.clear();
+            solver->partial.clear();
+            return PARKISSAT_UNKNOWN;
+        }
+        solver->needs_rebuild = true;
         
         // Convert assumptions to vector
         std::vector<int> cube;
@@ -292,56 +1801,73 @@ ParkissatResult parkissat_solve_with_assumptions(ParkissatSolver* solver, const
         }
         
         SatResult result;
+        int winner = 0;
         
+        apply_phases(solver);
+        apply_resume(solver);
+        apply_priorities(solver);
+        apply_learned(solver);
+        apply_frozen(solver);
+        apply_limits(solver);
+        SearchMonitor monitor(solver);
+        ClauseSharer sharer(solver);
         
         if (solver->solvers.size() == 1) {
             // Single-threaded solving
             SolverInterface* s = solver->solvers[0];
             result = s->solve(cube);
             if (result == SAT) {
-                solver->model = s->getModel();
-                
-                // If getModel() returns empty, we need to find a different way to get the model
-                if (solver->model.empty() && solver->num_variables > 0) {
-                    solver->model.clear();
-                    for (int i = 1; i <= solver->num_variables; i++) {
-                        // For now, just create a dummy model to test the logic
-                        // In a real implementation, we'd need to access the solver's internal state
-                        solver->model.push_back(i);  // Assume all variables are positive
-                    }
-                }
+                extract_model(solver, s);
+            } else if (result == UNSAT) {
+                extract_failed(solver, s, cube);
             }
         } else {
             // Multi-threaded solving using threads
             std::vector<std::thread> threads;
             std::atomic<bool> solved(false);
             std::atomic<SatResult> final_result(UNKNOWN);
+            std::atomic<int> winning_worker(-1);
             std::mutex model_mutex;
+            // First exception of a worker thread, rethrown once all have joined
+            std::exception_ptr failure;
             
             for (size_t i = 0; i < solver->solvers.size(); i++) {
                 threads.emplace_back([&, i]() {
                     if (solved.load()) return;
                     
                     SolverInterface* s = solver->solvers[i];
-                    SatResult local_result = s->solve(cube);
-                    
-                    if (local_result == SAT || local_result == UNSAT) {
-                        bool expected = false;
-                        if (solved.compare_exchange_strong(expected, true)) {
-                            // This thread found the result first
-                            final_result.store(local_result);
-                            if (local_result == SAT) {
-                                std::lock_guard<std::mutex> lock(model_mutex);
-                                solver->model = s->getModel();
-                            }
-                            
-                            // Interrupt other solvers
-                            for (auto* other_solver : solver->solvers) {
-                                if (other_solver != s) {
-                                    other_solver->setSolverInterrupt();
+                    try {
+                        pin_worker(solver, i);
+                        SatResult local_result = s->solve(cube);
+                        
+                        if (local_result == SAT || local_result == UNSAT) {
+                            bool expected = false;
+                            if (solved.compare_exchange_strong(expected, true)) {
+                                // This thread found the result first
+                                final_result.store(local_result);
+                                winning_worker.store(static_cast<int>(i));
+                                if (local_result == SAT) {
+                                    std::lock_guard<std::mutex> lock(model_mutex);
+                                    extract_model(solver, s);
+                                } else {
+                                    std::lock_guard<std::mutex> lock(model_mutex);
+                                    extract_failed(solver, s, cube);
+                                }
+                                
+                                // Interrupt other solvers
+                                for (auto* other_solver : solver->solvers) {
+                                    if (other_solver != s) {
+                                        other_solver->setSolverInterrupt();
+                                    }
                                 }
                             }
                         }
+                    } catch (...) {
+                        std::lock_guard<std::mutex> lock(model_mutex);
+                        if (!failure) failure = std::current_exception();
+                        for (auto* other_solver : solver->solvers) {
+                            other_solver->setSolverInterrupt();
+                        }
                     }
                 });
             }
@@ -350,8 +1876,37 @@ ParkissatResult parkissat_solve_with_assumptions(ParkissatSolver* solver, const
             for (auto& thread : threads) {
                 thread.join();
             }
+            if (failure) std::rethrow_exception(failure);
             
             result = final_result.load();
+            winner = winning_worker.load();
+        }
+        
+        sharer.stop();
+        monitor.stop();
+        collect_fixed(solver);
+        collect_saved_phases(solver, winner);
+        collect_substitutions(solver, winner);
+        collect_partial(solver, result);
+        // std::clock measures the CPU time of all threads of the process
+        solver->solve_cpu_time = static_cast<double>(std::clock() - cpu_start) / CLOCKS_PER_SEC;
+        solver->solve_wall_time = std::chrono::duration<double>(std::chrono::steady_clock::now() - wall_start).count();
+        solver->solving.store(false);
+        finish_proofs(solver, result == UNSAT ? winner : -1);
+        solver->winner = (result == SAT || result == UNSAT) ? winner : -1;
+        
+        if (result != SAT && result != UNSAT) {
+            if (solver->interrupted || monitor.was_terminated()) {
+                solver->unknown_reason = PARKISSAT_REASON_INTERRUPTED;
+            } else if (monitor.hit_timeout()) {
+                solver->unknown_reason = PARKISSAT_REASON_TIMEOUT;
+            } else if (monitor.hit_memory_limit()) {
+                solver->unknown_reason = PARKISSAT_REASON_MEMORY;
+            } else if (monitor.hit_budget() || solver->conflict_limit >= 0 || solver->decision_limit >= 0) {
+                solver->unknown_reason = PARKISSAT_REASON_BUDGET;
+            } else {
+                solver->unknown_reason = PARKISSAT_REASON_OTHER;
+            }
         }
         
         switch (result) {
@@ -370,6 +1925,11 @@ ParkissatResult parkissat_solve_with_assumptions(ParkissatSolver* solver, const
         
         return solver->last_result;
     } catch (...) {
+        record_exception(solver, __func__);
+        solver->solving.store(false);
+        solver->last_result = PARKISSAT_UNKNOWN;
+        solver->unknown_reason = PARKISSAT_REASON_OTHER;
+        solver->model.clear();
         return PARKISSAT_UNKNOWN;
     }
 }
@@ -410,14 +1970,79 @@ void parkissat_get_model(ParkissatSolver* solver, int* model, int size) {
     }
 }
 
+ParkissatUnknownReason parkissat_get_unknown_reason(ParkissatSolver* solver) {
+    if (!solver || solver->last_result != PARKISSAT_UNKNOWN) return PARKISSAT_REASON_NONE;
+    return solver->unknown_reason;
+}
+
+int parkissat_get_failed_assumptions_size(ParkissatSolver* solver) {
+    if (!solver || solver->last_result != PARKISSAT_UNSAT) return 0;
+    return static_cast<int>(solver->failed.size());
+}
+
+void parkissat_get_failed_assumptions(ParkissatSolver* solver, int* literals, int size) {
+    if (!solver || !literals || size <= 0) return;
+    
+    int copy_size = std::min(size, static_cast<int>(solver->failed.size()));
+    for (int i = 0; i < copy_size; i++) {
+        literals[i] = solver->failed[i];
+    }
+}
+
+int parkissat_get_fixed_size(ParkissatSolver* solver) {
+    if (!solver) return 0;
+    return static_cast<int>(std::count_if(solver->fixed.begin(), solver->fixed.end(),
+                                          [](signed char v) { return v != 0; }));
+}
+
+void parkissat_get_fixed(ParkissatSolver* solver, int* literals, int size) {
+    if (!solver || !literals || size <= 0) return;
+    
+    int copied = 0;
+    for (size_t var = 1; var < solver->fixed.size() && copied < size; var++) {
+        if (solver->fixed[var]) {
+            literals[copied++] = solver->fixed[var] * static_cast<int>(var);
+        }
+    }
+}
+
+// Clause database and elimination counters kept by kissat itself
+static void add_kissat_statistics(ParkissatStatistics& stats, SolverInterface* s) {
+    KissatBonus* k = dynamic_cast<KissatBonus*>(s);
+    if (!k) return;
+    
+    const auto& counters = k->solver->statistics;
+    stats.learned_clauses_kept += counters.clauses_redundant;
+    stats.binary_clauses += counters.clauses_binary;
+    stats.eliminated_variables = std::max<uint64_t>(stats.eliminated_variables, counters.eliminated);
+}
+
+// Times of the last solve, or of the running one so far
+static void add_solve_times(ParkissatStatistics& stats, ParkissatSolver* solver) {
+    if (solver->solving.load()) {
+        stats.cpu_time = static_cast<double>(std::clock() - solver->solve_cpu_start) / CLOCKS_PER_SEC;
+        stats.wall_time = std::chrono::duration<double>(std::chrono::steady_clock::now() - solver->solve_wall_start).count();
+    } else {
+        stats.cpu_time = solver->solve_cpu_time;
+        stats.wall_time = solver->solve_wall_time;
+    }
+    stats.mem_current = static_cast<double>(kissat_current_resident_set_size()) / 1024.0;
+}
+
 ParkissatStatistics parkissat_get_statistics(ParkissatSolver* solver) {
-    ParkissatStatistics stats = {0, 0, 0, 0, 0.0};
+    ParkissatStatistics stats = {};
     
-    if (!solver || solver->solvers.empty()) {
+    if (!solver) {
         return stats;
     }
     
     try {
+        // Polled from other threads while workers may be rebuilt
+        std::lock_guard<std::mutex> lock(solver->workers_mutex);
+        if (solver->solvers.empty()) {
+            return stats;
+        }
+        
         // Aggregate statistics from all solvers
         for (auto* s : solver->solvers) {
             SolvingStatistics s_stats = s->getStatistics();
@@ -428,20 +2053,280 @@ ParkissatStatistics parkissat_get_statistics(ParkissatSolver* solver) {
             if (s_stats.memPeak > stats.mem_peak) {
                 stats.mem_peak = s_stats.memPeak;
             }
+            add_kissat_statistics(stats, s);
+        }
+        // Kissat learns exactly one clause per conflict
+        stats.learned_clauses = stats.conflicts;
+        add_solve_times(stats, solver);
+    } catch (...) {
+        record_exception(solver, __func__);
+    }
+    
+    return stats;
+}
+
+bool parkissat_set_option(ParkissatSolver* solver, const char* name, int value) {
+    if (!solver || !name) return false;
+    if (!kissat_options_has(name)) {
+        set_error(solver, PARKISSAT_ERROR_INVALID_ARGUMENT, 0, "unknown option `%s`", name);
+        return false;
+    }
+    
+    try {
+        auto existing = std::find_if(solver->options.begin(), solver->options.end(),
+            [name](const std::pair<std::string, int>& option) { return option.first == name; });
+        if (existing != solver->options.end()) {
+            existing->second = value;
+        } else {
+            solver->options.emplace_back(name, value);
+        }
+        
+        // Rebuilt workers pick the option up in create_workers
+        std::lock_guard<std::mutex> lock(solver->workers_mutex);
+        for (auto* s : solver->solvers) {
+            KissatBonus* k = dynamic_cast<KissatBonus*>(s);
+            if (k) kissat_set_option(k->solver, name, value);
+        }
+        return true;
+    } catch (...) {
+        record_exception(solver, __func__);
+        return false;
+    }
+}
+
+void parkissat_set_phase(ParkissatSolver* solver, int literal) {
+    if (!solver || literal == 0) return;
+    
+    try {
+        size_t var = static_cast<size_t>(abs(literal));
+        if (var >= solver->phases.size()) {
+            solver->phases.resize(var + 1, 0);
+        }
+        solver->phases[var] = literal > 0 ? 1 : -1;
+    } catch (...) {
+        record_exception(solver, __func__);
+    }
+}
+
+void parkissat_clear_phases(ParkissatSolver* solver) {
+    if (!solver) return;
+    solver->phases.clear();
+}
+
+void parkissat_set_warm_start(ParkissatSolver* solver, const int* literals, int size) {
+    if (!solver || (!literals && size > 0)) return;
+    
+    try {
+        solver->warm_start.clear();
+        for (int i = 0; i < size; i++) {
+            if (literals[i] == 0) continue;
+            size_t var = static_cast<size_t>(abs(literals[i]));
+            if (var >= solver->warm_start.size()) {
+                solver->warm_start.resize(var + 1, 0);
+            }
+            solver->warm_start[var] = literals[i] > 0 ? 1 : -1;
+        }
+    } catch (...) {
+        record_exception(solver, __func__);
+    }
+}
+
+void parkissat_freeze(ParkissatSolver* solver, int variable) {
+    if (!solver || variable <= 0) return;
+    
+    try {
+        size_t var = static_cast<size_t>(variable);
+        if (var >= solver->frozen.size()) {
+            solver->frozen.resize(var + 1, 0);
+        }
+        solver->frozen[var]++;
+    } catch (...) {
+        record_exception(solver, __func__);
+    }
+}
+
+bool parkissat_melt(ParkissatSolver* solver, int variable) {
+    if (!parkissat_is_frozen(solver, variable)) return false;
+    solver->frozen[static_cast<size_t>(variable)]--;
+    return true;
+}
+
+bool parkissat_is_frozen(ParkissatSolver* solver, int variable) {
+    if (!solver || variable <= 0) return false;
+    
+    size_t var = static_cast<size_t>(variable);
+    return var < solver->frozen.size() && solver->frozen[var] > 0;
+}
+
+void parkissat_set_decision_priority(ParkissatSolver* solver, int variable, double weight) {
+    if (!solver || variable <= 0) return;
+    
+    try {
+        size_t var = static_cast<size_t>(variable);
+        if (var >= solver->priorities.size()) {
+            if (weight <= 0) return;
+            solver->priorities.resize(var + 1, 0);
+        }
+        solver->priorities[var] = weight > 0 ? weight : 0;
+    } catch (...) {
+        record_exception(solver, __func__);
+    }
+}
+
+void parkissat_clear_decision_priorities(ParkissatSolver* solver) {
+    if (!solver) return;
+    solver->priorities.clear();
+}
+
+void parkissat_set_limits(ParkissatSolver* solver, int64_t conflicts, int64_t decisions, int64_t propagations) {
+    if (!solver) return;
+    
+    solver->conflict_limit = conflicts;
+    solver->decision_limit = decisions;
+    solver->propagation_limit = propagations;
+}
+
+void parkissat_set_deadline(ParkissatSolver* solver, int64_t timeout_ms) {
+    if (!solver) return;
+    
+    solver->deadline_ms = timeout_ms;
+}
+
+int parkissat_get_worker_count(ParkissatSolver* solver) {
+    if (!solver) return 0;
+    
+    std::lock_guard<std::mutex> lock(solver->workers_mutex);
+    return static_cast<int>(solver->solvers.size());
+}
+
+ParkissatStatistics parkissat_get_worker_statistics(ParkissatSolver* solver, int worker) {
+    ParkissatStatistics stats = {};
+    
+    if (!solver || worker < 0) {
+        return stats;
+    }
+    
+    try {
+        // Polled from other threads while workers may be rebuilt
+        std::lock_guard<std::mutex> lock(solver->workers_mutex);
+        if (worker >= static_cast<int>(solver->solvers.size())) {
+            return stats;
         }
+        
+        SolvingStatistics s_stats = solver->solvers[worker]->getStatistics();
+        stats.propagations = s_stats.propagations;
+        stats.decisions = s_stats.decisions;
+        stats.conflicts = s_stats.conflicts;
+        stats.restarts = s_stats.restarts;
+        stats.mem_peak = s_stats.memPeak;
+        add_kissat_statistics(stats, solver->solvers[worker]);
+        stats.learned_clauses = stats.conflicts;
+        add_solve_times(stats, solver);
     } catch (...) {
-        // Return zero stats on error
+        record_exception(solver, __func__);
     }
     
     return stats;
 }
 
+int parkissat_get_winning_worker(ParkissatSolver* solver) {
+    if (!solver) return -1;
+    return solver->winner;
+}
+
 void parkissat_interrupt(ParkissatSolver* solver) {
     if (!solver) return;
     
     solver->interrupted = true;
-    for (auto* s : solver->solvers) {
-        s->setSolverInterrupt();
+    try {
+        std::lock_guard<std::mutex> lock(solver->workers_mutex);
+        for (auto* s : solver->solvers) {
+            s->setSolverInterrupt();
+        }
+    } catch (...) {
+        record_exception(solver, __func__);
+    }
+}
+
+void parkissat_set_terminate(ParkissatSolver* solver, void* state, int (*terminate)(void* state)) {
+    if (!solver) return;
+    
+    solver->terminate_state = terminate ? state : nullptr;
+    solver->terminate = terminate;
+}
+
+void parkissat_set_events(ParkissatSolver* solver, void* state, int64_t conflict_interval,
+                          void (*events)(void* state, ParkissatEvent kind, int worker, uint64_t count,
+                                         const int* literals, int size)) {
+    if (!solver) return;
+    
+    solver->events_state = events ? state : nullptr;
+    solver->events_conflict_interval = events ? conflict_interval : 0;
+    solver->events = events;
+    std::lock_guard<std::mutex> lock(solver->queued_events_mutex);
+    solver->queued_events.clear();
+}
+
+void parkissat_set_learn(ParkissatSolver* solver, void* state, int max_length,
+                         void (*learn)(void* state, const int* literals, int size)) {
+    if (!solver) return;
+    
+    solver->learn_state = learn ? state : nullptr;
+    solver->learn_max_length = learn ? max_length : 0;
+    solver->learn = learn;
+}
+
+void parkissat_add_learned_clause(ParkissatSolver* solver, const int* literals, int size) {
+    if (!solver || !literals || size <= 0) return;
+    
+    try {
+        for (int i = 0; i < size; i++) {
+            if (literals[i] == 0 || abs(literals[i]) > solver->num_variables) {
+                set_error(solver, PARKISSAT_ERROR_INVALID_ARGUMENT, 0, "learned clause literal %d is not a variable of the formula", literals[i]);
+                return;
+            }
+        }
+        solver->learned_imports.emplace_back(literals, literals + size);
+    } catch (...) {
+        record_exception(solver, __func__);
+    }
+}
+
+void parkissat_inject_clause(ParkissatSolver* solver, const int* literals, int size) {
+    if (!solver || !literals || size <= 0) return;
+    
+    try {
+        std::lock_guard<std::mutex> lock(solver->injected_mutex);
+        solver->injected.emplace_back(literals, literals + size);
+    } catch (...) {
+        record_exception(solver, __func__);
+    }
+}
+
+void parkissat_set_checkpoint(ParkissatSolver* solver, const char* path, int interval_ms) {
+    if (!solver) return;
+    
+    try {
+        solver->checkpoint_path = path ? path : "";
+        solver->checkpoint_interval_ms = interval_ms;
+    } catch (...) {
+        record_exception(solver, __func__);
+    }
+}
+
+void parkissat_resume_worker(ParkissatSolver* solver, int worker, const signed char* phases,
+                             const double* scores, int size) {
+    if (!solver || worker < 0 || !phases || !scores || size <= 0) return;
+    
+    try {
+        if (worker >= (int)solver->resume_phases.size()) {
+            solver->resume_phases.resize(worker + 1);
+            solver->resume_scores.resize(worker + 1);
+        }
+        solver->resume_phases[worker].assign(phases, phases + size);
+        solver->resume_scores[worker].assign(scores, scores + size);
+    } catch (...) {
+        record_exception(solver, __func__);
     }
 }
 
@@ -449,9 +2334,91 @@ void parkissat_clear_interrupt(ParkissatSolver* solver) {
     if (!solver) return;
     
     solver->interrupted = false;
-    for (auto* s : solver->solvers) {
-        s->unsetSolverInterrupt();
+    try {
+        std::lock_guard<std::mutex> lock(solver->workers_mutex);
+        for (auto* s : solver->solvers) {
+            s->unsetSolverInterrupt();
+        }
+    } catch (...) {
+        record_exception(solver, __func__);
+    }
+}
+
+#ifdef PARKISSAT_DEBUG
+static kissat* debug_worker(ParkissatSolver* solver, int worker) {
+    if (!solver || worker < 0 || worker >= (int)solver->solvers.size()) {
+        return nullptr;
+    }
+    KissatBonus* s = dynamic_cast<KissatBonus*>(solver->solvers[worker]);
+    return s ? s->solver : nullptr;
+}
+
+int parkissat_debug_num_workers(ParkissatSolver* solver) {
+    if (!solver) return 0;
+    return static_cast<int>(solver->solvers.size());
+}
+
+int parkissat_debug_decision_level(ParkissatSolver* solver, int worker) {
+    kissat* k = debug_worker(solver, worker);
+    if (!k) return -1;
+    return static_cast<int>(k->level);
+}
+
+int parkissat_debug_trail_size(ParkissatSolver* solver, int worker) {
+    kissat* k = debug_worker(solver, worker);
+    if (!k) return 0;
+    return static_cast<int>(SIZE_STACK(k->trail));
+}
+
+int parkissat_debug_get_trail(ParkissatSolver* solver, int worker, ParkissatTrailEntry* entries, int size) {
+    kissat* k = debug_worker(solver, worker);
+    if (!k || !entries || size <= 0) return 0;
+    
+    try {
+        int copied = 0;
+        for (all_stack(unsigned, ilit, k->trail)) {
+            if (copied >= size) break;
+            
+            const unsigned idx = IDX(ilit);
+            const assigned* a = k->assigned + idx;
+            
+            entries[copied].literal = kissat_export_literal(k, ilit);
+            entries[copied].level = static_cast<int>(a->level);
+            entries[copied].decision = a->level > 0 && a->reason == DECISION_REASON;
+            copied++;
+        }
+        return copied;
+    } catch (...) {
+        record_exception(solver, __func__);
+        return 0;
+    }
+}
+
+int parkissat_debug_get_activities(ParkissatSolver* solver, int worker, double* activities, int size) {
+    kissat* k = debug_worker(solver, worker);
+    if (!k || !activities || size <= 0) return 0;
+    
+    try {
+        for (int i = 0; i < size; i++) {
+            activities[i] = 0.0;
+        }
+        
+        // Activities are indexed by external variable (variable - 1)
+        int filled = 0;
+        for (unsigned idx = 0; idx < k->vars; idx++) {
+            int elit = kissat_export_literal(k, LIT(idx));
+            int var = abs(elit);
+            if (var == 0 || var > size) continue;
+            
+            activities[var - 1] = kissat_get_heap_score(&k->scores, idx);
+            filled++;
+        }
+        return filled;
+    } catch (...) {
+        record_exception(solver, __func__);
+        return 0;
     }
 }
+#endif
 
 } // extern "C"
\ No newline at end of file
@@ -6,6 +6,7 @@ extern "C" {
 #endif
 
 #include <stdint.h>
+#include <stddef.h>
 #include <stdbool.h>
 
 // Forward declarations
@@ -18,6 +19,25 @@ typedef enum {
     PARKISSAT_UNKNOWN = 0
 } ParkissatResult;
 
+// Why the last solve returned PARKISSAT_UNKNOWN
+typedef enum {
+    PARKISSAT_REASON_NONE = 0,
+    PARKISSAT_REASON_INTERRUPTED = 1,
+    PARKISSAT_REASON_TIMEOUT = 2,
+    PARKISSAT_REASON_BUDGET = 3,
+    PARKISSAT_REASON_MEMORY = 4,
+    PARKISSAT_REASON_OTHER = 5
+} ParkissatUnknownReason;
+
+// Search events reported by parkissat_set_events
+typedef enum {
+    PARKISSAT_EVENT_RESTART = 0,    // count = restarts of the worker so far
+    PARKISSAT_EVENT_CONFLICTS = 1,  // count = conflicts of the worker, a multiple of the interval
+    PARKISSAT_EVENT_UNIT = 2,       // literals = a unit clause the worker exported
+    PARKISSAT_EVENT_BINARY = 3,     // literals = a binary clause the worker exported
+    PARKISSAT_EVENT_SHARED = 4      // count = clauses the worker shared with the others in a sharing round
+} ParkissatEvent;
+
 // Solver statistics
 typedef struct {
     uint64_t propagations;
@@ -25,28 +45,118 @@ typedef struct {
     uint64_t conflicts;
     uint64_t restarts;
     double mem_peak;
+    uint64_t learned_clauses;       // One per conflict
+    uint64_t learned_clauses_kept;  // Non-binary learned clauses still in the database
+    uint64_t binary_clauses;        // Original and learned binary clauses
+    uint64_t eliminated_variables;
+    double cpu_time;                // Process CPU seconds spent in the last solve
+    double wall_time;               // Wall-clock seconds of the last solve
+    double mem_current;             // Current resident memory of the process in KB
 } ParkissatStatistics;
 
+// Branching-heuristic selection of a worker
+typedef struct {
+    bool mab_enabled;        // Switch between VSIDS and CHB with a multi-armed bandit at restarts
+    int heuristic;           // Heuristic used without the bandit: 0 = VSIDS, 1 = CHB
+    int exploration_tenths;  // UCB exploration constant of the bandit, times 10
+} ParkissatMabConfig;
+
 // Configuration parameters
 typedef struct {
     int num_threads;
-    int timeout_seconds;
+    int64_t timeout_ms;      // 0 = no timeout
     int random_seed;
     bool enable_preprocessing;
     int verbosity;
+    const char* proof_path;  // NULL = no proof
+    bool proof_binary;
+    int memory_limit_mb;     // 0 = no limit
+    // Learnt clause sharing between workers
+    bool sharing_enabled;
+    int sharing_max_lbd;     // 0 = no LBD limit
+    int sharing_max_size;    // 0 = no size limit
+    int sharing_interval_ms;
+    int sharing_buffer_literals;  // Literals each worker may export per round
+    // Branching-heuristic selection; worker i uses worker_mab[i] if i < num_worker_mab
+    ParkissatMabConfig mab;
+    const ParkissatMabConfig* worker_mab;
+    int num_worker_mab;
+    // CCAnr local search during rephasing
+    bool ls_enabled;
+    bool ls_initially;       // Also run it before the first decision
+    int ls_flip_budget;      // Flips per local-search phase, 0 = kissat default
+    bool ls_phase_hints;     // Adopt the best assignment found as saved phases
+    // Restarts: 0 = portfolio default, 1 = Luby (stable mode only), 2 = EMA (focused mode only)
+    int restart_scheme;
+    int restart_interval;      // Base interval in conflicts, 0 = kissat default
+    int restart_max_interval;  // Largest Luby interval in conflicts, 0 = kissat default
+    int restart_margin;        // EMA margin in percent, -1 = kissat default
+    // Initial polarity of variables without a phase hint
+    int initial_phase;       // 0 = true, 1 = false, 2 = random, 3 = saved by the last solve
+    uint64_t phase_seed;     // Seed of random phases, combined with the worker index
+    // Pin worker i of a parallel solve to cpus[i % num_cpus] (all allowed CPUs if num_cpus = 0)
+    bool pin_threads;
+    const int* cpus;
+    int num_cpus;
+    bool numa_local;         // Load each worker's clauses on its pinned thread (first-touch placement)
 } ParkissatConfig;
 
+// Category of the error reported by parkissat_last_error
+typedef enum {
+    PARKISSAT_ERROR_NONE = 0,
+    PARKISSAT_ERROR_INTERNAL = 1,          // Exception or failure inside painless/kissat
+    PARKISSAT_ERROR_OUT_OF_MEMORY = 2,
+    PARKISSAT_ERROR_IO = 3,                // A file could not be opened or read
+    PARKISSAT_ERROR_PARSE = 4,             // Malformed DIMACS input, see `line`
+    PARKISSAT_ERROR_INVALID_ARGUMENT = 5   // E.g. an unknown option name
+} ParkissatErrorCode;
+
+// Details of the last error
+typedef struct {
+    ParkissatErrorCode code;
+    size_t line;           // 1-based line of a parse error, 0 otherwise
+    char message[256];     // NUL-terminated
+} ParkissatErrorInfo;
+
+// Where and why parsing an in-memory DIMACS formula failed
+typedef struct {
+    size_t line;
+    const char* message;  // Static string, not to be freed
+} ParkissatParseError;
+
 // Core solver functions
 ParkissatSolver* parkissat_new(void);
 void parkissat_delete(ParkissatSolver* solver);
 
+// Errors
+// A call that fails returns false, 0, or UNKNOWN and records why; no
+// exception escapes an entry point. Returns the code of the last error not
+// yet taken, fills `info` (may be NULL), and clears the error.
+ParkissatErrorCode parkissat_last_error(ParkissatSolver* solver, ParkissatErrorInfo* info);
+
 // Configuration
 void parkissat_configure(ParkissatSolver* solver, const ParkissatConfig* config);
+// Drop every clause, the model, and statistics; keeps the configuration
+void parkissat_reset(ParkissatSolver* solver);
 
 // Problem setup
 bool parkissat_load_dimacs(ParkissatSolver* solver, const char* filename);
+// Parse a DIMACS CNF formula from memory; nothing is added if it is invalid
+bool parkissat_load_dimacs_buffer(ParkissatSolver* solver, const char* data, size_t length, ParkissatParseError* error);
 void parkissat_add_clause(ParkissatSolver* solver, const int* literals, int size);
+// Add every clause of a flat, zero-terminated literal buffer (DIMACS-style)
+void parkissat_add_clauses(ParkissatSolver* solver, const int* literals, size_t length);
 void parkissat_set_variable_count(ParkissatSolver* solver, int num_vars);
+int parkissat_get_variable_count(ParkissatSolver* solver);
+// Clauses and literals added so far, including those of DIMACS files
+uint64_t parkissat_get_clause_count(ParkissatSolver* solver);
+uint64_t parkissat_get_literal_count(ParkissatSolver* solver);
+// Learned clauses currently kept by all workers together
+uint64_t parkissat_get_learned_clause_count(ParkissatSolver* solver);
+// Clauses added through the API (not from DIMACS files) as a flat,
+// zero-terminated literal buffer
+size_t parkissat_get_clauses_size(ParkissatSolver* solver);
+void parkissat_get_clauses(ParkissatSolver* solver, int* literals, size_t length);
 
 // Solving
 ParkissatResult parkissat_solve(ParkissatSolver* solver);
@@ -56,13 +166,114 @@ ParkissatResult parkissat_solve_with_assumptions(ParkissatSolver* solver, const
 bool parkissat_get_model_value(ParkissatSolver* solver, int variable);
 int parkissat_get_model_size(ParkissatSolver* solver);
 void parkissat_get_model(ParkissatSolver* solver, int* model, int size);
+ParkissatUnknownReason parkissat_get_unknown_reason(ParkissatSolver* solver);
+int parkissat_get_failed_assumptions_size(ParkissatSolver* solver);
+void parkissat_get_failed_assumptions(ParkissatSolver* solver, int* literals, int size);
+// Literals fixed at decision level 0 by any worker of an earlier solve
+int parkissat_get_fixed_size(ParkissatSolver* solver);
+void parkissat_get_fixed(ParkissatSolver* solver, int* literals, int size);
+// Variables removed by the winning (or first) worker of the last solve:
+// substituted ones as (variable, representative literal) pairs, flattened,
+// and eliminated ones in ascending order
+int parkissat_get_equivalences_size(ParkissatSolver* solver);
+void parkissat_get_equivalences(ParkissatSolver* solver, int* pairs, int size);
+int parkissat_get_eliminated_size(ParkissatSolver* solver);
+void parkissat_get_eliminated(ParkissatSolver* solver, int* variables, int size);
+// Per-worker assignments at the end of the last UNKNOWN solve (none after SAT or
+// UNSAT): values by variable (entry 0 unused) of 1, -1, or 0 = none, copied if
+// size suffices; returns the size needed
+int parkissat_get_partial_workers(ParkissatSolver* solver);
+int parkissat_get_partial_assignment(ParkissatSolver* solver, int worker, signed char* values, int size);
+// Learned clauses still held by the workers of the last solve with at most
+// max_length literals and a glue of at most max_glue (0 = any), deduplicated,
+// each followed by 0; copied if size suffices, returns the size needed
+int parkissat_get_learned_clauses(ParkissatSolver* solver, int max_length, int max_glue, int* literals, int size);
+// Number of learned clauses of the last solve that the next one carries over
+// into its rebuilt workers
+int parkissat_get_carried_learned_count(ParkissatSolver* solver);
 
 // Statistics
+// Safe to call from any thread, also while a solve is running
 ParkissatStatistics parkissat_get_statistics(ParkissatSolver* solver);
+int parkissat_get_worker_count(ParkissatSolver* solver);
+ParkissatStatistics parkissat_get_worker_statistics(ParkissatSolver* solver, int worker);
+int parkissat_get_winning_worker(ParkissatSolver* solver);  // -1 = none
+
+// Options
+// Set a kissat option on every worker, including rebuilt ones; false = unknown name
+bool parkissat_set_option(ParkissatSolver* solver, const char* name, int value);
+
+// Phases
+// Preferred polarity for decisions on the literal's variable, kept across solves
+void parkissat_set_phase(ParkissatSolver* solver, int literal);
+void parkissat_clear_phases(ParkissatSolver* solver);
+// Seed the saved phases of every worker from an assignment for the next solve
+// only; phase hints take precedence
+void parkissat_set_warm_start(ParkissatSolver* solver, const int* literals, int size);
+
+// Freezing
+// Frozen variables are never eliminated; freezes are counted and each needs its own melt
+void parkissat_freeze(ParkissatSolver* solver, int variable);
+bool parkissat_melt(ParkissatSolver* solver, int variable);  // false = not frozen
+bool parkissat_is_frozen(ParkissatSolver* solver, int variable);
+
+// Decision priorities
+// Branch on variables with a higher weight first, kept across solves (0 = none)
+void parkissat_set_decision_priority(ParkissatSolver* solver, int variable, double weight);
+void parkissat_clear_decision_priorities(ParkissatSolver* solver);
+
+// Budgets
+// Per-worker limits applied to every following solve, -1 = unlimited
+void parkissat_set_limits(ParkissatSolver* solver, int64_t conflicts, int64_t decisions, int64_t propagations);
+// Wall-clock limit in milliseconds for every following solve, on top of the configured timeout; -1 = none
+void parkissat_set_deadline(ParkissatSolver* solver, int64_t timeout_ms);
 
 // Control
 void parkissat_interrupt(ParkissatSolver* solver);
 void parkissat_clear_interrupt(ParkissatSolver* solver);
+// Polled about every millisecond from a monitor thread during each solve;
+// a nonzero return stops the search (NULL = no callback)
+void parkissat_set_terminate(ParkissatSolver* solver, void* state, int (*terminate)(void* state));
+// Called from a background thread with each clause of at most max_length
+// literals that a worker exports during a solve (NULL = no callback)
+void parkissat_set_learn(ParkissatSolver* solver, void* state, int max_length,
+                         void (*learn)(void* state, const int* literals, int size));
+// Called from the monitor thread of each later solve with every restart of a
+// worker, every conflict_interval conflicts of one (0 = never), the unit
+// and binary clauses it exports, and its sharing rounds (NULL = no callback)
+void parkissat_set_events(ParkissatSolver* solver, void* state, int64_t conflict_interval,
+                          void (*events)(void* state, ParkissatEvent kind, int worker, uint64_t count,
+                                         const int* literals, int size));
+// Queue a clause for the workers of the running (or next) solve to import;
+// safe to call from any thread
+void parkissat_inject_clause(ParkissatSolver* solver, const int* literals, int size);
+// Add a clause implied by the formula as a learned clause of every worker of
+// the next solve only; its variables must be known already
+void parkissat_add_learned_clause(ParkissatSolver* solver, const int* literals, int size);
+
+// Checkpoints
+// Write the exported learned clauses and each worker's phases and scores to
+// path every interval_ms during solves, and once more when each ends (NULL = off)
+void parkissat_set_checkpoint(ParkissatSolver* solver, const char* path, int interval_ms);
+// Restore the phases and scores of one worker for the next solve; both arrays
+// are indexed by variable (entry 0 unused), phases hold 1, -1, or 0
+void parkissat_resume_worker(ParkissatSolver* solver, int worker, const signed char* phases,
+                             const double* scores, int size);
+
+#ifdef PARKISSAT_DEBUG
+// Search state inspection (debug builds only)
+typedef struct {
+    int literal;
+    int level;
+    bool decision;
+} ParkissatTrailEntry;
+
+int parkissat_debug_num_workers(ParkissatSolver* solver);
+int parkissat_debug_decision_level(ParkissatSolver* solver, int worker);
+int parkissat_debug_trail_size(ParkissatSolver* solver, int worker);
+int parkissat_debug_get_trail(ParkissatSolver* solver, int worker, ParkissatTrailEntry* entries, int size);
+int parkissat_debug_get_activities(ParkissatSolver* solver, int worker, double* activities, int size);
+#endif
 
 #ifdef __cplusplus
 }